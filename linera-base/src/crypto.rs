@@ -6,8 +6,13 @@
 
 use std::{borrow::Cow, num::ParseIntError, str::FromStr};
 
+use blst::{min_pk as bls, BLST_ERROR};
 use ed25519_dalek::{self as dalek, Signer, Verifier};
-use generic_array::typenum::Unsigned;
+use generic_array::{
+    typenum::{Unsigned, U33, U48, U65, U96},
+    GenericArray,
+};
+use k256::ecdsa::signature::hazmat::PrehashVerifier as _;
 use linera_witty::{
     GuestPointer, HList, InstanceWithMemory, Layout, Memory, Runtime, RuntimeError, RuntimeMemory,
     WitLoad, WitStore, WitType,
@@ -45,6 +50,68 @@ pub struct CryptoHash(HasherOutput);
 #[derive(Eq, PartialEq, Copy, Clone)]
 pub struct Signature(pub dalek::Signature);
 
+/// A BLS12-381 signature key-pair, used by validators to sign votes that can be aggregated.
+pub struct BlsKeyPair(bls::SecretKey);
+
+/// A BLS12-381 public key, in compressed form.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash)]
+pub struct BlsPublicKey(pub GenericArray<u8, U48>);
+
+/// A BLS12-381 signature value, possibly aggregating the signatures of several signers on
+/// the same message.
+#[derive(Eq, PartialEq, Copy, Clone, Hash)]
+pub struct BlsSignature(pub GenericArray<u8, U96>);
+
+/// A secp256k1 signature key-pair, as used by Ethereum accounts.
+pub struct Secp256k1KeyPair(k256::ecdsa::SigningKey);
+
+/// A secp256k1 public key, in compressed form.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash)]
+pub struct Secp256k1PublicKey(pub GenericArray<u8, U33>);
+
+/// A secp256k1 ECDSA signature of an EIP-191 `personal_sign` message, in the format of
+/// Ethereum wallets: `r`, `s` and the recovery byte `v`.
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub struct Secp256k1Signature(pub GenericArray<u8, U65>);
+
+/// The public key of an account or chain owner.
+#[derive(
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Copy,
+    Clone,
+    Hash,
+    Debug,
+    Serialize,
+    Deserialize,
+    WitLoad,
+    WitStore,
+    WitType,
+)]
+pub enum AccountPublicKey {
+    /// An Ed25519 public key, as used by Linera wallets.
+    Ed25519(PublicKey),
+    /// A secp256k1 public key, as used by Ethereum wallets.
+    Secp256k1(Secp256k1PublicKey),
+}
+
+/// A signature by an account or chain owner.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum AccountSignature {
+    /// An Ed25519 signature.
+    Ed25519(Signature),
+    /// A secp256k1 signature of an EIP-191 `personal_sign` message.
+    Secp256k1(Secp256k1Signature),
+}
+
+/// The domain separation tag of BLS signatures on values.
+const BLS_SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The domain separation tag of BLS proofs of possession.
+const BLS_PROOF_OF_POSSESSION_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
 /// Error type for cryptographic errors.
 #[derive(Error, Debug)]
 #[allow(missing_docs)]
@@ -65,6 +132,14 @@ pub enum CryptoError {
         expected = dalek::PUBLIC_KEY_LENGTH,
     )]
     IncorrectPublicKeySize(usize),
+    #[error("Invalid BLS public key: {0}")]
+    InvalidBlsPublicKey(String),
+    #[error("Invalid BLS signature: {0}")]
+    InvalidBlsSignature(String),
+    #[error("Invalid secp256k1 public key: {0}")]
+    InvalidSecp256k1PublicKey(String),
+    #[error("Invalid secp256k1 signature: {0}")]
+    InvalidSecp256k1Signature(String),
     #[error("Could not parse integer")]
     ParseIntError(#[from] ParseIntError),
 }
@@ -442,13 +517,26 @@ impl CryptoHash {
 impl Signature {
     /// Computes a signature.
     pub fn new<T>(value: &T, secret: &KeyPair) -> Self
+    where
+        T: BcsSignable,
+    {
+        Self::new_from_message(&Self::signing_message(value), secret)
+    }
+
+    /// Returns the bytes that are signed to create a signature of `value`.
+    pub fn signing_message<T>(value: &T) -> Vec<u8>
     where
         T: BcsSignable,
     {
         let mut message = Vec::new();
         value.write(&mut message);
-        let signature = secret.0.sign(&message);
-        Signature(signature)
+        message
+    }
+
+    /// Computes a signature of a message obtained with [`Signature::signing_message`], e.g. on
+    /// behalf of a client that doesn't hold the secret key itself.
+    pub fn new_from_message(message: &[u8], secret: &KeyPair) -> Self {
+        Signature(secret.0.sign(message))
     }
 
     fn check_internal<T>(&self, value: &T, author: PublicKey) -> Result<(), dalek::SignatureError>
@@ -523,6 +611,643 @@ impl Signature {
     }
 }
 
+impl BlsKeyPair {
+    #[cfg(all(with_getrandom, with_testing))]
+    /// Generates a new key-pair.
+    pub fn generate() -> Self {
+        let mut rng = rand::rngs::OsRng;
+        Self::generate_from(&mut rng)
+    }
+
+    #[cfg(with_getrandom)]
+    /// Generates a new key-pair from the given RNG. Use with care.
+    pub fn generate_from<R: CryptoRng>(rng: &mut R) -> Self {
+        let mut key_material = [0u8; 32];
+        rng.fill_bytes(&mut key_material);
+        let secret = bls::SecretKey::key_gen(&key_material, &[])
+            .expect("32 bytes of key material should be enough");
+        BlsKeyPair(secret)
+    }
+
+    /// Obtains the public key of a key-pair.
+    pub fn public(&self) -> BlsPublicKey {
+        BlsPublicKey(GenericArray::clone_from_slice(
+            &self.0.sk_to_pk().compress(),
+        ))
+    }
+
+    /// Signs the public key, proving that the owner of the public key knows the secret key.
+    ///
+    /// Proofs of possession must be checked before BLS public keys are added to a committee:
+    /// otherwise a validator could pick a public key that cancels the others' in an aggregate
+    /// signature.
+    pub fn proof_of_possession(&self) -> BlsSignature {
+        let signature = self
+            .0
+            .sign(&self.public().0, BLS_PROOF_OF_POSSESSION_DST, &[]);
+        BlsSignature::from_blst(&signature)
+    }
+
+    /// Copies the key-pair, **including the secret key**.
+    ///
+    /// The `Clone` and `Copy` traits are deliberately not implemented for `BlsKeyPair` to
+    /// prevent accidental copies of secret keys.
+    pub fn copy(&self) -> BlsKeyPair {
+        BlsKeyPair(self.0.clone())
+    }
+}
+
+impl BlsPublicKey {
+    /// Decompresses and validates the public key.
+    fn to_blst(self) -> Result<bls::PublicKey, CryptoError> {
+        bls::PublicKey::key_validate(&self.0)
+            .map_err(|error| CryptoError::InvalidBlsPublicKey(format!("{error:?}")))
+    }
+
+    /// Checks a proof of possession created with [`BlsKeyPair::proof_of_possession`].
+    pub fn check_proof_of_possession(&self, proof: &BlsSignature) -> Result<(), CryptoError> {
+        let public_key = self.to_blst()?;
+        let result = proof.to_blst()?.verify(
+            true,
+            &self.0,
+            BLS_PROOF_OF_POSSESSION_DST,
+            &[],
+            &public_key,
+            false,
+        );
+        match result {
+            BLST_ERROR::BLST_SUCCESS => Ok(()),
+            error => Err(CryptoError::InvalidBlsSignature(format!(
+                "invalid proof of possession: {error:?}"
+            ))),
+        }
+    }
+}
+
+impl BlsSignature {
+    /// Computes a signature.
+    pub fn new<T>(value: &T, secret: &BlsKeyPair) -> Self
+    where
+        T: BcsSignable,
+    {
+        let mut message = Vec::new();
+        value.write(&mut message);
+        BlsSignature::from_blst(&secret.0.sign(&message, BLS_SIGNATURE_DST, &[]))
+    }
+
+    fn from_blst(signature: &bls::Signature) -> Self {
+        BlsSignature(GenericArray::clone_from_slice(&signature.compress()))
+    }
+
+    fn to_blst(self) -> Result<bls::Signature, CryptoError> {
+        bls::Signature::from_bytes(&self.0)
+            .map_err(|error| CryptoError::InvalidBlsSignature(format!("{error:?}")))
+    }
+
+    /// Checks a signature.
+    pub fn check<T>(&self, value: &T, author: BlsPublicKey) -> Result<(), CryptoError>
+    where
+        T: BcsSignable + std::fmt::Debug,
+    {
+        self.check_aggregate(value, [&author])
+    }
+
+    /// Aggregates signatures of the same message into a single signature.
+    pub fn aggregate<'a>(
+        signatures: impl IntoIterator<Item = &'a BlsSignature>,
+    ) -> Result<BlsSignature, CryptoError> {
+        let signatures = signatures
+            .into_iter()
+            .map(|signature| signature.to_blst())
+            .collect::<Result<Vec<_>, _>>()?;
+        let signatures = signatures.iter().collect::<Vec<_>>();
+        let aggregate = bls::AggregateSignature::aggregate(&signatures, true)
+            .map_err(|error| CryptoError::InvalidBlsSignature(format!("{error:?}")))?;
+        Ok(BlsSignature::from_blst(&aggregate.to_signature()))
+    }
+
+    /// Checks an aggregate signature of `value` by all the `authors`.
+    ///
+    /// The public keys must have been registered with a valid proof of possession.
+    pub fn check_aggregate<'a, T>(
+        &self,
+        value: &T,
+        authors: impl IntoIterator<Item = &'a BlsPublicKey>,
+    ) -> Result<(), CryptoError>
+    where
+        T: BcsSignable + std::fmt::Debug,
+    {
+        let mut message = Vec::new();
+        value.write(&mut message);
+        let public_keys = authors
+            .into_iter()
+            .map(|public_key| public_key.to_blst())
+            .collect::<Result<Vec<_>, _>>()?;
+        let public_keys = public_keys.iter().collect::<Vec<_>>();
+        let result =
+            self.to_blst()?
+                .fast_aggregate_verify(true, &message, BLS_SIGNATURE_DST, &public_keys);
+        match result {
+            BLST_ERROR::BLST_SUCCESS => Ok(()),
+            error => Err(CryptoError::InvalidSignature {
+                error: format!("BLS {error:?}"),
+                type_name: T::type_name().to_string(),
+            }),
+        }
+    }
+}
+
+impl Serialize for BlsKeyPair {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        // This is only used for JSON configuration.
+        if !serializer.is_human_readable() {
+            return Err(serde::ser::Error::custom(
+                "BLS key-pairs can only be serialized in human-readable formats",
+            ));
+        }
+        serializer.serialize_str(&hex::encode(self.0.to_bytes()))
+    }
+}
+
+impl<'de> Deserialize<'de> for BlsKeyPair {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        // This is only used for JSON configuration.
+        if !deserializer.is_human_readable() {
+            return Err(serde::de::Error::custom(
+                "BLS key-pairs can only be deserialized from human-readable formats",
+            ));
+        }
+        let s = String::deserialize(deserializer)?;
+        let value = hex::decode(s).map_err(serde::de::Error::custom)?;
+        let key = bls::SecretKey::from_bytes(&value)
+            .map_err(|error| serde::de::Error::custom(format!("{error:?}")))?;
+        Ok(BlsKeyPair(key))
+    }
+}
+
+impl Serialize for BlsPublicKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            serializer.serialize_newtype_struct("BlsPublicKey", &self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BlsPublicKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            let value = Self::from_str(&s).map_err(serde::de::Error::custom)?;
+            Ok(value)
+        } else {
+            #[derive(Deserialize)]
+            #[serde(rename = "BlsPublicKey")]
+            struct Foo(GenericArray<u8, U48>);
+
+            let value = Foo::deserialize(deserializer)?;
+            Ok(Self(value.0))
+        }
+    }
+}
+
+impl Serialize for BlsSignature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            serializer.serialize_newtype_struct("BlsSignature", &self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BlsSignature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            let value = Self::from_str(&s).map_err(serde::de::Error::custom)?;
+            Ok(value)
+        } else {
+            #[derive(Deserialize)]
+            #[serde(rename = "BlsSignature")]
+            struct Foo(GenericArray<u8, U96>);
+
+            let value = Foo::deserialize(deserializer)?;
+            Ok(Self(value.0))
+        }
+    }
+}
+
+impl FromStr for BlsPublicKey {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = hex::decode(s)?;
+        let key = GenericArray::from_exact_iter(value.iter().copied())
+            .ok_or_else(|| CryptoError::InvalidBlsPublicKey("incorrect length".to_string()))?;
+        Ok(BlsPublicKey(key))
+    }
+}
+
+impl FromStr for BlsSignature {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = hex::decode(s)?;
+        let signature = GenericArray::from_exact_iter(value.iter().copied())
+            .ok_or_else(|| CryptoError::InvalidBlsSignature("incorrect length".to_string()))?;
+        Ok(BlsSignature(signature))
+    }
+}
+
+impl std::fmt::Display for BlsPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(&self.0[..]))
+    }
+}
+
+impl std::fmt::Display for BlsSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(&self.0[..]))
+    }
+}
+
+impl std::fmt::Debug for BlsPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(&self.0[..8]))
+    }
+}
+
+impl std::fmt::Debug for BlsSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(&self.0[..8]))
+    }
+}
+
+impl Secp256k1KeyPair {
+    #[cfg(all(with_getrandom, with_testing))]
+    /// Generates a new key-pair.
+    pub fn generate() -> Self {
+        let mut rng = rand::rngs::OsRng;
+        Self::generate_from(&mut rng)
+    }
+
+    #[cfg(with_getrandom)]
+    /// Generates a new key-pair from the given RNG. Use with care.
+    pub fn generate_from<R: CryptoRng>(rng: &mut R) -> Self {
+        Secp256k1KeyPair(k256::ecdsa::SigningKey::random(rng))
+    }
+
+    /// Obtains the public key of a key-pair.
+    pub fn public(&self) -> Secp256k1PublicKey {
+        Secp256k1PublicKey(GenericArray::clone_from_slice(
+            &self.0.verifying_key().to_sec1_bytes(),
+        ))
+    }
+
+    /// Copies the key-pair, **including the secret key**.
+    ///
+    /// The `Clone` and `Copy` traits are deliberately not implemented for `Secp256k1KeyPair`
+    /// to prevent accidental copies of secret keys.
+    pub fn copy(&self) -> Secp256k1KeyPair {
+        Secp256k1KeyPair(self.0.clone())
+    }
+}
+
+impl Secp256k1PublicKey {
+    /// Decompresses and validates the public key.
+    fn to_k256(self) -> Result<k256::ecdsa::VerifyingKey, CryptoError> {
+        k256::ecdsa::VerifyingKey::from_sec1_bytes(&self.0)
+            .map_err(|error| CryptoError::InvalidSecp256k1PublicKey(error.to_string()))
+    }
+
+    /// Builds a key from the four little-endian integers of its first 32 bytes and its last
+    /// byte, as represented in WIT.
+    pub fn from_parts(integers: [u64; 4], last_byte: u8) -> Self {
+        let mut key = GenericArray::default();
+        key[..32].copy_from_slice(&u64_array_to_le_bytes(integers));
+        key[32] = last_byte;
+        Secp256k1PublicKey(key)
+    }
+
+    /// Splits the key into the four little-endian integers of its first 32 bytes and its
+    /// last byte, as represented in WIT.
+    pub fn to_parts(&self) -> ([u64; 4], u8) {
+        (le_bytes_to_u64_array(&self.0[..32]), self.0[32])
+    }
+
+    /// Returns the Ethereum address of the key: the last 20 bytes of the Keccak-256 hash of
+    /// the uncompressed key.
+    pub fn ethereum_address(&self) -> Result<[u8; 20], CryptoError> {
+        use sha3::Digest as _;
+
+        let point = self.to_k256()?.to_encoded_point(false);
+        let hash = sha3::Keccak256::digest(&point.as_bytes()[1..]);
+        let mut address = [0; 20];
+        address.copy_from_slice(&hash[12..]);
+        Ok(address)
+    }
+}
+
+impl Secp256k1Signature {
+    /// Computes a signature.
+    pub fn new<T>(value: &T, secret: &Secp256k1KeyPair) -> Self
+    where
+        T: BcsSignable,
+    {
+        Self::new_from_message(&Signature::signing_message(value), secret)
+    }
+
+    /// Signs `message` like the `personal_sign` method of Ethereum wallets.
+    pub fn new_from_message(message: &[u8], secret: &Secp256k1KeyPair) -> Self {
+        let (signature, recovery_id) = secret
+            .0
+            .sign_prehash_recoverable(&eip191_hash(message))
+            .expect("Signing a 32-byte hash should not fail");
+        let mut bytes = GenericArray::default();
+        bytes[..64].copy_from_slice(&signature.to_bytes());
+        bytes[64] = 27 + recovery_id.to_byte();
+        Secp256k1Signature(bytes)
+    }
+
+    /// Checks a signature of a message obtained with [`Signature::signing_message`]. Only
+    /// signatures with a low `s`, as produced by [`Self::new`], are accepted, so that a valid
+    /// signature cannot be turned into another valid one.
+    pub fn check_message(
+        &self,
+        message: &[u8],
+        author: Secp256k1PublicKey,
+    ) -> Result<(), CryptoError> {
+        let signature = k256::ecdsa::Signature::from_slice(&self.0[..64])
+            .map_err(|error| CryptoError::InvalidSecp256k1Signature(error.to_string()))?;
+        if signature.normalize_s().is_some() {
+            return Err(CryptoError::InvalidSecp256k1Signature(
+                "the signature is not normalized to a low s".to_string(),
+            ));
+        }
+        author
+            .to_k256()?
+            .verify_prehash(&eip191_hash(message), &signature)
+            .map_err(|error| CryptoError::InvalidSecp256k1Signature(error.to_string()))
+    }
+
+    /// Checks a signature.
+    pub fn check<T>(&self, value: &T, author: Secp256k1PublicKey) -> Result<(), CryptoError>
+    where
+        T: BcsSignable + std::fmt::Debug,
+    {
+        self.check_message(&Signature::signing_message(value), author)
+            .map_err(|error| CryptoError::InvalidSignature {
+                error: error.to_string(),
+                type_name: T::type_name().to_string(),
+            })
+    }
+}
+
+/// Returns the hash that Ethereum wallets sign in `personal_sign`, as specified by EIP-191.
+fn eip191_hash(message: &[u8]) -> [u8; 32] {
+    use sha3::Digest as _;
+
+    let mut hasher = sha3::Keccak256::new();
+    hasher.update(format!("\x19Ethereum Signed Message:\n{}", message.len()));
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+impl AccountSignature {
+    /// Checks a signature.
+    pub fn check<T>(&self, value: &T, author: AccountPublicKey) -> Result<(), CryptoError>
+    where
+        T: BcsSignable + std::fmt::Debug,
+    {
+        match (self, author) {
+            (AccountSignature::Ed25519(signature), AccountPublicKey::Ed25519(public_key)) => {
+                signature.check(value, public_key)
+            }
+            (AccountSignature::Secp256k1(signature), AccountPublicKey::Secp256k1(public_key)) => {
+                signature.check(value, public_key)
+            }
+            (_, _) => Err(CryptoError::InvalidSignature {
+                error: "the signature scheme doesn't match the public key".to_string(),
+                type_name: T::type_name().to_string(),
+            }),
+        }
+    }
+}
+
+impl From<PublicKey> for AccountPublicKey {
+    fn from(public_key: PublicKey) -> Self {
+        AccountPublicKey::Ed25519(public_key)
+    }
+}
+
+impl From<Secp256k1PublicKey> for AccountPublicKey {
+    fn from(public_key: Secp256k1PublicKey) -> Self {
+        AccountPublicKey::Secp256k1(public_key)
+    }
+}
+
+impl From<Signature> for AccountSignature {
+    fn from(signature: Signature) -> Self {
+        AccountSignature::Ed25519(signature)
+    }
+}
+
+impl From<Secp256k1Signature> for AccountSignature {
+    fn from(signature: Secp256k1Signature) -> Self {
+        AccountSignature::Secp256k1(signature)
+    }
+}
+
+impl Serialize for Secp256k1KeyPair {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        // This is only used for JSON configuration.
+        if !serializer.is_human_readable() {
+            return Err(serde::ser::Error::custom(
+                "Secp256k1 key-pairs can only be serialized in human-readable formats",
+            ));
+        }
+        serializer.serialize_str(&hex::encode(self.0.to_bytes()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Secp256k1KeyPair {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        // This is only used for JSON configuration.
+        if !deserializer.is_human_readable() {
+            return Err(serde::de::Error::custom(
+                "Secp256k1 key-pairs can only be deserialized from human-readable formats",
+            ));
+        }
+        let s = String::deserialize(deserializer)?;
+        let value = hex::decode(s).map_err(serde::de::Error::custom)?;
+        let key = k256::ecdsa::SigningKey::from_slice(&value).map_err(serde::de::Error::custom)?;
+        Ok(Secp256k1KeyPair(key))
+    }
+}
+
+impl Serialize for Secp256k1PublicKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            serializer.serialize_newtype_struct("Secp256k1PublicKey", &self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Secp256k1PublicKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            let value = Self::from_str(&s).map_err(serde::de::Error::custom)?;
+            Ok(value)
+        } else {
+            #[derive(Deserialize)]
+            #[serde(rename = "Secp256k1PublicKey")]
+            struct Foo(GenericArray<u8, U33>);
+
+            let value = Foo::deserialize(deserializer)?;
+            Ok(Self(value.0))
+        }
+    }
+}
+
+impl Serialize for Secp256k1Signature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            serializer.serialize_newtype_struct("Secp256k1Signature", &self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Secp256k1Signature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            let value = Self::from_str(&s).map_err(serde::de::Error::custom)?;
+            Ok(value)
+        } else {
+            #[derive(Deserialize)]
+            #[serde(rename = "Secp256k1Signature")]
+            struct Foo(GenericArray<u8, U65>);
+
+            let value = Foo::deserialize(deserializer)?;
+            Ok(Self(value.0))
+        }
+    }
+}
+
+impl FromStr for Secp256k1PublicKey {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = hex::decode(s.strip_prefix("0x").unwrap_or(s))?;
+        let key = GenericArray::from_exact_iter(value.iter().copied()).ok_or_else(|| {
+            CryptoError::InvalidSecp256k1PublicKey("incorrect length".to_string())
+        })?;
+        Ok(Secp256k1PublicKey(key))
+    }
+}
+
+impl FromStr for Secp256k1Signature {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = hex::decode(s.strip_prefix("0x").unwrap_or(s))?;
+        let signature = GenericArray::from_exact_iter(value.iter().copied()).ok_or_else(|| {
+            CryptoError::InvalidSecp256k1Signature("incorrect length".to_string())
+        })?;
+        Ok(Secp256k1Signature(signature))
+    }
+}
+
+impl FromStr for AccountPublicKey {
+    type Err = CryptoError;
+
+    /// Parses an Ed25519 or a compressed secp256k1 public key in hex, depending on its
+    /// length.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() == 2 * dalek::PUBLIC_KEY_LENGTH {
+            Ok(AccountPublicKey::Ed25519(s.parse()?))
+        } else {
+            Ok(AccountPublicKey::Secp256k1(s.parse()?))
+        }
+    }
+}
+
+impl std::fmt::Display for Secp256k1PublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(&self.0[..]))
+    }
+}
+
+impl std::fmt::Display for Secp256k1Signature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(&self.0[..]))
+    }
+}
+
+impl std::fmt::Display for AccountPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountPublicKey::Ed25519(public_key) => write!(f, "{public_key}"),
+            AccountPublicKey::Secp256k1(public_key) => write!(f, "{public_key}"),
+        }
+    }
+}
+
+impl std::fmt::Debug for Secp256k1PublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(&self.0[..8]))
+    }
+}
+
+impl std::fmt::Debug for Secp256k1Signature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(&self.0[..8]))
+    }
+}
+
 impl WitType for CryptoHash {
     const SIZE: u32 = <(u64, u64, u64, u64) as WitType>::SIZE;
     type Layout = <(u64, u64, u64, u64) as WitType>::Layout;
@@ -673,6 +1398,88 @@ impl WitStore for PublicKey {
     }
 }
 
+impl WitType for Secp256k1PublicKey {
+    const SIZE: u32 = <(u64, u64, u64, u64, u8) as WitType>::SIZE;
+    type Layout = <(u64, u64, u64, u64, u8) as WitType>::Layout;
+    type Dependencies = HList![];
+
+    fn wit_type_name() -> Cow<'static, str> {
+        "secp256k1-public-key".into()
+    }
+
+    fn wit_type_declaration() -> Cow<'static, str> {
+        concat!(
+            "    record secp256k1-public-key {\n",
+            "        part1: u64,\n",
+            "        part2: u64,\n",
+            "        part3: u64,\n",
+            "        part4: u64,\n",
+            "        part5: u8,\n",
+            "    }\n",
+        )
+        .into()
+    }
+}
+
+impl WitLoad for Secp256k1PublicKey {
+    fn load<Instance>(
+        memory: &Memory<'_, Instance>,
+        location: GuestPointer,
+    ) -> Result<Self, RuntimeError>
+    where
+        Instance: InstanceWithMemory,
+        <Instance::Runtime as Runtime>::Memory: RuntimeMemory<Instance>,
+    {
+        let (part1, part2, part3, part4, part5) = WitLoad::load(memory, location)?;
+        Ok(Secp256k1PublicKey::from_parts(
+            [part1, part2, part3, part4],
+            part5,
+        ))
+    }
+
+    fn lift_from<Instance>(
+        flat_layout: <Self::Layout as Layout>::Flat,
+        memory: &Memory<'_, Instance>,
+    ) -> Result<Self, RuntimeError>
+    where
+        Instance: InstanceWithMemory,
+        <Instance::Runtime as Runtime>::Memory: RuntimeMemory<Instance>,
+    {
+        let (part1, part2, part3, part4, part5) = WitLoad::lift_from(flat_layout, memory)?;
+        Ok(Secp256k1PublicKey::from_parts(
+            [part1, part2, part3, part4],
+            part5,
+        ))
+    }
+}
+
+impl WitStore for Secp256k1PublicKey {
+    fn store<Instance>(
+        &self,
+        memory: &mut Memory<'_, Instance>,
+        location: GuestPointer,
+    ) -> Result<(), RuntimeError>
+    where
+        Instance: InstanceWithMemory,
+        <Instance::Runtime as Runtime>::Memory: RuntimeMemory<Instance>,
+    {
+        let ([part1, part2, part3, part4], part5) = self.to_parts();
+        (part1, part2, part3, part4, part5).store(memory, location)
+    }
+
+    fn lower<Instance>(
+        &self,
+        memory: &mut Memory<'_, Instance>,
+    ) -> Result<<Self::Layout as Layout>::Flat, RuntimeError>
+    where
+        Instance: InstanceWithMemory,
+        <Instance::Runtime as Runtime>::Memory: RuntimeMemory<Instance>,
+    {
+        let ([part1, part2, part3, part4], part5) = self.to_parts();
+        (part1, part2, part3, part4, part5).lower(memory)
+    }
+}
+
 #[cfg(with_testing)]
 impl Arbitrary for CryptoHash {
     type Parameters = ();
@@ -687,6 +1494,8 @@ impl Arbitrary for CryptoHash {
 
 impl BcsHashable for PublicKey {}
 
+impl BcsHashable for Secp256k1PublicKey {}
+
 doc_scalar!(CryptoHash, "A Sha3-256 value");
 doc_scalar!(PublicKey, "A signature public key");
 doc_scalar!(Signature, "A signature value");
@@ -730,6 +1539,91 @@ fn test_signatures() {
     assert!(s.check(&ts, addr2).is_err());
     assert!(s.check(&tsx, addr1).is_err());
     assert!(s.check(&foo, addr1).is_err());
+
+    let s = Signature::new_from_message(&Signature::signing_message(&ts), &key1);
+    assert!(s.check(&ts, addr1).is_ok());
+    assert!(s.check(&foo, addr1).is_err());
+}
+
+#[cfg(with_getrandom)]
+#[test]
+fn test_bls_signatures() {
+    let key1 = BlsKeyPair::generate();
+    let key2 = BlsKeyPair::generate();
+    let key3 = BlsKeyPair::generate();
+
+    let ts = TestString("hello".into());
+    let tsx = TestString("hellox".into());
+
+    let s1 = BlsSignature::new(&ts, &key1);
+    assert!(s1.check(&ts, key1.public()).is_ok());
+    assert!(s1.check(&ts, key2.public()).is_err());
+    assert!(s1.check(&tsx, key1.public()).is_err());
+
+    let s2 = BlsSignature::new(&ts, &key2);
+    let aggregate = BlsSignature::aggregate([&s1, &s2]).unwrap();
+    let (public1, public2, public3) = (key1.public(), key2.public(), key3.public());
+    assert!(aggregate.check_aggregate(&ts, [&public1, &public2]).is_ok());
+    assert!(aggregate.check_aggregate(&ts, [&public1]).is_err());
+    assert!(aggregate
+        .check_aggregate(&ts, [&public1, &public3])
+        .is_err());
+    assert!(aggregate
+        .check_aggregate(&tsx, [&public1, &public2])
+        .is_err());
+
+    let proof = key1.proof_of_possession();
+    assert!(public1.check_proof_of_possession(&proof).is_ok());
+    assert!(public2.check_proof_of_possession(&proof).is_err());
+
+    // Secret keys are only stored in human-readable configuration files.
+    assert!(bcs::to_bytes(&key1).is_err());
+}
+
+#[cfg(with_getrandom)]
+#[test]
+fn test_secp256k1_signatures() {
+    let key1 = Secp256k1KeyPair::generate();
+    let key2 = Secp256k1KeyPair::generate();
+    let ed25519_key = KeyPair::generate();
+
+    let ts = TestString("hello".into());
+    let tsx = TestString("hellox".into());
+
+    let s = Secp256k1Signature::new(&ts, &key1);
+    assert!(s.check(&ts, key1.public()).is_ok());
+    assert!(s.check(&ts, key2.public()).is_err());
+    assert!(s.check(&tsx, key1.public()).is_err());
+    assert!(matches!(s.0[64], 27 | 28));
+
+    // The same signature with a high `s` is rejected.
+    let signature = k256::ecdsa::Signature::from_slice(&s.0[..64]).unwrap();
+    let (r, low_s) = signature.split_scalars();
+    let high_s_signature = k256::ecdsa::Signature::from_scalars(r, -low_s).unwrap();
+    let mut malleated = s;
+    malleated.0[..64].copy_from_slice(&high_s_signature.to_bytes());
+    assert!(malleated.check(&ts, key1.public()).is_err());
+
+    // Secret keys are only stored in human-readable configuration files.
+    assert!(bcs::to_bytes(&key1).is_err());
+
+    let s = AccountSignature::from(s);
+    assert!(s.check(&ts, key1.public().into()).is_ok());
+    assert!(s.check(&ts, ed25519_key.public().into()).is_err());
+    let s = AccountSignature::from(Signature::new(&ts, &ed25519_key));
+    assert!(s.check(&ts, ed25519_key.public().into()).is_ok());
+    assert!(s.check(&ts, key1.public().into()).is_err());
+
+    let public_key = AccountPublicKey::from(key1.public());
+    assert_eq!(
+        public_key.to_string().parse::<AccountPublicKey>().unwrap(),
+        public_key
+    );
+    let public_key = AccountPublicKey::from(ed25519_key.public());
+    assert_eq!(
+        public_key.to_string().parse::<AccountPublicKey>().unwrap(),
+        public_key
+    );
 }
 
 /// Reads the `bytes` as four little-endian unsigned 64-bit integers and returns them.
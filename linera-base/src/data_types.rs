@@ -14,9 +14,9 @@ use serde::{Deserialize, Deserializer, Serialize};
 use thiserror::Error;
 
 use crate::{
-    crypto::BcsHashable,
+    crypto::{BcsHashable, CryptoHash},
     doc_scalar,
-    identifiers::{ApplicationId, BlobId, Destination, GenericApplicationId},
+    identifiers::{ApplicationId, BlobId, Destination, EventId, GenericApplicationId},
     time::{Duration, SystemTime},
 };
 
@@ -115,7 +115,7 @@ pub struct TimeDelta(u64);
 
 impl TimeDelta {
     /// Returns the given number of microseconds as a [`TimeDelta`].
-    pub fn from_micros(micros: u64) -> Self {
+    pub const fn from_micros(micros: u64) -> Self {
         TimeDelta(micros)
     }
 
@@ -125,7 +125,7 @@ impl TimeDelta {
     }
 
     /// Returns the given number of seconds as a [`TimeDelta`].
-    pub fn from_secs(secs: u64) -> Self {
+    pub const fn from_secs(secs: u64) -> Self {
         TimeDelta(secs.saturating_mul(1_000_000))
     }
 
@@ -727,6 +727,10 @@ pub enum OracleResponse {
     Post(Vec<u8>),
     /// An assertion oracle that passed.
     Assert,
+    /// The ID of a published blob that was read.
+    Blob(BlobId),
+    /// The random seed revealed by the certificate of an earlier block.
+    RandomSeed(CryptoHash),
 }
 
 impl fmt::Display for OracleResponse {
@@ -737,6 +741,8 @@ impl fmt::Display for OracleResponse {
             }
             OracleResponse::Post(bytes) => write!(f, "Post:{}", STANDARD_NO_PAD.encode(bytes))?,
             OracleResponse::Assert => write!(f, "Assert")?,
+            OracleResponse::Blob(blob_id) => write!(f, "Blob:{}", blob_id)?,
+            OracleResponse::RandomSeed(seed) => write!(f, "RandomSeed:{}", seed)?,
         };
 
         Ok(())
@@ -757,10 +763,34 @@ impl std::str::FromStr for OracleResponse {
                 STANDARD_NO_PAD.decode(string).context("Invalid base64")?,
             ));
         }
+        if let Some(string) = s.strip_prefix("Blob:") {
+            return Ok(OracleResponse::Blob(BlobId(
+                string.parse().context("Invalid blob ID")?,
+            )));
+        }
+        if let Some(string) = s.strip_prefix("RandomSeed:") {
+            return Ok(OracleResponse::RandomSeed(
+                string.parse().context("Invalid random seed")?,
+            ));
+        }
         Err(anyhow::anyhow!("Invalid enum! Enum: {}", s))
     }
 }
 
+/// An event of a stream on another chain, delivered to the applications subscribed to the
+/// stream.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct StreamEvent {
+    /// The ID of the event, including the chain and stream it belongs to.
+    pub event_id: EventId,
+    /// The key given by the emitting application.
+    #[serde(with = "serde_bytes")]
+    pub key: Vec<u8>,
+    /// The payload of the event.
+    #[serde(with = "serde_bytes")]
+    pub value: Vec<u8>,
+}
+
 /// A blob of binary data.
 #[derive(Eq, PartialEq, Debug, Hash, Clone, Serialize, Deserialize)]
 pub struct Blob {
@@ -15,8 +15,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     bcs_scalar,
-    crypto::{BcsHashable, CryptoError, CryptoHash, PublicKey},
-    data_types::{Blob, BlockHeight},
+    crypto::{
+        AccountPublicKey, BcsHashable, CryptoError, CryptoHash, PublicKey, Secp256k1PublicKey,
+    },
+    data_types::{Blob, BlockHeight, Timestamp},
     doc_scalar,
 };
 
@@ -143,7 +145,21 @@ impl ChainDescription {
 pub struct ChainId(pub CryptoHash);
 
 /// A content-addressed blob ID i.e. the hash of the Blob.
-#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash, Debug, Serialize, Deserialize)]
+#[derive(
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Clone,
+    Copy,
+    Hash,
+    Debug,
+    Serialize,
+    Deserialize,
+    WitLoad,
+    WitStore,
+    WitType,
+)]
 #[cfg_attr(with_testing, derive(test_strategy::Arbitrary, Default))]
 pub struct BlobId(pub CryptoHash);
 
@@ -249,6 +265,43 @@ pub struct BytecodeId<Abi = (), Parameters = (), InstantiationArgument = ()> {
 )]
 pub struct ChannelName(#[serde(with = "serde_bytes")] Vec<u8>);
 
+/// The name of an event stream, relative to a particular application.
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+    WitLoad,
+    WitStore,
+    WitType,
+)]
+pub struct StreamName(#[serde(with = "serde_bytes")] pub Vec<u8>);
+
+/// An event stream ID.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct StreamId {
+    /// The application that can add events to this stream.
+    pub application_id: GenericApplicationId,
+    /// The name of this stream: an application can have multiple streams with different names.
+    pub stream_name: StreamName,
+}
+
+/// The identifier of an event: the stream it belongs to and its index in that stream.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct EventId {
+    /// The ID of the chain that created the event.
+    pub chain_id: ChainId,
+    /// The ID of the stream this event belongs to.
+    pub stream_id: StreamId,
+    /// The position of the event in its stream, starting from 0 in emission order.
+    pub index: u32,
+}
+
 /// The destination of a message, relative to a particular application.
 #[derive(
     Clone,
@@ -269,6 +322,13 @@ pub enum Destination {
     Recipient(ChainId),
     /// Broadcast to the current subscribers of our channel.
     Subscribers(ChannelName),
+    /// Direct message to a chain that cannot be received before the given time.
+    Scheduled {
+        /// The chain receiving the message.
+        recipient: ChainId,
+        /// The earliest block timestamp at which the message can be received.
+        not_before: Timestamp,
+    },
 }
 
 impl Destination {
@@ -309,6 +369,18 @@ impl ChannelName {
     }
 }
 
+impl From<Vec<u8>> for StreamName {
+    fn from(name: Vec<u8>) -> Self {
+        StreamName(name)
+    }
+}
+
+impl AsRef<[u8]> for StreamName {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 // Cannot use #[derive(Clone)] because it requires `A: Clone`.
 impl<Abi, Parameters, InstantiationArgument> Clone
     for BytecodeId<Abi, Parameters, InstantiationArgument>
@@ -645,6 +717,21 @@ impl From<&PublicKey> for Owner {
     }
 }
 
+impl From<Secp256k1PublicKey> for Owner {
+    fn from(value: Secp256k1PublicKey) -> Self {
+        Self(CryptoHash::new(&value))
+    }
+}
+
+impl From<AccountPublicKey> for Owner {
+    fn from(value: AccountPublicKey) -> Self {
+        match value {
+            AccountPublicKey::Ed25519(public_key) => Owner::from(public_key),
+            AccountPublicKey::Secp256k1(public_key) => Owner::from(public_key),
+        }
+    }
+}
+
 impl std::str::FromStr for Owner {
     type Err = CryptoError;
 
@@ -794,6 +881,12 @@ doc_scalar!(
     ChainDescription."
 );
 doc_scalar!(ChannelName, "The name of a subscription channel");
+doc_scalar!(StreamName, "The name of an event stream");
+doc_scalar!(StreamId, "An event stream ID");
+doc_scalar!(
+    EventId,
+    "The identifier of an event: the stream it belongs to and its index in that stream"
+);
 bcs_scalar!(MessageId, "The index of a message in a chain");
 doc_scalar!(
     Owner,
@@ -4,14 +4,17 @@
 //! Structures defining the set of owners and super owners, as well as the consensus
 //! round types and timeouts for chains.
 
-use std::{collections::BTreeMap, iter};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    iter,
+};
 
 use linera_witty::{WitLoad, WitStore, WitType};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
-    crypto::PublicKey,
+    crypto::AccountPublicKey,
     data_types::{Round, TimeDelta},
     doc_scalar,
     identifiers::Owner,
@@ -48,29 +51,34 @@ impl Default for TimeoutConfig {
 )]
 pub struct ChainOwnership {
     /// Super owners can propose fast blocks in the first round, and regular blocks in any round.
-    pub super_owners: BTreeMap<Owner, PublicKey>,
+    pub super_owners: BTreeMap<Owner, AccountPublicKey>,
     /// The regular owners, with their weights that determine how often they are round leader.
-    pub owners: BTreeMap<Owner, (PublicKey, u64)>,
+    pub owners: BTreeMap<Owner, (AccountPublicKey, u64)>,
     /// The number of initial rounds after 0 in which all owners are allowed to propose blocks.
     pub multi_leader_rounds: u32,
     /// The timeout configuration: how long fast, multi-leader and single-leader rounds last.
     pub timeout_config: TimeoutConfig,
+    /// If set, blocks proposed by regular owners must be approved by owners whose weights add
+    /// up to at least this threshold. The proposer's own weight counts towards it.
+    pub approval_threshold: Option<u64>,
 }
 
 impl ChainOwnership {
     /// Creates a `ChainOwnership` with a single super owner.
-    pub fn single(public_key: PublicKey) -> Self {
+    pub fn single(public_key: impl Into<AccountPublicKey>) -> Self {
+        let public_key = public_key.into();
         ChainOwnership {
             super_owners: iter::once((Owner::from(public_key), public_key)).collect(),
             owners: BTreeMap::new(),
             multi_leader_rounds: 2,
             timeout_config: TimeoutConfig::default(),
+            approval_threshold: None,
         }
     }
 
     /// Creates a `ChainOwnership` with the specified regular owners.
-    pub fn multiple(
-        keys_and_weights: impl IntoIterator<Item = (PublicKey, u64)>,
+    pub fn multiple<K: Into<AccountPublicKey>>(
+        keys_and_weights: impl IntoIterator<Item = (K, u64)>,
         multi_leader_rounds: u32,
         timeout_config: TimeoutConfig,
     ) -> Self {
@@ -78,20 +86,36 @@ impl ChainOwnership {
             super_owners: BTreeMap::new(),
             owners: keys_and_weights
                 .into_iter()
-                .map(|(public_key, weight)| (Owner::from(public_key), (public_key, weight)))
+                .map(|(public_key, weight)| {
+                    let public_key = public_key.into();
+                    (Owner::from(public_key), (public_key, weight))
+                })
                 .collect(),
             multi_leader_rounds,
             timeout_config,
+            approval_threshold: None,
         }
     }
 
     /// Adds a regular owner.
-    pub fn with_regular_owner(mut self, public_key: PublicKey, weight: u64) -> Self {
+    pub fn with_regular_owner(
+        mut self,
+        public_key: impl Into<AccountPublicKey>,
+        weight: u64,
+    ) -> Self {
+        let public_key = public_key.into();
         self.owners
             .insert(Owner::from(public_key), (public_key, weight));
         self
     }
 
+    /// Requires blocks proposed by regular owners to be approved by owners with the given
+    /// total weight.
+    pub fn with_approval_threshold(mut self, threshold: u64) -> Self {
+        self.approval_threshold = Some(threshold);
+        self
+    }
+
     /// Returns whether there are any owners or super owners or it is a public chain.
     pub fn is_active(&self) -> bool {
         !self.super_owners.is_empty()
@@ -100,7 +124,7 @@ impl ChainOwnership {
     }
 
     /// Returns the given owner's public key, if they are an owner or super owner.
-    pub fn verify_owner(&self, owner: &Owner) -> Option<PublicKey> {
+    pub fn verify_owner(&self, owner: &Owner) -> Option<AccountPublicKey> {
         if let Some(public_key) = self.super_owners.get(owner) {
             Some(*public_key)
         } else {
@@ -108,6 +132,21 @@ impl ChainOwnership {
         }
     }
 
+    /// Returns whether a block proposed by `owner` must be approved by other owners.
+    pub fn requires_approvals(&self, owner: &Owner) -> bool {
+        self.approval_threshold.is_some() && !self.super_owners.contains_key(owner)
+    }
+
+    /// Returns the total weight of the given regular owners, counting each of them only once.
+    pub fn owners_weight<'a>(&self, owners: impl IntoIterator<Item = &'a Owner>) -> u64 {
+        owners
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|owner| self.owners.get(owner))
+            .fold(0, |total, (_, weight)| total.saturating_add(*weight))
+    }
+
     /// Returns the duration of the given round.
     pub fn round_timeout(&self, round: Round) -> Option<TimeDelta> {
         let tc = &self.timeout_config;
@@ -147,7 +186,7 @@ impl ChainOwnership {
     }
 
     /// Returns an iterator over all super owners' keys, followed by all owners'.
-    pub fn all_public_keys(&self) -> impl Iterator<Item = &PublicKey> {
+    pub fn all_public_keys(&self) -> impl Iterator<Item = &AccountPublicKey> {
         self.super_owners
             .values()
             .chain(self.owners.values().map(|(public_key, _)| public_key))
@@ -193,8 +232,8 @@ mod tests {
         let owner = Owner::from(pub_key);
 
         let ownership = ChainOwnership {
-            super_owners: BTreeMap::from_iter([(super_owner, super_pub_key)]),
-            owners: BTreeMap::from_iter([(owner, (pub_key, 100))]),
+            super_owners: BTreeMap::from_iter([(super_owner, super_pub_key.into())]),
+            owners: BTreeMap::from_iter([(owner, (pub_key.into(), 100))]),
             multi_leader_rounds: 10,
             timeout_config: TimeoutConfig {
                 fast_round_duration: Some(TimeDelta::from_secs(5)),
@@ -202,6 +241,7 @@ mod tests {
                 timeout_increment: TimeDelta::from_secs(1),
                 fallback_duration: TimeDelta::from_secs(60 * 60),
             },
+            approval_threshold: None,
         };
 
         assert_eq!(
@@ -226,6 +266,31 @@ mod tests {
             Some(TimeDelta::from_secs(18))
         );
     }
+
+    #[test]
+    fn test_ownership_approval_weights() {
+        use crate::crypto::KeyPair;
+
+        let super_owner = Owner::from(KeyPair::generate().public());
+        let keys = [(); 3].map(|()| KeyPair::generate().public());
+        let owners = keys.map(Owner::from);
+        let ownership = ChainOwnership::multiple(
+            [(keys[0], 1), (keys[1], 2), (keys[2], 3)],
+            2,
+            TimeoutConfig::default(),
+        )
+        .with_approval_threshold(4);
+
+        assert!(ownership.requires_approvals(&owners[0]));
+        assert!(!ChainOwnership::single(keys[0]).requires_approvals(&owners[0]));
+        assert_eq!(ownership.owners_weight(&owners), 6);
+        assert_eq!(ownership.owners_weight([&owners[0], &owners[2]]), 4);
+        // Duplicates and non-owners don't count.
+        assert_eq!(
+            ownership.owners_weight([&owners[1], &owners[1], &super_owner]),
+            2
+        );
+    }
 }
 
 doc_scalar!(ChainOwnership, "Represents the owner(s) of a chain");
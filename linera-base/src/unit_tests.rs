@@ -9,7 +9,7 @@ use linera_witty::{Layout, WitLoad, WitStore};
 use test_case::test_case;
 
 use crate::{
-    crypto::{CryptoHash, PublicKey},
+    crypto::{AccountPublicKey, CryptoHash, PublicKey, Secp256k1PublicKey},
     data_types::{Amount, BlockHeight, Resources, SendMessageRequest, TimeDelta, Timestamp},
     identifiers::{
         Account, ApplicationId, BytecodeId, ChainId, ChannelName, Destination, MessageId, Owner,
@@ -20,6 +20,7 @@ use crate::{
 /// Test roundtrip of types used in the WIT interface.
 #[test_case(CryptoHash::test_hash("hash"); "of_crypto_hash")]
 #[test_case(PublicKey::test_key(255); "of_public_key")]
+#[test_case(Secp256k1PublicKey::from_parts([1, 2, 3, 4], 5); "of_secp256k1_public_key")]
 #[test_case(Amount::from_tokens(500); "of_amount")]
 #[test_case(BlockHeight(1095); "of_block_height")]
 #[test_case(Timestamp::from(6_400_003); "of_timestamp")]
@@ -33,6 +34,10 @@ use crate::{
 #[test_case(bytecode_id_test_case(); "of_bytecode_id")]
 #[test_case(ChannelName::from(b"channel name".to_vec()); "of_channel_name")]
 #[test_case(Destination::Recipient(ChainId::root(0)); "of_destination")]
+#[test_case(
+    Destination::Scheduled { recipient: ChainId::root(0), not_before: Timestamp::from(100) };
+    "of_scheduled_destination"
+)]
 #[test_case(timeout_config_test_case(); "of_timeout_config")]
 #[test_case(chain_ownership_test_case(); "of_chain_ownership")]
 fn test_wit_roundtrip<T>(input: T)
@@ -139,7 +144,7 @@ fn chain_ownership_test_case() -> ChainOwnership {
         .map(|(index, owner_name)| {
             (
                 Owner(CryptoHash::test_hash(owner_name)),
-                PublicKey::test_key(index as u8),
+                AccountPublicKey::from(PublicKey::test_key(index as u8)),
             )
         })
         .collect::<BTreeMap<_, _>>();
@@ -148,12 +153,14 @@ fn chain_ownership_test_case() -> ChainOwnership {
         .into_iter()
         .enumerate()
         .map(|(index, owner_name)| {
+            let public_key = if index == 0 {
+                AccountPublicKey::from(Secp256k1PublicKey::from_parts([1, 2, 3, 4], 5))
+            } else {
+                AccountPublicKey::from(PublicKey::test_key((index + super_owners.len()) as u8))
+            };
             (
                 Owner(CryptoHash::test_hash(owner_name)),
-                (
-                    PublicKey::test_key((index + super_owners.len()) as u8),
-                    index as u64,
-                ),
+                (public_key, index as u64),
             )
         })
         .collect();
@@ -168,5 +175,6 @@ fn chain_ownership_test_case() -> ChainOwnership {
             timeout_increment: TimeDelta::from_secs(3_600),
             fallback_duration: TimeDelta::from_secs(10_000),
         },
+        approval_threshold: Some(2),
     }
 }
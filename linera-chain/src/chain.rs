@@ -10,19 +10,23 @@ use async_graphql::SimpleObject;
 use futures::stream::{self, StreamExt, TryStreamExt};
 use linera_base::{
     crypto::CryptoHash,
-    data_types::{Amount, ArithmeticError, BlockHeight, OracleRecord, Timestamp},
+    data_types::{
+        Amount, ArithmeticError, BlockHeight, OracleRecord, StreamEvent, TimeDelta, Timestamp,
+    },
     ensure,
-    identifiers::{ChainId, Destination, GenericApplicationId, MessageId},
+    identifiers::{ChainId, Destination, EventId, GenericApplicationId, MessageId, StreamId},
 };
 use linera_execution::{
-    system::SystemMessage, ExecutionOutcome, ExecutionRuntimeContext, ExecutionStateView, Message,
-    MessageContext, Operation, OperationContext, Query, QueryContext, RawExecutionOutcome,
+    system::{event_stream_channel_name, SystemMessage},
+    ExecutionOutcome, ExecutionRuntimeContext, ExecutionStateView, Message, MessageContext,
+    MessageKind, Operation, OperationContext, Query, QueryContext, RawExecutionOutcome,
     RawOutgoingMessage, ResourceController, ResourceTracker, Response, UserApplicationDescription,
     UserApplicationId,
 };
 use linera_views::{
     common::Context,
     log_view::LogView,
+    map_view::MapView,
     queue_view::QueueView,
     reentrant_collection_view::ReentrantCollectionView,
     register_view::RegisterView,
@@ -35,8 +39,8 @@ use {linera_base::identifiers::BytecodeId, linera_execution::BytecodeLocation};
 
 use crate::{
     data_types::{
-        Block, BlockExecutionOutcome, ChainAndHeight, ChannelFullName, Event, IncomingMessage,
-        MessageAction, MessageBundle, Origin, OutgoingMessage, Target,
+        Block, BlockExecutionOutcome, ChainAndHeight, ChannelFullName, Event, EventRecord,
+        IncomingMessage, MessageAction, MessageBundle, Origin, OutgoingMessage, Target,
     },
     inbox::{Cursor, InboxError, InboxStateView},
     manager::ChainManager,
@@ -201,6 +205,10 @@ static STATE_HASH_COMPUTATION_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
     .expect("Histogram can be created")
 });
 
+/// The maximal delay between a block and the time from which a message it schedules can be
+/// received.
+pub const MAX_SCHEDULED_MESSAGE_DELAY: TimeDelta = TimeDelta::from_secs(365 * 24 * 60 * 60);
+
 /// An origin, cursor and timestamp of a unskippable message in our inbox.
 #[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
 pub struct TimestampedInboxEntry {
@@ -208,6 +216,8 @@ pub struct TimestampedInboxEntry {
     pub entry: InboxEntry,
     /// The timestamp when the message was added to the inbox.
     pub seen: Timestamp,
+    /// The earliest timestamp of a block that can receive the message.
+    pub receivable_from: Timestamp,
 }
 
 /// An origin and cursor of a unskippable message that is no longer in our inbox.
@@ -267,6 +277,10 @@ where
     pub outbox_counters: RegisterView<C, BTreeMap<BlockHeight, u32>>,
     /// Channels able to multicast messages to subscribers.
     pub channels: ReentrantCollectionView<C, ChannelFullName, ChannelStateView<C>>,
+    /// The number of events in each event stream of this chain, i.e. the index of the next
+    /// event.
+    #[graphql(skip)]
+    pub event_counts: MapView<C, StreamId, u32>,
 }
 
 /// Block-chaining state.
@@ -557,6 +571,7 @@ where
                     chain_id, outgoing_message.destination
                 ))
             );
+            let timestamp = outgoing_message.receivable_from(bundle.timestamp);
             let OutgoingMessage {
                 destination: _,
                 authenticated_signer,
@@ -585,7 +600,7 @@ where
                 grant,
                 refund_grant_to,
                 kind,
-                timestamp: bundle.timestamp,
+                timestamp,
                 message,
             });
         }
@@ -604,6 +619,7 @@ where
         for event in events {
             let entry = InboxEntry::new(origin.clone(), &event);
             let skippable = event.is_skippable();
+            let receivable_from = event.timestamp;
             let newly_added = inbox.add_event(event).await.map_err(|error| match error {
                 InboxError::ViewError(error) => ChainError::ViewError(error),
                 error => ChainError::InternalError(format!(
@@ -611,9 +627,11 @@ where
                 )),
             })?;
             if newly_added && !skippable {
-                let seen = local_time;
-                self.unskippable
-                    .push_back(TimestampedInboxEntry { entry, seen });
+                self.unskippable.push_back(TimestampedInboxEntry {
+                    entry,
+                    seen: local_time,
+                    receivable_from,
+                });
             }
         }
         // Remember the certificate for future validator/client synchronizations.
@@ -706,6 +724,22 @@ where
         Ok(())
     }
 
+    /// Returns the oldest unskippable message in our inboxes that a block at `local_time` could
+    /// receive. Scheduled messages that are not due yet are ignored.
+    pub async fn oldest_receivable_unskippable(
+        &self,
+        local_time: Timestamp,
+    ) -> Result<Option<TimestampedInboxEntry>, ChainError> {
+        for ts_entry in self.unskippable.elements().await? {
+            if ts_entry.receivable_from <= local_time
+                && !self.removed_unskippable.contains(&ts_entry.entry).await?
+            {
+                return Ok(Some(ts_entry));
+            }
+        }
+        Ok(None)
+    }
+
     /// Executes a block: first the incoming messages, then the main operation.
     /// * Modifies the state of inboxes, outboxes, and channels, if needed.
     /// * As usual, in case of errors, `self` may not be consistent any more and should be thrown
@@ -717,6 +751,19 @@ where
         local_time: Timestamp,
         oracle_records: Option<Vec<OracleRecord>>,
     ) -> Result<BlockExecutionOutcome, ChainError> {
+        let (outcome, _) = self
+            .execute_block_with_resources(block, local_time, oracle_records)
+            .await?;
+        Ok(outcome)
+    }
+
+    /// Executes a block like [`Self::execute_block`], and also returns the resources it used.
+    pub async fn execute_block_with_resources(
+        &mut self,
+        block: &Block,
+        local_time: Timestamp,
+        oracle_records: Option<Vec<OracleRecord>>,
+    ) -> Result<(BlockExecutionOutcome, ResourceTracker), ChainError> {
         #[cfg(with_metrics)]
         let _execution_latency = BLOCK_EXECUTION_LATENCY.measure_latency();
 
@@ -735,8 +782,17 @@ where
             tracker: ResourceTracker::default(),
             account: block.authenticated_signer,
         };
+        resource_controller
+            .policy
+            .check_block_limits(
+                bcs::serialized_size(block)? as u64,
+                block.operations.len() as u64,
+                block.incoming_messages.len() as u64,
+            )
+            .map_err(|err| ChainError::ExecutionError(err, ChainExecutionContext::Block))?;
         let mut messages = Vec::new();
         let mut message_counts = Vec::new();
+        let mut events = Vec::new();
 
         if self.is_closed() {
             ensure!(
@@ -907,8 +963,8 @@ where
                     }
                 }
             };
-            let mut messages_out = self
-                .process_execution_outcomes(context.height, outcomes)
+            let (mut messages_out, events_out) = self
+                .process_execution_outcomes(context.height, block.timestamp, outcomes)
                 .await?;
             if let MessageAction::Accept = message.action {
                 for message_out in &messages_out {
@@ -922,6 +978,7 @@ where
             messages.append(&mut messages_out);
             message_counts
                 .push(u32::try_from(messages.len()).map_err(|_| ArithmeticError::Overflow)?);
+            events.push(events_out);
         }
         // Second, execute the operations in the block and remember the recipients to notify.
         for (index, operation) in block.operations.iter().enumerate() {
@@ -958,8 +1015,8 @@ where
                 .await
                 .map_err(|err| ChainError::ExecutionError(err, chain_execution_context))?;
             new_oracle_records.push(oracle_record);
-            let mut messages_out = self
-                .process_execution_outcomes(context.height, outcomes)
+            let (mut messages_out, events_out) = self
+                .process_execution_outcomes(context.height, block.timestamp, outcomes)
                 .await?;
             resource_controller
                 .with_state(&mut self.execution_state)
@@ -976,6 +1033,45 @@ where
             messages.append(&mut messages_out);
             message_counts
                 .push(u32::try_from(messages.len()).map_err(|_| ArithmeticError::Overflow)?);
+            events.push(events_out);
+        }
+
+        // Third, make the transfers of the standing orders that are due. Their messages are
+        // attributed to the last transaction of the block.
+        if !self.is_closed() {
+            if let Some(last_count) = message_counts.last_mut() {
+                let standing_order_messages = self
+                    .execution_state
+                    .system
+                    .execute_standing_orders()
+                    .await
+                    .map_err(|err| {
+                        ChainError::ExecutionError(err.into(), ChainExecutionContext::Block)
+                    })?;
+                let outcome = RawExecutionOutcome {
+                    messages: standing_order_messages,
+                    ..RawExecutionOutcome::default()
+                };
+                let (mut messages_out, _) = self
+                    .process_execution_outcomes(
+                        block.height,
+                        block.timestamp,
+                        vec![ExecutionOutcome::System(outcome)],
+                    )
+                    .await?;
+                for message_out in &messages_out {
+                    resource_controller
+                        .with_state(&mut self.execution_state)
+                        .await?
+                        .track_message(&message_out.message)
+                        .map_err(|err| {
+                            ChainError::ExecutionError(err, ChainExecutionContext::Block)
+                        })?;
+                }
+                messages.append(&mut messages_out);
+                *last_count =
+                    u32::try_from(messages.len()).map_err(|_| ArithmeticError::Overflow)?;
+            }
         }
 
         // Finally, charge for the block fee, except if the chain is closed. Closed chains should
@@ -1026,20 +1122,24 @@ where
             message_counts.len(),
             block.incoming_messages.len() + block.operations.len()
         );
-        Ok(BlockExecutionOutcome {
+        let outcome = BlockExecutionOutcome {
             messages,
             message_counts,
             state_hash,
             oracle_records: new_oracle_records,
-        })
+            events,
+        };
+        Ok((outcome, resource_controller.tracker))
     }
 
     async fn process_execution_outcomes(
         &mut self,
         height: BlockHeight,
+        timestamp: Timestamp,
         results: Vec<ExecutionOutcome>,
-    ) -> Result<Vec<OutgoingMessage>, ChainError> {
+    ) -> Result<(Vec<OutgoingMessage>, Vec<EventRecord>), ChainError> {
         let mut messages = Vec::new();
+        let mut events = Vec::new();
         for result in results {
             match result {
                 ExecutionOutcome::System(result) => {
@@ -1047,12 +1147,15 @@ where
                         GenericApplicationId::System,
                         Message::System,
                         &mut messages,
+                        &mut events,
                         height,
+                        timestamp,
                         result,
                     )
                     .await?;
                 }
                 ExecutionOutcome::User(application_id, result) => {
+                    let first_event = events.len();
                     self.process_raw_execution_outcome(
                         GenericApplicationId::User(application_id),
                         |bytes| Message::User {
@@ -1060,14 +1163,80 @@ where
                             bytes,
                         },
                         &mut messages,
+                        &mut events,
                         height,
+                        timestamp,
                         result,
                     )
                     .await?;
+                    let outcome = self.event_deliveries(&events[first_event..]).await?;
+                    if !outcome.messages.is_empty() {
+                        self.process_raw_execution_outcome(
+                            GenericApplicationId::System,
+                            Message::System,
+                            &mut messages,
+                            &mut events,
+                            height,
+                            timestamp,
+                            outcome,
+                        )
+                        .await?;
+                    }
                 }
             }
         }
-        Ok(messages)
+        Ok((messages, events))
+    }
+
+    /// Returns the system messages delivering the given events to the chains subscribed to
+    /// their streams. Streams without subscribers are skipped.
+    async fn event_deliveries(
+        &self,
+        events: &[EventRecord],
+    ) -> Result<RawExecutionOutcome<SystemMessage, Amount>, ChainError> {
+        let chain_id = self.chain_id();
+        let mut outcome = RawExecutionOutcome::default();
+        let mut has_subscribers = BTreeMap::new();
+        for event in events {
+            let name = event_stream_channel_name(&event.stream_id);
+            if !has_subscribers.contains_key(&event.stream_id) {
+                let full_name = ChannelFullName {
+                    application_id: GenericApplicationId::System,
+                    name: name.clone(),
+                };
+                let mut found = false;
+                if let Some(channel) = self.channels.try_load_entry(&full_name).await? {
+                    channel
+                        .subscribers
+                        .for_each_index_while(|_| {
+                            found = true;
+                            Ok(false)
+                        })
+                        .await?;
+                }
+                has_subscribers.insert(event.stream_id.clone(), found);
+            }
+            if !has_subscribers[&event.stream_id] {
+                continue;
+            }
+            let event = StreamEvent {
+                event_id: EventId {
+                    chain_id,
+                    stream_id: event.stream_id.clone(),
+                    index: event.index,
+                },
+                key: event.key.clone(),
+                value: event.value.clone(),
+            };
+            outcome.messages.push(RawOutgoingMessage {
+                destination: Destination::Subscribers(name),
+                authenticated: false,
+                grant: Amount::ZERO,
+                kind: MessageKind::Simple,
+                message: SystemMessage::NewEvent(event),
+            });
+        }
+        Ok(outcome)
     }
 
     async fn process_raw_execution_outcome<E, F>(
@@ -1075,16 +1244,35 @@ where
         application_id: GenericApplicationId,
         lift: F,
         messages: &mut Vec<OutgoingMessage>,
+        events: &mut Vec<EventRecord>,
         height: BlockHeight,
+        timestamp: Timestamp,
         raw_outcome: RawExecutionOutcome<E, Amount>,
     ) -> Result<(), ChainError>
     where
         F: Fn(E) -> Message,
     {
         let max_stream_queries = self.context().max_stream_queries();
+        // Record the events of the execution. Streams are understood within an application,
+        // and each event is appended to its stream.
+        for (stream_name, key, value) in raw_outcome.events {
+            let stream_id = StreamId {
+                application_id,
+                stream_name,
+            };
+            let count = self.event_counts.get_mut_or_default(&stream_id).await?;
+            let index = *count;
+            *count = count.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+            events.push(EventRecord {
+                stream_id,
+                index,
+                key,
+                value,
+            });
+        }
         // Record the messages of the execution. Messages are understood within an
         // application.
-        let mut recipients = HashSet::new();
+        let mut targets = HashSet::new();
         let mut channel_broadcasts = HashSet::new();
         for RawOutgoingMessage {
             destination,
@@ -1096,7 +1284,21 @@ where
         {
             match &destination {
                 Destination::Recipient(id) => {
-                    recipients.insert(*id);
+                    targets.insert(Target::chain(*id));
+                }
+                Destination::Scheduled {
+                    recipient,
+                    not_before,
+                } => {
+                    ensure!(
+                        *not_before <= timestamp.saturating_add(MAX_SCHEDULED_MESSAGE_DELAY),
+                        ChainError::ScheduledMessageTooLate {
+                            not_before: *not_before,
+                            timestamp,
+                            max_delay: MAX_SCHEDULED_MESSAGE_DELAY,
+                        }
+                    );
+                    targets.insert(Target::scheduled(*recipient));
                 }
                 Destination::Subscribers(name) => {
                     ensure!(grant == Amount::ZERO, ChainError::GrantUseOnBroadcast);
@@ -1117,10 +1319,7 @@ where
 
         // Update the (regular) outboxes.
         let outbox_counters = self.outbox_counters.get_mut();
-        let targets = recipients
-            .into_iter()
-            .map(Target::chain)
-            .collect::<Vec<_>>();
+        let targets = targets.into_iter().collect::<Vec<_>>();
         let outboxes = self.outboxes.try_load_entries_mut(&targets).await?;
         for mut outbox in outboxes {
             if outbox.schedule_message(height)? {
@@ -9,15 +9,20 @@ use std::{
 
 use async_graphql::{Object, SimpleObject};
 use linera_base::{
-    crypto::{BcsHashable, BcsSignable, CryptoError, CryptoHash, KeyPair, PublicKey, Signature},
-    data_types::{Amount, BlockHeight, HashedBlob, OracleRecord, Round, Timestamp},
+    crypto::{
+        AccountPublicKey, AccountSignature, BcsHashable, BcsSignable, BlsKeyPair, BlsSignature,
+        CryptoError, CryptoHash, HasTypeName, KeyPair, Signature,
+    },
+    data_types::{Amount, BlockHeight, HashedBlob, OracleRecord, OracleResponse, Round, Timestamp},
     doc_scalar, ensure,
     identifiers::{
         Account, BlobId, ChainId, ChannelName, Destination, GenericApplicationId, MessageId, Owner,
+        StreamId,
     },
+    ownership::ChainOwnership,
 };
 use linera_execution::{
-    committee::{Committee, Epoch, ValidatorName},
+    committee::{Committee, Epoch, SignatureScheme, ValidatorName},
     BytecodeLocation, Message, MessageKind, Operation, SystemOperation,
 };
 use serde::{de::Deserializer, Deserialize, Serialize};
@@ -165,7 +170,8 @@ pub struct Event {
     pub refund_grant_to: Option<Account>,
     /// The kind of event being delivered.
     pub kind: MessageKind,
-    /// The timestamp of the block that caused the message.
+    /// The timestamp of the block that caused the message, or the time when a scheduled
+    /// message can be received, if that is later.
     pub timestamp: Timestamp,
     /// The message of the event (i.e. the actual payload of a message).
     pub message: Message,
@@ -221,6 +227,10 @@ pub enum Medium {
     Direct,
     /// The message is a channel broadcast.
     Channel(ChannelFullName),
+    /// The message is a direct message that cannot be received before a given time. These
+    /// messages have their own inboxes, so that they do not hold up the direct messages
+    /// sent after them.
+    Scheduled,
 }
 
 /// An authenticated proposal for a new block.
@@ -231,7 +241,9 @@ pub enum Medium {
 pub struct BlockProposal {
     pub content: BlockAndRound,
     pub owner: Owner,
-    pub signature: Signature,
+    pub signature: AccountSignature,
+    /// Other owners' signatures of the block, if the chain requires an approval threshold.
+    pub approvals: Vec<(Owner, AccountSignature)>,
     pub hashed_certificate_values: Vec<HashedCertificateValue>,
     pub hashed_blobs: Vec<HashedBlob>,
     pub validated_block_certificate: Option<Certificate>,
@@ -260,9 +272,8 @@ impl OutgoingMessage {
     /// actually subscribed to that channel.
     pub fn has_destination(&self, medium: &Medium, recipient: ChainId) -> bool {
         match (&self.destination, medium) {
-            (Destination::Recipient(_), Medium::Channel(_))
-            | (Destination::Subscribers(_), Medium::Direct) => false,
-            (Destination::Recipient(id), Medium::Direct) => *id == recipient,
+            (Destination::Recipient(id), Medium::Direct)
+            | (Destination::Scheduled { recipient: id, .. }, Medium::Scheduled) => *id == recipient,
             (
                 Destination::Subscribers(dest_name),
                 Medium::Channel(ChannelFullName {
@@ -270,6 +281,16 @@ impl OutgoingMessage {
                     name,
                 }),
             ) => *application_id == self.message.application_id() && name == dest_name,
+            _ => false,
+        }
+    }
+
+    /// Returns the earliest timestamp of a block that can receive this message, given the
+    /// timestamp of the block that sent it.
+    pub fn receivable_from(&self, timestamp: Timestamp) -> Timestamp {
+        match self.destination {
+            Destination::Scheduled { not_before, .. } => not_before.max(timestamp),
+            Destination::Recipient(_) | Destination::Subscribers(_) => timestamp,
         }
     }
 }
@@ -293,6 +314,24 @@ pub struct BlockExecutionOutcome {
     pub state_hash: CryptoHash,
     /// The record of oracle responses for each transaction.
     pub oracle_records: Vec<OracleRecord>,
+    /// The events emitted by each transaction.
+    pub events: Vec<Vec<EventRecord>>,
+}
+
+/// An event emitted by an application during a block's execution.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct EventRecord {
+    /// The stream the event belongs to.
+    pub stream_id: StreamId,
+    /// The position of the event in its stream. Streams are append-only: their events are
+    /// numbered from 0 in emission order.
+    pub index: u32,
+    /// The key given by the application, e.g. to tag the event with a topic.
+    #[serde(with = "serde_bytes")]
+    pub key: Vec<u8>,
+    /// The payload of the event.
+    #[serde(with = "serde_bytes")]
+    pub value: Vec<u8>,
 }
 
 /// A statement to be certified by the validators.
@@ -365,6 +404,8 @@ pub struct Vote {
     pub round: Round,
     pub validator: ValidatorName,
     pub signature: Signature,
+    /// A signature that can be aggregated, if the validator has a BLS key.
+    pub bls_signature: Option<BlsSignature>,
 }
 
 impl Vote {
@@ -377,9 +418,18 @@ impl Vote {
             round,
             validator: ValidatorName(key_pair.public()),
             signature,
+            bls_signature: None,
         }
     }
 
+    /// Adds a BLS signature on the same value and round, if a BLS key pair is given.
+    pub fn with_bls_signature(mut self, bls_key_pair: Option<&BlsKeyPair>) -> Self {
+        let hash_and_round = ValueHashAndRound(self.value.hash, self.round);
+        self.bls_signature =
+            bls_key_pair.map(|bls_key_pair| BlsSignature::new(&hash_and_round, bls_key_pair));
+        self
+    }
+
     /// Returns the vote, with a `LiteValue` instead of the full value.
     pub fn lite(&self) -> LiteVote {
         LiteVote {
@@ -387,6 +437,7 @@ impl Vote {
             round: self.round,
             validator: self.validator,
             signature: self.signature,
+            bls_signature: self.bls_signature,
         }
     }
 
@@ -404,6 +455,8 @@ pub struct LiteVote {
     pub round: Round,
     pub validator: ValidatorName,
     pub signature: Signature,
+    /// A signature that can be aggregated, if the validator has a BLS key.
+    pub bls_signature: Option<BlsSignature>,
 }
 
 impl LiteVote {
@@ -417,6 +470,7 @@ impl LiteVote {
             round: self.round,
             validator: self.validator,
             signature: self.signature,
+            bls_signature: self.bls_signature,
         })
     }
 }
@@ -431,6 +485,9 @@ pub struct LiteCertificate<'a> {
     pub round: Round,
     /// Signatures on the value.
     pub signatures: Cow<'a, [(ValidatorName, Signature)]>,
+    /// The aggregate signature on the value, if the committee uses BLS signatures. In that
+    /// case, `signatures` is empty.
+    pub aggregate_signature: Option<AggregateSignature>,
 }
 
 impl<'a> LiteCertificate<'a> {
@@ -450,32 +507,66 @@ impl<'a> LiteCertificate<'a> {
             value,
             round,
             signatures,
+            aggregate_signature: None,
         }
     }
 
     /// Creates a `LiteCertificate` from a list of votes, without cryptographically checking the
     /// signatures. Returns `None` if the votes are empty or don't have matching values and rounds.
-    pub fn try_from_votes(votes: impl IntoIterator<Item = LiteVote>) -> Option<Self> {
+    ///
+    /// If the committee uses BLS signatures, these are aggregated. This returns `None` if a
+    /// vote is missing its BLS signature or is not from a member of the committee.
+    pub fn try_from_votes(
+        votes: impl IntoIterator<Item = LiteVote>,
+        committee: &Committee,
+    ) -> Option<Self> {
         let mut votes = votes.into_iter();
         let LiteVote {
             value,
             round,
             validator,
             signature,
+            bls_signature,
         } = votes.next()?;
         let mut signatures = vec![(validator, signature)];
+        let mut bls_signatures = vec![(validator, bls_signature)];
         for vote in votes {
             if vote.value.value_hash != value.value_hash || vote.round != round {
                 return None;
             }
             signatures.push((vote.validator, vote.signature));
+            bls_signatures.push((vote.validator, vote.bls_signature));
+        }
+        match committee.signature_scheme() {
+            SignatureScheme::Ed25519 => Some(LiteCertificate::new(value, round, signatures)),
+            SignatureScheme::Bls => {
+                let bls_signatures = bls_signatures
+                    .into_iter()
+                    .map(|(validator, bls_signature)| {
+                        Some((committee.validator_index(&validator)?, bls_signature?))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                let aggregate_signature =
+                    AggregateSignature::new(bls_signatures, committee.validators().len()).ok()?;
+                Some(LiteCertificate {
+                    value,
+                    round,
+                    signatures: Cow::Owned(Vec::new()),
+                    aggregate_signature: Some(aggregate_signature),
+                })
+            }
         }
-        Some(LiteCertificate::new(value, round, signatures))
     }
 
     /// Verifies the certificate.
     pub fn check(self, committee: &Committee) -> Result<LiteValue, ChainError> {
-        check_signatures(&self.value, self.round, &self.signatures, committee)?;
+        check_signatures(
+            &self.value,
+            self.round,
+            &self.signatures,
+            self.aggregate_signature.as_ref(),
+            committee,
+        )?;
         Ok(self.value)
     }
 
@@ -489,6 +580,7 @@ impl<'a> LiteCertificate<'a> {
             value,
             round: self.round,
             signatures: self.signatures.into_owned(),
+            aggregate_signature: self.aggregate_signature,
         })
     }
 
@@ -498,10 +590,61 @@ impl<'a> LiteCertificate<'a> {
             value: self.value.clone(),
             round: self.round,
             signatures: Cow::Owned(self.signatures.clone().into_owned()),
+            aggregate_signature: self.aggregate_signature.clone(),
         }
     }
 }
 
+/// A BLS signature aggregating the votes of a quorum of validators.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AggregateSignature {
+    /// The aggregate of the validators' BLS signatures.
+    pub signature: BlsSignature,
+    /// A bitmap of the signers: bit `i % 8` of byte `i / 8` is set if the `i`-th validator of
+    /// the committee signed.
+    pub signers: Vec<u8>,
+}
+
+impl AggregateSignature {
+    /// Aggregates the BLS signatures of the validators at the given positions in a committee
+    /// with `committee_size` members.
+    pub fn new(
+        signatures: impl IntoIterator<Item = (usize, BlsSignature)>,
+        committee_size: usize,
+    ) -> Result<Self, ChainError> {
+        let mut signers = vec![0u8; committee_size.div_ceil(8)];
+        let mut bls_signatures = Vec::new();
+        for (index, signature) in signatures {
+            ensure!(index < committee_size, ChainError::InvalidSignerBitmap);
+            ensure!(
+                signers[index / 8] & (1 << (index % 8)) == 0,
+                ChainError::CertificateValidatorReuse
+            );
+            signers[index / 8] |= 1 << (index % 8);
+            bls_signatures.push(signature);
+        }
+        let signature = BlsSignature::aggregate(&bls_signatures)?;
+        Ok(Self { signature, signers })
+    }
+
+    /// Returns whether the `index`-th validator of the committee signed.
+    pub fn has_signer(&self, index: usize) -> bool {
+        self.signers
+            .get(index / 8)
+            .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+}
+
+/// The data from which the random seed revealed by a certificate is derived.
+#[derive(Serialize, Deserialize)]
+struct RandomSeedSource {
+    value_hash: CryptoHash,
+    signatures: Vec<(ValidatorName, Signature)>,
+    aggregate_signature: Option<AggregateSignature>,
+}
+
+impl BcsHashable for RandomSeedSource {}
+
 /// A certified statement from the committee.
 #[derive(Clone, Debug, Serialize)]
 #[cfg_attr(with_testing, derive(Eq, PartialEq))]
@@ -512,6 +655,9 @@ pub struct Certificate {
     pub round: Round,
     /// Signatures on the value.
     signatures: Vec<(ValidatorName, Signature)>,
+    /// The aggregate signature on the value, if the committee uses BLS signatures. In that
+    /// case, `signatures` is empty.
+    aggregate_signature: Option<AggregateSignature>,
 }
 
 impl Origin {
@@ -528,6 +674,13 @@ impl Origin {
             medium: Medium::Channel(name),
         }
     }
+
+    pub fn scheduled(sender: ChainId) -> Self {
+        Self {
+            sender,
+            medium: Medium::Scheduled,
+        }
+    }
 }
 
 impl Target {
@@ -544,6 +697,13 @@ impl Target {
             medium: Medium::Channel(name),
         }
     }
+
+    pub fn scheduled(recipient: ChainId) -> Self {
+        Self {
+            recipient,
+            medium: Medium::Scheduled,
+        }
+    }
 }
 
 impl Serialize for HashedCertificateValue {
@@ -690,6 +850,27 @@ impl ExecutedBlock {
         &self.outcome.messages
     }
 
+    /// Returns the IDs of the blobs that were read during execution.
+    pub fn read_blob_ids(&self) -> HashSet<BlobId> {
+        self.outcome
+            .oracle_records
+            .iter()
+            .flat_map(|record| &record.responses)
+            .filter_map(|response| match response {
+                OracleResponse::Blob(blob_id) => Some(*blob_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the IDs of all the blobs the block depends on: the ones it publishes and the
+    /// ones it reads.
+    pub fn required_blob_ids(&self) -> HashSet<BlobId> {
+        let mut blob_ids = self.block.blob_ids();
+        blob_ids.extend(self.read_blob_ids());
+        blob_ids
+    }
+
     /// Returns the `message_index`th outgoing message created by the `operation_index`th operation,
     /// or `None` if there is no such operation or message.
     pub fn message_id_for_operation(
@@ -808,6 +989,12 @@ pub struct ProposalPayload<'a> {
     pub outcome: Option<Cow<'a, BlockExecutionOutcome>>,
 }
 
+/// What the owners of a chain with an approval threshold sign to approve a block.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApprovalPayload<'a> {
+    pub block: Cow<'a, Block>,
+}
+
 impl BlockProposal {
     pub fn new(
         content: BlockAndRound,
@@ -816,28 +1003,54 @@ impl BlockProposal {
         hashed_blobs: Vec<HashedBlob>,
         validated_block_certificate: Option<Certificate>,
     ) -> Self {
+        let message = Self::signing_message(&content, validated_block_certificate.as_ref());
+        let signature = Signature::new_from_message(&message, secret);
+        Self::new_with_signature(
+            content,
+            secret.public(),
+            signature,
+            hashed_certificate_values,
+            hashed_blobs,
+            validated_block_certificate,
+        )
+    }
+
+    /// Returns the bytes that the owner signs to propose `content`.
+    pub fn signing_message(
+        content: &BlockAndRound,
+        validated_block_certificate: Option<&Certificate>,
+    ) -> Vec<u8> {
         let outcome = validated_block_certificate
-            .as_ref()
             .and_then(|certificate| certificate.value().executed_block())
             .map(|executed_block| Cow::Borrowed(&executed_block.outcome));
-        let signature = Signature::new(
-            &ProposalPayload {
-                content: Cow::Borrowed(&content),
-                outcome,
-            },
-            secret,
-        );
+        Signature::signing_message(&ProposalPayload {
+            content: Cow::Borrowed(content),
+            outcome,
+        })
+    }
+
+    /// Creates a proposal from a signature of its [`BlockProposal::signing_message`] that was
+    /// computed elsewhere, e.g. by an external signer or an Ethereum wallet.
+    pub fn new_with_signature(
+        content: BlockAndRound,
+        public_key: impl Into<AccountPublicKey>,
+        signature: impl Into<AccountSignature>,
+        hashed_certificate_values: Vec<HashedCertificateValue>,
+        hashed_blobs: Vec<HashedBlob>,
+        validated_block_certificate: Option<Certificate>,
+    ) -> Self {
         Self {
             content,
-            owner: secret.public().into(),
-            signature,
+            owner: Owner::from(public_key.into()),
+            signature: signature.into(),
+            approvals: Vec::new(),
             hashed_certificate_values,
             hashed_blobs,
             validated_block_certificate,
         }
     }
 
-    pub fn check_signature(&self, public_key: PublicKey) -> Result<(), CryptoError> {
+    pub fn check_signature(&self, public_key: AccountPublicKey) -> Result<(), CryptoError> {
         let outcome = self
             .validated_block_certificate
             .as_ref()
@@ -851,6 +1064,57 @@ impl BlockProposal {
             public_key,
         )
     }
+
+    /// Returns the bytes that other owners sign to approve `block`.
+    pub fn approval_message(block: &Block) -> Vec<u8> {
+        Signature::signing_message(&ApprovalPayload {
+            block: Cow::Borrowed(block),
+        })
+    }
+
+    /// Returns the block that `message` asks an owner to propose or approve, or `None` if
+    /// `message` is neither a [`BlockProposal::signing_message`] nor a
+    /// [`BlockProposal::approval_message`].
+    pub fn signed_block(message: &[u8]) -> Option<Block> {
+        fn payload<'a, T: HasTypeName>(message: &'a [u8]) -> Option<&'a [u8]> {
+            message
+                .strip_prefix(T::type_name().as_bytes())?
+                .strip_prefix(b"::")
+        }
+        if let Some(bytes) = payload::<ProposalPayload>(message) {
+            let payload = bcs::from_bytes::<ProposalPayload>(bytes).ok()?;
+            return Some(payload.content.into_owned().block);
+        }
+        let bytes = payload::<ApprovalPayload>(message)?;
+        let payload = bcs::from_bytes::<ApprovalPayload>(bytes).ok()?;
+        Some(payload.block.into_owned())
+    }
+
+    /// Adds other owners' approvals of the proposed block.
+    pub fn with_approvals(
+        mut self,
+        approvals: impl IntoIterator<Item = (Owner, AccountSignature)>,
+    ) -> Self {
+        self.approvals.extend(approvals);
+        self
+    }
+
+    /// Verifies the approvals and returns the total weight of the regular owners who approved
+    /// the proposed block, including the proposer.
+    pub fn check_approvals(&self, ownership: &ChainOwnership) -> Result<u64, ChainError> {
+        let payload = ApprovalPayload {
+            block: Cow::Borrowed(&self.content.block),
+        };
+        for (owner, signature) in &self.approvals {
+            let (public_key, _) = ownership
+                .owners
+                .get(owner)
+                .ok_or(ChainError::InvalidApprover(*owner))?;
+            signature.check(&payload, *public_key)?;
+        }
+        let approvers = self.approvals.iter().map(|(owner, _)| owner);
+        Ok(ownership.owners_weight(approvers.chain([&self.owner])))
+    }
 }
 
 impl LiteVote {
@@ -863,9 +1127,18 @@ impl LiteVote {
             round,
             validator: ValidatorName(key_pair.public()),
             signature,
+            bls_signature: None,
         }
     }
 
+    /// Adds a BLS signature on the same value and round, if a BLS key pair is given.
+    pub fn with_bls_signature(mut self, bls_key_pair: Option<&BlsKeyPair>) -> Self {
+        let hash_and_round = ValueHashAndRound(self.value.value_hash, self.round);
+        self.bls_signature =
+            bls_key_pair.map(|bls_key_pair| BlsSignature::new(&hash_and_round, bls_key_pair));
+        self
+    }
+
     /// Verifies the signature in the vote.
     pub fn check(&self) -> Result<(), ChainError> {
         let hash_and_round = ValueHashAndRound(self.value.value_hash, self.round);
@@ -878,6 +1151,8 @@ pub struct SignatureAggregator<'a> {
     weight: u64,
     used_validators: HashSet<ValidatorName>,
     partial: Certificate,
+    /// The BLS signatures so far, with the positions of their validators in the committee.
+    bls_signatures: Vec<(usize, BlsSignature)>,
 }
 
 impl<'a> SignatureAggregator<'a> {
@@ -891,7 +1166,21 @@ impl<'a> SignatureAggregator<'a> {
                 value,
                 round,
                 signatures: Vec::new(),
+                aggregate_signature: None,
             },
+            bls_signatures: Vec::new(),
+        }
+    }
+
+    /// Tries to append the vote's signature, using the committee's signature scheme. See
+    /// [`SignatureAggregator::append`] and [`SignatureAggregator::append_bls`].
+    pub fn append_vote(&mut self, vote: &LiteVote) -> Result<Option<Certificate>, ChainError> {
+        match self.committee.signature_scheme() {
+            SignatureScheme::Ed25519 => self.append(vote.validator, vote.signature),
+            SignatureScheme::Bls => {
+                let bls_signature = vote.bls_signature.ok_or(ChainError::MissingBlsSignature)?;
+                self.append_bls(vote.validator, bls_signature)
+            }
         }
     }
 
@@ -903,6 +1192,10 @@ impl<'a> SignatureAggregator<'a> {
         validator: ValidatorName,
         signature: Signature,
     ) -> Result<Option<Certificate>, ChainError> {
+        ensure!(
+            self.committee.signature_scheme() == SignatureScheme::Ed25519,
+            ChainError::UnexpectedSignatureScheme
+        );
         let hash_and_round = ValueHashAndRound(self.partial.hash(), self.partial.round);
         signature.check(&hash_and_round, validator.0)?;
         // Check that each validator only appears once.
@@ -925,6 +1218,53 @@ impl<'a> SignatureAggregator<'a> {
             Ok(None)
         }
     }
+
+    /// Tries to append a BLS signature, if the committee uses BLS signatures. Returns
+    /// Some(certificate) with an aggregate signature if a quorum was reached.
+    pub fn append_bls(
+        &mut self,
+        validator: ValidatorName,
+        bls_signature: BlsSignature,
+    ) -> Result<Option<Certificate>, ChainError> {
+        ensure!(
+            self.committee.signature_scheme() == SignatureScheme::Bls,
+            ChainError::UnexpectedSignatureScheme
+        );
+        let index = self
+            .committee
+            .validator_index(&validator)
+            .ok_or(ChainError::InvalidSigner)?;
+        let public_key = self
+            .committee
+            .bls_public_key(&validator)
+            .ok_or(ChainError::MissingBlsPublicKey(validator))?;
+        let hash_and_round = ValueHashAndRound(self.partial.hash(), self.partial.round);
+        bls_signature.check(&hash_and_round, public_key)?;
+        // Check that each validator only appears once.
+        ensure!(
+            self.used_validators.insert(validator),
+            ChainError::CertificateValidatorReuse
+        );
+        // Update weight.
+        let voting_rights = self.committee.weight(&validator);
+        ensure!(voting_rights > 0, ChainError::InvalidSigner);
+        self.weight += voting_rights;
+        self.bls_signatures.push((index, bls_signature));
+
+        if self.weight >= self.committee.quorum_threshold() {
+            self.weight = 0; // Prevent from creating the certificate twice.
+            let aggregate_signature = AggregateSignature::new(
+                self.bls_signatures.iter().copied(),
+                self.committee.validators().len(),
+            )?;
+            Ok(Some(Certificate {
+                aggregate_signature: Some(aggregate_signature),
+                ..self.partial.clone()
+            }))
+        } else {
+            Ok(None)
+        }
+    }
 }
 
 // Checks if the array slice is strictly ordered. That means that if the array
@@ -944,6 +1284,7 @@ impl<'de> Deserialize<'de> for Certificate {
             value: HashedCertificateValue,
             round: Round,
             signatures: Vec<(ValidatorName, Signature)>,
+            aggregate_signature: Option<AggregateSignature>,
         }
 
         let helper: CertificateHelper = Deserialize::deserialize(deserializer)?;
@@ -954,6 +1295,7 @@ impl<'de> Deserialize<'de> for Certificate {
                 value: helper.value,
                 round: helper.round,
                 signatures: helper.signatures,
+                aggregate_signature: helper.aggregate_signature,
             })
         }
     }
@@ -975,6 +1317,21 @@ impl Certificate {
             value,
             round,
             signatures,
+            aggregate_signature: None,
+        }
+    }
+
+    /// Creates a certificate signed with an aggregate BLS signature.
+    pub fn new_aggregate(
+        value: HashedCertificateValue,
+        round: Round,
+        aggregate_signature: AggregateSignature,
+    ) -> Self {
+        Self {
+            value,
+            round,
+            signatures: Vec::new(),
+            aggregate_signature: Some(aggregate_signature),
         }
     }
 
@@ -982,6 +1339,23 @@ impl Certificate {
         &self.signatures
     }
 
+    pub fn aggregate_signature(&self) -> Option<&AggregateSignature> {
+        self.aggregate_signature.as_ref()
+    }
+
+    /// Returns the random seed revealed by this certificate.
+    ///
+    /// The seed is the hash of the validators' signatures, which are not known to anyone before
+    /// the value is certified. The signatures are unique per validator, but whoever assembles
+    /// the certificate can still choose which quorum of signers it contains.
+    pub fn random_seed(&self) -> CryptoHash {
+        CryptoHash::new(&RandomSeedSource {
+            value_hash: self.hash(),
+            signatures: self.signatures.clone(),
+            aggregate_signature: self.aggregate_signature.clone(),
+        })
+    }
+
     // Adds a signature to the certificate's list of signatures
     // It's the responsibility of the caller to not insert duplicates
     pub fn add_signature(
@@ -1001,7 +1375,13 @@ impl Certificate {
         &'a self,
         committee: &Committee,
     ) -> Result<&'a HashedCertificateValue, ChainError> {
-        check_signatures(&self.lite_value(), self.round, &self.signatures, committee)?;
+        check_signatures(
+            &self.lite_value(),
+            self.round,
+            &self.signatures,
+            self.aggregate_signature.as_ref(),
+            committee,
+        )?;
         Ok(&self.value)
     }
 
@@ -1011,6 +1391,7 @@ impl Certificate {
             value: self.lite_value(),
             round: self.round,
             signatures: Cow::Borrowed(&self.signatures),
+            aggregate_signature: self.aggregate_signature.clone(),
         }
     }
 
@@ -1033,10 +1414,19 @@ impl Certificate {
     }
 
     /// Returns whether the validator is among the signatories of this certificate.
-    pub fn is_signed_by(&self, validator_name: &ValidatorName) -> bool {
-        self.signatures
-            .binary_search_by(|(name, _)| name.cmp(validator_name))
-            .is_ok()
+    ///
+    /// The signers of an aggregate signature are looked up in `committee`, which must be the
+    /// committee that signed the certificate.
+    pub fn is_signed_by(&self, committee: &Committee, validator_name: &ValidatorName) -> bool {
+        match &self.aggregate_signature {
+            Some(aggregate_signature) => committee
+                .validator_index(validator_name)
+                .is_some_and(|index| aggregate_signature.has_signer(index)),
+            None => self
+                .signatures
+                .binary_search_by(|(name, _)| name.cmp(validator_name))
+                .is_ok(),
+        }
     }
 
     /// Returns the bundle of messages sent via the given medium to the specified
@@ -1061,6 +1451,63 @@ impl Certificate {
 
 /// Verifies certificate signatures.
 fn check_signatures(
+    value: &LiteValue,
+    round: Round,
+    signatures: &[(ValidatorName, Signature)],
+    aggregate_signature: Option<&AggregateSignature>,
+    committee: &Committee,
+) -> Result<(), ChainError> {
+    match (committee.signature_scheme(), aggregate_signature) {
+        (SignatureScheme::Ed25519, None) => {
+            check_individual_signatures(value, round, signatures, committee)
+        }
+        (SignatureScheme::Bls, Some(aggregate_signature)) if signatures.is_empty() => {
+            check_aggregate_signature(value, round, aggregate_signature, committee)
+        }
+        _ => Err(ChainError::UnexpectedSignatureScheme),
+    }
+}
+
+/// Verifies the aggregate BLS signature of a certificate.
+fn check_aggregate_signature(
+    value: &LiteValue,
+    round: Round,
+    aggregate_signature: &AggregateSignature,
+    committee: &Committee,
+) -> Result<(), ChainError> {
+    let validators = committee.validators();
+    ensure!(
+        aggregate_signature.signers.len() == validators.len().div_ceil(8)
+            && (validators.len()..aggregate_signature.signers.len() * 8)
+                .all(|index| !aggregate_signature.has_signer(index)),
+        ChainError::InvalidSignerBitmap
+    );
+    // Check the quorum.
+    let mut weight = 0;
+    let mut public_keys = Vec::new();
+    for (index, (name, state)) in validators.iter().enumerate() {
+        if !aggregate_signature.has_signer(index) {
+            continue;
+        }
+        let public_key = state
+            .bls_public_key
+            .ok_or(ChainError::MissingBlsPublicKey(*name))?;
+        public_keys.push(public_key);
+        weight += state.votes;
+    }
+    ensure!(
+        weight >= committee.quorum_threshold(),
+        ChainError::CertificateRequiresQuorum
+    );
+    let hash_and_round = ValueHashAndRound(value.value_hash, round);
+    aggregate_signature
+        .signature
+        .check_aggregate(&hash_and_round, &public_keys)?;
+    Ok(())
+}
+
+/// Verifies the individual Ed25519 signatures of a certificate.
+fn check_individual_signatures(
     value: &LiteValue,
     round: Round,
     signatures: &[(ValidatorName, Signature)],
@@ -1093,6 +1540,8 @@ fn check_signatures(
 
 impl<'a> BcsSignable for ProposalPayload<'a> {}
 
+impl<'a> BcsSignable for ApprovalPayload<'a> {}
+
 impl BcsSignable for ValueHashAndRound {}
 
 impl BcsHashable for CertificateValue {}
@@ -15,10 +15,10 @@ pub use chain::ChainStateView;
 use data_types::{Event, Origin};
 use linera_base::{
     crypto::CryptoError,
-    data_types::{ArithmeticError, BlockHeight, Round, Timestamp},
-    identifiers::{ApplicationId, ChainId},
+    data_types::{ArithmeticError, BlockHeight, Round, TimeDelta, Timestamp},
+    identifiers::{ApplicationId, ChainId, Owner},
 };
-use linera_execution::ExecutionError;
+use linera_execution::{committee::ValidatorName, ExecutionError};
 use linera_views::views::ViewError;
 use rand_distr::WeightedError;
 use thiserror::Error;
@@ -31,6 +31,8 @@ pub enum ChainError {
     ArithmeticError(#[from] ArithmeticError),
     #[error("Error in view operation: {0}")]
     ViewError(#[from] ViewError),
+    #[error("Serialization error: {0}")]
+    BcsError(#[from] bcs::Error),
     #[error("Execution error: {0} during {1:?}")]
     ExecutionError(ExecutionError, ChainExecutionContext),
 
@@ -124,6 +126,14 @@ pub enum ChainError {
     CertificateValidatorReuse,
     #[error("Signatures in a certificate must form a quorum")]
     CertificateRequiresQuorum,
+    #[error("The signatures do not match the signature scheme of the committee")]
+    UnexpectedSignatureScheme,
+    #[error("Validator {0} has no BLS public key")]
+    MissingBlsPublicKey(ValidatorName),
+    #[error("The vote is missing a BLS signature")]
+    MissingBlsSignature,
+    #[error("The bitmap of signers does not match the committee")]
+    InvalidSignerBitmap,
     #[error("Certificate signature verification failed: {error}")]
     CertificateSignatureVerificationFailed { error: String },
     #[error("Internal error {0}")]
@@ -132,6 +142,13 @@ pub enum ChainError {
     InsufficientBalance,
     #[error("Invalid owner weights: {0}")]
     OwnerWeightError(#[from] WeightedError),
+    #[error("{0} is not a regular owner of the chain and cannot approve blocks")]
+    InvalidApprover(Owner),
+    #[error(
+        "The block must be approved by owners with a total weight of at least {threshold}, \
+         but only has approvals with weight {weight}"
+    )]
+    InsufficientApprovals { weight: u64, threshold: u64 },
     #[error("Closed chains cannot have operations, accepted messages or empty blocks")]
     ClosedChain,
     #[error("All operations on this chain must be from one of the following applications: {0:?}")]
@@ -140,6 +157,15 @@ pub enum ChainError {
     MissingMandatoryApplications(Vec<ApplicationId>),
     #[error("Can't use grant across different broadcast messages")]
     GrantUseOnBroadcast,
+    #[error(
+        "Scheduled messages cannot be delivered later than {max_delay:?} after the block: \
+         {not_before} is too late for a block at {timestamp}"
+    )]
+    ScheduledMessageTooLate {
+        not_before: Timestamp,
+        timestamp: Timestamp,
+        max_delay: TimeDelta,
+    },
     #[error("ExecutedBlock contains fewer oracle responses than requests")]
     MissingOracleRecord,
 }
@@ -71,7 +71,7 @@
 use std::collections::BTreeMap;
 
 use linera_base::{
-    crypto::{KeyPair, PublicKey},
+    crypto::{AccountPublicKey, BlsKeyPair, KeyPair, PublicKey},
     data_types::{ArithmeticError, BlockHeight, HashedBlob, Round, Timestamp},
     doc_scalar, ensure,
     identifiers::{BlobId, ChainId, Owner},
@@ -235,6 +235,17 @@ impl ChainManager {
                         == Some(new_block),
                 ChainError::InvalidBlockProposal
             );
+        } else if let Some(threshold) = self.ownership.approval_threshold {
+            // A new block by a regular owner needs enough approvals. In fallback mode the
+            // validators propose blocks, so no approvals are needed.
+            if self.ownership.requires_approvals(owner) && !matches!(new_round, Round::Validator(_))
+            {
+                let weight = proposal.check_approvals(&self.ownership)?;
+                ensure!(
+                    weight >= threshold,
+                    ChainError::InsufficientApprovals { weight, threshold }
+                );
+            }
         }
         let expected_round = match validated_block_certificate {
             None => self.current_round,
@@ -304,6 +315,7 @@ impl ChainManager {
         height: BlockHeight,
         epoch: Epoch,
         key_pair: Option<&KeyPair>,
+        bls_key_pair: Option<&BlsKeyPair>,
         local_time: Timestamp,
     ) -> bool {
         let Some(key_pair) = key_pair else {
@@ -322,7 +334,8 @@ impl ChainManager {
             }
         }
         let value = HashedCertificateValue::new_timeout(chain_id, height, epoch);
-        self.timeout_vote = Some(Vote::new(value, current_round, key_pair));
+        self.timeout_vote =
+            Some(Vote::new(value, current_round, key_pair).with_bls_signature(bls_key_pair));
         true
     }
 
@@ -336,6 +349,7 @@ impl ChainManager {
         height: BlockHeight,
         epoch: Epoch,
         key_pair: Option<&KeyPair>,
+        bls_key_pair: Option<&BlsKeyPair>,
     ) -> bool {
         let Some(key_pair) = key_pair else {
             return false; // We are not a validator.
@@ -345,7 +359,8 @@ impl ChainManager {
         }
         let value = HashedCertificateValue::new_timeout(chain_id, height, epoch);
         let last_regular_round = Round::SingleLeader(u32::MAX);
-        self.fallback_vote = Some(Vote::new(value, last_regular_round, key_pair));
+        self.fallback_vote =
+            Some(Vote::new(value, last_regular_round, key_pair).with_bls_signature(bls_key_pair));
         true
     }
 
@@ -392,6 +407,7 @@ impl ChainManager {
         proposal: BlockProposal,
         outcome: BlockExecutionOutcome,
         key_pair: Option<&KeyPair>,
+        bls_key_pair: Option<&BlsKeyPair>,
         local_time: Timestamp,
     ) {
         // Record the proposed block, so it can be supplied to clients that request it.
@@ -419,7 +435,7 @@ impl ChainManager {
             } else {
                 HashedCertificateValue::new_validated(executed_block)
             };
-            self.pending = Some(Vote::new(value, round, key_pair));
+            self.pending = Some(Vote::new(value, round, key_pair).with_bls_signature(bls_key_pair));
         }
     }
 
@@ -428,6 +444,7 @@ impl ChainManager {
         &mut self,
         certificate: Certificate,
         key_pair: Option<&KeyPair>,
+        bls_key_pair: Option<&BlsKeyPair>,
         local_time: Timestamp,
     ) {
         let round = certificate.round;
@@ -445,7 +462,7 @@ impl ChainManager {
         self.update_current_round(local_time);
         if let Some(key_pair) = key_pair {
             // Vote to confirm.
-            let vote = Vote::new(value, round, key_pair);
+            let vote = Vote::new(value, round, key_pair).with_bls_signature(bls_key_pair);
             // Ok to overwrite validation votes with confirmation votes at equal or higher round.
             self.pending = Some(vote);
         }
@@ -495,8 +512,8 @@ impl ChainManager {
     }
 
     /// Returns the public key of the block proposal's signer, if they are a valid owner and allowed
-    /// to propose a block in the proposal's round.
-    pub fn verify_owner(&self, proposal: &BlockProposal) -> Option<PublicKey> {
+    /// to propose a block in the proposal's round. This can be an Ed25519 or a secp256k1 key.
+    pub fn verify_owner(&self, proposal: &BlockProposal) -> Option<AccountPublicKey> {
         if let Some(public_key) = self.ownership.super_owners.get(&proposal.owner) {
             return Some(*public_key);
         }
@@ -519,7 +536,7 @@ impl ChainManager {
             Round::Validator(r) => {
                 let index = self.fallback_round_leader_index(r)?;
                 let (leader, (public_key, _)) = self.fallback_owners.iter().nth(index)?;
-                (*leader == proposal.owner).then_some(*public_key)
+                (*leader == proposal.owner).then_some(AccountPublicKey::from(*public_key))
             }
         }
     }
@@ -146,6 +146,8 @@ impl VoteTestExt for Vote {
         let state = ValidatorState {
             network_address: "".to_string(),
             votes: 100,
+            bls_public_key: None,
+            bls_proof_of_possession: None,
         };
         let committee = Committee::new(
             vec![(self.validator, state)].into_iter().collect(),
@@ -6,15 +6,22 @@ use std::{iter, sync::Arc};
 use assert_matches::assert_matches;
 use linera_base::{
     crypto::{CryptoHash, PublicKey},
-    data_types::{Amount, ApplicationPermissions, BlockHeight, Timestamp},
-    identifiers::{ApplicationId, BytecodeId, ChainId, MessageId},
+    data_types::{
+        Amount, ApplicationPermissions, BlockHeight, Resources, SendMessageRequest, StreamEvent,
+        Timestamp,
+    },
+    identifiers::{
+        ApplicationId, BytecodeId, ChainId, Destination, EventId, GenericApplicationId, MessageId,
+        StreamId, StreamName,
+    },
     ownership::ChainOwnership,
 };
 use linera_execution::{
     committee::{Committee, Epoch},
-    system::OpenChainConfig,
+    system::{event_stream_channel_name, OpenChainConfig},
     test_utils::{ExpectedCall, MockApplication},
-    BytecodeLocation, ExecutionRuntimeConfig, ExecutionRuntimeContext, Operation, SystemMessage,
+    BytecodeLocation, ContractRuntime, ExecutionError, ExecutionRuntimeConfig,
+    ExecutionRuntimeContext, Message, MessageKind, Operation, ResourceControlPolicy, SystemMessage,
     TestExecutionRuntimeContext, UserApplicationDescription,
 };
 use linera_views::{
@@ -23,9 +30,13 @@ use linera_views::{
 };
 
 use crate::{
-    data_types::HashedCertificateValue,
+    chain::MAX_SCHEDULED_MESSAGE_DELAY,
+    data_types::{
+        ChannelFullName, EventRecord, HashedCertificateValue, IncomingMessage, Medium,
+        MessageAction, MessageBundle, Origin, OutgoingMessage, Target,
+    },
     test::{make_child_block, make_first_block, BlockTestExt, MessageTestExt},
-    ChainError, ChainStateView,
+    ChainError, ChainExecutionContext, ChainStateView,
 };
 
 impl ChainStateView<MemoryContext<TestExecutionRuntimeContext>>
@@ -162,3 +173,396 @@ async fn test_application_permissions() {
     let valid_block = make_child_block(&value).with_operation(app_operation);
     chain.execute_block(&valid_block, time, None).await.unwrap();
 }
+
+#[tokio::test]
+async fn test_block_limits() {
+    let time = Timestamp::from(0);
+    let message_id = make_admin_message_id(BlockHeight(3));
+    let chain_id = ChainId::child(message_id);
+    let mut chain = ChainStateView::new(chain_id).await;
+
+    // Initialize the chain, with a policy allowing a single operation and incoming message.
+    let policy = ResourceControlPolicy {
+        maximum_operations_per_block: 1,
+        maximum_incoming_messages_per_block: 1,
+        ..ResourceControlPolicy::default()
+    };
+    let validators = Committee::make_simple(vec![PublicKey::test_key(1).into()])
+        .validators()
+        .clone();
+    let config = OpenChainConfig {
+        committees: iter::once((Epoch::ZERO, Committee::new(validators, policy))).collect(),
+        ..make_open_chain_config()
+    };
+    let message = SystemMessage::OpenChain(config).into();
+    chain
+        .execute_init_message(message_id, &message, time, time)
+        .await
+        .unwrap();
+    let open_chain_message = message.to_simple_incoming(admin_id(), BlockHeight(1));
+
+    let invalid_block = make_first_block(chain_id)
+        .with_incoming_message(open_chain_message.clone())
+        .with_simple_transfer(admin_id(), Amount::ONE)
+        .with_simple_transfer(admin_id(), Amount::ONE);
+    let result = chain.execute_block(&invalid_block, time, None).await;
+    assert_matches!(
+        result,
+        Err(ChainError::ExecutionError(
+            ExecutionError::ExcessiveOperations,
+            ChainExecutionContext::Block
+        ))
+    );
+
+    let invalid_block = make_first_block(chain_id)
+        .with_incoming_message(open_chain_message.clone())
+        .with_incoming_message(open_chain_message.clone())
+        .with_simple_transfer(admin_id(), Amount::ONE);
+    let result = chain.execute_block(&invalid_block, time, None).await;
+    assert_matches!(
+        result,
+        Err(ChainError::ExecutionError(
+            ExecutionError::ExcessiveIncomingMessages,
+            ChainExecutionContext::Block
+        ))
+    );
+
+    let valid_block = make_first_block(chain_id)
+        .with_incoming_message(open_chain_message)
+        .with_simple_transfer(admin_id(), Amount::ONE);
+    chain.execute_block(&valid_block, time, None).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_scheduled_message() {
+    let time = Timestamp::from(0);
+    let message_id = make_admin_message_id(BlockHeight(3));
+    let chain_id = ChainId::child(message_id);
+    let mut chain = ChainStateView::new(chain_id).await;
+    let message = SystemMessage::OpenChain(make_open_chain_config()).into();
+    chain
+        .execute_init_message(message_id, &message, time, time)
+        .await
+        .unwrap();
+
+    // A block with timestamp 1 sends a message that cannot be received before 10.
+    let scheduled_message = OutgoingMessage {
+        destination: Destination::Scheduled {
+            recipient: chain_id,
+            not_before: Timestamp::from(10),
+        },
+        authenticated_signer: None,
+        grant: Amount::ZERO,
+        refund_grant_to: None,
+        kind: MessageKind::Tracked,
+        message: Message::System(SystemMessage::Credit {
+            target: None,
+            amount: Amount::ONE,
+            source: None,
+        }),
+    };
+    assert!(!scheduled_message.has_destination(&Medium::Direct, chain_id));
+    let origin = Origin::scheduled(admin_id());
+    let bundle = MessageBundle {
+        height: BlockHeight(4),
+        epoch: Epoch::ZERO,
+        timestamp: Timestamp::from(1),
+        hash: CryptoHash::test_hash("certificate"),
+        messages: vec![(0, scheduled_message)],
+    };
+    chain
+        .receive_message_bundle(&origin, bundle, time)
+        .await
+        .unwrap();
+    let event = chain
+        .inboxes
+        .try_load_entry(&origin)
+        .await
+        .unwrap()
+        .unwrap()
+        .added_events
+        .front()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.timestamp, Timestamp::from(10));
+    let entry = chain.unskippable.front().await.unwrap().unwrap();
+    assert_eq!(entry.seen, time);
+    assert_eq!(entry.receivable_from, Timestamp::from(10));
+    // The message is not overdue before it can be received.
+    let oldest = chain.oldest_receivable_unskippable(Timestamp::from(9));
+    assert!(oldest.await.unwrap().is_none());
+
+    // A later direct message is overdue even though the scheduled one is in front of it.
+    let direct_message = OutgoingMessage {
+        destination: Destination::Recipient(chain_id),
+        ..scheduled_message.clone()
+    };
+    let direct_origin = Origin::chain(admin_id());
+    let direct_bundle = MessageBundle {
+        height: BlockHeight(5),
+        epoch: Epoch::ZERO,
+        timestamp: Timestamp::from(2),
+        hash: CryptoHash::test_hash("direct certificate"),
+        messages: vec![(0, direct_message)],
+    };
+    chain
+        .receive_message_bundle(&direct_origin, direct_bundle, Timestamp::from(3))
+        .await
+        .unwrap();
+    let oldest = chain.oldest_receivable_unskippable(Timestamp::from(9));
+    let entry = oldest.await.unwrap().unwrap();
+    assert_eq!(entry.entry.origin, direct_origin);
+    assert_eq!(entry.seen, Timestamp::from(3));
+    let oldest = chain.oldest_receivable_unskippable(Timestamp::from(10));
+    let entry = oldest.await.unwrap().unwrap();
+    assert_eq!(entry.entry.origin, origin);
+
+    let incoming_message = IncomingMessage {
+        origin,
+        event,
+        action: MessageAction::Accept,
+    };
+    let early_block = make_first_block(chain_id)
+        .with_incoming_message(incoming_message.clone())
+        .with_timestamp(9);
+    let result = chain.remove_events_from_inboxes(&early_block).await;
+    assert_matches!(result, Err(ChainError::IncorrectEventTimestamp { .. }));
+
+    let block = make_first_block(chain_id)
+        .with_incoming_message(incoming_message)
+        .with_timestamp(10);
+    chain.remove_events_from_inboxes(&block).await.unwrap();
+    let entry = chain.unskippable.front().await.unwrap().unwrap();
+    assert_eq!(entry.entry.origin, direct_origin);
+}
+
+#[tokio::test]
+async fn test_scheduled_message_delay() {
+    let time = Timestamp::from(0);
+    let message_id = make_admin_message_id(BlockHeight(3));
+    let chain_id = ChainId::child(message_id);
+    let mut chain = ChainStateView::new(chain_id).await;
+
+    // Create a mock application.
+    let app_description = make_app_description();
+    let application_id = ApplicationId::from(&app_description);
+    let application = Arc::new(MockApplication::default());
+    let extra = &chain.context().extra;
+    extra
+        .user_contracts()
+        .insert(application_id, application.clone());
+
+    let message = SystemMessage::OpenChain(make_open_chain_config()).into();
+    chain
+        .execute_init_message(message_id, &message, time, time)
+        .await
+        .unwrap();
+    let open_chain_message = message.to_simple_incoming(admin_id(), BlockHeight(1));
+    let register_app_message = SystemMessage::RegisterApplications {
+        applications: vec![app_description],
+    }
+    .to_simple_incoming(admin_id(), BlockHeight(2));
+    let block = make_first_block(chain_id)
+        .with_incoming_message(open_chain_message)
+        .with_incoming_message(register_app_message)
+        .with_operation(Operation::User {
+            application_id,
+            bytes: vec![],
+        });
+
+    // Messages cannot be scheduled too far into the future.
+    let not_before = block
+        .timestamp
+        .saturating_add(MAX_SCHEDULED_MESSAGE_DELAY)
+        .saturating_add_micros(1);
+    application.expect_call(ExpectedCall::execute_operation(move |runtime, _, _| {
+        runtime.send_message(SendMessageRequest {
+            destination: Destination::Scheduled {
+                recipient: chain_id,
+                not_before,
+            },
+            authenticated: false,
+            is_tracked: false,
+            grant: Resources::default(),
+            message: vec![],
+        })?;
+        Ok(vec![])
+    }));
+    application.expect_call(ExpectedCall::default_finalize());
+    let result = chain.execute_block(&block, time, None).await;
+    assert_matches!(result, Err(ChainError::ScheduledMessageTooLate { .. }));
+}
+
+#[tokio::test]
+async fn test_event_indices() {
+    let time = Timestamp::from(0);
+    let message_id = make_admin_message_id(BlockHeight(3));
+    let chain_id = ChainId::child(message_id);
+    let mut chain = ChainStateView::new(chain_id).await;
+
+    // Create a mock application.
+    let app_description = make_app_description();
+    let application_id = ApplicationId::from(&app_description);
+    let application = Arc::new(MockApplication::default());
+    let extra = &chain.context().extra;
+    extra
+        .user_contracts()
+        .insert(application_id, application.clone());
+
+    let message = SystemMessage::OpenChain(make_open_chain_config()).into();
+    chain
+        .execute_init_message(message_id, &message, time, time)
+        .await
+        .unwrap();
+    let open_chain_message = message.to_simple_incoming(admin_id(), BlockHeight(1));
+    let register_app_message = SystemMessage::RegisterApplications {
+        applications: vec![app_description],
+    }
+    .to_simple_incoming(admin_id(), BlockHeight(2));
+    let app_operation = Operation::User {
+        application_id,
+        bytes: vec![],
+    };
+    let stream_a = StreamName(b"a".to_vec());
+    let stream_b = StreamName(b"b".to_vec());
+    let event = |stream_name: &StreamName, index, value: &[u8]| EventRecord {
+        stream_id: StreamId {
+            application_id: application_id.into(),
+            stream_name: stream_name.clone(),
+        },
+        index,
+        key: vec![],
+        value: value.to_vec(),
+    };
+
+    // Events are numbered from 0 in each stream, in emission order.
+    let (name_a, name_b) = (stream_a.clone(), stream_b.clone());
+    application.expect_call(ExpectedCall::execute_operation(move |runtime, _, _| {
+        runtime.emit(name_a.clone(), vec![], b"a0".to_vec())?;
+        runtime.emit(name_b, vec![], b"b0".to_vec())?;
+        runtime.emit(name_a, vec![], b"a1".to_vec())?;
+        Ok(vec![])
+    }));
+    application.expect_call(ExpectedCall::default_finalize());
+    let block = make_first_block(chain_id)
+        .with_incoming_message(open_chain_message)
+        .with_incoming_message(register_app_message)
+        .with_operation(app_operation.clone());
+    let outcome = chain.execute_block(&block, time, None).await.unwrap();
+    assert_eq!(
+        outcome.events,
+        vec![
+            vec![],
+            vec![],
+            vec![
+                event(&stream_a, 0, b"a0"),
+                event(&stream_b, 0, b"b0"),
+                event(&stream_a, 1, b"a1"),
+            ]
+        ]
+    );
+    let value = HashedCertificateValue::new_confirmed(outcome.with(block));
+
+    // The indices carry on in the next block.
+    let name_a = stream_a.clone();
+    application.expect_call(ExpectedCall::execute_operation(move |runtime, _, _| {
+        runtime.emit(name_a, vec![], b"a2".to_vec())?;
+        Ok(vec![])
+    }));
+    application.expect_call(ExpectedCall::default_finalize());
+    let block = make_child_block(&value).with_operation(app_operation);
+    let outcome = chain.execute_block(&block, time, None).await.unwrap();
+    assert_eq!(outcome.events, vec![vec![event(&stream_a, 2, b"a2")]]);
+}
+
+#[tokio::test]
+async fn test_event_deliveries() {
+    let time = Timestamp::from(0);
+    let message_id = make_admin_message_id(BlockHeight(3));
+    let chain_id = ChainId::child(message_id);
+    let mut chain = ChainStateView::new(chain_id).await;
+
+    // Create a mock application.
+    let app_description = make_app_description();
+    let application_id = ApplicationId::from(&app_description);
+    let application = Arc::new(MockApplication::default());
+    let extra = &chain.context().extra;
+    extra
+        .user_contracts()
+        .insert(application_id, application.clone());
+
+    let message = SystemMessage::OpenChain(make_open_chain_config()).into();
+    chain
+        .execute_init_message(message_id, &message, time, time)
+        .await
+        .unwrap();
+    let open_chain_message = message.to_simple_incoming(admin_id(), BlockHeight(1));
+    let register_app_message = SystemMessage::RegisterApplications {
+        applications: vec![app_description],
+    }
+    .to_simple_incoming(admin_id(), BlockHeight(2));
+
+    // Another chain is subscribed to stream "a", but not to stream "b".
+    let stream_a = StreamName(b"a".to_vec());
+    let stream_b = StreamName(b"b".to_vec());
+    let stream_id = StreamId {
+        application_id: application_id.into(),
+        stream_name: stream_a.clone(),
+    };
+    let channel_name = event_stream_channel_name(&stream_id);
+    let full_name = ChannelFullName {
+        application_id: GenericApplicationId::System,
+        name: channel_name.clone(),
+    };
+    let subscriber_id = ChainId::root(1);
+    chain
+        .channels
+        .load_entry_mut(&full_name)
+        .await
+        .unwrap()
+        .subscribers
+        .insert(&subscriber_id)
+        .unwrap();
+
+    let (name_a, name_b) = (stream_a.clone(), stream_b.clone());
+    application.expect_call(ExpectedCall::execute_operation(move |runtime, _, _| {
+        runtime.emit(name_a, b"key".to_vec(), b"a0".to_vec())?;
+        runtime.emit(name_b, b"key".to_vec(), b"b0".to_vec())?;
+        Ok(vec![])
+    }));
+    application.expect_call(ExpectedCall::default_finalize());
+    let block = make_first_block(chain_id)
+        .with_incoming_message(open_chain_message)
+        .with_incoming_message(register_app_message)
+        .with_operation(Operation::User {
+            application_id,
+            bytes: vec![],
+        });
+    let outcome = chain.execute_block(&block, time, None).await.unwrap();
+
+    // Only the event of stream "a" is sent, to the subscribers of its channel.
+    let event = StreamEvent {
+        event_id: EventId {
+            chain_id,
+            stream_id,
+            index: 0,
+        },
+        key: b"key".to_vec(),
+        value: b"a0".to_vec(),
+    };
+    assert_eq!(
+        outcome.messages,
+        vec![OutgoingMessage {
+            destination: Destination::Subscribers(channel_name),
+            authenticated_signer: None,
+            grant: Amount::ZERO,
+            refund_grant_to: None,
+            kind: MessageKind::Simple,
+            message: SystemMessage::NewEvent(event).into(),
+        }]
+    );
+    let target = Target::channel(subscriber_id, full_name);
+    let outbox = chain.outboxes.try_load_entry(&target).await.unwrap();
+    assert!(outbox.is_some());
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use linera_base::data_types::Amount;
+use linera_execution::{committee::ValidatorState, ResourceControlPolicy};
 
 use super::*;
 use crate::test::{make_first_block, BlockTestExt};
@@ -20,6 +21,7 @@ fn test_signed_values() {
         message_counts: vec![1],
         state_hash: CryptoHash::test_hash("state"),
         oracle_records: vec![OracleRecord::default()],
+        events: vec![Vec::new()],
     }
     .with(block);
     let value = HashedCertificateValue::new_confirmed(executed_block);
@@ -49,6 +51,7 @@ fn test_certificates() {
         message_counts: vec![1],
         state_hash: CryptoHash::test_hash("state"),
         oracle_records: vec![OracleRecord::default()],
+        events: vec![Vec::new()],
     }
     .with(block);
     let value = HashedCertificateValue::new_confirmed(executed_block);
@@ -64,6 +67,8 @@ fn test_certificates() {
         .is_none());
     let mut c = builder.append(v2.validator, v2.signature).unwrap().unwrap();
     assert!(c.check(&committee).is_ok());
+    assert!(c.is_signed_by(&committee, &name1));
+    assert!(!c.is_signed_by(&committee, &ValidatorName(key3.public())));
     c.signatures.pop();
     assert!(c.check(&committee).is_err());
 
@@ -74,3 +79,114 @@ fn test_certificates() {
         .is_none());
     assert!(builder.append(v3.validator, v3.signature).is_err());
 }
+
+#[test]
+fn test_bls_certificates() {
+    let keys = (0..4).map(|_| KeyPair::generate()).collect::<Vec<_>>();
+    let bls_keys = (0..4).map(|_| BlsKeyPair::generate()).collect::<Vec<_>>();
+    let validators = keys
+        .iter()
+        .zip(&bls_keys)
+        .map(|(key, bls_key)| {
+            let state = ValidatorState {
+                network_address: "Tcp:localhost:8080".to_string(),
+                votes: 1,
+                bls_public_key: Some(bls_key.public()),
+                bls_proof_of_possession: Some(bls_key.proof_of_possession()),
+            };
+            (ValidatorName(key.public()), state)
+        })
+        .collect();
+    let committee = Committee::new(validators, ResourceControlPolicy::default())
+        .with_signature_scheme(SignatureScheme::Bls);
+    assert!(committee.check_bls_proofs_of_possession().is_ok());
+
+    // A key without a valid proof of possession is rejected.
+    let mut validators = committee.validators().clone();
+    validators
+        .values_mut()
+        .next()
+        .unwrap()
+        .bls_proof_of_possession = Some(BlsKeyPair::generate().proof_of_possession());
+    let invalid_committee = Committee::new(validators.clone(), ResourceControlPolicy::default());
+    assert!(invalid_committee.check_bls_proofs_of_possession().is_err());
+    validators
+        .values_mut()
+        .next()
+        .unwrap()
+        .bls_proof_of_possession = None;
+    let invalid_committee = Committee::new(validators, ResourceControlPolicy::default());
+    assert!(invalid_committee.check_bls_proofs_of_possession().is_err());
+
+    let block =
+        make_first_block(ChainId::root(1)).with_simple_transfer(ChainId::root(1), Amount::ONE);
+    let executed_block = BlockExecutionOutcome {
+        messages: Vec::new(),
+        message_counts: vec![1],
+        state_hash: CryptoHash::test_hash("state"),
+        oracle_records: vec![OracleRecord::default()],
+        events: vec![Vec::new()],
+    }
+    .with(block);
+    let value = HashedCertificateValue::new_confirmed(executed_block);
+    let votes = keys
+        .iter()
+        .zip(&bls_keys)
+        .map(|(key, bls_key)| {
+            LiteVote::new(value.lite(), Round::Fast, key).with_bls_signature(Some(bls_key))
+        })
+        .collect::<Vec<_>>();
+
+    // Three of the four validators form a quorum.
+    let mut builder = SignatureAggregator::new(value.clone(), Round::Fast, &committee);
+    assert!(builder.append_vote(&votes[0]).unwrap().is_none());
+    assert!(builder.append_vote(&votes[0]).is_err());
+    assert!(builder.append_vote(&votes[1]).unwrap().is_none());
+    let certificate = builder.append_vote(&votes[3]).unwrap().unwrap();
+    assert!(certificate.signatures().is_empty());
+    assert!(certificate.check(&committee).is_ok());
+    assert!(certificate.lite_certificate().check(&committee).is_ok());
+    let aggregate_signature = certificate.aggregate_signature().unwrap();
+    assert_eq!(aggregate_signature.signers, vec![0b1011]);
+    for (i, vote) in votes.iter().enumerate() {
+        assert_eq!(
+            certificate.is_signed_by(&committee, &vote.validator),
+            i != 2
+        );
+    }
+
+    // Ed25519 signatures are not accepted in a BLS committee.
+    let mut builder = SignatureAggregator::new(value.clone(), Round::Fast, &committee);
+    assert!(builder
+        .append(votes[0].validator, votes[0].signature)
+        .is_err());
+
+    // A BLS signature from the wrong key is rejected.
+    let mut builder = SignatureAggregator::new(value.clone(), Round::Fast, &committee);
+    assert!(builder
+        .append_bls(votes[0].validator, votes[1].bls_signature.unwrap())
+        .is_err());
+
+    // Aggregating the votes directly gives the same result.
+    let lite_certificate =
+        LiteCertificate::try_from_votes(votes[..3].iter().cloned(), &committee).unwrap();
+    assert!(lite_certificate.clone().check(&committee).is_ok());
+    let certificate = lite_certificate.with_value(value.clone()).unwrap();
+    assert!(certificate.check(&committee).is_ok());
+
+    // Two validators are not a quorum, and the signers must match the signature.
+    let lite_certificate =
+        LiteCertificate::try_from_votes(votes[..2].iter().cloned(), &committee).unwrap();
+    assert!(lite_certificate.clone().check(&committee).is_err());
+    let mut lite_certificate = lite_certificate;
+    lite_certificate
+        .aggregate_signature
+        .as_mut()
+        .unwrap()
+        .signers = vec![0b0111];
+    assert!(lite_certificate.check(&committee).is_err());
+
+    // A vote without a BLS signature can't be aggregated.
+    let vote = LiteVote::new(value.lite(), Round::Fast, &keys[0]);
+    assert!(LiteCertificate::try_from_votes(vec![vote], &committee).is_none());
+}
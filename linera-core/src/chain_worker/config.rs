@@ -5,7 +5,7 @@
 
 use std::{sync::Arc, time::Duration};
 
-use linera_base::crypto::KeyPair;
+use linera_base::crypto::{BlsKeyPair, KeyPair};
 
 /// Configuration parameters for the [`ChainWorkerState`][`super::state::ChainWorkerState`].
 #[derive(Clone, Default)]
@@ -13,6 +13,8 @@ pub struct ChainWorkerConfig {
     /// The signature key pair of the validator. The key may be missing for replicas
     /// without voting rights (possibly with a partial view of chains).
     pub key_pair: Option<Arc<KeyPair>>,
+    /// The BLS key pair of the validator, if it signs votes that can be aggregated.
+    pub bls_key_pair: Option<Arc<BlsKeyPair>>,
     /// Whether inactive chains are allowed in storage.
     pub allow_inactive_chains: bool,
     /// Whether new messages from deprecated epochs are allowed.
@@ -33,4 +35,15 @@ impl ChainWorkerConfig {
     pub fn key_pair(&self) -> Option<&KeyPair> {
         self.key_pair.as_ref().map(Arc::as_ref)
     }
+
+    /// Configures the `bls_key_pair` in this [`ChainWorkerConfig`].
+    pub fn with_bls_key_pair(mut self, bls_key_pair: impl Into<Option<BlsKeyPair>>) -> Self {
+        self.bls_key_pair = bls_key_pair.into().map(Arc::new);
+        self
+    }
+
+    /// Gets a reference to the [`BlsKeyPair`], if available.
+    pub fn bls_key_pair(&self) -> Option<&BlsKeyPair> {
+        self.bls_key_pair.as_ref().map(Arc::as_ref)
+    }
 }
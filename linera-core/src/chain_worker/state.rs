@@ -166,11 +166,16 @@ where
         let local_time = self.storage.clock().current_time();
         let signer = block.authenticated_signer;
 
-        let executed_block = self
-            .chain
-            .execute_block(&block, local_time, None)
-            .await?
-            .with(block);
+        // Make the blobs published by the block readable during execution, without
+        // persisting them.
+        let blobs_in_block = self.get_blobs(block.blob_ids()).await?;
+        self.chain
+            .context()
+            .extra()
+            .set_blobs_in_block(blobs_in_block);
+        let outcome = self.chain.execute_block(&block, local_time, None).await;
+        self.chain.context().extra().clear_blobs_in_block();
+        let executed_block = outcome?.with(block);
 
         let mut response = ChainInfoResponse::new(&self.chain, None);
         if let Some(signer) = signer {
@@ -256,6 +261,7 @@ where
             hashed_blobs,
             validated_block_certificate,
             signature: _,
+            approvals: _,
         } = &proposal;
         self.ensure_is_active()?;
         // Check the epoch.
@@ -297,12 +303,20 @@ where
         self.chain.remove_events_from_inboxes(block).await?;
         // Verify that all required bytecode hashed certificate values are available, and no
         // unrelated ones provided.
-        self.check_no_missing_blobs(block, hashed_certificate_values, hashed_blobs)
-            .await?;
-        // Write the values so that the bytecode is available during execution.
+        self.check_no_missing_blobs(
+            block,
+            &HashSet::new(),
+            hashed_certificate_values,
+            hashed_blobs,
+        )
+        .await?;
+        // Write the values so that the bytecode is available during execution. The blobs
+        // published by the block are only persisted once it is confirmed.
         self.storage
             .write_hashed_certificate_values(hashed_certificate_values)
             .await?;
+        let mut blobs_in_block = self.get_blobs(block.blob_ids()).await?;
+        blobs_in_block.extend(hashed_blobs.iter().cloned());
         let local_time = self.storage.clock().current_time();
         ensure!(
             block.timestamp.duration_since(local_time) <= self.config.grace_period,
@@ -318,7 +332,13 @@ where
                 .outcome
                 .clone()
         } else {
-            self.chain.execute_block(block, local_time, None).await?
+            self.chain
+                .context()
+                .extra()
+                .set_blobs_in_block(blobs_in_block);
+            let outcome = self.chain.execute_block(block, local_time, None).await;
+            self.chain.context().extra().clear_blobs_in_block();
+            outcome?
         };
         if round.is_fast() {
             let mut records = outcome.oracle_records.iter();
@@ -338,7 +358,13 @@ where
         self.chain.rollback();
         // Create the vote and store it in the chain state.
         let manager = self.chain.manager.get_mut();
-        manager.create_vote(proposal, outcome, self.config.key_pair(), local_time);
+        manager.create_vote(
+            proposal,
+            outcome,
+            self.config.key_pair(),
+            self.config.bls_key_pair(),
+            local_time,
+        );
         // Cache the value we voted on, so the client doesn't have to send it again.
         if let Some(vote) = manager.pending() {
             self.recent_hashed_certificate_values
@@ -399,6 +425,7 @@ where
         self.chain.manager.get_mut().create_final_vote(
             certificate,
             self.config.key_pair(),
+            self.config.bls_key_pair(),
             self.storage.clock().current_time(),
         );
         let info = ChainInfoResponse::new(&self.chain, self.config.key_pair());
@@ -429,6 +456,7 @@ where
             message_counts,
             state_hash,
             oracle_records,
+            events,
         } = &executed_block.outcome;
         // Check that the chain is active and ready for this confirmation.
         let tip = self.chain.tip_state.get().clone();
@@ -476,9 +504,14 @@ where
             WorkerError::InvalidBlockChaining
         );
         // Verify that all required bytecode hashed certificate values are available, and no
-        // unrelated ones provided.
-        self.check_no_missing_blobs(block, hashed_certificate_values, hashed_blobs)
-            .await?;
+        // unrelated ones provided. Blobs that were read during execution may be provided too.
+        self.check_no_missing_blobs(
+            block,
+            &executed_block.read_blob_ids(),
+            hashed_certificate_values,
+            hashed_blobs,
+        )
+        .await?;
         // Persist certificate and hashed certificate values.
         self.recent_hashed_certificate_values
             .insert_all(hashed_certificate_values.iter().map(Cow::Borrowed))
@@ -487,7 +520,7 @@ where
             self.cache_recent_blob(Cow::Borrowed(hashed_blob)).await;
         }
 
-        let blobs_in_block = self.get_blobs(block.blob_ids()).await?;
+        let blobs_in_block = self.get_blobs(executed_block.required_blob_ids()).await?;
         let (result_hashed_certificate_value, result_blobs, result_certificate) = tokio::join!(
             self.storage
                 .write_hashed_certificate_values(hashed_certificate_values),
@@ -497,12 +530,15 @@ where
         result_hashed_certificate_value?;
         result_blobs?;
         result_certificate?;
+        self.storage
+            .write_random_seed(block.chain_id, block.height, certificate.random_seed())
+            .await?;
         // Execute the block and update inboxes.
         self.chain.remove_events_from_inboxes(block).await?;
         let local_time = self.storage.clock().current_time();
-        let verified_outcome = self
+        let (verified_outcome, resources) = self
             .chain
-            .execute_block(block, local_time, Some(oracle_records.clone()))
+            .execute_block_with_resources(block, local_time, Some(oracle_records.clone()))
             .await?;
         // We should always agree on the messages and state hash.
         ensure!(
@@ -516,10 +552,23 @@ where
             *message_counts == verified_outcome.message_counts,
             WorkerError::IncorrectMessageCounts
         );
+        ensure!(
+            *events == verified_outcome.events,
+            WorkerError::IncorrectEvents
+        );
         ensure!(
             *state_hash == verified_outcome.state_hash,
             WorkerError::IncorrectStateHash
         );
+        // Persist the events, indexed by chain, stream and position in the stream.
+        let events_in_block = events.iter().flatten().cloned().collect::<Vec<_>>();
+        self.storage
+            .write_events(block.chain_id, &events_in_block)
+            .await?;
+        // Record the fuel the block used, for this validator's fuel usage reports.
+        self.storage
+            .write_block_fuel(block.epoch, block.chain_id, block.height, resources.fuel)
+            .await?;
         // Advance to next block height.
         let tip = self.chain.tip_state.get_mut();
         tip.block_hash = Some(certificate.hash());
@@ -537,6 +586,20 @@ where
                 hash: certificate.value.hash(),
             },
         });
+        if !events_in_block.is_empty() {
+            let event_streams = events_in_block
+                .into_iter()
+                .map(|event| event.stream_id)
+                .collect::<BTreeSet<_>>();
+            actions.notifications.push(Notification {
+                chain_id: block.chain_id,
+                reason: Reason::NewEvents {
+                    height: block.height,
+                    hash: certificate.value.hash(),
+                    event_streams,
+                },
+            });
+        }
         // Persist chain.
         self.chain.save().await?;
         self.recent_hashed_certificate_values
@@ -625,25 +688,36 @@ where
             if let Some(epoch) = self.chain.execution_state.system.epoch.get() {
                 let height = self.chain.tip_state.get().next_block_height;
                 let key_pair = self.config.key_pair();
+                let bls_key_pair = self.config.bls_key_pair();
                 let local_time = self.storage.clock().current_time();
                 let manager = self.chain.manager.get_mut();
-                if manager.vote_timeout(chain_id, height, *epoch, key_pair, local_time) {
+                if manager.vote_timeout(
+                    chain_id,
+                    height,
+                    *epoch,
+                    key_pair,
+                    bls_key_pair,
+                    local_time,
+                ) {
                     self.chain.save().await?;
                 }
             }
         }
         if query.request_fallback {
+            let local_time = self.storage.clock().current_time();
             if let (Some(epoch), Some(entry)) = (
                 self.chain.execution_state.system.epoch.get(),
-                self.chain.unskippable.front().await?,
+                self.chain.oldest_receivable_unskippable(local_time).await?,
             ) {
                 let ownership = self.chain.execution_state.system.ownership.get();
-                let elapsed = self.storage.clock().current_time().delta_since(entry.seen);
+                // A scheduled message is only overdue once it can be received.
+                let elapsed = local_time.delta_since(entry.seen.max(entry.receivable_from));
                 if elapsed >= ownership.timeout_config.fallback_duration {
                     let height = self.chain.tip_state.get().next_block_height;
                     let key_pair = self.config.key_pair();
+                    let bls_key_pair = self.config.bls_key_pair();
                     let manager = self.chain.manager.get_mut();
-                    if manager.vote_fallback(chain_id, height, *epoch, key_pair) {
+                    if manager.vote_fallback(chain_id, height, *epoch, key_pair, bls_key_pair) {
                         self.chain.save().await?;
                     }
                 }
@@ -746,17 +820,21 @@ where
     }
 
     /// Returns an error if the block requires bytecode or a blob we don't have, or if unrelated bytecode
-    /// hashed certificate values or blobs were provided.
+    /// hashed certificate values or blobs were provided. Blobs in `read_blob_ids` may be
+    /// provided, but are not required.
     async fn check_no_missing_blobs(
         &self,
         block: &Block,
+        read_blob_ids: &HashSet<BlobId>,
         hashed_certificate_values: &[HashedCertificateValue],
         hashed_blobs: &[HashedBlob],
     ) -> Result<(), WorkerError> {
         let missing_bytecodes = self
             .get_missing_bytecodes(block, hashed_certificate_values)
             .await?;
-        let missing_blobs = self.get_missing_blobs(block, hashed_blobs).await?;
+        let missing_blobs = self
+            .get_missing_blobs(block, read_blob_ids, hashed_blobs)
+            .await?;
 
         if missing_bytecodes.is_empty() {
             if missing_blobs.is_empty() {
@@ -778,6 +856,7 @@ where
     async fn get_missing_blobs(
         &self,
         block: &Block,
+        read_blob_ids: &HashSet<BlobId>,
         hashed_blobs: &[HashedBlob],
     ) -> Result<Vec<BlobId>, WorkerError> {
         let mut required_blob_ids = block.blob_ids();
@@ -785,7 +864,7 @@ where
         for hashed_blob in hashed_blobs {
             let blob_id = hashed_blob.id();
             ensure!(
-                required_blob_ids.remove(&blob_id),
+                required_blob_ids.remove(&blob_id) || read_blob_ids.contains(&blob_id),
                 WorkerError::UnneededBlob { blob_id }
             );
         }
@@ -3,7 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    collections::{hash_map, BTreeMap, HashMap},
+    borrow::Cow,
+    collections::{hash_map, BTreeMap, HashMap, HashSet},
     convert::Infallible,
     iter,
     ops::Deref,
@@ -17,7 +18,7 @@ use futures::{
 };
 use linera_base::{
     abi::Abi,
-    crypto::{CryptoHash, KeyPair, PublicKey},
+    crypto::{AccountPublicKey, AccountSignature, CryptoHash, KeyPair, PublicKey, Signature},
     data_types::{
         Amount, ApplicationPermissions, ArithmeticError, BlockHeight, HashedBlob, Round, Timestamp,
     },
@@ -27,18 +28,20 @@ use linera_base::{
 };
 use linera_chain::{
     data_types::{
-        Block, BlockAndRound, BlockProposal, Certificate, CertificateValue, ExecutedBlock,
-        HashedCertificateValue, IncomingMessage, LiteCertificate, LiteVote, MessageAction,
+        ApprovalPayload, Block, BlockAndRound, BlockProposal, Certificate, CertificateValue,
+        ExecutedBlock, HashedCertificateValue, IncomingMessage, LiteCertificate, LiteVote, Medium,
+        MessageAction,
     },
     ChainError, ChainExecutionContext, ChainStateView,
 };
 use linera_execution::{
-    committee::{Committee, Epoch, ValidatorName},
+    committee::{Committee, Epoch, SignedFuelUsage, ValidatorName},
     system::{
-        AdminOperation, OpenChainConfig, Recipient, SystemChannel, SystemOperation, UserData,
-        CREATE_APPLICATION_MESSAGE_INDEX, OPEN_CHAIN_MESSAGE_INDEX, PUBLISH_BYTECODE_MESSAGE_INDEX,
+        AdminOperation, MessageGrantPolicy, OpenChainConfig, Recipient, SystemChannel,
+        SystemOperation, UserData, CREATE_APPLICATION_MESSAGE_INDEX, OPEN_CHAIN_MESSAGE_INDEX,
+        PUBLISH_BYTECODE_MESSAGE_INDEX,
     },
-    Bytecode, BytecodeLocation, ExecutionError, Message, Operation, Query, Response,
+    Bytecode, BytecodeLocation, ExecutionError, Message, MessageKind, Operation, Query, Response,
     SystemExecutionError, SystemMessage, SystemQuery, SystemResponse, UserApplicationId,
 };
 use linera_storage::Storage;
@@ -58,6 +61,7 @@ use crate::{
         NotificationStream, ValidatorNodeProvider,
     },
     notifier::Notifier,
+    signer::{InMemorySigner, Signer, SignerError},
     updater::{communicate_with_quorum, CommunicateAction, CommunicationError, ValidatorUpdater},
     value_cache::ValueCache,
     worker::{DeliveryNotifiers, Notification, Reason, WorkerError, WorkerState},
@@ -98,7 +102,7 @@ impl<ValidatorNodeProvider: Clone> Client<ValidatorNodeProvider> {
         Self {
             validator_node_provider,
             max_pending_messages,
-            message_policy: MessagePolicy::Accept,
+            message_policy: MessagePolicy::default(),
             cross_chain_message_delivery,
             recent_hashed_certificate_values: Arc::new(ValueCache::default()),
             recent_hashed_blobs: Arc::new(ValueCache::default()),
@@ -127,10 +131,7 @@ impl<ValidatorNodeProvider: Clone> Client<ValidatorNodeProvider> {
         pending_block: Option<Block>,
         pending_blobs: BTreeMap<BlobId, HashedBlob>,
     ) -> ChainClient<ValidatorNodeProvider, Storage> {
-        let known_key_pairs = known_key_pairs
-            .into_iter()
-            .map(|kp| (Owner::from(kp.public()), kp))
-            .collect();
+        let known_key_pairs = InMemorySigner::new(known_key_pairs);
         let state = WorkerState::new_for_client(
             format!("Client node {:?}", chain_id),
             storage,
@@ -144,16 +145,18 @@ impl<ValidatorNodeProvider: Clone> Client<ValidatorNodeProvider> {
         ChainClient {
             chain_id,
             known_key_pairs,
+            signer: None,
             validator_node_provider: self.validator_node_provider.clone(),
             admin_id,
             max_pending_messages: self.max_pending_messages,
-            message_policy: self.message_policy,
+            message_policy: self.message_policy.clone(),
             cross_chain_message_delivery: self.cross_chain_message_delivery,
             received_certificate_trackers: HashMap::new(),
             block_hash,
             timestamp,
             next_block_height,
             pending_block,
+            pending_approvals: BTreeMap::new(),
             node_client,
             pending_blobs,
             notifier: self.notifier.clone(),
@@ -161,12 +164,13 @@ impl<ValidatorNodeProvider: Clone> Client<ValidatorNodeProvider> {
     }
 }
 
-/// Policies for automatically handling incoming messages.
+/// Blanket policies for automatically handling incoming messages.
 ///
 /// These apply to all messages except for the initial `OpenChain`, which is always accepted.
-#[derive(Copy, Clone, clap::ValueEnum)]
-pub enum MessagePolicy {
+#[derive(Copy, Clone, Debug, Default, clap::ValueEnum)]
+pub enum BlanketMessagePolicy {
     /// Automatically accept all incoming messages. Reject them only if execution fails.
+    #[default]
     Accept,
     /// Automatically reject tracked messages, ignore or skip untracked messages, but accept
     /// protected ones.
@@ -176,13 +180,140 @@ pub enum MessagePolicy {
     Ignore,
 }
 
+/// The policy for automatically handling incoming messages: a blanket policy, together with
+/// filtering rules for the messages it accepts.
+///
+/// Messages that don't pass the rules are handled as if the blanket policy was
+/// [`BlanketMessagePolicy::Reject`]. Protected and bouncing messages are always accepted.
+/// System messages are only subject to the blanket policy and the message kind rules.
+#[derive(Clone, Debug, Default)]
+pub struct MessagePolicy {
+    /// The blanket policy applied to all messages.
+    blanket: BlanketMessagePolicy,
+    /// If set, user messages from all other chains are rejected.
+    restrict_chain_ids_to: Option<HashSet<ChainId>>,
+    /// User messages from these chains are rejected.
+    reject_chain_ids: HashSet<ChainId>,
+    /// If set, user messages from all other applications are rejected. System messages are
+    /// not affected.
+    restrict_application_ids_to: Option<HashSet<UserApplicationId>>,
+    /// User messages from these applications are rejected.
+    reject_application_ids: HashSet<UserApplicationId>,
+    /// If set, messages of all other kinds are rejected.
+    restrict_kinds_to: Option<HashSet<MessageKind>>,
+    /// User messages with a smaller grant are rejected.
+    minimum_grant: Amount,
+    /// The maximum number of messages from the same origin chain to include in a block.
+    max_messages_per_origin: Option<usize>,
+}
+
+impl From<BlanketMessagePolicy> for MessagePolicy {
+    fn from(blanket: BlanketMessagePolicy) -> Self {
+        MessagePolicy::new(blanket)
+    }
+}
+
 impl MessagePolicy {
+    /// Creates a policy without any filtering rules.
+    pub fn new(blanket: BlanketMessagePolicy) -> Self {
+        MessagePolicy {
+            blanket,
+            ..MessagePolicy::default()
+        }
+    }
+
+    /// Returns this policy, only accepting user messages from the given chains.
+    pub fn with_restrict_chain_ids_to(
+        mut self,
+        chain_ids: impl IntoIterator<Item = ChainId>,
+    ) -> Self {
+        self.restrict_chain_ids_to = Some(chain_ids.into_iter().collect());
+        self
+    }
+
+    /// Returns this policy, rejecting user messages from the given chains.
+    pub fn with_reject_chain_ids(mut self, chain_ids: impl IntoIterator<Item = ChainId>) -> Self {
+        self.reject_chain_ids.extend(chain_ids);
+        self
+    }
+
+    /// Returns this policy, only accepting user messages from the given applications.
+    pub fn with_restrict_application_ids_to(
+        mut self,
+        application_ids: impl IntoIterator<Item = UserApplicationId>,
+    ) -> Self {
+        self.restrict_application_ids_to = Some(application_ids.into_iter().collect());
+        self
+    }
+
+    /// Returns this policy, rejecting user messages from the given applications.
+    pub fn with_reject_application_ids(
+        mut self,
+        application_ids: impl IntoIterator<Item = UserApplicationId>,
+    ) -> Self {
+        self.reject_application_ids.extend(application_ids);
+        self
+    }
+
+    /// Returns this policy, only accepting messages of the given kinds.
+    pub fn with_restrict_kinds_to(mut self, kinds: impl IntoIterator<Item = MessageKind>) -> Self {
+        self.restrict_kinds_to = Some(kinds.into_iter().collect());
+        self
+    }
+
+    /// Returns this policy, rejecting user messages with a grant smaller than `minimum_grant`.
+    pub fn with_minimum_grant(mut self, minimum_grant: Amount) -> Self {
+        self.minimum_grant = minimum_grant;
+        self
+    }
+
+    /// Returns this policy, including at most `max_messages` messages from the same origin
+    /// chain in a block.
+    pub fn with_max_messages_per_origin(mut self, max_messages: usize) -> Self {
+        self.max_messages_per_origin = Some(max_messages);
+        self
+    }
+
+    /// Returns the blanket policy.
+    pub fn blanket(&self) -> BlanketMessagePolicy {
+        self.blanket
+    }
+
     fn is_ignore(&self) -> bool {
-        matches!(self, Self::Ignore)
+        matches!(self.blanket, BlanketMessagePolicy::Ignore)
     }
 
-    fn is_reject(&self) -> bool {
-        matches!(self, Self::Reject)
+    /// Returns whether the message must be rejected, either by the blanket policy or because
+    /// it doesn't pass the filtering rules.
+    fn must_reject(&self, message: &IncomingMessage) -> bool {
+        if message.event.kind == MessageKind::Bouncing {
+            // Bouncing messages return what was sent to another chain; they can't bounce again.
+            return false;
+        }
+        if matches!(self.blanket, BlanketMessagePolicy::Reject) {
+            return true;
+        }
+        if self
+            .restrict_kinds_to
+            .as_ref()
+            .is_some_and(|kinds| !kinds.contains(&message.event.kind))
+        {
+            return true;
+        }
+        let Message::User { application_id, .. } = &message.event.message else {
+            return false;
+        };
+        let sender = &message.origin.sender;
+        self.restrict_chain_ids_to
+            .as_ref()
+            .is_some_and(|chain_ids| !chain_ids.contains(sender))
+            || self.reject_chain_ids.contains(sender)
+            || self
+                .restrict_application_ids_to
+                .as_ref()
+                .is_some_and(|application_ids| !application_ids.contains(application_id))
+            || self.reject_application_ids.contains(application_id)
+            || message.event.grant < self.minimum_grant
     }
 }
 
@@ -205,8 +336,12 @@ pub struct ChainClient<ValidatorNodeProvider, Storage> {
     next_block_height: BlockHeight,
     /// Pending block.
     pending_block: Option<Block>,
+    /// Other owners' approvals of the pending block, if the chain requires an approval threshold.
+    pending_approvals: BTreeMap<Owner, AccountSignature>,
     /// Known key pairs from present and past identities.
-    known_key_pairs: BTreeMap<Owner, KeyPair>,
+    known_key_pairs: InMemorySigner,
+    /// A signer holding other keys, outside of this client's memory.
+    signer: Option<Arc<dyn Signer>>,
     /// The ID of the admin chain.
     admin_id: ChainId,
 
@@ -280,6 +415,16 @@ pub enum ChainClientError {
     #[error("Found several possible identities to interact with chain {0}")]
     FoundMultipleKeysForChain(ChainId),
 
+    #[error("Failed to sign the block proposal: {0}")]
+    SignerError(#[from] SignerError),
+
+    #[error(
+        "The block must be approved by owners with a total weight of at least {threshold}, \
+         but only has approvals with weight {weight}; \
+         use the `linera request-approval` command to collect them"
+    )]
+    MissingApprovals { weight: u64, threshold: u64 },
+
     #[error(transparent)]
     ViewError(#[from] ViewError),
 }
@@ -291,6 +436,13 @@ impl From<Infallible> for ChainClientError {
 }
 
 impl<P, S> ChainClient<P, S> {
+    /// Returns this client, signing proposals with the given signer if the key of the chain
+    /// owner is not among the known key pairs.
+    pub fn with_signer(mut self, signer: Arc<dyn Signer>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
     pub fn chain_id(&self) -> ChainId {
         self.chain_id
     }
@@ -316,6 +468,21 @@ impl<P, S> ChainClient<P, S> {
     pub fn pending_blobs(&self) -> &BTreeMap<BlobId, HashedBlob> {
         &self.pending_blobs
     }
+
+    /// Returns the other owners' approvals of the pending block collected so far.
+    pub fn pending_approvals(&self) -> &BTreeMap<Owner, AccountSignature> {
+        &self.pending_approvals
+    }
+
+    /// Returns the policy for automatically handling incoming messages.
+    pub fn message_policy(&self) -> &MessagePolicy {
+        &self.message_policy
+    }
+
+    /// Sets the policy for automatically handling incoming messages.
+    pub fn set_message_policy(&mut self, message_policy: MessagePolicy) {
+        self.message_policy = message_policy;
+    }
 }
 
 enum ReceiveCertificateMode {
@@ -407,6 +574,9 @@ where
         if self.message_policy.is_ignore() {
             return Ok(pending_messages); // Ignore messages other than OpenChain.
         }
+        let local_time = self.storage_client().await.clock().current_time();
+        let mut waiting_origins = HashSet::new();
+        let mut messages_per_origin = HashMap::<ChainId, usize>::new();
         for mut message in requested_pending_messages {
             if pending_messages.len() >= self.max_pending_messages {
                 tracing::warn!(
@@ -415,12 +585,27 @@ where
                 );
                 break;
             }
-            if self.message_policy.is_reject() {
+            if waiting_origins.contains(&message.origin) {
+                continue;
+            }
+            if message.origin.medium == Medium::Scheduled && message.event.timestamp > local_time {
+                // Later messages from the same origin must not be received before this one.
+                waiting_origins.insert(message.origin);
+                continue;
+            }
+            if let Some(max_messages) = self.message_policy.max_messages_per_origin {
+                let count = messages_per_origin
+                    .entry(message.origin.sender)
+                    .or_default();
+                if *count >= max_messages {
+                    continue;
+                }
+            }
+            if !message.event.is_protected() && self.message_policy.must_reject(&message) {
                 if message.event.is_skippable() {
                     continue;
-                } else if message.event.is_tracked() {
-                    message.action = MessageAction::Reject;
                 }
+                message.action = MessageAction::Reject;
             }
             if let Message::System(SystemMessage::RegisterApplications { applications }) =
                 &message.event.message
@@ -439,11 +624,31 @@ where
                     continue; // These applications are already registered; skip register message.
                 }
             }
+            *messages_per_origin
+                .entry(message.origin.sender)
+                .or_default() += 1;
             pending_messages.push(message);
         }
         Ok(pending_messages)
     }
 
+    /// Returns the earliest time when a scheduled message in the inbox can be received, if
+    /// there are scheduled messages that are not due yet.
+    pub async fn next_scheduled_message_time(
+        &mut self,
+    ) -> Result<Option<Timestamp>, ChainClientError> {
+        let query = ChainInfoQuery::new(self.chain_id).with_pending_messages();
+        let info = self.node_client.handle_chain_info_query(query).await?.info;
+        let local_time = self.storage_client().await.clock().current_time();
+        Ok(info
+            .requested_pending_messages
+            .into_iter()
+            .filter(|message| message.origin.medium == Medium::Scheduled)
+            .map(|message| message.event.timestamp)
+            .filter(|timestamp| *timestamp > local_time)
+            .min())
+    }
+
     /// Obtains the set of committees trusted by the local chain.
     async fn committees(&mut self) -> Result<BTreeMap<Epoch, Committee>, LocalNodeError> {
         let (_epoch, committees) = self.epoch_and_committees(self.chain_id).await?;
@@ -524,33 +729,56 @@ where
             manager.ownership.is_active(),
             LocalNodeError::InactiveChain(self.chain_id)
         );
-        let mut identities = manager
-            .ownership
-            .all_owners()
-            .chain(&manager.leader)
-            .filter(|owner| self.known_key_pairs.contains_key(owner));
-        let Some(identity) = identities.next() else {
-            return Err(ChainClientError::CannotFindKeyForChain(self.chain_id));
-        };
-        ensure!(
-            identities.all(|id| id == identity),
-            ChainClientError::FoundMultipleKeysForChain(self.chain_id)
-        );
-        Ok(*identity)
+        let mut identity = None;
+        for owner in manager.ownership.all_owners().chain(&manager.leader) {
+            if self.known_public_key(owner).await?.is_none() {
+                continue;
+            }
+            ensure!(
+                identity.map_or(true, |id| id == *owner),
+                ChainClientError::FoundMultipleKeysForChain(self.chain_id)
+            );
+            identity = Some(*owner);
+        }
+        identity.ok_or(ChainClientError::CannotFindKeyForChain(self.chain_id))
     }
 
-    /// Obtains the key pair associated to the current identity.
+    /// Obtains the key pair associated to the current identity, if it is held in memory
+    /// rather than by an external signer.
     pub async fn key_pair(&mut self) -> Result<&KeyPair, ChainClientError> {
         let id = self.identity().await?;
-        Ok(self
-            .known_key_pairs
-            .get(&id)
-            .expect("key should be known at this point"))
+        self.known_key_pairs
+            .key_pair(&id)
+            .ok_or(ChainClientError::CannotFindKeyForChain(self.chain_id))
     }
 
     /// Obtains the public key associated to the current identity.
     pub async fn public_key(&mut self) -> Result<PublicKey, ChainClientError> {
-        Ok(self.key_pair().await?.public())
+        let id = self.identity().await?;
+        self.known_public_key(&id)
+            .await?
+            .ok_or(ChainClientError::CannotFindKeyForChain(self.chain_id))
+    }
+
+    /// Returns the public key of `owner`, if we can sign on its behalf.
+    async fn known_public_key(&self, owner: &Owner) -> Result<Option<PublicKey>, SignerError> {
+        if let Some(public_key) = self.known_key_pairs.public_key(owner).await? {
+            return Ok(Some(public_key));
+        }
+        match &self.signer {
+            Some(signer) => signer.public_key(owner).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Signs `message` on behalf of `owner`, with a known key pair or the signer.
+    async fn sign(&self, owner: &Owner, message: &[u8]) -> Result<Signature, SignerError> {
+        match &self.signer {
+            Some(signer) if self.known_key_pairs.key_pair(owner).is_none() => {
+                signer.sign(owner, message).await
+            }
+            _ => self.known_key_pairs.sign(owner, message).await,
+        }
     }
 
     /// Prepares the chain for the next operation.
@@ -577,11 +805,14 @@ where
                 ChainClientError::InternalError("Invalid chain of blocks in local node")
             );
         }
-        let ownership = &info.manager.ownership;
-        if ownership
-            .all_owners()
-            .any(|owner| !self.known_key_pairs.contains_key(owner))
-        {
+        let mut has_other_owners = false;
+        for owner in info.manager.ownership.all_owners() {
+            if self.known_public_key(owner).await?.is_none() {
+                has_other_owners = true;
+                break;
+            }
+        }
+        if has_other_owners {
             // For chains with any owner other than ourselves, we could be missing recent
             // certificates created by other owners. Further synchronize blocks from the network.
             // This is a best-effort that depends on network conditions.
@@ -668,6 +899,7 @@ where
             |name, node| {
                 let mut updater = ValidatorUpdater {
                     name,
+                    committee: committee.clone(),
                     node,
                     storage: storage_client.clone(),
                     local_node_recent_hashed_blobs: recent_hashed_blobs.clone(),
@@ -706,6 +938,7 @@ where
             |name, node| {
                 let mut updater = ValidatorUpdater {
                     name,
+                    committee: committee.clone(),
                     node,
                     storage: storage_client.clone(),
                     local_node_recent_hashed_blobs: recent_hashed_blobs.clone(),
@@ -729,7 +962,7 @@ where
         // * `communicate_with_quorum` ensured a sufficient "weight" of
         // (non-error) answers were returned by validators.
         // * each answer is a vote signed by the expected validator.
-        let certificate = LiteCertificate::try_from_votes(votes)
+        let certificate = LiteCertificate::try_from_votes(votes, committee)
             .ok_or_else(|| {
                 ChainClientError::InternalError("Vote values or rounds don't match; this is a bug")
             })?
@@ -737,6 +970,13 @@ where
             .ok_or_else(|| {
                 ChainClientError::ProtocolError("A quorum voted for an unexpected value")
             })?;
+        // Individual BLS signatures are not checked when the votes are received, so an
+        // invalid one is only detected in the aggregate.
+        if certificate.aggregate_signature().is_some() {
+            certificate.check(committee).map_err(|_| {
+                ChainClientError::ProtocolError("A validator sent an invalid BLS signature")
+            })?;
+        }
         Ok(certificate)
     }
 
@@ -1276,17 +1516,49 @@ where
             .await?;
         let hashed_blobs = self.read_local_blobs(block.blob_ids()).await?;
         // Create the final block proposal.
-        let key_pair = self.key_pair().await?;
-        let proposal = BlockProposal::new(
-            BlockAndRound {
-                block: block.clone(),
-                round,
-            },
-            key_pair,
+        let owner = self.identity().await?;
+        let public_key = self
+            .known_public_key(&owner)
+            .await?
+            .ok_or(ChainClientError::CannotFindKeyForChain(self.chain_id))?;
+        let content = BlockAndRound { block, round };
+        let message =
+            BlockProposal::signing_message(&content, validated_block_certificate.as_ref());
+        let signature = self.sign(&owner, &message).await?;
+        // A new block needs the other owners' approvals if the chain has an approval threshold.
+        let needs_approvals = validated_block_certificate.is_none()
+            && !matches!(round, Round::Validator(_))
+            && manager.ownership.requires_approvals(&owner);
+        let mut proposal = BlockProposal::new_with_signature(
+            content,
+            public_key,
+            signature,
             values,
             hashed_blobs,
             validated_block_certificate,
         );
+        if needs_approvals {
+            proposal = proposal.with_approvals(self.pending_approvals.clone());
+            let weight = proposal.check_approvals(&manager.ownership)?;
+            let threshold = manager.ownership.approval_threshold.unwrap_or_default();
+            ensure!(
+                weight >= threshold,
+                ChainClientError::MissingApprovals { weight, threshold }
+            );
+        }
+        self.submit_proposal_and_communicate(committee, proposal, hashed_value)
+            .await
+    }
+
+    /// Checks the block proposal locally, sends it to the validators and communicates the
+    /// resulting certificate.
+    async fn submit_proposal_and_communicate(
+        &mut self,
+        committee: Committee,
+        proposal: BlockProposal,
+        hashed_value: HashedCertificateValue,
+    ) -> Result<Certificate, ChainClientError> {
+        let block = proposal.content.block.clone();
         // Check the final block proposal. This will be cheaper after #1401.
         self.node_client
             .handle_block_proposal(proposal.clone())
@@ -1299,6 +1571,7 @@ where
             .await?;
         self.pending_block = None;
         self.pending_blobs.clear();
+        self.pending_approvals.clear();
         // Communicate the new certificate now.
         self.communicate_chain_updates(
             &committee,
@@ -1426,6 +1699,7 @@ where
             .stage_block_execution_and_discard_failing_messages(block)
             .await?;
         self.pending_block = Some(executed_block.block.clone());
+        self.pending_approvals.clear();
         Ok(HashedCertificateValue::new_confirmed(executed_block))
     }
 
@@ -1693,6 +1967,7 @@ where
             if block.height != info.next_block_height {
                 self.pending_block = None;
                 self.pending_blobs.clear();
+                self.pending_approvals.clear();
             }
         }
         // If there is a validated block in the current round, finalize it.
@@ -1784,6 +2059,148 @@ where
     pub fn clear_pending_block(&mut self) {
         self.pending_block = None;
         self.pending_blobs.clear();
+        self.pending_approvals.clear();
+    }
+
+    /// Signs an approval of another owner's `block`, on a chain that requires an approval
+    /// threshold.
+    pub async fn approve_block(
+        &mut self,
+        block: &Block,
+    ) -> Result<(Owner, AccountSignature), ChainClientError> {
+        ensure!(
+            block.chain_id == self.chain_id,
+            ChainClientError::BlockProposalError("The block belongs to a different chain")
+        );
+        let owner = self.identity().await?;
+        let message = BlockProposal::approval_message(block);
+        let signature = self.sign(&owner, &message).await?;
+        Ok((owner, signature.into()))
+    }
+
+    /// Adds another owner's approval of the pending block. It will be included in the
+    /// block proposal.
+    pub async fn add_approval(
+        &mut self,
+        owner: Owner,
+        signature: AccountSignature,
+    ) -> Result<(), ChainClientError> {
+        let block = self
+            .pending_block
+            .clone()
+            .ok_or(ChainClientError::BlockProposalError(
+                "There is no pending block to approve",
+            ))?;
+        let ownership = self.chain_info().await?.manager.ownership;
+        let (public_key, _) = ownership
+            .owners
+            .get(&owner)
+            .ok_or(ChainError::InvalidApprover(owner))?;
+        let payload = ApprovalPayload {
+            block: Cow::Borrowed(&block),
+        };
+        signature
+            .check(&payload, *public_key)
+            .map_err(ChainError::from)?;
+        self.pending_approvals.insert(owner, signature);
+        Ok(())
+    }
+
+    /// Creates a block with the given operations and the incoming messages from the inbox,
+    /// authenticated by `owner`, without signing or proposing it. Returns it together with the
+    /// round to propose it in, e.g. to be signed offline and submitted with
+    /// [`ChainClient::submit_signed_proposal`].
+    pub async fn build_block(
+        &mut self,
+        operations: Vec<Operation>,
+        owner: Owner,
+    ) -> Result<BlockAndRound, ChainClientError> {
+        self.prepare_chain().await?;
+        ensure!(
+            self.pending_block.is_none(),
+            ChainClientError::BlockProposalError(
+                "Client state has a pending block; \
+                 use the `linera retry-pending-block` command to commit that first"
+            )
+        );
+        let manager = self.chain_info_with_manager_values().await?.manager;
+        ensure!(
+            manager.highest_validated().is_none(),
+            ChainClientError::BlockProposalError(
+                "A different block has already been validated at this height"
+            )
+        );
+        let incoming_messages = self.pending_messages().await?;
+        let timestamp = self.next_timestamp(&incoming_messages).await;
+        let block = Block {
+            epoch: self.epoch().await?,
+            chain_id: self.chain_id,
+            incoming_messages,
+            operations,
+            previous_block_hash: self.block_hash,
+            height: self.next_block_height,
+            authenticated_signer: Some(owner),
+            timestamp,
+        };
+        // Make sure every incoming message succeeds and otherwise remove them.
+        let (executed_block, _) = self
+            .stage_block_execution_and_discard_failing_messages(block)
+            .await?;
+        Ok(BlockAndRound {
+            block: executed_block.block,
+            round: manager.current_round,
+        })
+    }
+
+    /// Sends a block proposal that was signed elsewhere, e.g. offline, to the validators, and
+    /// returns the certificate of the committed block.
+    pub async fn submit_signed_proposal(
+        &mut self,
+        mut proposal: BlockProposal,
+    ) -> Result<Certificate, ChainClientError> {
+        self.prepare_chain().await?;
+        let block = proposal.content.block.clone();
+        ensure!(
+            block.chain_id == self.chain_id,
+            ChainClientError::BlockProposalError("The block belongs to a different chain")
+        );
+        ensure!(
+            block.height == self.next_block_height,
+            ChainClientError::BlockProposalError("Unexpected block height")
+        );
+        ensure!(
+            block.previous_block_hash == self.block_hash,
+            ChainClientError::BlockProposalError("Unexpected previous block hash")
+        );
+        if let Some(pending) = &self.pending_block {
+            ensure!(
+                *pending == block,
+                ChainClientError::BlockProposalError(
+                    "Client state has a different pending block; \
+                     use the `linera retry-pending-block` command to commit that first"
+                )
+            );
+        }
+        let (executed_block, _) = self
+            .node_client
+            .stage_block_execution(block.clone())
+            .await?;
+        let hashed_value = if proposal.content.round.is_fast() {
+            HashedCertificateValue::new_confirmed(executed_block)
+        } else {
+            HashedCertificateValue::new_validated(executed_block)
+        };
+        // Collect the hashed certificate values and blobs required for execution.
+        let committee = self.local_committee().await?;
+        let nodes: Vec<(ValidatorName, P::Node)> =
+            self.validator_node_provider.make_nodes(&committee)?;
+        proposal.hashed_certificate_values = self
+            .node_client
+            .read_or_download_hashed_certificate_values(nodes, block.bytecode_locations())
+            .await?;
+        proposal.hashed_blobs = self.read_local_blobs(block.blob_ids()).await?;
+        self.submit_proposal_and_communicate(committee, proposal, hashed_value)
+            .await
     }
 
     /// Processes confirmed operation for which this chain is a recipient.
@@ -1801,20 +2218,21 @@ where
         key_pair: KeyPair,
     ) -> Result<ClientOutcome<Certificate>, ChainClientError> {
         let new_public_key = key_pair.public();
-        self.known_key_pairs.insert(new_public_key.into(), key_pair);
+        self.known_key_pairs.insert(key_pair);
         self.transfer_ownership(new_public_key).await
     }
 
     /// Transfers ownership of the chain to a single super owner.
     pub async fn transfer_ownership(
         &mut self,
-        new_public_key: PublicKey,
+        new_public_key: impl Into<AccountPublicKey>,
     ) -> Result<ClientOutcome<Certificate>, ChainClientError> {
         self.execute_operation(Operation::System(SystemOperation::ChangeOwnership {
-            super_owners: vec![new_public_key],
+            super_owners: vec![new_public_key.into()],
             owners: Vec::new(),
             multi_leader_rounds: 2,
             timeout_config: TimeoutConfig::default(),
+            approval_threshold: None,
         }))
         .await
     }
@@ -1822,9 +2240,10 @@ where
     /// Adds another owner to the chain, and turns existing super owners into regular owners.
     pub async fn share_ownership(
         &mut self,
-        new_public_key: PublicKey,
+        new_public_key: impl Into<AccountPublicKey>,
         new_weight: u64,
     ) -> Result<ClientOutcome<Certificate>, ChainClientError> {
+        let new_public_key = new_public_key.into();
         loop {
             let ownership = self.prepare_chain().await?.manager.ownership;
             ensure!(
@@ -1846,6 +2265,7 @@ where
                 owners,
                 multi_leader_rounds: ownership.multi_leader_rounds,
                 timeout_config: ownership.timeout_config,
+                approval_threshold: ownership.approval_threshold,
             })];
             match self.execute_block(messages, operations).await? {
                 ExecuteBlockOutcome::Executed(certificate) => {
@@ -1875,6 +2295,7 @@ where
             owners: ownership.owners.values().cloned().collect(),
             multi_leader_rounds: ownership.multi_leader_rounds,
             timeout_config: ownership.timeout_config.clone(),
+            approval_threshold: ownership.approval_threshold,
         }))
         .await
     }
@@ -1888,6 +2309,15 @@ where
         self.execute_operation(operation.into()).await
     }
 
+    /// Changes the grant required for incoming user messages on this chain.
+    pub async fn change_message_grant_policy(
+        &mut self,
+        message_grant_policy: MessageGrantPolicy,
+    ) -> Result<ClientOutcome<Certificate>, ChainClientError> {
+        let operation = SystemOperation::ChangeMessageGrantPolicy(message_grant_policy);
+        self.execute_operation(operation.into()).await
+    }
+
     /// Opens a new chain with a derived UID.
     pub async fn open_chain(
         &mut self,
@@ -2062,6 +2492,39 @@ where
         }
     }
 
+    /// Creates a new committee with the prices of the current one adjusted to the fuel
+    /// consumption per block reported by the validators (admin chains only).
+    pub async fn adjust_fees(
+        &mut self,
+        reports: Vec<SignedFuelUsage>,
+    ) -> Result<ClientOutcome<Certificate>, ChainClientError> {
+        loop {
+            self.prepare_chain().await?;
+            let epoch = self.epoch().await?;
+            let messages = self.pending_messages().await?;
+            match self
+                .execute_block(
+                    messages,
+                    vec![Operation::System(SystemOperation::Admin(
+                        AdminOperation::AdjustFees {
+                            epoch: epoch.try_add_one()?,
+                            reports: reports.clone(),
+                        },
+                    ))],
+                )
+                .await?
+            {
+                ExecuteBlockOutcome::Executed(certificate) => {
+                    return Ok(ClientOutcome::Committed(certificate))
+                }
+                ExecuteBlockOutcome::Conflict(_) => continue,
+                ExecuteBlockOutcome::WaitForTimeout(timeout) => {
+                    return Ok(ClientOutcome::WaitForTimeout(timeout));
+                }
+            };
+        }
+    }
+
     /// Creates an empty block to process all incoming messages. This may require several blocks.
     ///
     /// If not all certificates could be processed due to a timeout, the timestamp for when to retry
@@ -2371,6 +2834,9 @@ where
                     error!("Fail to synchronize new block after notification");
                 }
             }
+            Reason::NewEvents { .. } => {
+                // Events are always accompanied by a `NewBlock` notification for the same block.
+            }
         }
     }
 
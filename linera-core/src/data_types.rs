@@ -24,7 +24,7 @@ use linera_storage::ChainRuntimeContext;
 use linera_views::{common::Context, views::ViewError};
 use serde::{Deserialize, Serialize};
 
-use crate::client::ChainClientError;
+use crate::{client::ChainClientError, node::MAX_CERTIFICATES_PER_REQUEST};
 
 /// A range of block heights as used in ChainInfoQuery.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -42,6 +42,28 @@ impl BlockHeightRange {
         let limit = Some(1);
         BlockHeightRange { start, limit }
     }
+
+    /// Returns the number of certificates to serve for this range in a single response: its
+    /// limit, capped at [`MAX_CERTIFICATES_PER_REQUEST`].
+    pub fn page_limit(&self) -> u64 {
+        self.limit.map_or(MAX_CERTIFICATES_PER_REQUEST, |limit| {
+            limit.min(MAX_CERTIFICATES_PER_REQUEST)
+        })
+    }
+
+    /// Returns the rest of the range after a page of `count` certificates, or `None` if the
+    /// page was the last one.
+    pub fn after_page(&self, count: u64) -> Option<BlockHeightRange> {
+        if count < self.page_limit() {
+            return None;
+        }
+        let limit = match self.limit {
+            Some(limit) => Some(limit.checked_sub(count).filter(|limit| *limit > 0)?),
+            None => None,
+        };
+        let start = self.start.try_add(BlockHeight(count)).ok()?;
+        Some(BlockHeightRange { start, limit })
+    }
 }
 
 /// Request information about a chain.
@@ -10,6 +10,7 @@ pub mod data_types;
 pub mod local_node;
 pub mod node;
 pub mod notifier;
+pub mod signer;
 #[cfg(with_testing)]
 #[path = "unit_tests/test_utils.rs"]
 pub mod test_utils;
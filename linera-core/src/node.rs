@@ -4,7 +4,7 @@
 
 use futures::stream::{BoxStream, LocalBoxStream, Stream};
 use linera_base::{
-    crypto::CryptoError,
+    crypto::{CryptoError, CryptoHash},
     data_types::{ArithmeticError, Blob, BlockHeight, HashedBlob},
     identifiers::{BlobId, ChainId},
 };
@@ -22,7 +22,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
-    data_types::{ChainInfoQuery, ChainInfoResponse},
+    data_types::{BlockHeightRange, ChainInfoQuery, ChainInfoResponse},
     worker::{Notification, WorkerError},
 };
 
@@ -31,6 +31,9 @@ pub type NotificationStream = BoxStream<'static, Notification>;
 /// A pinned [`Stream`] of Notifications, without the `Send` constraint.
 pub type LocalNotificationStream = LocalBoxStream<'static, Notification>;
 
+/// The maximum number of certificates that a validator returns in a single response.
+pub const MAX_CERTIFICATES_PER_REQUEST: u64 = 100;
+
 /// Whether to wait for the delivery of outgoing cross-chain messages.
 #[derive(Debug, Default, Clone, Copy)]
 pub enum CrossChainMessageDelivery {
@@ -82,6 +85,27 @@ pub trait LocalValidatorNode {
     ) -> Result<Self::NotificationStream, NodeError>;
 
     async fn download_blob(&mut self, blob_id: BlobId) -> Result<Blob, NodeError>;
+
+    /// Downloads the certificate with the given hash.
+    async fn download_certificate(&mut self, hash: CryptoHash) -> Result<Certificate, NodeError>;
+
+    /// Downloads the certificates with the given hashes, in the same order. At most
+    /// [`MAX_CERTIFICATES_PER_REQUEST`] hashes can be requested at once.
+    async fn download_certificates(
+        &mut self,
+        hashes: Vec<CryptoHash>,
+    ) -> Result<Vec<Certificate>, NodeError>;
+
+    /// Downloads the certificates of the confirmed blocks of a chain in the given range of
+    /// heights, in increasing order. Validators serve at most [`MAX_CERTIFICATES_PER_REQUEST`]
+    /// certificates per page: over gRPC all the pages of the range are streamed, while other
+    /// transports only return the first one, so that longer ranges are read one page at a
+    /// time, each starting after the last certificate of the previous one.
+    async fn download_certificates_by_height(
+        &mut self,
+        chain_id: ChainId,
+        range: BlockHeightRange,
+    ) -> Result<Vec<Certificate>, NodeError>;
 }
 
 /// Turn an address into a validator node.
@@ -207,6 +231,12 @@ pub enum NodeError {
 
     #[error("Failed to make a chain info query on the local node: {error}")]
     LocalNodeQuery { error: String },
+
+    #[error(
+        "Cannot download {0} certificates at once; the maximum is {max}",
+        max = MAX_CERTIFICATES_PER_REQUEST
+    )]
+    TooManyCertificatesRequested(u64),
 }
 
 impl From<tonic::Status> for NodeError {
@@ -0,0 +1,88 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing of block proposals on behalf of chain owners.
+//!
+//! A [`ChainClient`](crate::client::ChainClient) never needs the secret keys of the owners
+//! itself: it asks a [`Signer`] to sign the proposals. The keys may live in memory, e.g. in a
+//! wallet, or in a separate process or device.
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use linera_base::{
+    crypto::{KeyPair, PublicKey, Signature},
+    identifiers::Owner,
+};
+use thiserror::Error;
+
+/// A backend holding the secret keys of some chain owners.
+#[cfg_attr(not(web), async_trait)]
+#[cfg_attr(web, async_trait(?Send))]
+pub trait Signer: Send + Sync {
+    /// Returns the public key of `owner`, or `None` if this signer doesn't hold its key.
+    async fn public_key(&self, owner: &Owner) -> Result<Option<PublicKey>, SignerError>;
+
+    /// Signs `message` with the key of `owner`.
+    ///
+    /// The message is the output of [`Signature::signing_message`], so the signature can be
+    /// checked against the signed value with [`Signature::check`].
+    async fn sign(&self, owner: &Owner, message: &[u8]) -> Result<Signature, SignerError>;
+}
+
+/// Error type for [`Signer`] implementations.
+#[derive(Debug, Error)]
+pub enum SignerError {
+    #[error("No key is available for owner {0}")]
+    UnknownOwner(Owner),
+
+    #[error("Signer I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Signer backend error: {0}")]
+    Backend(String),
+}
+
+/// A [`Signer`] holding the key pairs in memory, e.g. the keys stored in a wallet.
+#[derive(Default)]
+pub struct InMemorySigner {
+    key_pairs: BTreeMap<Owner, KeyPair>,
+}
+
+impl InMemorySigner {
+    /// Creates a signer holding the given key pairs.
+    pub fn new(key_pairs: impl IntoIterator<Item = KeyPair>) -> Self {
+        let key_pairs = key_pairs
+            .into_iter()
+            .map(|key_pair| (Owner::from(key_pair.public()), key_pair))
+            .collect();
+        InMemorySigner { key_pairs }
+    }
+
+    /// Adds a key pair to the signer.
+    pub fn insert(&mut self, key_pair: KeyPair) {
+        self.key_pairs
+            .insert(Owner::from(key_pair.public()), key_pair);
+    }
+
+    /// Returns the key pair of `owner`, if known.
+    pub fn key_pair(&self, owner: &Owner) -> Option<&KeyPair> {
+        self.key_pairs.get(owner)
+    }
+}
+
+#[cfg_attr(not(web), async_trait)]
+#[cfg_attr(web, async_trait(?Send))]
+impl Signer for InMemorySigner {
+    async fn public_key(&self, owner: &Owner) -> Result<Option<PublicKey>, SignerError> {
+        Ok(self.key_pairs.get(owner).map(KeyPair::public))
+    }
+
+    async fn sign(&self, owner: &Owner, message: &[u8]) -> Result<Signature, SignerError> {
+        let key_pair = self
+            .key_pairs
+            .get(owner)
+            .ok_or(SignerError::UnknownOwner(*owner))?;
+        Ok(Signature::new_from_message(message, key_pair))
+    }
+}
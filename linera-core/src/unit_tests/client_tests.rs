@@ -14,7 +14,9 @@ use linera_base::{
     ownership::{ChainOwnership, TimeoutConfig},
 };
 use linera_chain::{
-    data_types::{CertificateValue, Event, ExecutedBlock, IncomingMessage, Medium, Origin},
+    data_types::{
+        BlockProposal, CertificateValue, Event, ExecutedBlock, IncomingMessage, Medium, Origin,
+    },
     ChainError, ChainExecutionContext,
 };
 use linera_execution::{
@@ -36,12 +38,16 @@ use crate::test_utils::ScyllaDbStorageBuilder;
 #[cfg(feature = "storage_service")]
 use crate::test_utils::ServiceStorageBuilder;
 use crate::{
-    client::{ArcChainClient, ChainClientError, ClientOutcome, MessageAction, MessagePolicy},
+    client::{
+        ArcChainClient, BlanketMessagePolicy, ChainClientError, ClientOutcome, MessageAction,
+        MessagePolicy,
+    },
+    data_types::BlockHeightRange,
     local_node::LocalNodeError,
     node::{
         CrossChainMessageDelivery,
         NodeError::{self, ClientIoError},
-        ValidatorNode,
+        ValidatorNode, MAX_CERTIFICATES_PER_REQUEST,
     },
     test_utils::{FaultType, MemoryStorageBuilder, StorageBuilder, TestBuilder},
     updater::CommunicationError,
@@ -285,6 +291,88 @@ where
     Ok(())
 }
 
+#[test_case(MemoryStorageBuilder::default(); "memory")]
+#[cfg_attr(feature = "storage_service", test_case(ServiceStorageBuilder::new().await; "storage_service"))]
+#[cfg_attr(feature = "rocksdb", test_case(RocksDbStorageBuilder::new().await; "rocks_db"))]
+#[cfg_attr(feature = "dynamodb", test_case(DynamoDbStorageBuilder::default(); "dynamo_db"))]
+#[cfg_attr(feature = "scylladb", test_case(ScyllaDbStorageBuilder::default(); "scylla_db"))]
+#[test_log::test(tokio::test)]
+async fn test_download_certificates<B>(storage_builder: B) -> anyhow::Result<()>
+where
+    B: StorageBuilder,
+    ViewError: From<<B::Storage as Storage>::ContextError>,
+{
+    let mut builder = TestBuilder::new(storage_builder, 1, 0).await?;
+    let mut sender = builder
+        .add_initial_chain(ChainDescription::Root(1), Amount::from_tokens(4))
+        .await?;
+    let mut certificates = Vec::new();
+    for _ in 0..3 {
+        let certificate = sender
+            .transfer_to_account(
+                None,
+                Amount::ONE,
+                Account::chain(ChainId::root(2)),
+                UserData::default(),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        certificates.push(certificate);
+    }
+    let node = builder.node(0);
+
+    let certificate = node.download_certificate(certificates[1].hash()).await?;
+    assert_eq!(certificate.value, certificates[1].value);
+
+    let hashes = certificates
+        .iter()
+        .rev()
+        .map(|certificate| certificate.hash());
+    let downloaded = node.download_certificates(hashes.collect()).await?;
+    assert_eq!(downloaded.len(), 3);
+    assert_eq!(downloaded[0].value, certificates[2].value);
+    assert_eq!(downloaded[2].value, certificates[0].value);
+
+    let range = BlockHeightRange {
+        start: BlockHeight::from(1),
+        limit: Some(1),
+    };
+    let downloaded = node
+        .download_certificates_by_height(sender.chain_id, range)
+        .await?;
+    assert_eq!(downloaded.len(), 1);
+    assert_eq!(downloaded[0].value, certificates[1].value);
+
+    let range = BlockHeightRange {
+        start: BlockHeight::from(1),
+        limit: None,
+    };
+    let downloaded = node
+        .download_certificates_by_height(sender.chain_id, range)
+        .await?;
+    assert_eq!(downloaded.len(), 2);
+    assert_eq!(downloaded[1].value, certificates[2].value);
+
+    let range = BlockHeightRange {
+        start: BlockHeight::from(5),
+        limit: None,
+    };
+    assert!(node
+        .download_certificates_by_height(sender.chain_id, range)
+        .await?
+        .is_empty());
+
+    // Larger batches must be split into several requests.
+    let hashes = vec![certificates[0].hash(); MAX_CERTIFICATES_PER_REQUEST as usize + 1];
+    assert_matches!(
+        node.download_certificates(hashes).await,
+        Err(NodeError::TooManyCertificatesRequested(count))
+            if count == MAX_CERTIFICATES_PER_REQUEST + 1
+    );
+    Ok(())
+}
+
 #[test_case(MemoryStorageBuilder::default(); "memory")]
 #[cfg_attr(feature = "storage_service", test_case(ServiceStorageBuilder::new().await; "storage_service"))]
 #[cfg_attr(feature = "rocksdb", test_case(RocksDbStorageBuilder::new().await; "rocks_db"))]
@@ -1421,12 +1509,13 @@ where
     let pub_key1 = key_pair1.public();
     let owner_change_op = SystemOperation::ChangeOwnership {
         super_owners: Vec::new(),
-        owners: vec![(pub_key0, 100), (pub_key1, 100)],
+        owners: vec![(pub_key0.into(), 100), (pub_key1.into(), 100)],
         multi_leader_rounds: 10,
         timeout_config: TimeoutConfig {
             fast_round_duration: Some(TimeDelta::from_secs(5)),
             ..TimeoutConfig::default()
         },
+        approval_threshold: None,
     }
     .into();
     client_a.execute_operation(owner_change_op).await.unwrap();
@@ -1480,6 +1569,153 @@ where
     Ok(())
 }
 
+#[test_case(MemoryStorageBuilder::default(); "memory")]
+#[cfg_attr(feature = "storage_service", test_case(ServiceStorageBuilder::new().await; "storage_service"))]
+#[cfg_attr(feature = "rocksdb", test_case(RocksDbStorageBuilder::new().await; "rocks_db"))]
+#[cfg_attr(feature = "dynamodb", test_case(DynamoDbStorageBuilder::default(); "dynamo_db"))]
+#[cfg_attr(feature = "scylladb", test_case(ScyllaDbStorageBuilder::default(); "scylla_db"))]
+#[test_log::test(tokio::test)]
+async fn test_approval_threshold<B>(storage_builder: B) -> anyhow::Result<()>
+where
+    B: StorageBuilder,
+    ViewError: From<<B::Storage as Storage>::ContextError>,
+{
+    // Configure a chain with two regular owners who both have to approve every block.
+    let mut builder = TestBuilder::new(storage_builder, 4, 1).await?;
+    let description = ChainDescription::Root(1);
+    let chain_id = ChainId::from(description);
+    let mut client_a = builder
+        .add_initial_chain(description, Amount::from_tokens(10))
+        .await?;
+    let pub_key0 = client_a.public_key().await.unwrap();
+    let key_pair1 = KeyPair::generate();
+    let owner1 = Owner::from(key_pair1.public());
+    let owner_change_op = SystemOperation::ChangeOwnership {
+        super_owners: Vec::new(),
+        owners: vec![(pub_key0.into(), 1), (key_pair1.public().into(), 1)],
+        multi_leader_rounds: 10,
+        timeout_config: TimeoutConfig::default(),
+        approval_threshold: Some(2),
+    }
+    .into();
+    client_a.execute_operation(owner_change_op).await.unwrap();
+    let mut client_b = builder
+        .make_client(
+            chain_id,
+            key_pair1,
+            client_a.block_hash,
+            BlockHeight::from(1),
+        )
+        .await?;
+
+    // Owner 0 alone cannot commit a block.
+    let result = client_a
+        .burn(None, Amount::from_tokens(1), UserData::default())
+        .await;
+    assert_matches!(
+        result,
+        Err(ChainClientError::MissingApprovals {
+            weight: 1,
+            threshold: 2
+        })
+    );
+    let block = client_a.pending_block().clone().unwrap();
+
+    // An approval must be signed by the owner it claims to be from.
+    let (_, wrong_signature) = client_a.approve_block(&block).await?;
+    assert!(client_a
+        .add_approval(owner1, wrong_signature)
+        .await
+        .is_err());
+
+    // Once owner 1 approves the block, it can be committed.
+    client_b.synchronize_from_validators().await?;
+    let (owner, signature) = client_b.approve_block(&block).await?;
+    assert_eq!(owner, owner1);
+    client_a.add_approval(owner, signature).await?;
+    let certificate = match client_a.process_pending_block().await? {
+        ClientOutcome::Committed(Some(certificate)) => certificate,
+        outcome => panic!("Unexpected outcome: {outcome:?}"),
+    };
+    assert_eq!(certificate.value().block(), Some(&block));
+    assert!(client_a.pending_approvals().is_empty());
+    assert_eq!(
+        client_a.local_balance().await.unwrap(),
+        Amount::from_tokens(9)
+    );
+    Ok(())
+}
+
+#[test_case(MemoryStorageBuilder::default(); "memory")]
+#[cfg_attr(feature = "storage_service", test_case(ServiceStorageBuilder::new().await; "storage_service"))]
+#[cfg_attr(feature = "rocksdb", test_case(RocksDbStorageBuilder::new().await; "rocks_db"))]
+#[cfg_attr(feature = "dynamodb", test_case(DynamoDbStorageBuilder::default(); "dynamo_db"))]
+#[cfg_attr(feature = "scylladb", test_case(ScyllaDbStorageBuilder::default(); "scylla_db"))]
+#[test_log::test(tokio::test)]
+async fn test_offline_block_signing<B>(storage_builder: B) -> anyhow::Result<()>
+where
+    B: StorageBuilder,
+    ViewError: From<<B::Storage as Storage>::ContextError>,
+{
+    let mut builder = TestBuilder::new(storage_builder, 4, 1).await?;
+    let mut sender = builder
+        .add_initial_chain(ChainDescription::Root(1), Amount::from_tokens(4))
+        .await?;
+    let mut receiver = builder
+        .add_initial_chain(ChainDescription::Root(2), Amount::ZERO)
+        .await?;
+    sender
+        .transfer_to_account(
+            None,
+            Amount::from_tokens(3),
+            Account::chain(ChainId::root(2)),
+            UserData::default(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Build a block that receives the tokens and burns one of them, without signing it.
+    let key_pair = receiver.key_pair().await?.copy();
+    let owner = Owner::from(key_pair.public());
+    receiver.synchronize_from_validators().await?;
+    let burn = SystemOperation::Transfer {
+        owner: None,
+        recipient: Recipient::Burn,
+        amount: Amount::ONE,
+        user_data: UserData::default(),
+    };
+    let content = receiver.build_block(vec![burn.into()], owner).await?;
+    assert_eq!(content.block.incoming_messages.len(), 1);
+    assert_eq!(content.block.authenticated_signer, Some(owner));
+    assert!(receiver.pending_block().is_none());
+
+    // Sign it with nothing but the key, and submit the proposal.
+    let message = BlockProposal::signing_message(&content, None);
+    let signature = Signature::new_from_message(&message, &key_pair);
+    let proposal = BlockProposal::new_with_signature(
+        content.clone(),
+        key_pair.public(),
+        signature,
+        Vec::new(),
+        Vec::new(),
+        None,
+    );
+    let certificate = receiver.submit_signed_proposal(proposal.clone()).await?;
+    assert_eq!(certificate.value().block(), Some(&content.block));
+    assert_eq!(
+        receiver.local_balance().await.unwrap(),
+        Amount::from_tokens(2)
+    );
+
+    // The same proposal cannot be submitted twice.
+    assert_matches!(
+        receiver.submit_signed_proposal(proposal).await,
+        Err(ChainClientError::BlockProposalError(_))
+    );
+    Ok(())
+}
+
 #[test_case(MemoryStorageBuilder::default(); "memory")]
 #[cfg_attr(feature = "storage_service", test_case(ServiceStorageBuilder::new().await; "storage_service"))]
 #[cfg_attr(feature = "rocksdb", test_case(RocksDbStorageBuilder::new().await; "rocks_db"))]
@@ -1503,9 +1739,10 @@ where
 
     let owner_change_op = SystemOperation::ChangeOwnership {
         super_owners: Vec::new(),
-        owners: vec![(pub_key0, 100), (pub_key1, 100)],
+        owners: vec![(pub_key0.into(), 100), (pub_key1.into(), 100)],
         multi_leader_rounds: 0,
         timeout_config: TimeoutConfig::default(),
+        approval_threshold: None,
     }
     .into();
     client.execute_operation(owner_change_op).await.unwrap();
@@ -1629,12 +1866,13 @@ where
     let pub_key1 = key_pair1.public();
     let owner_change_op = SystemOperation::ChangeOwnership {
         super_owners: Vec::new(),
-        owners: vec![(pub_key0, 100), (pub_key1, 100)],
+        owners: vec![(pub_key0.into(), 100), (pub_key1.into(), 100)],
         multi_leader_rounds: 10,
         timeout_config: TimeoutConfig {
             fast_round_duration: Some(TimeDelta::from_secs(5)),
             ..TimeoutConfig::default()
         },
+        approval_threshold: None,
     }
     .into();
     client0.execute_operation(owner_change_op).await.unwrap();
@@ -1785,12 +2023,13 @@ where
     let pub_key1 = key_pair1.public();
     let owner_change_op = SystemOperation::ChangeOwnership {
         super_owners: Vec::new(),
-        owners: vec![(pub_key0, 100), (pub_key1, 100)],
+        owners: vec![(pub_key0.into(), 100), (pub_key1.into(), 100)],
         multi_leader_rounds: 10,
         timeout_config: TimeoutConfig {
             fast_round_duration: Some(TimeDelta::from_secs(5)),
             ..TimeoutConfig::default()
         },
+        approval_threshold: None,
     }
     .into();
     client0.execute_operation(owner_change_op).await.unwrap();
@@ -1910,7 +2149,7 @@ where
         Amount::from_tokens(3)
     );
 
-    receiver.message_policy = MessagePolicy::Ignore;
+    receiver.message_policy = BlanketMessagePolicy::Ignore.into();
     receiver.receive_certificate(cert).await?;
     assert!(receiver.process_inbox().await?.0.is_empty());
     // The message was ignored.
@@ -1921,7 +2160,7 @@ where
         Amount::from_tokens(3)
     );
 
-    receiver.message_policy = MessagePolicy::Reject;
+    receiver.message_policy = BlanketMessagePolicy::Reject.into();
     let certs = receiver.process_inbox().await?.0;
     assert_eq!(certs.len(), 1);
     sender
@@ -1937,3 +2176,90 @@ where
 
     Ok(())
 }
+
+#[test_case(MemoryStorageBuilder::default(); "memory")]
+#[cfg_attr(feature = "storage_service", test_case(ServiceStorageBuilder::new().await; "storage_service"))]
+#[test_log::test(tokio::test)]
+async fn test_message_policy_rules<B>(storage_builder: B) -> anyhow::Result<()>
+where
+    B: StorageBuilder,
+    ViewError: From<<B::Storage as Storage>::ContextError>,
+{
+    let mut builder = TestBuilder::new(storage_builder, 4, 1).await?;
+    let mut sender = builder
+        .add_initial_chain(ChainDescription::Root(1), Amount::from_tokens(4))
+        .await?;
+    let mut receiver = builder
+        .add_initial_chain(ChainDescription::Root(2), Amount::ZERO)
+        .await?;
+    let mut spammer = builder
+        .add_initial_chain(ChainDescription::Root(3), Amount::from_tokens(4))
+        .await?;
+    let recipient = Recipient::chain(ChainId::root(2));
+    for _ in 0..2 {
+        sender
+            .transfer(None, Amount::ONE, recipient, UserData(None))
+            .await
+            .unwrap()
+            .unwrap();
+    }
+    spammer
+        .transfer(None, Amount::ONE, recipient, UserData(None))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Chain and grant filters only apply to user messages, so the spammer's transfer is
+    // accepted. Only one message per origin is taken in each block.
+    receiver.message_policy = MessagePolicy::new(BlanketMessagePolicy::Accept)
+        .with_reject_chain_ids([ChainId::root(3)])
+        .with_minimum_grant(Amount::ONE)
+        .with_max_messages_per_origin(1);
+    receiver.synchronize_from_validators().await?;
+    let certs = receiver.process_inbox().await?.0;
+    assert_eq!(certs.len(), 2);
+    let blocks = certs
+        .iter()
+        .map(|cert| cert.value().block().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(blocks[0].incoming_messages.len(), 2);
+    assert_eq!(blocks[1].incoming_messages.len(), 1);
+    for message in blocks.iter().flat_map(|block| &block.incoming_messages) {
+        assert_eq!(message.action, MessageAction::Accept);
+    }
+    assert_eq!(
+        receiver.local_balance().await.unwrap(),
+        Amount::from_tokens(3)
+    );
+
+    // The next transfer from the spammer is rejected.
+    receiver.message_policy = MessagePolicy::new(BlanketMessagePolicy::Reject);
+    spammer
+        .transfer(None, Amount::ONE, recipient, UserData(None))
+        .await
+        .unwrap()
+        .unwrap();
+    receiver.synchronize_from_validators().await?;
+    let certs = receiver.process_inbox().await?.0;
+    assert_eq!(certs.len(), 1);
+    let block = certs[0].value().block().unwrap();
+    assert_eq!(block.incoming_messages.len(), 1);
+    assert_eq!(block.incoming_messages[0].action, MessageAction::Reject);
+    assert_eq!(
+        receiver.local_balance().await.unwrap(),
+        Amount::from_tokens(3)
+    );
+
+    // The spammer's message bounced, and bouncing messages are never rejected.
+    spammer.message_policy = MessagePolicy::new(BlanketMessagePolicy::Reject);
+    spammer.synchronize_from_validators().await?;
+    let certs = spammer.process_inbox().await?.0;
+    assert_eq!(certs.len(), 1);
+    let block = certs[0].value().block().unwrap();
+    assert_eq!(block.incoming_messages[0].action, MessageAction::Accept);
+    assert_eq!(
+        spammer.local_balance().await.unwrap(),
+        Amount::from_tokens(3)
+    );
+    Ok(())
+}
@@ -55,7 +55,7 @@ use crate::{
     data_types::*,
     node::{
         CrossChainMessageDelivery, LocalValidatorNodeProvider, NodeError, NotificationStream,
-        ValidatorNode,
+        ValidatorNode, MAX_CERTIFICATES_PER_REQUEST,
     },
     notifier::Notifier,
     worker::{Notification, ValidatorWorker, WorkerState},
@@ -158,6 +158,34 @@ where
         self.spawn_and_receive(move |validator, sender| validator.do_download_blob(blob_id, sender))
             .await
     }
+
+    async fn download_certificate(&mut self, hash: CryptoHash) -> Result<Certificate, NodeError> {
+        self.spawn_and_receive(move |validator, sender| {
+            validator.do_download_certificate(hash, sender)
+        })
+        .await
+    }
+
+    async fn download_certificates(
+        &mut self,
+        hashes: Vec<CryptoHash>,
+    ) -> Result<Vec<Certificate>, NodeError> {
+        self.spawn_and_receive(move |validator, sender| {
+            validator.do_download_certificates(hashes, sender)
+        })
+        .await
+    }
+
+    async fn download_certificates_by_height(
+        &mut self,
+        chain_id: ChainId,
+        range: BlockHeightRange,
+    ) -> Result<Vec<Certificate>, NodeError> {
+        self.spawn_and_receive(move |validator, sender| {
+            validator.do_download_certificates_by_height(chain_id, range, sender)
+        })
+        .await
+    }
 }
 
 impl<S> LocalValidatorClient<S>
@@ -339,6 +367,56 @@ where
             .map_err(Into::into);
         sender.send(hashed_blob.map(|hashed_blob| hashed_blob.blob().clone()))
     }
+
+    async fn do_download_certificate(
+        self,
+        hash: CryptoHash,
+        sender: oneshot::Sender<Result<Certificate, NodeError>>,
+    ) -> Result<(), Result<Certificate, NodeError>> {
+        let validator = self.client.lock().await;
+        let certificate = validator
+            .state
+            .storage_client()
+            .read_certificate(hash)
+            .await
+            .map_err(Into::into);
+        sender.send(certificate)
+    }
+
+    async fn do_download_certificates(
+        self,
+        hashes: Vec<CryptoHash>,
+        sender: oneshot::Sender<Result<Vec<Certificate>, NodeError>>,
+    ) -> Result<(), Result<Vec<Certificate>, NodeError>> {
+        let count = hashes.len() as u64;
+        if count > MAX_CERTIFICATES_PER_REQUEST {
+            return sender.send(Err(NodeError::TooManyCertificatesRequested(count)));
+        }
+        let validator = self.client.lock().await;
+        let certificates = validator
+            .state
+            .storage_client()
+            .read_certificates(hashes)
+            .await
+            .map_err(Into::into);
+        sender.send(certificates)
+    }
+
+    async fn do_download_certificates_by_height(
+        self,
+        chain_id: ChainId,
+        range: BlockHeightRange,
+        sender: oneshot::Sender<Result<Vec<Certificate>, NodeError>>,
+    ) -> Result<(), Result<Vec<Certificate>, NodeError>> {
+        let validator = self.client.lock().await;
+        let certificates = validator
+            .state
+            .storage_client()
+            .read_certificates_by_height(chain_id, range.start, Some(range.page_limit()))
+            .await
+            .map_err(Into::into);
+        sender.send(certificates)
+    }
 }
 
 #[derive(Clone)]
@@ -149,6 +149,7 @@ where
             message_counts: vec![1],
             state_hash: publisher_state_hash,
             oracle_records: vec![OracleRecord::default()],
+            events: vec![Vec::new()],
         }
         .with(publish_block),
     );
@@ -219,6 +220,7 @@ where
             message_counts: vec![1],
             state_hash: publisher_state_hash,
             oracle_records: vec![OracleRecord::default()],
+            events: vec![Vec::new()],
         }
         .with(broadcast_block.clone()),
     );
@@ -244,6 +246,7 @@ where
             message_counts: vec![1],
             state_hash: publisher_state_hash,
             oracle_records: vec![OracleRecord::default()],
+            events: vec![Vec::new()],
         }
         .with(broadcast_block),
     );
@@ -299,6 +302,7 @@ where
             message_counts: vec![1],
             state_hash: creator_state.crypto_hash().await?,
             oracle_records: vec![OracleRecord::default()],
+            events: vec![Vec::new()],
         }
         .with(subscribe_block),
     );
@@ -352,6 +356,7 @@ where
             message_counts: vec![1],
             state_hash: publisher_state_hash,
             oracle_records: vec![OracleRecord::default()],
+            events: vec![Vec::new()],
         }
         .with(accept_block),
     );
@@ -447,6 +452,7 @@ where
             message_counts: vec![0, 1],
             state_hash: creator_state.crypto_hash().await?,
             oracle_records: vec![OracleRecord::default(); 2],
+            events: vec![Vec::new(); 2],
         }
         .with(create_block),
     );
@@ -501,6 +507,7 @@ where
             message_counts: vec![0],
             state_hash: creator_state.crypto_hash().await?,
             oracle_records: vec![OracleRecord::default()],
+            events: vec![Vec::new()],
         }
         .with(run_block),
     );
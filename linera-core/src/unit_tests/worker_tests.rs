@@ -24,9 +24,9 @@ use linera_base::{
 };
 use linera_chain::{
     data_types::{
-        Block, BlockExecutionOutcome, BlockProposal, Certificate, ChainAndHeight, ChannelFullName,
-        Event, HashedCertificateValue, IncomingMessage, LiteVote, Medium, MessageAction, Origin,
-        OutgoingMessage, ProposalPayload, SignatureAggregator,
+        Block, BlockAndRound, BlockExecutionOutcome, BlockProposal, Certificate, ChainAndHeight,
+        ChannelFullName, Event, HashedCertificateValue, IncomingMessage, LiteVote, Medium,
+        MessageAction, Origin, OutgoingMessage, ProposalPayload, SignatureAggregator,
     },
     test::{make_child_block, make_first_block, BlockTestExt, VoteTestExt},
     ChainError, ChainExecutionContext,
@@ -277,9 +277,11 @@ async fn make_transfer_certificate_for_epoch<S>(
         Recipient::Burn => (),
     }
     message_counts.push(message_count);
+    let tx_count = block.operations.len() + block.incoming_messages.len();
     let oracle_records = iter::repeat_with(OracleRecord::default)
-        .take(block.operations.len() + block.incoming_messages.len())
+        .take(tx_count)
         .collect();
+    let events = vec![Vec::new(); tx_count];
     let state_hash = system_state.into_hash().await;
     let value = HashedCertificateValue::new_confirmed(
         BlockExecutionOutcome {
@@ -287,6 +289,7 @@ async fn make_transfer_certificate_for_epoch<S>(
             message_counts,
             state_hash,
             oracle_records,
+            events,
         }
         .with(block),
     );
@@ -404,7 +407,8 @@ where
             outcome: None,
         },
         &unknown_key_pair,
-    );
+    )
+    .into();
     assert_matches!(
         worker
             .handle_block_proposal(bad_signature_block_proposal)
@@ -734,6 +738,7 @@ where
                 .into_hash()
                 .await,
                 oracle_records: vec![OracleRecord::default(); 2],
+                events: vec![Vec::new(); 2],
             }
             .with(
                 make_first_block(ChainId::root(1))
@@ -761,6 +766,7 @@ where
                 .into_hash()
                 .await,
                 oracle_records: vec![OracleRecord::default()],
+                events: vec![Vec::new()],
             }
             .with(
                 make_child_block(&certificate0.value)
@@ -1021,6 +1027,7 @@ where
                     .into_hash()
                     .await,
                     oracle_records: vec![OracleRecord::default(); 2],
+                    events: vec![Vec::new(); 2],
                 }
                 .with(block_proposal.content.block),
             ),
@@ -1121,6 +1128,53 @@ where
     Ok(())
 }
 
+#[test_case(MemoryStorageBuilder::default(); "memory")]
+#[cfg_attr(feature = "rocksdb", test_case(RocksDbStorageBuilder::new().await; "rocks_db"))]
+#[cfg_attr(feature = "dynamodb", test_case(DynamoDbStorageBuilder::default(); "dynamo_db"))]
+#[cfg_attr(feature = "scylladb", test_case(ScyllaDbStorageBuilder::default(); "scylla_db"))]
+#[test_log::test(tokio::test)]
+async fn test_handle_block_proposal_does_not_persist_blobs<B>(
+    mut storage_builder: B,
+) -> anyhow::Result<()>
+where
+    B: StorageBuilder,
+    ViewError: From<<B::Storage as Storage>::ContextError>,
+{
+    let sender_key_pair = KeyPair::generate();
+    let (_, mut worker) = init_worker_with_chains(
+        storage_builder.build().await?,
+        vec![(
+            ChainDescription::Root(1),
+            sender_key_pair.public(),
+            Amount::from_tokens(5),
+        )],
+    )
+    .await;
+    let blob = HashedBlob::test_blob("blob1");
+    let block = make_first_block(ChainId::root(1))
+        .with_operation(SystemOperation::PublishBlob { blob_id: blob.id() })
+        .with_simple_transfer(ChainId::root(2), Amount::from_tokens(1000));
+    let content = BlockAndRound {
+        block,
+        round: Round::Fast,
+    };
+    let block_proposal =
+        BlockProposal::new(content, &sender_key_pair, vec![], vec![blob.clone()], None);
+    assert_matches!(
+        worker.handle_block_proposal(block_proposal).await,
+        Err(WorkerError::ChainError(error)) if matches!(
+            *error,
+            ChainError::ExecutionError(
+                ExecutionError::SystemError(SystemExecutionError::InsufficientFunding { .. }),
+                ChainExecutionContext::Operation(_)
+            )
+        )
+    );
+    // The blob is only written to the storage once a block publishing it is confirmed.
+    assert!(!worker.storage.contains_blob(blob.id()).await?);
+    Ok(())
+}
+
 #[test_case(MemoryStorageBuilder::default(); "memory")]
 #[cfg_attr(feature = "rocksdb", test_case(RocksDbStorageBuilder::new().await; "rocks_db"))]
 #[cfg_attr(feature = "dynamodb", test_case(DynamoDbStorageBuilder::default(); "dynamo_db"))]
@@ -1316,6 +1370,7 @@ where
             message_counts: vec![0],
             state_hash: state.into_hash().await,
             oracle_records: vec![OracleRecord::default()],
+            events: vec![Vec::new()],
         }
         .with(make_first_block(chain_id).with_incoming_message(open_chain_message)),
     );
@@ -1578,6 +1633,12 @@ where
         Some(certificate.hash()),
         new_sender_chain.tip_state.get().block_hash
     );
+    // The certificate reveals the random seed of the block.
+    let seed = worker
+        .storage
+        .read_random_seed(ChainId::root(1), BlockHeight::ZERO)
+        .await?;
+    assert_eq!(seed, certificate.random_seed());
     let new_recipient_chain = worker.storage.load_active_chain(ChainId::root(2)).await?;
     assert_eq!(
         Amount::MAX,
@@ -1812,6 +1873,72 @@ where
     Ok(())
 }
 
+#[test_case(MemoryStorageBuilder::default(); "memory")]
+#[cfg_attr(feature = "rocksdb", test_case(RocksDbStorageBuilder::new().await; "rocks_db"))]
+#[cfg_attr(feature = "dynamodb", test_case(DynamoDbStorageBuilder::default(); "dynamo_db"))]
+#[cfg_attr(feature = "scylladb", test_case(ScyllaDbStorageBuilder::default(); "scylla_db"))]
+#[test_log::test(tokio::test)]
+async fn test_handle_certificate_records_block_fuel<B>(mut storage_builder: B) -> anyhow::Result<()>
+where
+    B: StorageBuilder,
+    ViewError: From<<B::Storage as Storage>::ContextError>,
+{
+    let sender_key_pair = KeyPair::generate();
+    let (committee, mut worker) = init_worker_with_chains(
+        storage_builder.build().await?,
+        vec![
+            (
+                ChainDescription::Root(1),
+                sender_key_pair.public(),
+                Amount::from_tokens(5),
+            ),
+            (
+                ChainDescription::Root(2),
+                PublicKey::test_key(2),
+                Amount::ZERO,
+            ),
+        ],
+    )
+    .await;
+    assert_eq!(
+        worker.storage.read_average_block_fuel(Epoch::ZERO).await?,
+        None
+    );
+
+    let certificate = make_simple_transfer_certificate(
+        ChainDescription::Root(1),
+        &sender_key_pair,
+        ChainId::root(2),
+        Amount::from_tokens(5),
+        Vec::new(),
+        &committee,
+        Amount::ZERO,
+        &worker,
+        None,
+    )
+    .await;
+    worker
+        .fully_handle_certificate(certificate, vec![], vec![])
+        .await?;
+    // A transfer does not run any WebAssembly code.
+    assert_eq!(
+        worker.storage.read_average_block_fuel(Epoch::ZERO).await?,
+        Some(0)
+    );
+
+    // The average only covers the blocks of the requested epoch.
+    let storage = &worker.storage;
+    storage
+        .write_block_fuel(Epoch(1), ChainId::root(1), BlockHeight(1), 100)
+        .await?;
+    storage
+        .write_block_fuel(Epoch(1), ChainId::root(2), BlockHeight(0), 300)
+        .await?;
+    assert_eq!(storage.read_average_block_fuel(Epoch(1)).await?, Some(200));
+    assert_eq!(storage.read_average_block_fuel(Epoch::ZERO).await?, Some(0));
+    Ok(())
+}
+
 #[test_case(MemoryStorageBuilder::default(); "memory")]
 #[cfg_attr(feature = "rocksdb", test_case(RocksDbStorageBuilder::new().await; "rocks_db"))]
 #[cfg_attr(feature = "dynamodb", test_case(DynamoDbStorageBuilder::default(); "dynamo_db"))]
@@ -2351,6 +2478,7 @@ where
                 .into_hash()
                 .await,
                 oracle_records: vec![OracleRecord::default()],
+                events: vec![Vec::new()],
             }
             .with(make_first_block(admin_id).with_operation(
                 SystemOperation::OpenChain(OpenChainConfig {
@@ -2414,6 +2542,7 @@ where
                 .into_hash()
                 .await,
                 oracle_records: vec![OracleRecord::default(); 2],
+                events: vec![Vec::new(); 2],
             }
             .with(
                 make_child_block(&certificate0.value)
@@ -2450,6 +2579,7 @@ where
                 .into_hash()
                 .await,
                 oracle_records: vec![OracleRecord::default()],
+                events: vec![Vec::new()],
             }
             .with(
                 make_child_block(&certificate1.value)
@@ -2577,6 +2707,7 @@ where
                 .into_hash()
                 .await,
                 oracle_records: vec![OracleRecord::default(); 4],
+                events: vec![Vec::new(); 4],
             }
             .with(
                 make_first_block(user_id)
@@ -2746,6 +2877,7 @@ where
                 .into_hash()
                 .await,
                 oracle_records: vec![OracleRecord::default()],
+                events: vec![Vec::new()],
             }
             .with(make_first_block(user_id).with_simple_transfer(admin_id, Amount::ONE)),
         ),
@@ -2773,6 +2905,7 @@ where
                 .into_hash()
                 .await,
                 oracle_records: vec![OracleRecord::default()],
+                events: vec![Vec::new()],
             }
             .with(
                 make_first_block(admin_id).with_operation(SystemOperation::Admin(
@@ -2873,6 +3006,7 @@ where
                 .into_hash()
                 .await,
                 oracle_records: vec![OracleRecord::default()],
+                events: vec![Vec::new()],
             }
             .with(make_first_block(user_id).with_simple_transfer(admin_id, Amount::ONE)),
         ),
@@ -2907,6 +3041,7 @@ where
                 .into_hash()
                 .await,
                 oracle_records: vec![OracleRecord::default(); 2],
+                events: vec![Vec::new(); 2],
             }
             .with(
                 make_first_block(admin_id)
@@ -2967,6 +3102,7 @@ where
                 .into_hash()
                 .await,
                 oracle_records: vec![OracleRecord::default()],
+                events: vec![Vec::new()],
             }
             .with(
                 make_child_block(&certificate1.value)
@@ -3214,6 +3350,78 @@ async fn test_cross_chain_helper() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test_case(MemoryStorageBuilder::default(); "memory")]
+#[cfg_attr(feature = "rocksdb", test_case(RocksDbStorageBuilder::new().await; "rocks_db"))]
+#[cfg_attr(feature = "dynamodb", test_case(DynamoDbStorageBuilder::default(); "dynamo_db"))]
+#[cfg_attr(feature = "scylladb", test_case(ScyllaDbStorageBuilder::default(); "scylla_db"))]
+#[test_log::test(tokio::test)]
+async fn test_secp256k1_owner_proposal<B>(mut storage_builder: B) -> anyhow::Result<()>
+where
+    B: StorageBuilder,
+    ViewError: From<<B::Storage as Storage>::ContextError>,
+{
+    let storage = storage_builder.build().await?;
+    let chain_id = ChainId::root(0);
+    let key_pair = KeyPair::generate();
+    let eth_key_pair = Secp256k1KeyPair::generate();
+    let eth_public_key = eth_key_pair.public();
+    let balances = vec![(
+        ChainDescription::Root(0),
+        key_pair.public(),
+        Amount::from_tokens(2),
+    )];
+    let (committee, mut worker) = init_worker_with_chains(storage, balances).await;
+
+    // Hand the chain over to an Ethereum account.
+    let block0 = make_first_block(chain_id).with_operation(SystemOperation::ChangeOwnership {
+        super_owners: Vec::new(),
+        owners: vec![(eth_public_key.into(), 100)],
+        multi_leader_rounds: 10,
+        timeout_config: TimeoutConfig::default(),
+        approval_threshold: None,
+    });
+    let (executed_block0, _) = worker.stage_block_execution(block0).await?;
+    let value0 = HashedCertificateValue::new_confirmed(executed_block0);
+    let certificate0 = make_certificate(&committee, &worker, value0.clone());
+    worker
+        .fully_handle_certificate(certificate0, vec![], vec![])
+        .await?;
+
+    // The old Ed25519 key can't propose anymore.
+    let block1 = make_child_block(&value0);
+    let proposal = block1
+        .clone()
+        .into_proposal_with_round(&key_pair, Round::MultiLeader(0));
+    let result = worker.handle_block_proposal(proposal).await;
+    assert_matches!(result, Err(WorkerError::InvalidOwner));
+
+    // A proposal signed with the wrong secp256k1 key is rejected.
+    let content = BlockAndRound {
+        block: block1,
+        round: Round::MultiLeader(0),
+    };
+    let message = BlockProposal::signing_message(&content, None);
+    let wrong_signature =
+        Secp256k1Signature::new_from_message(&message, &Secp256k1KeyPair::generate());
+    let proposal = BlockProposal::new_with_signature(
+        content.clone(),
+        eth_public_key,
+        wrong_signature,
+        vec![],
+        vec![],
+        None,
+    );
+    assert!(worker.handle_block_proposal(proposal).await.is_err());
+
+    // An EIP-191 signature by the Ethereum account is accepted.
+    let signature = Secp256k1Signature::new_from_message(&message, &eth_key_pair);
+    let proposal =
+        BlockProposal::new_with_signature(content, eth_public_key, signature, vec![], vec![], None);
+    let (response, _) = worker.handle_block_proposal(proposal).await?;
+    assert!(response.info.manager.pending.is_some());
+    Ok(())
+}
+
 #[test_case(MemoryStorageBuilder::default(); "memory")]
 #[cfg_attr(feature = "rocksdb", test_case(RocksDbStorageBuilder::new().await; "rocks_db"))]
 #[cfg_attr(feature = "dynamodb", test_case(DynamoDbStorageBuilder::default(); "dynamo_db"))]
@@ -3235,9 +3443,10 @@ where
     // Add another owner and use the leader-based protocol in all rounds.
     let block0 = make_first_block(chain_id).with_operation(SystemOperation::ChangeOwnership {
         super_owners: Vec::new(),
-        owners: vec![(pub_key0, 100), (pub_key1, 100)],
+        owners: vec![(pub_key0.into(), 100), (pub_key1.into(), 100)],
         multi_leader_rounds: 0,
         timeout_config: TimeoutConfig::default(),
+        approval_threshold: None,
     });
     let (executed_block0, _) = worker.stage_block_execution(block0).await?;
     let value0 = HashedCertificateValue::new_confirmed(executed_block0);
@@ -3433,13 +3642,14 @@ where
 
     // Add another owner and configure two multi-leader rounds.
     let block0 = make_first_block(chain_id).with_operation(SystemOperation::ChangeOwnership {
-        super_owners: vec![pub_key0],
-        owners: vec![(pub_key0, 100), (pub_key1, 100)],
+        super_owners: vec![pub_key0.into()],
+        owners: vec![(pub_key0.into(), 100), (pub_key1.into(), 100)],
         multi_leader_rounds: 2,
         timeout_config: TimeoutConfig {
             fast_round_duration: Some(TimeDelta::from_secs(5)),
             ..TimeoutConfig::default()
         },
+        approval_threshold: None,
     });
     let (executed_block0, _) = worker.stage_block_execution(block0).await?;
     let value0 = HashedCertificateValue::new_confirmed(executed_block0);
@@ -3521,13 +3731,14 @@ where
 
     // Add another owner and configure two multi-leader rounds.
     let block0 = make_first_block(chain_id).with_operation(SystemOperation::ChangeOwnership {
-        super_owners: vec![pub_key0],
-        owners: vec![(pub_key0, 100), (pub_key1, 100)],
+        super_owners: vec![pub_key0.into()],
+        owners: vec![(pub_key0.into(), 100), (pub_key1.into(), 100)],
         multi_leader_rounds: 3,
         timeout_config: TimeoutConfig {
             fast_round_duration: Some(TimeDelta::from_millis(5)),
             ..TimeoutConfig::default()
         },
+        approval_threshold: None,
     });
     let (executed_block0, _) = worker.stage_block_execution(block0).await?;
     let value0 = HashedCertificateValue::new_confirmed(executed_block0);
@@ -68,6 +68,8 @@ pub enum CommunicateAction {
 
 pub struct ValidatorUpdater<A, S> {
     pub name: ValidatorName,
+    /// The committee that the validator belongs to.
+    pub committee: Committee,
     pub node: A,
     pub storage: S,
     pub local_node_recent_hashed_blobs: Arc<ValueCache<BlobId, HashedBlob>>,
@@ -189,7 +191,7 @@ where
         certificate: &Certificate,
         delivery: CrossChainMessageDelivery,
     ) -> Result<ChainInfoResponse, NodeError> {
-        if certificate.is_signed_by(&self.name) {
+        if certificate.is_signed_by(&self.committee, &self.name) {
             let result = self
                 .node
                 .handle_lite_certificate(certificate.lite_certificate(), delivery)
@@ -256,7 +258,7 @@ where
         let required = match certificate.value() {
             CertificateValue::ConfirmedBlock { executed_block, .. }
             | CertificateValue::ValidatedBlock { executed_block, .. } => {
-                executed_block.block.blob_ids()
+                executed_block.required_blob_ids()
             }
             CertificateValue::Timeout { .. } => HashSet::new(),
         };
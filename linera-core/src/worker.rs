@@ -4,25 +4,25 @@
 
 use std::{
     borrow::Cow,
-    collections::{hash_map, BTreeMap, HashMap, VecDeque},
+    collections::{hash_map, BTreeMap, BTreeSet, HashMap, VecDeque},
     sync::Arc,
     time::Duration,
 };
 
 use async_trait::async_trait;
 use linera_base::{
-    crypto::{CryptoHash, KeyPair},
+    crypto::{BlsKeyPair, CryptoHash, KeyPair},
     data_types::{ArithmeticError, BlockHeight, HashedBlob, Round},
     doc_scalar, ensure,
-    identifiers::{BlobId, ChainId, Owner},
+    identifiers::{BlobId, ChainId, Owner, StreamId},
 };
 use linera_chain::data_types::{
     Block, BlockProposal, Certificate, CertificateValue, ExecutedBlock, HashedCertificateValue,
     LiteCertificate, MessageBundle, Origin, OutgoingMessage, Target,
 };
 use linera_execution::{
-    committee::Epoch, BytecodeLocation, Query, Response, UserApplicationDescription,
-    UserApplicationId,
+    committee::Epoch, BytecodeLocation, ExecutionError, Query, Response,
+    UserApplicationDescription, UserApplicationId,
 };
 use linera_storage::Storage;
 use linera_views::views::ViewError;
@@ -173,6 +173,11 @@ pub enum Reason {
         height: BlockHeight,
         round: Round,
     },
+    NewEvents {
+        height: BlockHeight,
+        hash: CryptoHash,
+        event_streams: BTreeSet<StreamId>,
+    },
 }
 
 /// Error type for [`ValidatorWorker`].
@@ -234,6 +239,8 @@ pub enum WorkerError {
     },
     #[error("The given message counts are not what we computed after executing the block")]
     IncorrectMessageCounts,
+    #[error("The given events are not what we computed after executing the block")]
+    IncorrectEvents,
     #[error("The timestamp of a Tick operation is in the future.")]
     InvalidTimestamp,
     #[error("We don't have the value for the certificate.")]
@@ -258,7 +265,13 @@ pub enum WorkerError {
 
 impl From<linera_chain::ChainError> for WorkerError {
     fn from(chain_error: linera_chain::ChainError) -> Self {
-        WorkerError::ChainError(Box::new(chain_error))
+        match chain_error {
+            linera_chain::ChainError::ExecutionError(
+                ExecutionError::BlobsNotFound(blob_ids),
+                _,
+            ) => WorkerError::BlobsNotFound(blob_ids),
+            chain_error => WorkerError::ChainError(Box::new(chain_error)),
+        }
     }
 }
 
@@ -312,6 +325,13 @@ impl<StorageClient> WorkerState<StorageClient> {
         }
     }
 
+    /// Returns an instance that also signs its votes with the given BLS key pair, so that
+    /// they can be aggregated in committees using BLS signatures.
+    pub fn with_bls_key_pair(mut self, bls_key_pair: Option<BlsKeyPair>) -> Self {
+        self.chain_worker_config = self.chain_worker_config.with_bls_key_pair(bls_key_pair);
+        self
+    }
+
     pub fn with_allow_inactive_chains(mut self, value: bool) -> Self {
         self.chain_worker_config.allow_inactive_chains = value;
         self
@@ -630,6 +650,7 @@ where
 
         let medium = match outgoing_message.destination {
             Destination::Recipient(_) => Medium::Direct,
+            Destination::Scheduled { .. } => Medium::Scheduled,
             Destination::Subscribers(name) => {
                 let application_id = outgoing_message.message.application_id();
                 Medium::Channel(ChannelFullName {
@@ -4,9 +4,9 @@
 
 use std::{borrow::Cow, collections::BTreeMap, str::FromStr};
 
-use async_graphql::InputObject;
+use async_graphql::{Enum, InputObject};
 use linera_base::{
-    crypto::{CryptoError, PublicKey},
+    crypto::{BcsSignable, BlsPublicKey, BlsSignature, CryptoError, PublicKey, Signature},
     data_types::ArithmeticError,
 };
 use serde::{Deserialize, Serialize};
@@ -97,6 +97,43 @@ pub struct ValidatorState {
     pub network_address: String,
     /// The voting power.
     pub votes: u64,
+    /// The public key used to sign votes that are aggregated into BLS certificates.
+    #[serde(default)]
+    pub bls_public_key: Option<BlsPublicKey>,
+    /// The signature of the BLS public key with itself, proving that the validator knows
+    /// the secret key.
+    #[serde(default)]
+    pub bls_proof_of_possession: Option<BlsSignature>,
+}
+
+/// The average fuel consumed per block by the blocks that a validator executed during an
+/// epoch.
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct FuelUsage {
+    /// The epoch in which the blocks were executed.
+    pub epoch: Epoch,
+    /// The average fuel consumed per block.
+    pub fuel_per_block: u64,
+}
+
+impl BcsSignable for FuelUsage {}
+
+/// A fuel usage report, signed by the validator that observed it.
+#[derive(Eq, PartialEq, Hash, Clone, Debug, Serialize, Deserialize)]
+pub struct SignedFuelUsage {
+    pub validator: ValidatorName,
+    pub usage: FuelUsage,
+    pub signature: Signature,
+}
+
+/// The kind of signatures that certificates of a committee contain.
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug, Default, Serialize, Deserialize, Enum)]
+pub enum SignatureScheme {
+    /// One Ed25519 signature for each validator in the quorum.
+    #[default]
+    Ed25519,
+    /// A single BLS12-381 signature, aggregating the votes of the quorum.
+    Bls,
 }
 
 /// A set of validators (identified by their public keys) and their voting rights.
@@ -112,6 +149,8 @@ pub struct Committee {
     validity_threshold: u64,
     /// The policy agreed on for this epoch.
     policy: ResourceControlPolicy,
+    /// The kind of signatures in the certificates of this epoch.
+    signature_scheme: SignatureScheme,
 }
 
 impl Serialize for Committee {
@@ -150,6 +189,8 @@ struct CommitteeFull<'a> {
     quorum_threshold: u64,
     validity_threshold: u64,
     policy: Cow<'a, ResourceControlPolicy>,
+    #[serde(default)]
+    signature_scheme: SignatureScheme,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -157,6 +198,7 @@ struct CommitteeFull<'a> {
 struct CommitteeMinimal<'a> {
     validators: Cow<'a, BTreeMap<ValidatorName, ValidatorState>>,
     policy: Cow<'a, ResourceControlPolicy>,
+    signature_scheme: SignatureScheme,
 }
 
 impl TryFrom<CommitteeFull<'static>> for Committee {
@@ -169,8 +211,10 @@ impl TryFrom<CommitteeFull<'static>> for Committee {
             quorum_threshold,
             validity_threshold,
             policy,
+            signature_scheme,
         } = committee_full;
-        let committee = Committee::new(validators.into_owned(), policy.into_owned());
+        let committee = Committee::new(validators.into_owned(), policy.into_owned())
+            .with_signature_scheme(signature_scheme);
         if total_votes != committee.total_votes {
             Err(format!(
                 "invalid committee: total_votes is {}; should be {}",
@@ -200,6 +244,7 @@ impl<'a> From<&'a Committee> for CommitteeFull<'a> {
             quorum_threshold,
             validity_threshold,
             policy,
+            signature_scheme,
         } = committee;
         CommitteeFull {
             validators: Cow::Borrowed(validators),
@@ -207,14 +252,20 @@ impl<'a> From<&'a Committee> for CommitteeFull<'a> {
             quorum_threshold: *quorum_threshold,
             validity_threshold: *validity_threshold,
             policy: Cow::Borrowed(policy),
+            signature_scheme: *signature_scheme,
         }
     }
 }
 
 impl From<CommitteeMinimal<'static>> for Committee {
     fn from(committee_min: CommitteeMinimal) -> Committee {
-        let CommitteeMinimal { validators, policy } = committee_min;
+        let CommitteeMinimal {
+            validators,
+            policy,
+            signature_scheme,
+        } = committee_min;
         Committee::new(validators.into_owned(), policy.into_owned())
+            .with_signature_scheme(signature_scheme)
     }
 }
 
@@ -226,10 +277,12 @@ impl<'a> From<&'a Committee> for CommitteeMinimal<'a> {
             quorum_threshold: _,
             validity_threshold: _,
             policy,
+            signature_scheme,
         } = committee;
         CommitteeMinimal {
             validators: Cow::Borrowed(validators),
             policy: Cow::Borrowed(policy),
+            signature_scheme: *signature_scheme,
         }
     }
 }
@@ -307,9 +360,16 @@ impl Committee {
             quorum_threshold,
             validity_threshold,
             policy,
+            signature_scheme: SignatureScheme::default(),
         }
     }
 
+    /// Returns the committee with the given signature scheme.
+    pub fn with_signature_scheme(mut self, signature_scheme: SignatureScheme) -> Self {
+        self.signature_scheme = signature_scheme;
+        self
+    }
+
     #[cfg(with_testing)]
     pub fn make_simple(keys: Vec<ValidatorName>) -> Self {
         let map = keys
@@ -320,6 +380,8 @@ impl Committee {
                     ValidatorState {
                         network_address: k.to_string(),
                         votes: 1,
+                        bls_public_key: None,
+                        bls_proof_of_possession: None,
                     },
                 )
             })
@@ -371,4 +433,72 @@ impl Committee {
     pub fn policy(&self) -> &ResourceControlPolicy {
         &self.policy
     }
+
+    pub fn signature_scheme(&self) -> SignatureScheme {
+        self.signature_scheme
+    }
+
+    /// Returns the BLS public key of the validator, if any.
+    pub fn bls_public_key(&self, author: &ValidatorName) -> Option<BlsPublicKey> {
+        self.validators.get(author)?.bls_public_key
+    }
+
+    /// Returns whether all validators have a BLS public key, so that the committee can use
+    /// [`SignatureScheme::Bls`].
+    pub fn has_bls_public_keys(&self) -> bool {
+        self.validators
+            .values()
+            .all(|state| state.bls_public_key.is_some())
+    }
+
+    /// Checks that every BLS public key of the committee comes with a valid proof of
+    /// possession. Otherwise, a validator could choose a public key that cancels the others'
+    /// in aggregate signatures.
+    pub fn check_bls_proofs_of_possession(&self) -> Result<(), CryptoError> {
+        for (name, state) in &self.validators {
+            let Some(public_key) = state.bls_public_key else {
+                continue;
+            };
+            let proof = state.bls_proof_of_possession.ok_or_else(|| {
+                CryptoError::InvalidBlsSignature(format!(
+                    "missing proof of possession for validator {name}"
+                ))
+            })?;
+            public_key
+                .check_proof_of_possession(&proof)
+                .map_err(|error| {
+                    CryptoError::InvalidBlsSignature(format!("validator {name}: {error}"))
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Returns the position of the validator in the committee, i.e. in the bitmaps of
+    /// aggregate signatures.
+    pub fn validator_index(&self, author: &ValidatorName) -> Option<usize> {
+        self.validators.keys().position(|name| name == author)
+    }
+}
+
+impl std::fmt::Display for SignatureScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignatureScheme::Ed25519 => write!(f, "ed25519"),
+            SignatureScheme::Bls => write!(f, "bls"),
+        }
+    }
+}
+
+impl FromStr for SignatureScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ed25519" => Ok(SignatureScheme::Ed25519),
+            "bls" => Ok(SignatureScheme::Bls),
+            _ => Err(format!(
+                "unknown signature scheme {s:?}; expected ed25519 or bls"
+            )),
+        }
+    }
 }
@@ -5,7 +5,8 @@ use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use futures::{stream::FuturesUnordered, StreamExt, TryStreamExt};
 use linera_base::{
-    data_types::{Amount, BlockHeight, OracleRecord, Timestamp},
+    data_types::{Amount, ArithmeticError, BlockHeight, OracleRecord, StreamEvent, Timestamp},
+    ensure,
     identifiers::{Account, ChainId, Destination, Owner},
 };
 use linera_views::{
@@ -14,7 +15,7 @@ use linera_views::{
     reentrant_collection_view::HashedReentrantCollectionView,
     views::{View, ViewError},
 };
-use linera_views_derive::CryptoHashView;
+use linera_views_derive::{CryptoHashView, ProvableView};
 #[cfg(with_testing)]
 use {
     crate::{
@@ -28,12 +29,12 @@ use crate::{
     resources::ResourceController, system::SystemExecutionStateView, ContractSyncRuntime,
     ExecutionError, ExecutionOutcome, ExecutionRuntimeConfig, ExecutionRuntimeContext, Message,
     MessageContext, MessageKind, Operation, OperationContext, Query, QueryContext,
-    RawExecutionOutcome, RawOutgoingMessage, Response, ServiceSyncRuntime, SystemMessage,
-    UserApplicationDescription, UserApplicationId,
+    RawExecutionOutcome, RawOutgoingMessage, Response, ServiceSyncRuntime, SystemExecutionError,
+    SystemMessage, UserApplicationDescription, UserApplicationId,
 };
 
 /// A view accessing the execution state of a chain.
-#[derive(Debug, CryptoHashView)]
+#[derive(Debug, CryptoHashView, ProvableView)]
 pub struct ExecutionStateView<C> {
     /// System application.
     pub system: SystemExecutionStateView<C>,
@@ -106,6 +107,7 @@ pub enum UserAction {
     Instantiate(OperationContext, Vec<u8>),
     Operation(OperationContext, Vec<u8>),
     Message(MessageContext, Vec<u8>),
+    Event(MessageContext, Vec<u8>),
 }
 
 impl UserAction {
@@ -115,6 +117,7 @@ impl UserAction {
             Instantiate(context, _) => context.authenticated_signer,
             Operation(context, _) => context.authenticated_signer,
             Message(context, _) => context.authenticated_signer,
+            Event(context, _) => context.authenticated_signer,
         }
     }
 
@@ -123,6 +126,7 @@ impl UserAction {
             UserAction::Instantiate(context, _) => context.height,
             UserAction::Operation(context, _) => context.height,
             UserAction::Message(context, _) => context.height,
+            UserAction::Event(context, _) => context.height,
         }
     }
 
@@ -131,6 +135,7 @@ impl UserAction {
             UserAction::Instantiate(context, _) => context.next_message_index,
             UserAction::Operation(context, _) => context.next_message_index,
             UserAction::Message(context, _) => context.next_message_index,
+            UserAction::Event(context, _) => context.next_message_index,
         }
     }
 }
@@ -350,6 +355,16 @@ where
     ) -> Result<(Vec<ExecutionOutcome>, OracleRecord), ExecutionError> {
         assert_eq!(context.chain_id, self.context().extra().chain_id());
         match message {
+            Message::System(SystemMessage::NewEvent(event)) => {
+                self.process_event(
+                    context,
+                    local_time,
+                    event,
+                    oracle_record,
+                    resource_controller,
+                )
+                .await
+            }
             Message::System(message) => {
                 let outcome = self.system.execute_message(context, message).await?;
                 Ok((
@@ -361,6 +376,8 @@ where
                 application_id,
                 bytes,
             } => {
+                let granted = grant.as_deref().copied().unwrap_or_default();
+                self.system.check_message_grant(&context, granted)?;
                 self.run_user_action(
                     application_id,
                     context.chain_id,
@@ -376,6 +393,66 @@ where
         }
     }
 
+    /// Delivers an event of a stream on another chain to the applications of this chain that
+    /// are subscribed to the stream. They all process it in the same transaction.
+    async fn process_event(
+        &mut self,
+        mut context: MessageContext,
+        local_time: Timestamp,
+        event: StreamEvent,
+        oracle_record: Option<OracleRecord>,
+        resource_controller: &mut ResourceController<Option<Owner>>,
+    ) -> Result<(Vec<ExecutionOutcome>, OracleRecord), ExecutionError> {
+        let chain_id = event.event_id.chain_id;
+        ensure!(
+            chain_id == context.message_id.chain_id,
+            SystemExecutionError::IncorrectChainId(chain_id)
+        );
+        let key = (chain_id, event.event_id.stream_id.clone());
+        let subscribers = self
+            .system
+            .event_subscriptions
+            .get(&key)
+            .await?
+            .unwrap_or_default();
+        let bytes = bcs::to_bytes(&event).expect("`StreamEvent` can be serialized");
+        // When replaying, each subscriber uses the responses left over by the previous ones.
+        let is_replaying = oracle_record.is_some();
+        let mut remaining_record = oracle_record;
+        let mut outcomes = Vec::new();
+        let mut new_record = OracleRecord::default();
+        for application_id in subscribers {
+            let (application_outcomes, record) = self
+                .run_user_action(
+                    application_id,
+                    context.chain_id,
+                    local_time,
+                    UserAction::Event(context, bytes.clone()),
+                    context.refund_grant_to,
+                    None,
+                    remaining_record.take(),
+                    resource_controller,
+                )
+                .await?;
+            // The messages of each subscriber follow those of the previous ones.
+            for outcome in &application_outcomes {
+                let count = u32::try_from(outcome.message_count())
+                    .map_err(|_| ArithmeticError::Overflow)?;
+                context.next_message_index = context
+                    .next_message_index
+                    .checked_add(count)
+                    .ok_or(ArithmeticError::Overflow)?;
+            }
+            outcomes.extend(application_outcomes);
+            if is_replaying {
+                remaining_record = Some(record);
+            } else {
+                new_record.responses.extend(record.responses);
+            }
+        }
+        Ok((outcomes, new_record))
+    }
+
     pub async fn bounce_message(
         &self,
         context: MessageContext,
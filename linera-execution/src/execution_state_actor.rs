@@ -7,8 +7,9 @@ use std::fmt::{self, Debug, Formatter};
 
 use futures::channel::mpsc;
 use linera_base::{
-    data_types::{Amount, ApplicationPermissions, Timestamp},
-    identifiers::{Account, MessageId, Owner},
+    crypto::CryptoHash,
+    data_types::{Amount, ApplicationPermissions, ArithmeticError, BlockHeight, Timestamp},
+    identifiers::{Account, BlobId, ChainId, MessageId, Owner, StreamId},
     ownership::ChainOwnership,
 };
 #[cfg(with_metrics)]
@@ -18,7 +19,8 @@ use linera_base::{
 };
 use linera_views::{
     batch::Batch,
-    common::Context,
+    common::{Context, KeyRange},
+    key_value_store_view::SizeData,
     views::{View, ViewError},
 };
 use oneshot::Sender;
@@ -27,6 +29,7 @@ use prometheus::HistogramVec;
 use reqwest::{header::CONTENT_TYPE, Client};
 
 use crate::{
+    resources::StorageChange,
     system::{OpenChainConfig, Recipient, UserData},
     util::RespondExt,
     ExecutionError, ExecutionRuntimeContext, ExecutionStateView, RawExecutionOutcome,
@@ -66,6 +69,11 @@ static LOAD_SERVICE_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
 
 pub(crate) type ExecutionStateSender = mpsc::UnboundedSender<Request>;
 
+/// Returns the number of bytes stored, counting both keys and values.
+fn stored_bytes(size: SizeData) -> u64 {
+    u64::from(size.key) + u64::from(size.value)
+}
+
 impl<C> ExecutionStateView<C>
 where
     C: Context + Clone + Send + Sync + 'static,
@@ -219,14 +227,54 @@ where
                 callback.respond(result);
             }
 
+            FindKeysInRange {
+                id,
+                key_prefix,
+                range,
+                callback,
+            } => {
+                let view = self.users.try_load_entry_or_insert(&id).await?;
+                let result = view.find_keys_in_range(&key_prefix, &range).await?;
+                callback.respond(result);
+            }
+
+            FindKeyValuesInRange {
+                id,
+                key_prefix,
+                range,
+                callback,
+            } => {
+                let view = self.users.try_load_entry_or_insert(&id).await?;
+                let result = view.find_key_values_in_range(&key_prefix, &range).await?;
+                callback.respond(result);
+            }
+
             WriteBatch {
                 id,
                 batch,
+                byte_price,
                 callback,
             } => {
                 let mut view = self.users.try_load_entry_mut(&id).await?;
+                let previous_bytes = stored_bytes(view.total_size());
                 view.write_batch(batch).await?;
-                callback.respond(());
+                let application_bytes = stored_bytes(view.total_size());
+                drop(view);
+                let delta = i64::try_from(application_bytes)
+                    .ok()
+                    .zip(i64::try_from(previous_bytes).ok())
+                    .and_then(|(new, old)| i32::try_from(new - old).ok())
+                    .ok_or(ArithmeticError::Overflow)?;
+                let refund = self
+                    .system
+                    .update_stored_bytes(id, previous_bytes, application_bytes, byte_price)
+                    .await?;
+                callback.respond(StorageChange {
+                    delta,
+                    application_bytes,
+                    chain_bytes: *self.system.stored_bytes.get(),
+                    refund,
+                });
             }
 
             OpenChain {
@@ -284,6 +332,44 @@ where
                 let bytes = body.as_ref().to_vec();
                 callback.respond(bytes);
             }
+
+            ReadBlob { blob_id, callback } => {
+                let blob = self.context().extra().get_blob(blob_id).await?;
+                callback.respond(blob.into_inner().bytes);
+            }
+
+            ReadRandomSeed { height, callback } => {
+                let seed = self.context().extra().get_random_seed(height).await?;
+                callback.respond(seed);
+            }
+
+            SubscribeToEvents {
+                chain_id,
+                stream_id,
+                subscriber,
+                callback,
+            } => {
+                let id = self.context().extra().chain_id();
+                let message = self
+                    .system
+                    .subscribe_to_events(id, chain_id, stream_id, subscriber)
+                    .await?;
+                callback.respond(message);
+            }
+
+            UnsubscribeFromEvents {
+                chain_id,
+                stream_id,
+                subscriber,
+                callback,
+            } => {
+                let id = self.context().extra().chain_id();
+                let message = self
+                    .system
+                    .unsubscribe_from_events(id, chain_id, stream_id, subscriber)
+                    .await?;
+                callback.respond(message);
+            }
         }
 
         Ok(())
@@ -373,10 +459,25 @@ pub enum Request {
         callback: Sender<Vec<(Vec<u8>, Vec<u8>)>>,
     },
 
+    FindKeysInRange {
+        id: UserApplicationId,
+        key_prefix: Vec<u8>,
+        range: KeyRange,
+        callback: Sender<Vec<Vec<u8>>>,
+    },
+
+    FindKeyValuesInRange {
+        id: UserApplicationId,
+        key_prefix: Vec<u8>,
+        range: KeyRange,
+        callback: Sender<Vec<(Vec<u8>, Vec<u8>)>>,
+    },
+
     WriteBatch {
         id: UserApplicationId,
         batch: Batch,
-        callback: Sender<()>,
+        byte_price: Amount,
+        callback: Sender<StorageChange>,
     },
 
     OpenChain {
@@ -403,6 +504,30 @@ pub enum Request {
         payload: Vec<u8>,
         callback: oneshot::Sender<Vec<u8>>,
     },
+
+    ReadBlob {
+        blob_id: BlobId,
+        callback: Sender<Vec<u8>>,
+    },
+
+    ReadRandomSeed {
+        height: BlockHeight,
+        callback: Sender<CryptoHash>,
+    },
+
+    SubscribeToEvents {
+        chain_id: ChainId,
+        stream_id: StreamId,
+        subscriber: UserApplicationId,
+        callback: Sender<Option<RawOutgoingMessage<SystemMessage, Amount>>>,
+    },
+
+    UnsubscribeFromEvents {
+        chain_id: ChainId,
+        stream_id: StreamId,
+        subscriber: UserApplicationId,
+        callback: Sender<Option<RawOutgoingMessage<SystemMessage, Amount>>>,
+    },
 }
 
 impl Debug for Request {
@@ -501,10 +626,40 @@ impl Debug for Request {
                 .field("key_prefix", key_prefix)
                 .finish_non_exhaustive(),
 
-            Request::WriteBatch { id, batch, .. } => formatter
+            Request::FindKeysInRange {
+                id,
+                key_prefix,
+                range,
+                ..
+            } => formatter
+                .debug_struct("Request::FindKeysInRange")
+                .field("id", id)
+                .field("key_prefix", key_prefix)
+                .field("range", range)
+                .finish_non_exhaustive(),
+
+            Request::FindKeyValuesInRange {
+                id,
+                key_prefix,
+                range,
+                ..
+            } => formatter
+                .debug_struct("Request::FindKeyValuesInRange")
+                .field("id", id)
+                .field("key_prefix", key_prefix)
+                .field("range", range)
+                .finish_non_exhaustive(),
+
+            Request::WriteBatch {
+                id,
+                batch,
+                byte_price,
+                ..
+            } => formatter
                 .debug_struct("Request::WriteBatch")
                 .field("id", id)
                 .field("batch", batch)
+                .field("byte_price", byte_price)
                 .finish_non_exhaustive(),
 
             Request::OpenChain { balance, .. } => formatter
@@ -529,6 +684,40 @@ impl Debug for Request {
                 .field("url", url)
                 .field("content_type", content_type)
                 .finish_non_exhaustive(),
+
+            Request::ReadBlob { blob_id, .. } => formatter
+                .debug_struct("Request::ReadBlob")
+                .field("blob_id", blob_id)
+                .finish_non_exhaustive(),
+
+            Request::ReadRandomSeed { height, .. } => formatter
+                .debug_struct("Request::ReadRandomSeed")
+                .field("height", height)
+                .finish_non_exhaustive(),
+
+            Request::SubscribeToEvents {
+                chain_id,
+                stream_id,
+                subscriber,
+                ..
+            } => formatter
+                .debug_struct("Request::SubscribeToEvents")
+                .field("chain_id", chain_id)
+                .field("stream_id", stream_id)
+                .field("subscriber", subscriber)
+                .finish_non_exhaustive(),
+
+            Request::UnsubscribeFromEvents {
+                chain_id,
+                stream_id,
+                subscriber,
+                ..
+            } => formatter
+                .debug_struct("Request::UnsubscribeFromEvents")
+                .field("chain_id", chain_id)
+                .field("stream_id", stream_id)
+                .field("subscriber", subscriber)
+                .finish_non_exhaustive(),
         }
     }
 }
@@ -13,8 +13,10 @@ use linera_base::{
 use linera_views::{common::Context, map_view::MapView, views::ViewError};
 
 use crate::{
-    committee::{Committee, Epoch, ValidatorName, ValidatorState},
-    system::{Recipient, UserData},
+    committee::{
+        Committee, Epoch, SignatureScheme, SignedFuelUsage, ValidatorName, ValidatorState,
+    },
+    system::{MessageGrantPolicy, Recipient, StandingOrder, UserData},
     Bytecode, ChannelSubscription, ExecutionStateView, SystemExecutionStateView,
     UserApplicationDescription,
 };
@@ -31,6 +33,10 @@ doc_scalar!(
 );
 doc_scalar!(UserData, "Optional user message attached to a transfer");
 doc_scalar!(ValidatorName, "The identity of a validator");
+doc_scalar!(
+    SignedFuelUsage,
+    "A fuel usage report, signed by the validator that observed it"
+);
 
 #[Object]
 impl Committee {
@@ -53,6 +59,11 @@ impl Committee {
     async fn _validity_threshold(&self) -> u64 {
         self.validity_threshold()
     }
+
+    #[graphql(derived(name = "signature_scheme"))]
+    async fn _signature_scheme(&self) -> SignatureScheme {
+        self.signature_scheme()
+    }
 }
 
 #[async_graphql::Object]
@@ -115,4 +126,14 @@ where
     async fn _timestamp(&self) -> &Timestamp {
         self.timestamp.get()
     }
+
+    #[graphql(derived(name = "standing_orders"))]
+    async fn _standing_orders(&self) -> &MapView<C, u64, StandingOrder> {
+        &self.standing_orders
+    }
+
+    #[graphql(derived(name = "message_grant_policy"))]
+    async fn _message_grant_policy(&self) -> &MessageGrantPolicy {
+        self.message_grant_policy.get()
+    }
 }
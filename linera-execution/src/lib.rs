@@ -28,22 +28,24 @@ use linera_base::{
     abi::Abi,
     crypto::CryptoHash,
     data_types::{
-        Amount, ApplicationPermissions, ArithmeticError, BlockHeight, Resources,
+        Amount, ApplicationPermissions, ArithmeticError, BlockHeight, HashedBlob, Resources,
         SendMessageRequest, Timestamp,
     },
     doc_scalar, hex_debug,
     identifiers::{
-        Account, ApplicationId, BytecodeId, ChainId, ChannelName, Destination,
-        GenericApplicationId, MessageId, Owner,
+        Account, ApplicationId, BlobId, BytecodeId, ChainId, ChannelName, Destination,
+        GenericApplicationId, MessageId, Owner, StreamName,
     },
     ownership::ChainOwnership,
 };
-use linera_views::{batch::Batch, views::ViewError};
+use linera_views::{batch::Batch, common::KeyRange, views::ViewError};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[cfg(with_testing)]
 pub use crate::applications::ApplicationRegistry;
+#[cfg(all(with_fs, with_wasm_runtime))]
+pub use crate::wasm::enable_module_disk_cache;
 #[cfg(all(with_testing, with_wasm_runtime))]
 pub use crate::wasm::test as wasm_test;
 #[cfg(with_wasm_runtime)]
@@ -126,11 +128,39 @@ pub enum ExecutionError {
     ServiceWriteAttempt,
     #[error("Failed to load bytecode from storage {0:?}")]
     ApplicationBytecodeNotFound(Box<UserApplicationDescription>),
+    #[error("The following blobs are missing: {0:?}.")]
+    BlobsNotFound(Vec<BlobId>),
 
     #[error("Excessive number of bytes read from storage")]
     ExcessiveRead,
     #[error("Excessive number of bytes written to storage")]
     ExcessiveWrite,
+    #[error("Excessive amount of fuel consumed by the block")]
+    ExcessiveFuel,
+    #[error("Excessive number of operations in the block")]
+    ExcessiveOperations,
+    #[error("Excessive number of incoming messages in the block")]
+    ExcessiveIncomingMessages,
+    #[error("Excessive number of outgoing messages created by the block")]
+    ExcessiveOutgoingMessages,
+    #[error("Excessive number of bytes in the arguments of the operations of the block")]
+    ExcessiveOperationBytes,
+    #[error("Excessive size of the block")]
+    ExcessiveBlockSize,
+    #[error(
+        "Application {application_id} would store {size} bytes, exceeding the maximum of \
+        {maximum} bytes per application"
+    )]
+    ExcessiveApplicationStorage {
+        application_id: Box<UserApplicationId>,
+        size: u64,
+        maximum: u64,
+    },
+    #[error(
+        "The applications of the chain would store {size} bytes, exceeding the maximum of \
+        {maximum} bytes per chain"
+    )]
+    ExcessiveChainStorage { size: u64, maximum: u64 },
     #[error("Runtime failed to respond to application")]
     MissingRuntimeResponse,
     #[error("Bytecode ID {0:?} is invalid")]
@@ -154,6 +184,10 @@ pub enum ExecutionError {
         timestamp: Timestamp,
         local_time: Timestamp,
     },
+    #[error("The random seed of block {0} is only revealed once the block is confirmed")]
+    RandomSeedNotRevealed(BlockHeight),
+    #[error("The random seed of block {0} was not found")]
+    RandomSeedNotFound(BlockHeight),
 }
 
 /// The public entry points provided by the contract part of an application.
@@ -179,6 +213,14 @@ pub trait UserContract {
         message: Vec<u8>,
     ) -> Result<(), ExecutionError>;
 
+    /// Processes a serialized [`StreamEvent`](linera_base::data_types::StreamEvent) of a stream
+    /// that the application subscribed to.
+    fn process_event(
+        &mut self,
+        context: MessageContext,
+        event: Vec<u8>,
+    ) -> Result<(), ExecutionError>;
+
     /// Finishes execution of the current transaction.
     fn finalize(&mut self, context: FinalizeContext) -> Result<(), ExecutionError>;
 }
@@ -238,6 +280,13 @@ pub trait ExecutionRuntimeContext {
         &self,
         description: &UserApplicationDescription,
     ) -> Result<UserServiceCode, ExecutionError>;
+
+    /// Returns a blob that was published before, or declared by the block being executed.
+    async fn get_blob(&self, blob_id: BlobId) -> Result<HashedBlob, ExecutionError>;
+
+    /// Returns the random seed revealed by the certificate of the block of this chain at the
+    /// given height.
+    async fn get_random_seed(&self, height: BlockHeight) -> Result<CryptoHash, ExecutionError>;
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -307,6 +356,8 @@ pub trait BaseRuntime {
     type ReadValueBytes: fmt::Debug + Send + Sync;
     type FindKeysByPrefix: fmt::Debug + Send + Sync;
     type FindKeyValuesByPrefix: fmt::Debug + Send + Sync;
+    type FindKeysInRange: fmt::Debug + Send + Sync;
+    type FindKeyValuesInRange: fmt::Debug + Send + Sync;
 
     /// The current chain ID.
     fn chain_id(&mut self) -> Result<ChainId, ExecutionError>;
@@ -428,6 +479,58 @@ pub trait BaseRuntime {
         promise: &Self::FindKeyValuesByPrefix,
     ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ExecutionError>;
 
+    /// Reads the keys having a specific prefix whose remainder lies in a range.
+    #[cfg(feature = "test")]
+    fn find_keys_in_range(
+        &mut self,
+        key_prefix: Vec<u8>,
+        range: KeyRange,
+    ) -> Result<Vec<Vec<u8>>, ExecutionError> {
+        let promise = self.find_keys_in_range_new(key_prefix, range)?;
+        self.find_keys_in_range_wait(&promise)
+    }
+
+    /// Reads the keys having a specific prefix whose remainder lies in a range (new).
+    fn find_keys_in_range_new(
+        &mut self,
+        key_prefix: Vec<u8>,
+        range: KeyRange,
+    ) -> Result<Self::FindKeysInRange, ExecutionError>;
+
+    /// Reads the keys having a specific prefix whose remainder lies in a range (wait).
+    fn find_keys_in_range_wait(
+        &mut self,
+        promise: &Self::FindKeysInRange,
+    ) -> Result<Vec<Vec<u8>>, ExecutionError>;
+
+    /// Reads the key/values having a specific prefix whose key remainder lies in a range.
+    #[cfg(feature = "test")]
+    #[allow(clippy::type_complexity)]
+    fn find_key_values_in_range(
+        &mut self,
+        key_prefix: Vec<u8>,
+        range: KeyRange,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ExecutionError> {
+        let promise = self.find_key_values_in_range_new(key_prefix, range)?;
+        self.find_key_values_in_range_wait(&promise)
+    }
+
+    /// Reads the key/values having a specific prefix whose key remainder lies in a range
+    /// (new).
+    fn find_key_values_in_range_new(
+        &mut self,
+        key_prefix: Vec<u8>,
+        range: KeyRange,
+    ) -> Result<Self::FindKeyValuesInRange, ExecutionError>;
+
+    /// Reads the key/values having a specific prefix whose key remainder lies in a range
+    /// (wait).
+    #[allow(clippy::type_complexity)]
+    fn find_key_values_in_range_wait(
+        &mut self,
+        promise: &Self::FindKeyValuesInRange,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ExecutionError>;
+
     /// Queries a service.
     fn query_service(
         &mut self,
@@ -449,6 +552,12 @@ pub trait BaseRuntime {
     /// Cannot be used in fast blocks: A block using this call should be proposed by a regular
     /// owner, not a super owner.
     fn assert_before(&mut self, timestamp: Timestamp) -> Result<(), ExecutionError>;
+
+    /// Reads the contents of a blob published by an earlier block, or declared with a
+    /// `PublishBlob` operation in the current block. Other blobs are reported as
+    /// [`ExecutionError::BlobsNotFound`]. In contracts, the blob ID is recorded as an oracle
+    /// response, so that validators missing the blob can be sent it along with the block.
+    fn read_blob(&mut self, blob_id: BlobId) -> Result<Vec<u8>, ExecutionError>;
 }
 
 pub trait ServiceRuntime: BaseRuntime {
@@ -493,6 +602,32 @@ pub trait ContractRuntime: BaseRuntime {
     /// Schedules to unsubscribe to some `channel` on a `chain`.
     fn unsubscribe(&mut self, chain: ChainId, channel: ChannelName) -> Result<(), ExecutionError>;
 
+    /// Adds an event with the given `key` and `value` to one of this application's event streams.
+    fn emit(
+        &mut self,
+        name: StreamName,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), ExecutionError>;
+
+    /// Subscribes this application to the event stream `name` of an application on another
+    /// chain. The events added to the stream from then on are delivered to this application.
+    fn subscribe_to_events(
+        &mut self,
+        chain_id: ChainId,
+        application_id: UserApplicationId,
+        name: StreamName,
+    ) -> Result<(), ExecutionError>;
+
+    /// Unsubscribes this application from the event stream `name` of an application on
+    /// another chain.
+    fn unsubscribe_from_events(
+        &mut self,
+        chain_id: ChainId,
+        application_id: UserApplicationId,
+        name: StreamName,
+    ) -> Result<(), ExecutionError>;
+
     /// Transfers amount from source to destination.
     fn transfer(
         &mut self,
@@ -529,6 +664,11 @@ pub trait ContractRuntime: BaseRuntime {
     /// Closes the current chain.
     fn close_chain(&mut self) -> Result<(), ExecutionError>;
 
+    /// Returns the random seed revealed by the certificate of this chain's block at the given
+    /// height, which must be lower than the current block height. The seed is recorded as an
+    /// oracle response, so it cannot be used in fast blocks.
+    fn random_seed(&mut self, height: BlockHeight) -> Result<CryptoHash, ExecutionError>;
+
     /// Writes a batch of changes.
     fn write_batch(&mut self, batch: Batch) -> Result<(), ExecutionError>;
 }
@@ -631,7 +771,7 @@ impl<Message> From<SendMessageRequest<Message>> for RawOutgoingMessage<Message,
 }
 
 /// The kind of outgoing message being sent.
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize, Copy, clap::ValueEnum)]
 pub enum MessageKind {
     /// The message can be skipped or rejected. No receipt is requested.
     Simple,
@@ -661,6 +801,8 @@ pub struct RawExecutionOutcome<Message, Grant = Resources> {
     pub subscribe: Vec<(ChannelName, ChainId)>,
     /// Unsubscribe chains to channels.
     pub unsubscribe: Vec<(ChannelName, ChainId)>,
+    /// Events added to the application's streams, with their stream name, key and value.
+    pub events: Vec<(StreamName, Vec<u8>, Vec<u8>)>,
 }
 
 /// The identifier of a channel, relative to a particular application.
@@ -690,6 +832,14 @@ impl ExecutionOutcome {
             ExecutionOutcome::User(app_id, _) => GenericApplicationId::User(*app_id),
         }
     }
+
+    /// Returns the number of messages sent by this outcome.
+    pub fn message_count(&self) -> usize {
+        match self {
+            ExecutionOutcome::System(outcome) => outcome.messages.len(),
+            ExecutionOutcome::User(_, outcome) => outcome.messages.len(),
+        }
+    }
 }
 
 impl<Message, Grant> RawExecutionOutcome<Message, Grant> {
@@ -718,6 +868,7 @@ impl<Message, Grant> Default for RawExecutionOutcome<Message, Grant> {
             messages: Vec::new(),
             subscribe: Vec::new(),
             unsubscribe: Vec::new(),
+            events: Vec::new(),
         }
     }
 }
@@ -755,6 +906,7 @@ impl<Message> RawExecutionOutcome<Message, Resources> {
             messages,
             subscribe,
             unsubscribe,
+            events,
         } = self;
         let messages = messages
             .into_iter()
@@ -766,6 +918,7 @@ impl<Message> RawExecutionOutcome<Message, Resources> {
             messages,
             subscribe,
             unsubscribe,
+            events,
         })
     }
 }
@@ -794,6 +947,8 @@ pub struct TestExecutionRuntimeContext {
     execution_runtime_config: ExecutionRuntimeConfig,
     user_contracts: Arc<DashMap<UserApplicationId, UserContractCode>>,
     user_services: Arc<DashMap<UserApplicationId, UserServiceCode>>,
+    blobs: Arc<DashMap<BlobId, HashedBlob>>,
+    random_seeds: Arc<DashMap<BlockHeight, CryptoHash>>,
 }
 
 #[cfg(with_testing)]
@@ -804,8 +959,18 @@ impl TestExecutionRuntimeContext {
             execution_runtime_config,
             user_contracts: Arc::default(),
             user_services: Arc::default(),
+            blobs: Arc::default(),
+            random_seeds: Arc::default(),
         }
     }
+
+    pub fn add_blob(&self, blob: HashedBlob) {
+        self.blobs.insert(blob.id(), blob);
+    }
+
+    pub fn add_random_seed(&self, height: BlockHeight, seed: CryptoHash) {
+        self.random_seeds.insert(height, seed);
+    }
 }
 
 #[cfg(with_testing)]
@@ -854,6 +1019,21 @@ impl ExecutionRuntimeContext for TestExecutionRuntimeContext {
             })?
             .clone())
     }
+
+    async fn get_blob(&self, blob_id: BlobId) -> Result<HashedBlob, ExecutionError> {
+        Ok(self
+            .blobs
+            .get(&blob_id)
+            .ok_or_else(|| ExecutionError::BlobsNotFound(vec![blob_id]))?
+            .clone())
+    }
+
+    async fn get_random_seed(&self, height: BlockHeight) -> Result<CryptoHash, ExecutionError> {
+        Ok(*self
+            .random_seeds
+            .get(&height)
+            .ok_or(ExecutionError::RandomSeedNotFound(height))?)
+    }
 }
 
 impl From<SystemOperation> for Operation {
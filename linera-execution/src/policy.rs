@@ -4,9 +4,17 @@
 //! This module contains types related to fees and pricing.
 
 use async_graphql::InputObject;
-use linera_base::data_types::{Amount, ArithmeticError, Resources};
+use linera_base::{
+    data_types::{Amount, ArithmeticError, Resources},
+    ensure,
+};
 use serde::{Deserialize, Serialize};
 
+use crate::ExecutionError;
+
+/// The inverse of the maximum relative change of prices between two epochs, as in EIP-1559.
+const PRICE_CHANGE_DENOMINATOR: u128 = 8;
+
 /// A collection of prices and limits associated with block execution.
 #[derive(Eq, PartialEq, Hash, Clone, Debug, Serialize, Deserialize, InputObject)]
 pub struct ResourceControlPolicy {
@@ -22,8 +30,8 @@ pub struct ResourceControlPolicy {
     pub byte_read: Amount,
     /// The price of writing a byte
     pub byte_written: Amount,
-    /// The price of increasing storage by a byte.
-    // TODO(#1536): This is not fully supported.
+    /// The price of increasing storage by a byte. Decreasing storage refunds the fees
+    /// that were paid for the freed bytes.
     pub byte_stored: Amount,
     /// The base price of adding an operation to a block.
     pub operation: Amount,
@@ -34,12 +42,29 @@ pub struct ResourceControlPolicy {
     /// The additional price for each byte in the argument of a user message.
     pub message_byte: Amount,
 
-    // TODO(#1538): Cap the number of transactions per block and the total size of their
-    // arguments.
+    /// The maximum amount of fuel a block can consume
+    pub maximum_fuel_per_block: u64,
+    /// The maximum number of operations in a block
+    pub maximum_operations_per_block: u64,
+    /// The maximum number of incoming messages in a block
+    pub maximum_incoming_messages_per_block: u64,
+    /// The maximum number of outgoing messages created by a block
+    pub maximum_outgoing_messages_per_block: u64,
+    /// The maximum total size of the arguments of the user operations in a block
+    pub maximum_operation_bytes_per_block: u64,
+    /// The maximum size of a serialized block
+    pub maximum_block_size: u64,
     /// The maximum data to read per block
     pub maximum_bytes_read_per_block: u64,
     /// The maximum data to write per block
     pub maximum_bytes_written_per_block: u64,
+    /// The maximum data stored by a single application on a chain
+    pub maximum_bytes_stored_per_application: u64,
+    /// The maximum data stored by all the applications of a chain
+    pub maximum_bytes_stored_per_chain: u64,
+    /// The fuel consumed per block that the fuel and block prices are adjusted towards
+    /// from one epoch to the next, or zero if prices are fixed
+    pub target_fuel_per_block: u64,
 }
 
 impl Default for ResourceControlPolicy {
@@ -56,8 +81,17 @@ impl Default for ResourceControlPolicy {
             operation_byte: Amount::default(),
             message: Amount::default(),
             message_byte: Amount::default(),
+            maximum_fuel_per_block: u64::MAX,
+            maximum_operations_per_block: u64::MAX,
+            maximum_incoming_messages_per_block: u64::MAX,
+            maximum_outgoing_messages_per_block: u64::MAX,
+            maximum_operation_bytes_per_block: u64::MAX,
+            maximum_block_size: u64::MAX,
             maximum_bytes_read_per_block: u64::MAX,
             maximum_bytes_written_per_block: u64::MAX,
+            maximum_bytes_stored_per_application: u64::MAX,
+            maximum_bytes_stored_per_chain: u64::MAX,
+            target_fuel_per_block: 0,
         }
     }
 }
@@ -67,6 +101,62 @@ impl ResourceControlPolicy {
         self.block
     }
 
+    /// Checks the limits that only depend on the contents of a block, before executing it.
+    pub fn check_block_limits(
+        &self,
+        block_size: u64,
+        operations: u64,
+        incoming_messages: u64,
+    ) -> Result<(), ExecutionError> {
+        ensure!(
+            block_size <= self.maximum_block_size,
+            ExecutionError::ExcessiveBlockSize
+        );
+        ensure!(
+            operations <= self.maximum_operations_per_block,
+            ExecutionError::ExcessiveOperations
+        );
+        ensure!(
+            incoming_messages <= self.maximum_incoming_messages_per_block,
+            ExecutionError::ExcessiveIncomingMessages
+        );
+        Ok(())
+    }
+
+    /// Returns the policy of the next epoch if prices are adjusted dynamically. Like the
+    /// base fee of EIP-1559, the fuel and block prices increase or decrease by up to 1/8,
+    /// in proportion to how much the observed fuel per block exceeds or falls short of
+    /// the target.
+    pub fn adjusted(&self, observed_fuel_per_block: u64) -> Result<Option<Self>, ArithmeticError> {
+        let target = u128::from(self.target_fuel_per_block);
+        if target == 0 {
+            return Ok(None);
+        }
+        // Usage beyond twice the target counts as twice the target.
+        let observed = u128::from(observed_fuel_per_block).min(2 * target);
+        let adjust = |price: Amount| -> Result<Amount, ArithmeticError> {
+            let attos = price.saturating_div(Amount::from_attos(1));
+            let change = |difference: u128| {
+                attos
+                    .checked_mul(difference)
+                    .map(|product| product / target / PRICE_CHANGE_DENOMINATOR)
+                    .ok_or(ArithmeticError::Overflow)
+            };
+            if observed > target {
+                // Always increase by at least one unit, so that zero prices can grow.
+                let increase = change(observed - target)?.max(1);
+                price.try_add(Amount::from_attos(increase))
+            } else {
+                price.try_sub(Amount::from_attos(change(target - observed)?))
+            }
+        };
+        Ok(Some(Self {
+            fuel_unit: adjust(self.fuel_unit)?,
+            block: adjust(self.block)?,
+            ..self.clone()
+        }))
+    }
+
     pub fn total_price(&self, resources: &Resources) -> Result<Amount, ArithmeticError> {
         let mut amount = Amount::ZERO;
         amount.try_add_assign(self.fuel_price(resources.fuel)?)?;
@@ -104,8 +194,6 @@ impl ResourceControlPolicy {
         self.byte_written.try_mul(count as u128)
     }
 
-    // TODO(#1536): This is not fully implemented.
-    #[allow(dead_code)]
     pub(crate) fn bytes_stored_price(&self, count: u64) -> Result<Amount, ArithmeticError> {
         self.byte_stored.try_mul(count as u128)
     }
@@ -174,8 +262,17 @@ impl ResourceControlPolicy {
             operation_byte: Amount::from_nanos(10),
             operation: Amount::from_micros(10),
             message: Amount::from_micros(10),
+            maximum_fuel_per_block: u64::MAX,
+            maximum_operations_per_block: u64::MAX,
+            maximum_incoming_messages_per_block: u64::MAX,
+            maximum_outgoing_messages_per_block: u64::MAX,
+            maximum_operation_bytes_per_block: u64::MAX,
+            maximum_block_size: u64::MAX,
             maximum_bytes_read_per_block: 100_000_000,
             maximum_bytes_written_per_block: 10_000_000,
+            maximum_bytes_stored_per_application: u64::MAX,
+            maximum_bytes_stored_per_chain: u64::MAX,
+            target_fuel_per_block: 0,
         }
     }
 }
@@ -14,7 +14,7 @@ use linera_views::{common::Context, views::ViewError};
 
 use crate::{
     system::SystemExecutionError, ExecutionError, ExecutionStateView, Message, Operation,
-    ResourceControlPolicy,
+    ResourceControlPolicy, UserApplicationId,
 };
 
 #[derive(Clone, Debug, Default)]
@@ -103,10 +103,13 @@ where
         Ok(())
     }
 
-    /// Obtains the amount of fuel that could be spent by consuming the entire balance.
-    pub(crate) fn remaining_fuel(&self) -> u64 {
+    /// Obtains the amount of fuel that could be spent by consuming the entire balance,
+    /// without exceeding the fuel limit of the block.
+    pub(crate) fn remaining_fuel(&mut self) -> u64 {
+        let fuel = self.tracker.as_mut().fuel;
         self.policy
             .remaining_fuel(self.balance().unwrap_or(Amount::MAX))
+            .min(self.policy.maximum_fuel_per_block.saturating_sub(fuel))
     }
 
     /// Tracks the allocation of a grant.
@@ -145,6 +148,11 @@ where
                     .operation_bytes
                     .checked_add(size as u64)
                     .ok_or(ArithmeticError::Overflow)?;
+                if self.tracker.as_mut().operation_bytes
+                    > self.policy.maximum_operation_bytes_per_block
+                {
+                    return Err(ExecutionError::ExcessiveOperationBytes);
+                }
                 self.update_balance(self.policy.operation_bytes_price(size as u64)?)?;
                 Ok(())
             }
@@ -159,6 +167,11 @@ where
             .messages
             .checked_add(1)
             .ok_or(ArithmeticError::Overflow)?;
+        if u64::from(self.tracker.as_mut().messages)
+            > self.policy.maximum_outgoing_messages_per_block
+        {
+            return Err(ExecutionError::ExcessiveOutgoingMessages);
+        }
         self.update_balance(self.policy.message)?;
         match message {
             Message::System(_) => Ok(()),
@@ -184,6 +197,9 @@ where
             .fuel
             .checked_add(fuel)
             .ok_or(ArithmeticError::Overflow)?;
+        if self.tracker.as_mut().fuel > self.policy.maximum_fuel_per_block {
+            return Err(ExecutionError::ExcessiveFuel);
+        }
         self.update_balance(self.policy.fuel_price(fuel)?)
     }
 
@@ -239,20 +255,60 @@ where
         Ok(())
     }
 
-    /// Tracks a change in the number of bytes stored.
-    // TODO(#1536): This is not fully implemented.
-    #[allow(dead_code)]
-    pub(crate) fn track_stored_bytes(&mut self, delta: i32) -> Result<(), ExecutionError> {
+    /// Tracks a change in the number of bytes stored by an application. Growth is
+    /// charged and must stay within the storage limits of the policy, while shrinking is
+    /// refunded from the fees the application paid for the freed bytes.
+    pub(crate) fn track_stored_bytes(
+        &mut self,
+        application_id: UserApplicationId,
+        change: StorageChange,
+    ) -> Result<(), ExecutionError> {
         self.tracker.as_mut().bytes_stored = self
             .tracker
             .as_mut()
             .bytes_stored
-            .checked_add(delta)
+            .checked_add(change.delta)
             .ok_or(ArithmeticError::Overflow)?;
-        Ok(())
+        if change.delta <= 0 {
+            // Applications above their limit, e.g. after a policy change, can still shrink.
+            self.account.try_add_assign(change.refund)?;
+            return Ok(());
+        }
+        let maximum = self.policy.maximum_bytes_stored_per_application;
+        if change.application_bytes > maximum {
+            return Err(ExecutionError::ExcessiveApplicationStorage {
+                application_id: Box::new(application_id),
+                size: change.application_bytes,
+                maximum,
+            });
+        }
+        let maximum = self.policy.maximum_bytes_stored_per_chain;
+        if change.chain_bytes > maximum {
+            return Err(ExecutionError::ExcessiveChainStorage {
+                size: change.chain_bytes,
+                maximum,
+            });
+        }
+        let price = self
+            .policy
+            .bytes_stored_price(u64::from(change.delta.unsigned_abs()))?;
+        self.update_balance(price)
     }
 }
 
+/// The change in the storage used by an application after writing a batch.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct StorageChange {
+    /// The change in the number of bytes stored by the application.
+    pub delta: i32,
+    /// The number of bytes stored by the application after the change.
+    pub application_bytes: u64,
+    /// The number of bytes stored by all the applications of the chain after the change.
+    pub chain_bytes: u64,
+    /// The amount refunded for the freed bytes, if the storage shrank.
+    pub refund: Amount,
+}
+
 // The simplest `BalanceHolder` is an `Amount`.
 impl BalanceHolder for Amount {
     fn balance(&self) -> Result<Amount, ArithmeticError> {
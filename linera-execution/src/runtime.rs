@@ -10,15 +10,19 @@ use std::{
 
 use custom_debug_derive::Debug;
 use linera_base::{
+    crypto::CryptoHash,
     data_types::{
         Amount, ApplicationPermissions, ArithmeticError, BlockHeight, OracleRecord, OracleResponse,
         Resources, SendMessageRequest, Timestamp,
     },
     ensure,
-    identifiers::{Account, ApplicationId, ChainId, ChannelName, MessageId, Owner},
+    identifiers::{
+        Account, ApplicationId, BlobId, ChainId, ChannelName, GenericApplicationId, MessageId,
+        Owner, StreamId, StreamName,
+    },
     ownership::ChainOwnership,
 };
-use linera_views::batch::Batch;
+use linera_views::{batch::Batch, common::KeyRange};
 use oneshot::Receiver;
 
 use crate::{
@@ -104,11 +108,8 @@ impl<UserInstance> SyncRuntimeInternal<UserInstance> {
     fn next_message_index(&self) -> Result<u32, ArithmeticError> {
         let mut index = self.next_message_index;
         for outcome in &self.execution_outcomes {
-            let len = match outcome {
-                ExecutionOutcome::System(outcome) => outcome.messages.len(),
-                ExecutionOutcome::User(_, outcome) => outcome.messages.len(),
-            };
-            let len = u32::try_from(len).map_err(|_| ArithmeticError::Overflow)?;
+            let len =
+                u32::try_from(outcome.message_count()).map_err(|_| ArithmeticError::Overflow)?;
             index = index.checked_add(len).ok_or(ArithmeticError::Overflow)?;
         }
         Ok(index)
@@ -530,6 +531,9 @@ impl<UserInstance> BaseRuntime for SyncRuntime<UserInstance> {
     type FindKeysByPrefix = <SyncRuntimeInternal<UserInstance> as BaseRuntime>::FindKeysByPrefix;
     type FindKeyValuesByPrefix =
         <SyncRuntimeInternal<UserInstance> as BaseRuntime>::FindKeyValuesByPrefix;
+    type FindKeysInRange = <SyncRuntimeInternal<UserInstance> as BaseRuntime>::FindKeysInRange;
+    type FindKeyValuesInRange =
+        <SyncRuntimeInternal<UserInstance> as BaseRuntime>::FindKeyValuesInRange;
 
     fn chain_id(&mut self) -> Result<ChainId, ExecutionError> {
         self.inner().chain_id()
@@ -635,6 +639,36 @@ impl<UserInstance> BaseRuntime for SyncRuntime<UserInstance> {
         self.inner().find_key_values_by_prefix_wait(promise)
     }
 
+    fn find_keys_in_range_new(
+        &mut self,
+        key_prefix: Vec<u8>,
+        range: KeyRange,
+    ) -> Result<Self::FindKeysInRange, ExecutionError> {
+        self.inner().find_keys_in_range_new(key_prefix, range)
+    }
+
+    fn find_keys_in_range_wait(
+        &mut self,
+        promise: &Self::FindKeysInRange,
+    ) -> Result<Vec<Vec<u8>>, ExecutionError> {
+        self.inner().find_keys_in_range_wait(promise)
+    }
+
+    fn find_key_values_in_range_new(
+        &mut self,
+        key_prefix: Vec<u8>,
+        range: KeyRange,
+    ) -> Result<Self::FindKeyValuesInRange, ExecutionError> {
+        self.inner().find_key_values_in_range_new(key_prefix, range)
+    }
+
+    fn find_key_values_in_range_wait(
+        &mut self,
+        promise: &Self::FindKeyValuesInRange,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ExecutionError> {
+        self.inner().find_key_values_in_range_wait(promise)
+    }
+
     fn query_service(
         &mut self,
         application_id: ApplicationId,
@@ -655,6 +689,10 @@ impl<UserInstance> BaseRuntime for SyncRuntime<UserInstance> {
     fn assert_before(&mut self, timestamp: Timestamp) -> Result<(), ExecutionError> {
         self.inner().assert_before(timestamp)
     }
+
+    fn read_blob(&mut self, blob_id: BlobId) -> Result<Vec<u8>, ExecutionError> {
+        self.inner().read_blob(blob_id)
+    }
 }
 
 impl<UserInstance> BaseRuntime for SyncRuntimeInternal<UserInstance> {
@@ -664,6 +702,8 @@ impl<UserInstance> BaseRuntime for SyncRuntimeInternal<UserInstance> {
     type ReadMultiValuesBytes = u32;
     type FindKeysByPrefix = u32;
     type FindKeyValuesByPrefix = u32;
+    type FindKeysInRange = u32;
+    type FindKeyValuesInRange = u32;
 
     fn chain_id(&mut self) -> Result<ChainId, ExecutionError> {
         Ok(self.chain_id)
@@ -856,6 +896,58 @@ impl<UserInstance> BaseRuntime for SyncRuntimeInternal<UserInstance> {
         Ok(key_values)
     }
 
+    fn find_keys_in_range_new(
+        &mut self,
+        key_prefix: Vec<u8>,
+        range: KeyRange,
+    ) -> Result<Self::FindKeysInRange, ExecutionError> {
+        let id = self.application_id()?;
+        let state = self.view_user_states.entry(id).or_default();
+        self.resource_controller.track_read_operations(1)?;
+        let receiver =
+            self.execution_state_sender
+                .send_request(move |callback| Request::FindKeysInRange {
+                    id,
+                    key_prefix,
+                    range,
+                    callback,
+                })?;
+        state.find_keys_queries.register(receiver)
+    }
+
+    fn find_keys_in_range_wait(
+        &mut self,
+        promise: &Self::FindKeysInRange,
+    ) -> Result<Vec<Vec<u8>>, ExecutionError> {
+        self.find_keys_by_prefix_wait(promise)
+    }
+
+    fn find_key_values_in_range_new(
+        &mut self,
+        key_prefix: Vec<u8>,
+        range: KeyRange,
+    ) -> Result<Self::FindKeyValuesInRange, ExecutionError> {
+        let id = self.application_id()?;
+        let state = self.view_user_states.entry(id).or_default();
+        self.resource_controller.track_read_operations(1)?;
+        let receiver = self.execution_state_sender.send_request(move |callback| {
+            Request::FindKeyValuesInRange {
+                id,
+                key_prefix,
+                range,
+                callback,
+            }
+        })?;
+        state.find_key_values_queries.register(receiver)
+    }
+
+    fn find_key_values_in_range_wait(
+        &mut self,
+        promise: &Self::FindKeyValuesInRange,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ExecutionError> {
+        self.find_key_values_by_prefix_wait(promise)
+    }
+
     fn query_service(
         &mut self,
         application_id: ApplicationId,
@@ -930,6 +1022,26 @@ impl<UserInstance> BaseRuntime for SyncRuntimeInternal<UserInstance> {
         }
         Ok(())
     }
+
+    fn read_blob(&mut self, blob_id: BlobId) -> Result<Vec<u8>, ExecutionError> {
+        if let OracleResponses::Replay(responses) = &mut self.oracle_responses {
+            match responses.next() {
+                Some(OracleResponse::Blob(recorded_id)) if recorded_id == blob_id => {}
+                Some(_) => return Err(ExecutionError::OracleResponseMismatch),
+                None => return Err(ExecutionError::MissingOracleResponse),
+            }
+        }
+        let bytes = self
+            .execution_state_sender
+            .send_request(|callback| Request::ReadBlob { blob_id, callback })?
+            .recv_response()?;
+        self.resource_controller
+            .track_bytes_read(bytes.len() as u64)?;
+        if let OracleResponses::Record(responses) = &mut self.oracle_responses {
+            responses.push(OracleResponse::Blob(blob_id));
+        }
+        Ok(bytes)
+    }
 }
 
 impl<UserInstance> Clone for SyncRuntime<UserInstance> {
@@ -940,6 +1052,9 @@ impl<UserInstance> Clone for SyncRuntime<UserInstance> {
 
 impl ContractSyncRuntime {
     /// Main entry point to start executing a user action.
+    ///
+    /// Returns the oracle responses recorded by the action or, if `oracle_record` is given, the
+    /// responses that the action did not use.
     #[allow(clippy::type_complexity, clippy::too_many_arguments)]
     pub(crate) fn run_action(
         execution_state_sender: ExecutionStateSender,
@@ -952,7 +1067,7 @@ impl ContractSyncRuntime {
         oracle_record: Option<OracleRecord>,
     ) -> Result<(Vec<ExecutionOutcome>, OracleRecord, ResourceController), ExecutionError> {
         let executing_message = match &action {
-            UserAction::Message(context, _) => Some(context.into()),
+            UserAction::Message(context, _) | UserAction::Event(context, _) => Some(context.into()),
             _ => None,
         };
         let signer = action.signer();
@@ -987,15 +1102,18 @@ impl ContractSyncRuntime {
                 code.execute_operation(context, operation).map(|_| ())
             }
             UserAction::Message(context, message) => code.execute_message(context, message),
+            UserAction::Event(context, event) => code.process_event(context, event),
         })?;
         runtime.finalize(finalize_context)?;
         let runtime = runtime
             .into_inner()
             .expect("Runtime clones should have been freed by now");
-        let oracle_record = if let OracleResponses::Record(responses) = runtime.oracle_responses {
-            OracleRecord { responses }
-        } else {
-            OracleRecord::default()
+        let oracle_record = match runtime.oracle_responses {
+            OracleResponses::Record(responses) => OracleRecord { responses },
+            OracleResponses::Replay(responses) => OracleRecord {
+                responses: responses.collect(),
+            },
+            OracleResponses::Forget => OracleRecord::default(),
         };
         Ok((
             runtime.execution_outcomes,
@@ -1153,6 +1271,80 @@ impl ContractRuntime for ContractSyncRuntime {
         Ok(())
     }
 
+    fn emit(
+        &mut self,
+        name: StreamName,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), ExecutionError> {
+        let mut this = self.inner();
+        this.resource_controller
+            .track_bytes_written((key.len() + value.len()) as u64)?;
+        let application = this.current_application_mut();
+
+        application.outcome.events.push((name, key, value));
+
+        Ok(())
+    }
+
+    fn subscribe_to_events(
+        &mut self,
+        chain_id: ChainId,
+        application_id: UserApplicationId,
+        name: StreamName,
+    ) -> Result<(), ExecutionError> {
+        let mut this = self.inner();
+        let subscriber = this.current_application().id;
+        let stream_id = StreamId {
+            application_id: GenericApplicationId::User(application_id),
+            stream_name: name,
+        };
+        let maybe_message = this
+            .execution_state_sender
+            .send_request(|callback| Request::SubscribeToEvents {
+                chain_id,
+                stream_id,
+                subscriber,
+                callback,
+            })?
+            .recv_response()?;
+        if let Some(message) = maybe_message {
+            let outcome = RawExecutionOutcome::default().with_message(message);
+            this.execution_outcomes
+                .push(ExecutionOutcome::System(outcome));
+        }
+        Ok(())
+    }
+
+    fn unsubscribe_from_events(
+        &mut self,
+        chain_id: ChainId,
+        application_id: UserApplicationId,
+        name: StreamName,
+    ) -> Result<(), ExecutionError> {
+        let mut this = self.inner();
+        let subscriber = this.current_application().id;
+        let stream_id = StreamId {
+            application_id: GenericApplicationId::User(application_id),
+            stream_name: name,
+        };
+        let maybe_message = this
+            .execution_state_sender
+            .send_request(|callback| Request::UnsubscribeFromEvents {
+                chain_id,
+                stream_id,
+                subscriber,
+                callback,
+            })?
+            .recv_response()?;
+        if let Some(message) = maybe_message {
+            let outcome = RawExecutionOutcome::default().with_message(message);
+            this.execution_outcomes
+                .push(ExecutionOutcome::System(outcome));
+        }
+        Ok(())
+    }
+
     fn transfer(
         &mut self,
         source: Option<Owner>,
@@ -1265,6 +1457,29 @@ impl ContractRuntime for ContractSyncRuntime {
             .recv_response()?
     }
 
+    fn random_seed(&mut self, height: BlockHeight) -> Result<CryptoHash, ExecutionError> {
+        let mut this = self.inner();
+        ensure!(
+            height < this.height,
+            ExecutionError::RandomSeedNotRevealed(height)
+        );
+        if let OracleResponses::Replay(responses) = &mut this.oracle_responses {
+            return match responses.next() {
+                Some(OracleResponse::RandomSeed(seed)) => Ok(seed),
+                Some(_) => Err(ExecutionError::OracleResponseMismatch),
+                None => Err(ExecutionError::MissingOracleResponse),
+            };
+        }
+        let seed = this
+            .execution_state_sender
+            .send_request(|callback| Request::ReadRandomSeed { height, callback })?
+            .recv_response()?;
+        if let OracleResponses::Record(responses) = &mut this.oracle_responses {
+            responses.push(OracleResponse::RandomSeed(seed));
+        }
+        Ok(seed)
+    }
+
     fn write_batch(&mut self, batch: Batch) -> Result<(), ExecutionError> {
         let mut this = self.inner();
         let id = this.application_id()?;
@@ -1278,14 +1493,17 @@ impl ContractRuntime for ContractSyncRuntime {
         )?;
         this.resource_controller
             .track_bytes_written(batch.size() as u64)?;
-        this.execution_state_sender
+        let byte_price = this.resource_controller.policy.byte_stored;
+        let change = this
+            .execution_state_sender
             .send_request(|callback| Request::WriteBatch {
                 id,
                 batch,
+                byte_price,
                 callback,
             })?
             .recv_response()?;
-        Ok(())
+        this.resource_controller.track_stored_bytes(id, change)
     }
 }
 
@@ -3,18 +3,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fmt::{self, Display, Formatter},
     iter,
 };
 
-use async_graphql::Enum;
+use async_graphql::{Enum, SimpleObject};
 use custom_debug_derive::Debug;
 use linera_base::{
-    crypto::{CryptoHash, PublicKey},
-    data_types::{Amount, ApplicationPermissions, ArithmeticError, Timestamp},
+    crypto::{AccountPublicKey, CryptoError, CryptoHash, PublicKey},
+    data_types::{
+        Amount, ApplicationPermissions, ArithmeticError, StreamEvent, TimeDelta, Timestamp,
+    },
     ensure, hex_debug,
-    identifiers::{Account, BlobId, BytecodeId, ChainDescription, ChainId, MessageId, Owner},
+    identifiers::{
+        Account, BlobId, BytecodeId, ChainDescription, ChainId, MessageId, Owner, StreamId,
+    },
     ownership::{ChainOwnership, TimeoutConfig},
 };
 use linera_views::{
@@ -22,7 +26,7 @@ use linera_views::{
     map_view::HashedMapView,
     register_view::HashedRegisterView,
     set_view::HashedSetView,
-    views::{HashableView, View, ViewError},
+    views::{HashableView, ProvableView, View, ViewError},
 };
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -35,7 +39,7 @@ use {
 #[cfg(test)]
 use crate::test_utils::SystemExecutionState;
 use crate::{
-    committee::{Committee, Epoch},
+    committee::{Committee, Epoch, SignatureScheme, SignedFuelUsage, ValidatorName},
     ApplicationRegistryView, Bytecode, BytecodeLocation, ChannelName, ChannelSubscription,
     Destination, MessageContext, MessageKind, OperationContext, QueryContext, RawExecutionOutcome,
     RawOutgoingMessage, UserApplicationDescription, UserApplicationId,
@@ -62,7 +66,7 @@ static OPEN_CHAIN_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
 });
 
 /// A view accessing the execution state of the system of a chain.
-#[derive(Debug, HashableView)]
+#[derive(Debug, HashableView, ProvableView)]
 pub struct SystemExecutionStateView<C> {
     /// How the chain was created. May be unknown for inactive chains.
     pub description: HashedRegisterView<C, Option<ChainDescription>>,
@@ -91,6 +95,19 @@ pub struct SystemExecutionStateView<C> {
     pub closed: HashedRegisterView<C, bool>,
     /// Permissions for applications on this chain.
     pub application_permissions: HashedRegisterView<C, ApplicationPermissions>,
+    /// The recurring transfers from this chain, indexed by their ID.
+    pub standing_orders: HashedMapView<C, u64, StandingOrder>,
+    /// The ID of the next standing order to be created.
+    pub next_standing_order_id: HashedRegisterView<C, u64>,
+    /// The grant required for incoming user messages from unknown senders.
+    pub message_grant_policy: HashedRegisterView<C, MessageGrantPolicy>,
+    /// The number of bytes stored by all the user applications of the chain.
+    pub stored_bytes: HashedRegisterView<C, u64>,
+    /// The fees paid for the bytes currently stored by each user application. Refunds
+    /// for freed bytes never exceed them.
+    pub storage_deposits: HashedMapView<C, UserApplicationId, Amount>,
+    /// The applications of this chain subscribed to each event stream of another chain.
+    pub event_subscriptions: HashedMapView<C, (ChainId, StreamId), BTreeSet<UserApplicationId>>,
 }
 
 /// The configuration for a new chain.
@@ -104,6 +121,44 @@ pub struct OpenChainConfig {
     pub application_permissions: ApplicationPermissions,
 }
 
+/// A transfer that is repeated at regular intervals, until an end time.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct StandingOrder {
+    /// The account to debit, or `None` for the chain balance.
+    pub owner: Option<Owner>,
+    /// The recipient of the transfers.
+    pub recipient: Recipient,
+    /// The amount of each transfer.
+    pub amount: Amount,
+    /// The time between two transfers.
+    pub interval: TimeDelta,
+    /// The time when the next transfer is due.
+    pub next_due: Timestamp,
+    /// No transfers are due after this time.
+    pub end_time: Timestamp,
+}
+
+/// The grant that incoming user messages must carry to be executed on a chain, unless they
+/// come from a trusted sender.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct MessageGrantPolicy {
+    /// The minimum grant of incoming user messages.
+    pub minimum_grant: Amount,
+    /// The chains whose messages don't need a minimum grant.
+    pub trusted_senders: Vec<ChainId>,
+}
+
+impl MessageGrantPolicy {
+    /// Returns the minimum grant required for user messages from `sender` to `receiver`.
+    pub fn minimum_grant_from(&self, sender: ChainId, receiver: ChainId) -> Amount {
+        if sender == receiver || self.trusted_senders.contains(&sender) {
+            Amount::ZERO
+        } else {
+            self.minimum_grant
+        }
+    }
+}
+
 /// A system operation.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub enum SystemOperation {
@@ -133,13 +188,16 @@ pub enum SystemOperation {
     /// Changes the ownership of the chain.
     ChangeOwnership {
         /// Super owners can propose fast blocks in the first round, and regular blocks in any round.
-        super_owners: Vec<PublicKey>,
+        super_owners: Vec<AccountPublicKey>,
         /// The regular owners, with their weights that determine how often they are round leader.
-        owners: Vec<(PublicKey, u64)>,
+        owners: Vec<(AccountPublicKey, u64)>,
         /// The number of initial rounds after 0 in which all owners are allowed to propose blocks.
         multi_leader_rounds: u32,
         /// The timeout configuration: how long fast, multi-leader and single-leader rounds last.
         timeout_config: TimeoutConfig,
+        /// If set, blocks proposed by regular owners must be approved by owners whose weights
+        /// add up to at least this threshold.
+        approval_threshold: Option<u64>,
     },
     /// Changes the application permissions configuration on this chain.
     ChangeApplicationPermissions(ApplicationPermissions),
@@ -178,6 +236,35 @@ pub enum SystemOperation {
     },
     /// Operations that are only allowed on the admin chain.
     Admin(AdminOperation),
+    /// Creates, updates or cancels a standing order.
+    StandingOrder(StandingOrderOperation),
+    /// Changes the grant required for incoming user messages on this chain.
+    ChangeMessageGrantPolicy(MessageGrantPolicy),
+}
+
+/// Operations managing the standing orders of a chain.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub enum StandingOrderOperation {
+    /// Creates a standing order transferring `amount` from the given owner's account (or
+    /// from the chain balance) every `interval`, starting one interval after the current
+    /// block, and until `end_time`.
+    Create {
+        owner: Option<Owner>,
+        recipient: Recipient,
+        amount: Amount,
+        interval: TimeDelta,
+        end_time: Timestamp,
+    },
+    /// Changes the amount, interval and end time of a standing order. The next transfer
+    /// remains due at the same time.
+    Update {
+        id: u64,
+        amount: Amount,
+        interval: TimeDelta,
+        end_time: Timestamp,
+    },
+    /// Cancels a standing order.
+    Cancel { id: u64 },
 }
 
 /// Operations that are only allowed on the admin chain.
@@ -191,6 +278,14 @@ pub enum AdminOperation {
     /// blocks from the retired epoch will not be accepted until they are followed (hence
     /// re-certified) by a block certified by a recent committee.
     RemoveCommittee { epoch: Epoch },
+    /// Registers a new committee with the same validators as the current one, and with
+    /// prices adjusted to the fuel consumed per block reported by the validators. This is
+    /// only allowed if the current policy has a target fuel per block, and if the reports
+    /// for the current epoch are signed by a quorum of the current committee.
+    AdjustFees {
+        epoch: Epoch,
+        reports: Vec<SignedFuelUsage>,
+    },
 }
 
 /// A system message meant to be executed on a remote chain.
@@ -247,6 +342,8 @@ pub enum SystemMessage {
     /// Requests a `RegisterApplication` message from the target chain to register the specified
     /// application on the sender chain.
     RequestApplication(UserApplicationId),
+    /// Delivers an event of a stream that the recipient chain is subscribed to.
+    NewEvent(StreamEvent),
 }
 
 impl SystemMessage {
@@ -277,7 +374,8 @@ impl SystemMessage {
             | SystemMessage::Unsubscribe { .. }
             | SystemMessage::ApplicationCreated
             | SystemMessage::Notify { .. }
-            | SystemMessage::RequestApplication(_) => Box::new(iter::empty()),
+            | SystemMessage::RequestApplication(_)
+            | SystemMessage::NewEvent(_) => Box::new(iter::empty()),
         }
     }
 }
@@ -313,6 +411,15 @@ impl SystemChannel {
     }
 }
 
+/// Returns the [`ChannelName`] of the system channel that delivers the events of a stream
+/// to the chains subscribed to it. It is longer than the name of any [`SystemChannel`], so
+/// the two cannot be confused.
+pub fn event_stream_channel_name(stream_id: &StreamId) -> ChannelName {
+    let mut name = b"events:".to_vec();
+    bcs::serialize_into(&mut name, stream_id).expect("`StreamId` can be serialized");
+    name.into()
+}
+
 impl Display for SystemChannel {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         let display_name = match self {
@@ -377,12 +484,37 @@ pub enum SystemExecutionError {
     IncorrectClaimAmount,
     #[error("Claim must be authenticated by the right signer")]
     UnauthenticatedClaimOwner,
+    #[error("Standing order must have positive amount and interval")]
+    IncorrectStandingOrder,
+    #[error("Standing order from owned account must be authenticated by the right signer")]
+    UnauthenticatedStandingOrderOwner,
+    #[error("Unknown standing order {0}")]
+    UnknownStandingOrder(u64),
+    #[error(
+        "Message from chain {sender} has a grant of {grant}, but at least {minimum_grant} is \
+         required"
+    )]
+    InsufficientMessageGrant {
+        sender: ChainId,
+        grant: Amount,
+        minimum_grant: Amount,
+    },
     #[error("Admin operations are only allowed on the admin chain.")]
     AdminOperationOnNonAdminChain,
     #[error("Failed to create new committee")]
     InvalidCommitteeCreation,
     #[error("Failed to remove committee")]
     InvalidCommitteeRemoval,
+    #[error("Committees using BLS signatures require a BLS public key for every validator")]
+    MissingBlsPublicKeys,
+    #[error("Invalid BLS proof of possession: {0}")]
+    InvalidBlsProofOfPossession(CryptoError),
+    #[error("The prices of the current committee are fixed and cannot be adjusted")]
+    FixedFees,
+    #[error("Invalid fuel usage report from validator {0}")]
+    InvalidFuelUsageReport(ValidatorName),
+    #[error("The fuel usage reports are not signed by a quorum of validators")]
+    InsufficientFuelUsageReports,
     #[error(
         "Chain {0} tried to subscribe to the admin channel ({1}) of a chain that is not the admin chain"
     )]
@@ -393,6 +525,8 @@ pub enum SystemExecutionError {
     AlreadySubscribedToChannel(ChainId, SystemChannel),
     #[error("Invalid unsubscription request to channel {1} on chain {0}")]
     InvalidUnsubscription(ChainId, SystemChannel),
+    #[error("Cannot subscribe to an event stream on the same chain ({0})")]
+    SelfEventSubscription(ChainId),
     #[error("Amount overflow")]
     AmountOverflow,
     #[error("Amount underflow")]
@@ -411,6 +545,10 @@ pub enum SystemExecutionError {
     UnknownApplicationId(Box<UserApplicationId>),
     #[error("Chain is not active yet.")]
     InactiveChain,
+    #[error(
+        "Invalid approval threshold {0}: it must be positive and at most the owners' total weight"
+    )]
+    InvalidApprovalThreshold(u64),
 }
 
 impl<C> SystemExecutionStateView<C>
@@ -433,6 +571,38 @@ where
         Some((*epoch, committee))
     }
 
+    /// Registers the committee of a new epoch and returns the message notifying the
+    /// subscribers of the admin chain.
+    fn create_committee(
+        &mut self,
+        epoch: Epoch,
+        committee: Committee,
+    ) -> Result<RawOutgoingMessage<SystemMessage, Amount>, SystemExecutionError> {
+        ensure!(
+            epoch == self.epoch.get().expect("chain is active").try_add_one()?,
+            SystemExecutionError::InvalidCommitteeCreation
+        );
+        ensure!(
+            committee.signature_scheme() != SignatureScheme::Bls || committee.has_bls_public_keys(),
+            SystemExecutionError::MissingBlsPublicKeys
+        );
+        committee
+            .check_bls_proofs_of_possession()
+            .map_err(SystemExecutionError::InvalidBlsProofOfPossession)?;
+        self.committees.get_mut().insert(epoch, committee);
+        self.epoch.set(Some(epoch));
+        Ok(RawOutgoingMessage {
+            destination: Destination::Subscribers(SystemChannel::Admin.name()),
+            authenticated: false,
+            grant: Amount::ZERO,
+            kind: MessageKind::Protected,
+            message: SystemMessage::SetCommittees {
+                epoch,
+                committees: self.committees.get().clone(),
+            },
+        })
+    }
+
     /// Executes the sender's side of an operation and returns a list of actions to be
     /// taken.
     pub async fn execute_operation(
@@ -462,8 +632,9 @@ where
                 owners,
                 multi_leader_rounds,
                 timeout_config,
+                approval_threshold,
             } => {
-                self.ownership.set(ChainOwnership {
+                let ownership = ChainOwnership {
                     super_owners: super_owners
                         .into_iter()
                         .map(|public_key| (Owner::from(public_key), public_key))
@@ -474,11 +645,24 @@ where
                         .collect(),
                     multi_leader_rounds,
                     timeout_config,
-                });
+                    approval_threshold,
+                };
+                if let Some(threshold) = approval_threshold {
+                    // Otherwise no block could ever be approved again.
+                    let total_weight = ownership.owners_weight(ownership.owners.keys());
+                    ensure!(
+                        threshold > 0 && threshold <= total_weight,
+                        SystemExecutionError::InvalidApprovalThreshold(threshold)
+                    );
+                }
+                self.ownership.set(ownership);
             }
             ChangeApplicationPermissions(application_permissions) => {
                 self.application_permissions.set(application_permissions);
             }
+            ChangeMessageGrantPolicy(message_grant_policy) => {
+                self.message_grant_policy.set(message_grant_policy);
+            }
             CloseChain => {
                 let messages = self.close_chain(context.chain_id).await?;
                 outcome.messages.extend(messages);
@@ -524,22 +708,22 @@ where
                 );
                 match admin_operation {
                     AdminOperation::CreateCommittee { epoch, committee } => {
-                        ensure!(
-                            epoch == self.epoch.get().expect("chain is active").try_add_one()?,
-                            SystemExecutionError::InvalidCommitteeCreation
-                        );
-                        self.committees.get_mut().insert(epoch, committee);
-                        self.epoch.set(Some(epoch));
-                        let message = RawOutgoingMessage {
-                            destination: Destination::Subscribers(SystemChannel::Admin.name()),
-                            authenticated: false,
-                            grant: Amount::ZERO,
-                            kind: MessageKind::Protected,
-                            message: SystemMessage::SetCommittees {
-                                epoch,
-                                committees: self.committees.get().clone(),
-                            },
-                        };
+                        let message = self.create_committee(epoch, committee)?;
+                        outcome.messages.push(message);
+                    }
+                    AdminOperation::AdjustFees { epoch, reports } => {
+                        let (current_epoch, committee) = self
+                            .current_committee()
+                            .ok_or(SystemExecutionError::InactiveChain)?;
+                        let observed_fuel_per_block =
+                            reported_fuel_per_block(committee, current_epoch, &reports)?;
+                        let policy = committee
+                            .policy()
+                            .adjusted(observed_fuel_per_block)?
+                            .ok_or(SystemExecutionError::FixedFees)?;
+                        let committee = Committee::new(committee.validators().clone(), policy)
+                            .with_signature_scheme(committee.signature_scheme());
+                        let message = self.create_committee(epoch, committee)?;
                         outcome.messages.push(message);
                     }
                     AdminOperation::RemoveCommittee { epoch } => {
@@ -673,11 +857,176 @@ where
                 outcome.messages.push(message);
             }
             PublishBlob { .. } => (),
+            SystemOperation::StandingOrder(standing_order_operation) => {
+                self.execute_standing_order_operation(
+                    context.authenticated_signer,
+                    standing_order_operation,
+                )
+                .await?;
+            }
         }
 
         Ok((outcome, new_application))
     }
 
+    /// Checks that an incoming user message carries the grant required by this chain's
+    /// message grant policy. Messages bouncing back to this chain are always accepted.
+    pub fn check_message_grant(
+        &self,
+        context: &MessageContext,
+        grant: Amount,
+    ) -> Result<(), SystemExecutionError> {
+        if context.is_bouncing {
+            return Ok(());
+        }
+        let sender = context.message_id.chain_id;
+        let minimum_grant = self
+            .message_grant_policy
+            .get()
+            .minimum_grant_from(sender, context.chain_id);
+        ensure!(
+            grant >= minimum_grant,
+            SystemExecutionError::InsufficientMessageGrant {
+                sender,
+                grant,
+                minimum_grant,
+            }
+        );
+        Ok(())
+    }
+
+    async fn execute_standing_order_operation(
+        &mut self,
+        authenticated_signer: Option<Owner>,
+        operation: StandingOrderOperation,
+    ) -> Result<(), SystemExecutionError> {
+        match operation {
+            StandingOrderOperation::Create {
+                owner,
+                recipient,
+                amount,
+                interval,
+                end_time,
+            } => {
+                if owner.is_some() {
+                    ensure!(
+                        authenticated_signer == owner,
+                        SystemExecutionError::UnauthenticatedStandingOrderOwner
+                    );
+                }
+                ensure!(
+                    amount > Amount::ZERO && interval > TimeDelta::ZERO,
+                    SystemExecutionError::IncorrectStandingOrder
+                );
+                let id = *self.next_standing_order_id.get();
+                self.next_standing_order_id
+                    .set(id.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+                let order = StandingOrder {
+                    owner,
+                    recipient,
+                    amount,
+                    interval,
+                    next_due: self.timestamp.get().saturating_add(interval),
+                    end_time,
+                };
+                self.standing_orders.insert(&id, order)?;
+            }
+            StandingOrderOperation::Update {
+                id,
+                amount,
+                interval,
+                end_time,
+            } => {
+                let mut order = self
+                    .authorized_standing_order(authenticated_signer, id)
+                    .await?;
+                ensure!(
+                    amount > Amount::ZERO && interval > TimeDelta::ZERO,
+                    SystemExecutionError::IncorrectStandingOrder
+                );
+                order.amount = amount;
+                order.interval = interval;
+                order.end_time = end_time;
+                self.standing_orders.insert(&id, order)?;
+            }
+            StandingOrderOperation::Cancel { id } => {
+                self.authorized_standing_order(authenticated_signer, id)
+                    .await?;
+                self.standing_orders.remove(&id)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the standing order with the given ID, if it exists and the signer is allowed
+    /// to modify it.
+    async fn authorized_standing_order(
+        &self,
+        authenticated_signer: Option<Owner>,
+        id: u64,
+    ) -> Result<StandingOrder, SystemExecutionError> {
+        let order = self
+            .standing_orders
+            .get(&id)
+            .await?
+            .ok_or(SystemExecutionError::UnknownStandingOrder(id))?;
+        if order.owner.is_some() {
+            ensure!(
+                authenticated_signer == order.owner,
+                SystemExecutionError::UnauthenticatedStandingOrderOwner
+            );
+        }
+        Ok(order)
+    }
+
+    /// Executes the transfers of all standing orders that are due at the current timestamp.
+    ///
+    /// If several intervals have elapsed since the last payment, the missed transfers are
+    /// made at once. Orders whose account does not have enough funds are skipped and retried
+    /// in the next block. Orders are removed after their last transfer.
+    pub async fn execute_standing_orders(
+        &mut self,
+    ) -> Result<Vec<RawOutgoingMessage<SystemMessage, Amount>>, SystemExecutionError> {
+        let now = *self.timestamp.get();
+        let mut messages = Vec::new();
+        for id in self.standing_orders.indices().await? {
+            let Some(mut order) = self.standing_orders.get(&id).await? else {
+                continue;
+            };
+            if order.next_due > now {
+                continue;
+            }
+            if order.next_due > order.end_time {
+                self.standing_orders.remove(&id)?;
+                continue;
+            }
+            let last_due = now.min(order.end_time);
+            let count =
+                last_due.delta_since(order.next_due).as_micros() / order.interval.as_micros() + 1;
+            let total = order.amount.try_mul(u128::from(count))?;
+            let balance = match &order.owner {
+                Some(owner) => self.balances.get(owner).await?.unwrap_or_default(),
+                None => *self.balance.get(),
+            };
+            if balance < total {
+                continue;
+            }
+            messages.extend(
+                self.transfer(order.owner, order.owner, order.recipient, total)
+                    .await?,
+            );
+            order.next_due = order
+                .next_due
+                .saturating_add(order.interval.saturating_mul(count));
+            if order.next_due > order.end_time {
+                self.standing_orders.remove(&id)?;
+            } else {
+                self.standing_orders.insert(&id, order)?;
+            }
+        }
+        Ok(messages)
+    }
+
     pub async fn transfer(
         &mut self,
         authenticated_signer: Option<Owner>,
@@ -903,7 +1252,8 @@ where
             OpenChain(_) => {
                 // This special message is executed immediately when cross-chain requests are received.
             }
-            ApplicationCreated | Notify { .. } => (),
+            // Events are delivered to the subscribed applications by the execution state.
+            ApplicationCreated | Notify { .. } | NewEvent(_) => (),
         }
         Ok(outcome)
     }
@@ -1028,9 +1378,176 @@ where
             })
             .await?;
         self.subscriptions.clear();
+        // Unsubscribe from all event streams.
+        self.event_subscriptions
+            .for_each_index(|(chain_id, stream_id)| {
+                let subscription = ChannelSubscription {
+                    chain_id,
+                    name: event_stream_channel_name(&stream_id),
+                };
+                let message = RawOutgoingMessage {
+                    destination: Destination::Recipient(chain_id),
+                    authenticated: false,
+                    grant: Amount::ZERO,
+                    kind: MessageKind::Protected,
+                    message: SystemMessage::Unsubscribe { id, subscription },
+                };
+                messages.push(message);
+                Ok(())
+            })
+            .await?;
+        self.event_subscriptions.clear();
         self.closed.set(true);
         Ok(messages)
     }
+
+    /// Subscribes an application of this chain to an event stream of another chain. Returns
+    /// the message subscribing this chain to the stream, if no other application of this
+    /// chain is subscribed to it yet.
+    pub async fn subscribe_to_events(
+        &mut self,
+        id: ChainId,
+        chain_id: ChainId,
+        stream_id: StreamId,
+        subscriber: UserApplicationId,
+    ) -> Result<Option<RawOutgoingMessage<SystemMessage, Amount>>, SystemExecutionError> {
+        ensure!(
+            id != chain_id,
+            SystemExecutionError::SelfEventSubscription(id)
+        );
+        let subscription = ChannelSubscription {
+            chain_id,
+            name: event_stream_channel_name(&stream_id),
+        };
+        let subscribers = self
+            .event_subscriptions
+            .get_mut_or_default(&(chain_id, stream_id))
+            .await?;
+        let is_first = subscribers.is_empty();
+        subscribers.insert(subscriber);
+        Ok(is_first.then(|| RawOutgoingMessage {
+            destination: Destination::Recipient(chain_id),
+            authenticated: false,
+            grant: Amount::ZERO,
+            kind: MessageKind::Protected,
+            message: SystemMessage::Subscribe { id, subscription },
+        }))
+    }
+
+    /// Unsubscribes an application of this chain from an event stream of another chain.
+    /// Returns the message unsubscribing this chain from the stream, if no other application
+    /// of this chain is subscribed to it anymore.
+    pub async fn unsubscribe_from_events(
+        &mut self,
+        id: ChainId,
+        chain_id: ChainId,
+        stream_id: StreamId,
+        subscriber: UserApplicationId,
+    ) -> Result<Option<RawOutgoingMessage<SystemMessage, Amount>>, SystemExecutionError> {
+        let subscription = ChannelSubscription {
+            chain_id,
+            name: event_stream_channel_name(&stream_id),
+        };
+        let key = (chain_id, stream_id);
+        let Some(subscribers) = self.event_subscriptions.get_mut(&key).await? else {
+            return Ok(None);
+        };
+        if !subscribers.remove(&subscriber) || !subscribers.is_empty() {
+            return Ok(None);
+        }
+        self.event_subscriptions.remove(&key)?;
+        Ok(Some(RawOutgoingMessage {
+            destination: Destination::Recipient(chain_id),
+            authenticated: false,
+            grant: Amount::ZERO,
+            kind: MessageKind::Protected,
+            message: SystemMessage::Unsubscribe { id, subscription },
+        }))
+    }
+
+    /// Records a change in the number of bytes stored by an application, from
+    /// `previous_bytes` to `application_bytes`. Growth adds its fees at `byte_price` to the
+    /// deposit of the application. Shrinking returns the refund, i.e. the share of the
+    /// deposit paid for the freed bytes, and removes it from the deposit.
+    pub async fn update_stored_bytes(
+        &mut self,
+        application_id: UserApplicationId,
+        previous_bytes: u64,
+        application_bytes: u64,
+        byte_price: Amount,
+    ) -> Result<Amount, SystemExecutionError> {
+        let stored_bytes = self.stored_bytes.get_mut();
+        // Bytes written before the total was tracked are not counted.
+        *stored_bytes = stored_bytes
+            .saturating_sub(previous_bytes)
+            .checked_add(application_bytes)
+            .ok_or(ArithmeticError::Overflow)?;
+        let mut deposit = self
+            .storage_deposits
+            .get(&application_id)
+            .await?
+            .unwrap_or_default();
+        let refund = if application_bytes >= previous_bytes {
+            let fees = byte_price.try_mul(u128::from(application_bytes - previous_bytes))?;
+            deposit.try_add_assign(fees)?;
+            Amount::ZERO
+        } else {
+            let freed = u128::from(previous_bytes - application_bytes);
+            let refund = u128::from(deposit)
+                .checked_mul(freed)
+                .ok_or(ArithmeticError::Overflow)?
+                / u128::from(previous_bytes);
+            let refund = Amount::from(refund);
+            deposit.try_sub_assign(refund)?;
+            refund
+        };
+        if deposit == Amount::ZERO {
+            self.storage_deposits.remove(&application_id)?;
+        } else {
+            self.storage_deposits.insert(&application_id, deposit)?;
+        }
+        Ok(refund)
+    }
+}
+
+/// Returns the median of the fuel per block that the validators of `committee` reported
+/// for `epoch`, weighted by their votes. Each validator may report once and the reports
+/// must come from a quorum: since faulty validators have fewer than a third of the votes,
+/// the result is within the range reported by correct validators.
+fn reported_fuel_per_block(
+    committee: &Committee,
+    epoch: Epoch,
+    reports: &[SignedFuelUsage],
+) -> Result<u64, SystemExecutionError> {
+    let mut reported = BTreeMap::new();
+    for report in reports {
+        let validator = report.validator;
+        let weight = committee.weight(&validator);
+        ensure!(
+            weight > 0
+                && report.usage.epoch == epoch
+                && report.signature.check(&report.usage, validator.0).is_ok()
+                && reported
+                    .insert(validator, (report.usage.fuel_per_block, weight))
+                    .is_none(),
+            SystemExecutionError::InvalidFuelUsageReport(validator)
+        );
+    }
+    let total_weight = reported.values().map(|(_, weight)| weight).sum::<u64>();
+    ensure!(
+        total_weight >= committee.quorum_threshold(),
+        SystemExecutionError::InsufficientFuelUsageReports
+    );
+    let mut usages = reported.into_values().collect::<Vec<_>>();
+    usages.sort_unstable();
+    let mut cumulative_weight = 0;
+    for (fuel_per_block, weight) in usages {
+        cumulative_weight += weight;
+        if 2 * cumulative_weight >= total_weight {
+            return Ok(fuel_per_block);
+        }
+    }
+    unreachable!("the reports have a positive total weight")
 }
 
 #[cfg(test)]
@@ -75,6 +75,11 @@ type ExecuteMessageHandler = Box<
         + Send
         + Sync,
 >;
+type ProcessEventHandler = Box<
+    dyn FnOnce(&mut ContractSyncRuntime, MessageContext, Vec<u8>) -> Result<(), ExecutionError>
+        + Send
+        + Sync,
+>;
 type FinalizeHandler = Box<
     dyn FnOnce(&mut ContractSyncRuntime, FinalizeContext) -> Result<(), ExecutionError>
         + Send
@@ -94,6 +99,8 @@ pub enum ExpectedCall {
     ExecuteOperation(ExecuteOperationHandler),
     /// An expected call to [`UserContract::execute_message`].
     ExecuteMessage(ExecuteMessageHandler),
+    /// An expected call to [`UserContract::process_event`].
+    ProcessEvent(ProcessEventHandler),
     /// An expected call to [`UserContract::finalize`].
     Finalize(FinalizeHandler),
     /// An expected call to [`UserService::handle_query`].
@@ -106,6 +113,7 @@ impl Display for ExpectedCall {
             ExpectedCall::Instantiate(_) => "instantiate",
             ExpectedCall::ExecuteOperation(_) => "execute_operation",
             ExpectedCall::ExecuteMessage(_) => "execute_message",
+            ExpectedCall::ProcessEvent(_) => "process_event",
             ExpectedCall::Finalize(_) => "finalize",
             ExpectedCall::HandleQuery(_) => "handle_query",
         };
@@ -158,6 +166,18 @@ impl ExpectedCall {
         ExpectedCall::ExecuteMessage(Box::new(handler))
     }
 
+    /// Creates an [`ExpectedCall`] to the [`MockApplicationInstance`]'s
+    /// [`UserContract::process_event`] implementation, which is handled by the provided
+    /// `handler`.
+    pub fn process_event(
+        handler: impl FnOnce(&mut ContractSyncRuntime, MessageContext, Vec<u8>) -> Result<(), ExecutionError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        ExpectedCall::ProcessEvent(Box::new(handler))
+    }
+
     /// Creates an [`ExpectedCall`] to the [`MockApplicationInstance`]'s [`UserContract::finalize`]
     /// implementation, which is handled by the provided `handler`.
     pub fn finalize(
@@ -265,6 +285,20 @@ impl UserContract for MockApplicationInstance<ContractSyncRuntime> {
         }
     }
 
+    fn process_event(
+        &mut self,
+        context: MessageContext,
+        event: Vec<u8>,
+    ) -> Result<(), ExecutionError> {
+        match self.next_expected_call() {
+            Some(ExpectedCall::ProcessEvent(handler)) => handler(&mut self.runtime, context, event),
+            Some(unexpected_call) => panic!(
+                "Expected a call to `process_event`, got a call to `{unexpected_call}` instead."
+            ),
+            None => panic!("Unexpected call to `process_event`"),
+        }
+    }
+
     fn finalize(&mut self, context: FinalizeContext) -> Result<(), ExecutionError> {
         match self.next_expected_call() {
             Some(ExpectedCall::Finalize(handler)) => handler(&mut self.runtime, context),
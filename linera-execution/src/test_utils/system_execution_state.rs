@@ -22,7 +22,7 @@ use crate::{
     applications::ApplicationRegistry,
     committee::{Committee, Epoch},
     execution::UserAction,
-    system::SystemChannel,
+    system::{MessageGrantPolicy, StandingOrder, SystemChannel},
     ChannelSubscription, ExecutionError, ExecutionRuntimeConfig, ExecutionRuntimeContext,
     ExecutionStateView, OperationContext, ResourceControlPolicy, ResourceController,
     ResourceTracker, TestExecutionRuntimeContext, UserApplicationDescription, UserContractCode,
@@ -43,6 +43,9 @@ pub struct SystemExecutionState {
     pub registry: ApplicationRegistry,
     pub closed: bool,
     pub application_permissions: ApplicationPermissions,
+    pub standing_orders: BTreeMap<u64, StandingOrder>,
+    pub next_standing_order_id: u64,
+    pub message_grant_policy: MessageGrantPolicy,
 }
 
 impl SystemExecutionState {
@@ -100,6 +103,9 @@ impl SystemExecutionState {
             registry,
             closed,
             application_permissions,
+            standing_orders,
+            next_standing_order_id,
+            message_grant_policy,
         } = self;
         let extra = TestExecutionRuntimeContext::new(chain_id, execution_runtime_config);
         let context = MemoryContext::new(TEST_MEMORY_MAX_STREAM_QUERIES, extra);
@@ -133,6 +139,16 @@ impl SystemExecutionState {
         view.system
             .application_permissions
             .set(application_permissions);
+        for (id, order) in standing_orders {
+            view.system
+                .standing_orders
+                .insert(&id, order)
+                .expect("insertion of standing orders should not fail");
+        }
+        view.system
+            .next_standing_order_id
+            .set(next_standing_order_id);
+        view.system.message_grant_policy.set(message_grant_policy);
         view
     }
 }
@@ -0,0 +1,126 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    cell::Cell,
+    fs,
+    time::{Duration, SystemTime},
+};
+
+use once_cell::sync::Lazy;
+
+use super::{engine_fingerprint, ArtifactKind, ModuleDiskCache};
+use crate::Bytecode;
+
+static KIND: Lazy<ArtifactKind> = Lazy::new(|| ArtifactKind {
+    runtime: "test",
+    module: "contract",
+    engine: engine_fingerprint(("test", 1)),
+});
+
+/// A fake runtime "compiling" a bytecode by reversing it.
+fn load_or_compile(
+    cache: &ModuleDiskCache,
+    bytecode: &Bytecode,
+    compilations: &Cell<u32>,
+) -> Vec<u8> {
+    cache
+        .load_or_compile(
+            bytecode.clone(),
+            &KIND,
+            |artifact| Ok(artifact.to_vec()),
+            |bytecode| {
+                compilations.set(compilations.get() + 1);
+                Ok(bytecode.bytes.into_iter().rev().collect())
+            },
+            |module| Ok(module.clone()),
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_artifacts_are_reused_after_restart() {
+    let directory = tempfile::tempdir().unwrap();
+    let bytecode = Bytecode::new(vec![1, 2, 3]);
+    let compilations = Cell::new(0);
+
+    let cache = ModuleDiskCache::new(directory.path().to_owned(), u64::MAX).unwrap();
+    assert_eq!(
+        load_or_compile(&cache, &bytecode, &compilations),
+        vec![3, 2, 1]
+    );
+    assert_eq!(compilations.get(), 1);
+
+    let cache = ModuleDiskCache::new(directory.path().to_owned(), u64::MAX).unwrap();
+    assert_eq!(
+        load_or_compile(&cache, &bytecode, &compilations),
+        vec![3, 2, 1]
+    );
+    assert_eq!(compilations.get(), 1);
+}
+
+#[test]
+fn test_corrupted_artifacts_are_recompiled() {
+    let directory = tempfile::tempdir().unwrap();
+    let bytecode = Bytecode::new(vec![1, 2, 3]);
+    let compilations = Cell::new(0);
+
+    let cache = ModuleDiskCache::new(directory.path().to_owned(), u64::MAX).unwrap();
+    load_or_compile(&cache, &bytecode, &compilations);
+    let path = cache.path(&bytecode, &KIND);
+    let mut contents = fs::read(&path).unwrap();
+    *contents.last_mut().unwrap() ^= 0xff;
+    fs::write(&path, contents).unwrap();
+
+    assert_eq!(
+        load_or_compile(&cache, &bytecode, &compilations),
+        vec![3, 2, 1]
+    );
+    assert_eq!(compilations.get(), 2);
+    assert_eq!(cache.load(&path), Some(vec![3, 2, 1]));
+}
+
+#[test]
+fn test_eviction_by_size() {
+    let directory = tempfile::tempdir().unwrap();
+    let compilations = Cell::new(0);
+    let artifact_file_size = (super::MAGIC.len() + super::CHECKSUM_SIZE + 100) as u64;
+
+    let cache = ModuleDiskCache::new(directory.path().to_owned(), 2 * artifact_file_size).unwrap();
+    let bytecodes = (0..3u8)
+        .map(|byte| Bytecode::new(vec![byte; 100]))
+        .collect::<Vec<_>>();
+    for (seconds, bytecode) in bytecodes[..2].iter().enumerate() {
+        load_or_compile(&cache, bytecode, &compilations);
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(seconds as u64 + 1);
+        fs::File::options()
+            .write(true)
+            .open(cache.path(bytecode, &KIND))
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+    load_or_compile(&cache, &bytecodes[2], &compilations);
+
+    assert!(!cache.path(&bytecodes[0], &KIND).exists());
+    assert!(cache.path(&bytecodes[1], &KIND).exists());
+    assert!(cache.path(&bytecodes[2], &KIND).exists());
+}
+
+#[test]
+fn test_artifacts_are_keyed_by_engine() {
+    let directory = tempfile::tempdir().unwrap();
+    let bytecode = Bytecode::new(vec![1, 2, 3]);
+    let compilations = Cell::new(0);
+    let other_engine = ArtifactKind {
+        engine: engine_fingerprint(("test", 2)),
+        ..KIND.clone()
+    };
+    assert_eq!(KIND.engine, engine_fingerprint(("test", 1)));
+    assert_ne!(KIND.engine, other_engine.engine);
+
+    let cache = ModuleDiskCache::new(directory.path().to_owned(), u64::MAX).unwrap();
+    load_or_compile(&cache, &bytecode, &compilations);
+    assert!(cache.path(&bytecode, &KIND).exists());
+    assert!(!cache.path(&bytecode, &other_engine).exists());
+}
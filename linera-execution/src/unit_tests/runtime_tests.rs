@@ -7,15 +7,15 @@
 
 use futures::{channel::mpsc, StreamExt};
 use linera_base::{
-    data_types::{BlockHeight, Timestamp},
+    data_types::{Amount, BlockHeight, Timestamp},
     identifiers::{ApplicationId, BytecodeId, ChainDescription, MessageId},
 };
 use linera_views::batch::Batch;
 
 use super::{ApplicationStatus, SyncRuntime, SyncRuntimeInternal};
 use crate::{
-    execution_state_actor::Request, runtime::ResourceController, ContractRuntime,
-    RawExecutionOutcome, UserContractInstance,
+    execution_state_actor::Request, resources::StorageChange, runtime::ResourceController,
+    ContractRuntime, RawExecutionOutcome, UserContractInstance,
 };
 
 /// Test writing a batch of changes.
@@ -52,6 +52,7 @@ async fn test_write_batch() {
         let Request::WriteBatch {
             id,
             batch,
+            byte_price,
             callback,
         } = request
         else {
@@ -60,9 +61,10 @@ async fn test_write_batch() {
 
         assert_eq!(id, expected_application_id);
         assert_eq!(batch, expected_batch);
+        assert_eq!(byte_price, Amount::ZERO);
 
         callback
-            .send(())
+            .send(StorageChange::default())
             .expect("Failed to notify that writing the batch finished");
     });
 
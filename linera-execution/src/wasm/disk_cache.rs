@@ -0,0 +1,278 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A persistent cache of compiled WebAssembly modules.
+//!
+//! Compiling application bytecodes with Wasmer or Wasmtime is slow, so the serialized native
+//! artifacts can be stored in a directory and reused after a restart. The cache is
+//! content-addressed: each file is named after the hash of the bytecode, the kind of module and a
+//! fingerprint of the version and configuration of the engine that compiled it. Every file starts
+//! with a checksum of the artifact, which is verified before the artifact is handed to the runtime.
+//! When the files exceed the maximum size of the cache, the least recently used ones are removed.
+
+use std::hash::Hash;
+#[cfg(with_fs)]
+use std::{
+    fs,
+    hash::Hasher,
+    io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+#[cfg(with_fs)]
+use once_cell::sync::OnceCell;
+#[cfg(with_fs)]
+use sha3::{Digest as _, Sha3_256};
+#[cfg(with_fs)]
+use tracing::warn;
+
+use crate::Bytecode;
+
+/// The first bytes of every file in the cache.
+#[cfg(with_fs)]
+const MAGIC: &[u8; 8] = b"LNRWASM1";
+
+/// The size of the checksum following [`MAGIC`].
+#[cfg(with_fs)]
+const CHECKSUM_SIZE: usize = 32;
+
+/// The extension of the files in the cache.
+#[cfg(with_fs)]
+const EXTENSION: &str = "module";
+
+/// The disk cache used by the runtimes, if enabled.
+#[cfg(with_fs)]
+static DISK_CACHE: OnceCell<ModuleDiskCache> = OnceCell::new();
+
+/// Describes how an artifact was compiled.
+#[derive(Clone, Debug)]
+pub(crate) struct ArtifactKind {
+    /// The runtime that compiled the artifact.
+    pub runtime: &'static str,
+    /// Whether the artifact is a `contract` or a `service`.
+    pub module: &'static str,
+    /// Identifies the version and configuration of the runtime's engine, see
+    /// [`engine_fingerprint`].
+    pub engine: String,
+}
+
+/// Returns a short name for an engine, given a `hash` that changes with the engine's version
+/// and configuration, such as `wasmtime::Engine::precompile_compatibility_hash`.
+#[cfg_attr(not(with_fs), allow(unused_variables))]
+pub(crate) fn engine_fingerprint(hash: impl Hash) -> String {
+    #[cfg(with_fs)]
+    {
+        let mut hasher = Sha3Hasher(Sha3_256::new());
+        hash.hash(&mut hasher);
+        hex::encode(&hasher.0.finalize()[..8])
+    }
+
+    #[cfg(not(with_fs))]
+    {
+        String::new()
+    }
+}
+
+/// A [`Hasher`] whose output doesn't depend on the Rust version, unlike the standard ones.
+#[cfg(with_fs)]
+struct Sha3Hasher(Sha3_256);
+
+#[cfg(with_fs)]
+impl Hasher for Sha3Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().finalize();
+        u64::from_le_bytes(digest[..8].try_into().expect("the digest has 32 bytes"))
+    }
+}
+
+/// Enables the persistent cache of compiled modules in `directory`, storing at most `max_size`
+/// bytes.
+#[cfg(with_fs)]
+pub fn enable_module_disk_cache(directory: impl Into<PathBuf>, max_size: u64) -> io::Result<()> {
+    let cache = ModuleDiskCache::new(directory.into(), max_size)?;
+    if DISK_CACHE.set(cache).is_err() {
+        warn!("The disk cache of compiled WebAssembly modules is already enabled");
+    }
+    Ok(())
+}
+
+/// Returns the module compiled from `bytecode`, loading it from the disk cache if possible.
+///
+/// Otherwise, the module is created with `compile`, and its serialized artifact is stored in the
+/// disk cache, if enabled.
+#[cfg_attr(not(with_fs), allow(unused_variables))]
+pub(crate) fn load_or_compile<Module>(
+    bytecode: Bytecode,
+    kind: &ArtifactKind,
+    deserialize: impl FnOnce(&[u8]) -> anyhow::Result<Module>,
+    compile: impl FnOnce(Bytecode) -> anyhow::Result<Module>,
+    serialize: impl FnOnce(&Module) -> anyhow::Result<Vec<u8>>,
+) -> anyhow::Result<Module> {
+    #[cfg(with_fs)]
+    if let Some(cache) = DISK_CACHE.get() {
+        return cache.load_or_compile(bytecode, kind, deserialize, compile, serialize);
+    }
+    compile(bytecode)
+}
+
+/// A directory of compiled modules.
+#[cfg(with_fs)]
+pub(crate) struct ModuleDiskCache {
+    directory: PathBuf,
+    max_size: u64,
+}
+
+#[cfg(with_fs)]
+impl ModuleDiskCache {
+    /// Creates a cache in `directory`, creating the directory if needed.
+    pub fn new(directory: PathBuf, max_size: u64) -> io::Result<Self> {
+        fs::create_dir_all(&directory)?;
+        Ok(ModuleDiskCache {
+            directory,
+            max_size,
+        })
+    }
+
+    /// Returns the module compiled from `bytecode`, see [`load_or_compile`].
+    pub fn load_or_compile<Module>(
+        &self,
+        bytecode: Bytecode,
+        kind: &ArtifactKind,
+        deserialize: impl FnOnce(&[u8]) -> anyhow::Result<Module>,
+        compile: impl FnOnce(Bytecode) -> anyhow::Result<Module>,
+        serialize: impl FnOnce(&Module) -> anyhow::Result<Vec<u8>>,
+    ) -> anyhow::Result<Module> {
+        let path = self.path(&bytecode, kind);
+        if let Some(artifact) = self.load(&path) {
+            match deserialize(&artifact) {
+                Ok(module) => return Ok(module),
+                Err(error) => {
+                    warn!(
+                        "Failed to load cached WebAssembly module {}: {error}",
+                        path.display()
+                    );
+                    self.remove(&path);
+                }
+            }
+        }
+        let module = compile(bytecode)?;
+        match serialize(&module) {
+            Ok(artifact) => {
+                if let Err(error) = self.store(&path, &artifact) {
+                    warn!(
+                        "Failed to cache WebAssembly module {}: {error}",
+                        path.display()
+                    );
+                }
+            }
+            Err(error) => warn!("Failed to serialize WebAssembly module: {error}"),
+        }
+        Ok(module)
+    }
+
+    /// Returns the path of the artifact compiled from `bytecode`.
+    fn path(&self, bytecode: &Bytecode, kind: &ArtifactKind) -> PathBuf {
+        let ArtifactKind {
+            runtime,
+            module,
+            engine,
+        } = kind;
+        let hash = hex::encode(Sha3_256::digest(&bytecode.bytes));
+        let version = env!("CARGO_PKG_VERSION");
+        self.directory.join(format!(
+            "{hash}-{runtime}-{module}-{engine}-{version}.{EXTENSION}"
+        ))
+    }
+
+    /// Reads the artifact at `path`, if it exists and its checksum is valid.
+    fn load(&self, path: &Path) -> Option<Vec<u8>> {
+        let mut contents = match fs::read(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return None,
+            Err(error) => {
+                warn!("Failed to read {}: {error}", path.display());
+                return None;
+            }
+        };
+        let header_size = MAGIC.len() + CHECKSUM_SIZE;
+        if contents.len() < header_size
+            || &contents[..MAGIC.len()] != MAGIC
+            || contents[MAGIC.len()..header_size] != *Sha3_256::digest(&contents[header_size..])
+        {
+            warn!(
+                "Removing corrupted WebAssembly module {} from the cache",
+                path.display()
+            );
+            self.remove(path);
+            return None;
+        }
+        // Mark the artifact as recently used.
+        if let Err(error) = fs::File::options()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+        {
+            warn!("Failed to update {}: {error}", path.display());
+        }
+        Some(contents.split_off(header_size))
+    }
+
+    /// Writes `artifact` at `path`, then evicts old artifacts if the cache is too large.
+    fn store(&self, path: &Path, artifact: &[u8]) -> io::Result<()> {
+        let mut contents = Vec::with_capacity(MAGIC.len() + CHECKSUM_SIZE + artifact.len());
+        contents.extend_from_slice(MAGIC);
+        contents.extend_from_slice(&Sha3_256::digest(artifact));
+        contents.extend_from_slice(artifact);
+        // Readers must never see a partially written file.
+        let temporary_path = path.with_extension(format!("{EXTENSION}.{}.tmp", std::process::id()));
+        fs::write(&temporary_path, &contents)?;
+        fs::rename(&temporary_path, path)?;
+        self.evict()
+    }
+
+    /// Removes the file at `path`, if it exists.
+    fn remove(&self, path: &Path) {
+        if let Err(error) = fs::remove_file(path) {
+            if error.kind() != io::ErrorKind::NotFound {
+                warn!("Failed to remove {}: {error}", path.display());
+            }
+        }
+    }
+
+    /// Removes the least recently used artifacts until their total size is at most
+    /// `max_size`.
+    fn evict(&self) -> io::Result<()> {
+        let mut artifacts = Vec::new();
+        let mut total_size = 0;
+        for entry in fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .map_or(true, |extension| extension != EXTENSION)
+            {
+                continue;
+            }
+            let metadata = fs::metadata(&path)?;
+            total_size += metadata.len();
+            artifacts.push((metadata.modified()?, metadata.len(), path));
+        }
+        artifacts.sort();
+        for (_, size, path) in artifacts {
+            if total_size <= self.max_size {
+                break;
+            }
+            self.remove(&path);
+            total_size -= size;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, with_fs))]
+#[path = "../unit_tests/disk_cache_tests.rs"]
+mod tests;
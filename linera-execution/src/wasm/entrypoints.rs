@@ -11,6 +11,7 @@ pub trait ContractEntrypoints {
     fn instantiate(argument: Vec<u8>);
     fn execute_operation(operation: Vec<u8>) -> Vec<u8>;
     fn execute_message(message: Vec<u8>);
+    fn process_event(event: Vec<u8>);
     fn finalize();
 }
 
@@ -10,6 +10,7 @@
 
 #![cfg(with_wasm_runtime)]
 
+mod disk_cache;
 mod entrypoints;
 mod module_cache;
 mod sanitizer;
@@ -35,6 +36,8 @@ use wasmer::{WasmerContractInstance, WasmerServiceInstance};
 #[cfg(with_wasmtime)]
 use wasmtime::{WasmtimeContractInstance, WasmtimeServiceInstance};
 
+#[cfg(with_fs)]
+pub use self::disk_cache::enable_module_disk_cache;
 use self::sanitizer::sanitize;
 pub use self::{
     entrypoints::{ContractEntrypoints, ServiceEntrypoints},
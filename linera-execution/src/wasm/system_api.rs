@@ -4,11 +4,17 @@
 use std::{any::Any, collections::HashMap, marker::PhantomData};
 
 use linera_base::{
+    crypto::CryptoHash,
     data_types::{Amount, ApplicationPermissions, BlockHeight, SendMessageRequest, Timestamp},
-    identifiers::{Account, ApplicationId, ChainId, ChannelName, MessageId, Owner},
+    identifiers::{
+        Account, ApplicationId, BlobId, ChainId, ChannelName, MessageId, Owner, StreamName,
+    },
     ownership::{ChainOwnership, CloseChainError},
 };
-use linera_views::batch::{Batch, WriteOperation};
+use linera_views::{
+    batch::{Batch, WriteOperation},
+    common::{Direction, KeyBound, KeyRange},
+};
 use linera_witty::{wit_export, Instance, RuntimeError};
 use tracing::log;
 
@@ -221,6 +227,50 @@ where
             .map_err(|error| RuntimeError::Custom(error.into()))
     }
 
+    /// Adds an event with the given `key` and `value` to one of the application's event streams.
+    fn emit(
+        caller: &mut Caller,
+        name: StreamName,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), RuntimeError> {
+        caller
+            .user_data_mut()
+            .runtime
+            .emit(name, key, value)
+            .map_err(|error| RuntimeError::Custom(error.into()))
+    }
+
+    /// Subscribes the application to the event stream `name` of `application_id` on another
+    /// chain.
+    fn subscribe_to_events(
+        caller: &mut Caller,
+        chain_id: ChainId,
+        application_id: ApplicationId,
+        name: StreamName,
+    ) -> Result<(), RuntimeError> {
+        caller
+            .user_data_mut()
+            .runtime
+            .subscribe_to_events(chain_id, application_id, name)
+            .map_err(|error| RuntimeError::Custom(error.into()))
+    }
+
+    /// Unsubscribes the application from the event stream `name` of `application_id` on
+    /// another chain.
+    fn unsubscribe_from_events(
+        caller: &mut Caller,
+        chain_id: ChainId,
+        application_id: ApplicationId,
+        name: StreamName,
+    ) -> Result<(), RuntimeError> {
+        caller
+            .user_data_mut()
+            .runtime
+            .unsubscribe_from_events(chain_id, application_id, name)
+            .map_err(|error| RuntimeError::Custom(error.into()))
+    }
+
     /// Transfers an `amount` of native tokens from `source` owner account (or the current chain's
     /// balance) to `destination`.
     fn transfer(
@@ -338,6 +388,25 @@ where
             .map_err(|error| RuntimeError::Custom(error.into()))
     }
 
+    /// Reads the contents of the published blob with the given ID.
+    fn read_blob(caller: &mut Caller, blob_id: BlobId) -> Result<Vec<u8>, RuntimeError> {
+        caller
+            .user_data_mut()
+            .runtime
+            .read_blob(blob_id)
+            .map_err(|error| RuntimeError::Custom(error.into()))
+    }
+
+    /// Returns the random seed revealed by the certificate of this chain's block at the given
+    /// height.
+    fn random_seed(caller: &mut Caller, height: BlockHeight) -> Result<CryptoHash, RuntimeError> {
+        caller
+            .user_data_mut()
+            .runtime
+            .random_seed(height)
+            .map_err(|error| RuntimeError::Custom(error.into()))
+    }
+
     /// Logs a `message` with the provided information `level`.
     fn log(_caller: &mut Caller, message: String, level: log::Level) -> Result<(), RuntimeError> {
         match level {
@@ -513,6 +582,15 @@ where
             .map_err(|error| RuntimeError::Custom(error.into()))
     }
 
+    /// Reads the contents of the published blob with the given ID.
+    fn read_blob(caller: &mut Caller, blob_id: BlobId) -> Result<Vec<u8>, RuntimeError> {
+        caller
+            .user_data_mut()
+            .runtime
+            .read_blob(blob_id)
+            .map_err(|error| RuntimeError::Custom(error.into()))
+    }
+
     /// Logs a `message` with the provided information `level`.
     fn log(_caller: &mut Caller, message: String, level: log::Level) -> Result<(), RuntimeError> {
         match level {
@@ -655,6 +733,73 @@ where
             .map_err(|error| RuntimeError::Custom(error.into()))
     }
 
+    /// Creates a new promise to search for keys that start with the `key_prefix` and whose
+    /// remainder lies between `start` and `end`.
+    fn find_keys_in_range_new(
+        caller: &mut Caller,
+        key_prefix: Vec<u8>,
+        start: KeyBound,
+        end: KeyBound,
+        direction: Direction,
+        limit: Option<u32>,
+    ) -> Result<u32, RuntimeError> {
+        let range = key_range(start, end, direction, limit);
+        let mut data = caller.user_data_mut();
+        let promise = data
+            .runtime
+            .find_keys_in_range_new(key_prefix, range)
+            .map_err(|error| RuntimeError::Custom(error.into()))?;
+
+        data.register_promise(promise)
+    }
+
+    /// Waits for the promise to search for keys in a range.
+    fn find_keys_in_range_wait(
+        caller: &mut Caller,
+        promise_id: u32,
+    ) -> Result<Vec<Vec<u8>>, RuntimeError> {
+        let mut data = caller.user_data_mut();
+        let promise = data.take_promise(promise_id)?;
+
+        data.runtime
+            .find_keys_in_range_wait(&promise)
+            .map_err(|error| RuntimeError::Custom(error.into()))
+    }
+
+    /// Creates a new promise to search for entries whose keys start with the `key_prefix`
+    /// and have a remainder between `start` and `end`.
+    fn find_key_values_in_range_new(
+        caller: &mut Caller,
+        key_prefix: Vec<u8>,
+        start: KeyBound,
+        end: KeyBound,
+        direction: Direction,
+        limit: Option<u32>,
+    ) -> Result<u32, RuntimeError> {
+        let range = key_range(start, end, direction, limit);
+        let mut data = caller.user_data_mut();
+        let promise = data
+            .runtime
+            .find_key_values_in_range_new(key_prefix, range)
+            .map_err(|error| RuntimeError::Custom(error.into()))?;
+
+        data.register_promise(promise)
+    }
+
+    /// Waits for the promise to search for entries whose keys are in a range.
+    #[allow(clippy::type_complexity)]
+    fn find_key_values_in_range_wait(
+        caller: &mut Caller,
+        promise_id: u32,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, RuntimeError> {
+        let mut data = caller.user_data_mut();
+        let promise = data.take_promise(promise_id)?;
+
+        data.runtime
+            .find_key_values_in_range_wait(&promise)
+            .map_err(|error| RuntimeError::Custom(error.into()))
+    }
+
     /// Writes a batch of `operations` to storage.
     fn write_batch(
         caller: &mut Caller,
@@ -665,6 +810,16 @@ where
     }
 }
 
+/// Builds the [`KeyRange`] described by the arguments of a range query.
+fn key_range(start: KeyBound, end: KeyBound, direction: Direction, limit: Option<u32>) -> KeyRange {
+    KeyRange {
+        start: start.into(),
+        end: end.into(),
+        direction,
+        limit: limit.map(|limit| limit as usize),
+    }
+}
+
 // TODO(#1977): Remove once the WIT interface does not include `write-batch` in the service system
 // API
 /// An extension trait to separate the behavior between the contract runtime and the service
@@ -16,6 +16,7 @@ use wasm_instrument::{gas_metering, parity_wasm};
 use wasmer::{Engine, Module, Store};
 
 use super::{
+    disk_cache::{self, ArtifactKind},
     module_cache::ModuleCache,
     system_api::{ContractSystemApi, ServiceSystemApi, SystemApiData, ViewSystemApi, WriteBatch},
     ContractEntrypoints, ServiceEntrypoints, WasmExecutionError,
@@ -45,6 +46,23 @@ static CONTRACT_CACHE: Lazy<Mutex<ModuleCache<CachedContractModule>>> = Lazy::ne
 /// A cache of compiled service modules.
 static SERVICE_CACHE: Lazy<Mutex<ModuleCache<Module>>> = Lazy::new(Mutex::default);
 
+/// The key of compiled contract modules in the disk cache.
+static CONTRACT_ARTIFACT: Lazy<ArtifactKind> = Lazy::new(|| {
+    let engine = CachedContractModule::create_compilation_engine();
+    ArtifactKind {
+        runtime: "wasmer",
+        module: "contract",
+        engine: disk_cache::engine_fingerprint((wasmer::VERSION, engine.deterministic_id())),
+    }
+});
+
+/// The key of compiled service modules in the disk cache.
+static SERVICE_ARTIFACT: Lazy<ArtifactKind> = Lazy::new(|| ArtifactKind {
+    runtime: "wasmer",
+    module: "service",
+    engine: disk_cache::engine_fingerprint((wasmer::VERSION, SERVICE_ENGINE.deterministic_id())),
+});
+
 /// Type representing a running [Wasmer](https://wasmer.io/) contract.
 pub(crate) struct WasmerContractInstance<Runtime> {
     /// The Wasmer instance.
@@ -62,7 +80,15 @@ impl WasmContractModule {
     pub async fn from_wasmer(contract_bytecode: Bytecode) -> Result<Self, WasmExecutionError> {
         let mut contract_cache = CONTRACT_CACHE.lock().await;
         let (engine, module) = contract_cache
-            .get_or_insert_with(contract_bytecode, CachedContractModule::new)
+            .get_or_insert_with(contract_bytecode, |bytecode| {
+                disk_cache::load_or_compile(
+                    bytecode,
+                    &CONTRACT_ARTIFACT,
+                    CachedContractModule::from_artifact,
+                    CachedContractModule::new,
+                    |module| Ok(module.compiled_bytecode.to_vec()),
+                )
+            })
             .map_err(WasmExecutionError::LoadContractModule)?
             .create_execution_instance()
             .map_err(WasmExecutionError::LoadContractModule)?;
@@ -98,7 +124,14 @@ impl WasmServiceModule {
         let mut service_cache = SERVICE_CACHE.lock().await;
         let module = service_cache
             .get_or_insert_with(service_bytecode, |bytecode| {
-                Module::new(&*SERVICE_ENGINE, bytecode).map_err(anyhow::Error::from)
+                disk_cache::load_or_compile(
+                    bytecode,
+                    &SERVICE_ARTIFACT,
+                    // The artifact was produced by `Module::serialize` and its checksum verified.
+                    |artifact| Ok(unsafe { Module::deserialize(&*SERVICE_ENGINE, artifact) }?),
+                    |bytecode| Ok(Module::new(&*SERVICE_ENGINE, bytecode)?),
+                    |module| Ok(module.serialize()?.to_vec()),
+                )
             })
             .map_err(WasmExecutionError::LoadServiceModule)?;
         Ok(WasmServiceModule::Wasmer { module })
@@ -159,6 +192,17 @@ where
         Ok(())
     }
 
+    fn process_event(
+        &mut self,
+        _context: MessageContext,
+        event: Vec<u8>,
+    ) -> Result<(), ExecutionError> {
+        ContractEntrypoints::new(&mut self.instance)
+            .process_event(event)
+            .map_err(WasmExecutionError::from)?;
+        Ok(())
+    }
+
     fn finalize(&mut self, _context: FinalizeContext) -> Result<(), ExecutionError> {
         ContractEntrypoints::new(&mut self.instance)
             .finalize()
@@ -255,6 +299,16 @@ impl CachedContractModule {
         Ok(CachedContractModule { compiled_bytecode })
     }
 
+    /// Creates a [`CachedContractModule`] from a compiled contract loaded from the disk cache.
+    fn from_artifact(artifact: &[u8]) -> Result<Self, anyhow::Error> {
+        let module = CachedContractModule {
+            compiled_bytecode: Bytes::copy_from_slice(artifact),
+        };
+        // Make sure that this version of Wasmer can load the artifact.
+        module.create_execution_instance()?;
+        Ok(module)
+    }
+
     /// Creates a new [`Engine`] to compile a contract bytecode.
     fn create_compilation_engine() -> Engine {
         #[cfg(not(web))]
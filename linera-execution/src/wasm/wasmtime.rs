@@ -11,6 +11,7 @@ use tokio::sync::Mutex;
 use wasmtime::{AsContextMut, Config, Engine, Linker, Module, Store};
 
 use super::{
+    disk_cache::{self, ArtifactKind},
     module_cache::ModuleCache,
     system_api::{ContractSystemApi, ServiceSystemApi, SystemApiData, ViewSystemApi, WriteBatch},
     ContractEntrypoints, ServiceEntrypoints, WasmExecutionError,
@@ -40,6 +41,22 @@ static CONTRACT_CACHE: Lazy<Mutex<ModuleCache<Module>>> = Lazy::new(Mutex::defau
 /// A cache of compiled service modules.
 static SERVICE_CACHE: Lazy<Mutex<ModuleCache<Module>>> = Lazy::new(Mutex::default);
 
+/// The key of compiled contract modules in the disk cache.
+///
+/// Wasmtime also refuses to deserialize artifacts compiled by another version.
+static CONTRACT_ARTIFACT: Lazy<ArtifactKind> = Lazy::new(|| ArtifactKind {
+    runtime: "wasmtime",
+    module: "contract",
+    engine: disk_cache::engine_fingerprint(CONTRACT_ENGINE.precompile_compatibility_hash()),
+});
+
+/// The key of compiled service modules in the disk cache.
+static SERVICE_ARTIFACT: Lazy<ArtifactKind> = Lazy::new(|| ArtifactKind {
+    runtime: "wasmtime",
+    module: "service",
+    engine: disk_cache::engine_fingerprint(SERVICE_ENGINE.precompile_compatibility_hash()),
+});
+
 /// Type representing a running [Wasmtime](https://wasmtime.dev/) contract.
 ///
 /// The runtime has a lifetime so that it does not outlive the trait object used to export the
@@ -115,7 +132,14 @@ impl WasmContractModule {
         let mut contract_cache = CONTRACT_CACHE.lock().await;
         let module = contract_cache
             .get_or_insert_with(contract_bytecode, |bytecode| {
-                Module::new(&CONTRACT_ENGINE, bytecode)
+                disk_cache::load_or_compile(
+                    bytecode,
+                    &CONTRACT_ARTIFACT,
+                    // The artifact was produced by `Module::serialize` and its checksum verified.
+                    |artifact| unsafe { Module::deserialize(&CONTRACT_ENGINE, artifact) },
+                    |bytecode| Module::new(&CONTRACT_ENGINE, bytecode),
+                    Module::serialize,
+                )
             })
             .map_err(WasmExecutionError::LoadContractModule)?;
         Ok(WasmContractModule::Wasmtime { module })
@@ -152,7 +176,14 @@ impl WasmServiceModule {
         let mut service_cache = SERVICE_CACHE.lock().await;
         let module = service_cache
             .get_or_insert_with(service_bytecode, |bytecode| {
-                Module::new(&SERVICE_ENGINE, bytecode)
+                disk_cache::load_or_compile(
+                    bytecode,
+                    &SERVICE_ARTIFACT,
+                    // The artifact was produced by `Module::serialize` and its checksum verified.
+                    |artifact| unsafe { Module::deserialize(&SERVICE_ENGINE, artifact) },
+                    |bytecode| Module::new(&SERVICE_ENGINE, bytecode),
+                    Module::serialize,
+                )
             })
             .map_err(WasmExecutionError::LoadServiceModule)?;
         Ok(WasmServiceModule::Wasmtime { module })
@@ -221,6 +252,18 @@ where
         Ok(())
     }
 
+    fn process_event(
+        &mut self,
+        _context: MessageContext,
+        event: Vec<u8>,
+    ) -> Result<(), ExecutionError> {
+        self.configure_initial_fuel()?;
+        let result = ContractEntrypoints::new(&mut self.instance).process_event(event);
+        self.persist_remaining_fuel()?;
+        result.map_err(WasmExecutionError::from)?;
+        Ok(())
+    }
+
     fn finalize(&mut self, _context: FinalizeContext) -> Result<(), ExecutionError> {
         self.configure_initial_fuel()?;
         let result = ContractEntrypoints::new(&mut self.instance).finalize();
@@ -146,8 +146,17 @@ async fn test_fee_consumption(
         operation_byte: Amount::from_tokens(23),
         message: Amount::from_tokens(29),
         message_byte: Amount::from_tokens(31),
+        maximum_fuel_per_block: u64::MAX,
+        maximum_operations_per_block: u64::MAX,
+        maximum_incoming_messages_per_block: u64::MAX,
+        maximum_outgoing_messages_per_block: u64::MAX,
+        maximum_operation_bytes_per_block: u64::MAX,
+        maximum_block_size: u64::MAX,
         maximum_bytes_read_per_block: 37,
         maximum_bytes_written_per_block: 41,
+        maximum_bytes_stored_per_application: 43,
+        maximum_bytes_stored_per_chain: 47,
+        target_fuel_per_block: 0,
     };
 
     let consumed_fees = spends
@@ -3,17 +3,20 @@
 
 #![allow(clippy::field_reassign_with_default)]
 
-use std::{collections::BTreeMap, vec};
+use std::{collections::BTreeMap, sync::Arc, vec};
 
 use assert_matches::assert_matches;
 use futures::{stream, StreamExt, TryStreamExt};
 use linera_base::{
-    crypto::PublicKey,
+    crypto::{CryptoHash, PublicKey},
     data_types::{
-        Amount, ApplicationPermissions, BlockHeight, OracleRecord, Resources, SendMessageRequest,
-        Timestamp,
+        Amount, ApplicationPermissions, BlockHeight, HashedBlob, OracleRecord, OracleResponse,
+        Resources, SendMessageRequest, StreamEvent, Timestamp,
+    },
+    identifiers::{
+        Account, ChainDescription, ChainId, Destination, EventId, GenericApplicationId, MessageId,
+        Owner, StreamId, StreamName,
     },
-    identifiers::{Account, ChainDescription, ChainId, Destination, MessageId, Owner},
     ownership::ChainOwnership,
 };
 use linera_execution::{
@@ -23,11 +26,12 @@ use linera_execution::{
         create_dummy_user_application_registrations, register_mock_applications, ExpectedCall,
         SystemExecutionState,
     },
-    BaseRuntime, ContractRuntime, ExecutionError, ExecutionOutcome, MessageKind, Operation,
-    OperationContext, Query, QueryContext, RawExecutionOutcome, RawOutgoingMessage,
-    ResourceControlPolicy, ResourceController, Response, SystemOperation,
+    BaseRuntime, ContractRuntime, ExecutionError, ExecutionOutcome, Message, MessageContext,
+    MessageKind, Operation, OperationContext, Query, QueryContext, RawExecutionOutcome,
+    RawOutgoingMessage, ResourceControlPolicy, ResourceController, Response, SystemExecutionError,
+    SystemOperation,
 };
-use linera_views::batch::Batch;
+use linera_views::{batch::Batch, views::View};
 
 fn make_operation_context() -> OperationContext {
     OperationContext {
@@ -1621,3 +1625,546 @@ async fn test_close_chain() {
     .unwrap();
     assert!(view.system.closed.get());
 }
+
+/// Tests that growing the storage of an application is charged, that shrinking it is
+/// refunded up to the fees paid, and that the storage limits of the policy are enforced.
+#[tokio::test]
+async fn test_storage_quota() -> anyhow::Result<()> {
+    let mut state = SystemExecutionState::default();
+    state.description = Some(ChainDescription::Root(0));
+    let mut view = state.into_view().await;
+    view.system.balance.set(Amount::from_tokens(100));
+
+    let mut applications = register_mock_applications(&mut view, 1).await?;
+    let (application_id, application) = applications
+        .next()
+        .expect("Mock application should be registered");
+
+    let policy = ResourceControlPolicy {
+        byte_stored: Amount::ONE,
+        maximum_bytes_stored_per_application: 20,
+        maximum_bytes_stored_per_chain: 20,
+        ..ResourceControlPolicy::default()
+    };
+    let mut controller = ResourceController {
+        policy: Arc::new(policy),
+        ..ResourceController::default()
+    };
+    let operation = Operation::User {
+        application_id,
+        bytes: vec![],
+    };
+
+    // Storing a key of 1 byte and a value of 10 bytes costs 11 tokens.
+    application.expect_call(ExpectedCall::execute_operation(
+        |runtime, _context, _operation| {
+            let mut batch = Batch::new();
+            batch.put_key_value_bytes(vec![0], vec![1; 10]);
+            runtime.write_batch(batch)?;
+            Ok(vec![])
+        },
+    ));
+    application.expect_call(ExpectedCall::default_finalize());
+    view.execute_operation(
+        make_operation_context(),
+        Timestamp::from(0),
+        operation.clone(),
+        Some(OracleRecord::default()),
+        &mut controller,
+    )
+    .await?;
+    assert_eq!(controller.tracker.bytes_stored, 11);
+    assert_eq!(*view.system.balance.get(), Amount::from_tokens(89));
+
+    // Deleting the key is refunded.
+    application.expect_call(ExpectedCall::execute_operation(
+        |runtime, _context, _operation| {
+            let mut batch = Batch::new();
+            batch.delete_key(vec![0]);
+            runtime.write_batch(batch)?;
+            Ok(vec![])
+        },
+    ));
+    application.expect_call(ExpectedCall::default_finalize());
+    view.execute_operation(
+        make_operation_context(),
+        Timestamp::from(0),
+        operation.clone(),
+        Some(OracleRecord::default()),
+        &mut controller,
+    )
+    .await?;
+    assert_eq!(controller.tracker.bytes_stored, 0);
+    assert_eq!(*view.system.balance.get(), Amount::from_tokens(100));
+
+    // After a price rise, the refund is still what was paid for the freed bytes.
+    application.expect_call(ExpectedCall::execute_operation(
+        |runtime, _context, _operation| {
+            let mut batch = Batch::new();
+            batch.put_key_value_bytes(vec![0], vec![1; 10]);
+            runtime.write_batch(batch)?;
+            Ok(vec![])
+        },
+    ));
+    application.expect_call(ExpectedCall::default_finalize());
+    view.execute_operation(
+        make_operation_context(),
+        Timestamp::from(0),
+        operation.clone(),
+        Some(OracleRecord::default()),
+        &mut controller,
+    )
+    .await?;
+    assert_eq!(*view.system.stored_bytes.get(), 11);
+    assert_eq!(*view.system.balance.get(), Amount::from_tokens(89));
+    controller.policy = Arc::new(ResourceControlPolicy {
+        byte_stored: Amount::from_tokens(2),
+        ..(*controller.policy).clone()
+    });
+    application.expect_call(ExpectedCall::execute_operation(
+        |runtime, _context, _operation| {
+            let mut batch = Batch::new();
+            batch.delete_key(vec![0]);
+            runtime.write_batch(batch)?;
+            Ok(vec![])
+        },
+    ));
+    application.expect_call(ExpectedCall::default_finalize());
+    view.execute_operation(
+        make_operation_context(),
+        Timestamp::from(0),
+        operation.clone(),
+        Some(OracleRecord::default()),
+        &mut controller,
+    )
+    .await?;
+    assert_eq!(*view.system.stored_bytes.get(), 0);
+    assert_eq!(*view.system.balance.get(), Amount::from_tokens(100));
+    assert_eq!(
+        view.system.storage_deposits.get(&application_id).await?,
+        None
+    );
+
+    // Storing more than the limit fails.
+    application.expect_call(ExpectedCall::execute_operation(
+        |runtime, _context, _operation| {
+            let mut batch = Batch::new();
+            batch.put_key_value_bytes(vec![0], vec![1; 20]);
+            runtime.write_batch(batch)?;
+            Ok(vec![])
+        },
+    ));
+    let result = view
+        .execute_operation(
+            make_operation_context(),
+            Timestamp::from(0),
+            operation,
+            Some(OracleRecord::default()),
+            &mut controller,
+        )
+        .await;
+    assert_matches!(
+        result,
+        Err(ExecutionError::ExcessiveApplicationStorage {
+            size: 21,
+            maximum: 20,
+            ..
+        })
+    );
+    Ok(())
+}
+
+/// Tests that applications can read published blobs, that the blob ID is recorded as an oracle
+/// response, and that the blob's size is charged as bytes read.
+#[tokio::test]
+async fn test_read_blob() -> anyhow::Result<()> {
+    let mut state = SystemExecutionState::default();
+    state.description = Some(ChainDescription::Root(0));
+    let mut view = state.into_view().await;
+    view.system.balance.set(Amount::from_tokens(100));
+
+    let blob = HashedBlob::test_blob("published blob");
+    let blob_id = blob.id();
+    view.context().extra().add_blob(blob);
+    let missing_blob_id = HashedBlob::test_blob("missing blob").id();
+
+    let mut applications = register_mock_applications(&mut view, 1).await?;
+    let (application_id, application) = applications
+        .next()
+        .expect("Mock application should be registered");
+
+    let policy = ResourceControlPolicy {
+        byte_read: Amount::ONE,
+        ..ResourceControlPolicy::default()
+    };
+    let mut controller = ResourceController {
+        policy: Arc::new(policy),
+        ..ResourceController::default()
+    };
+    let operation = Operation::User {
+        application_id,
+        bytes: vec![],
+    };
+
+    // Reading the 14-byte blob costs 14 tokens and is recorded.
+    application.expect_call(ExpectedCall::execute_operation(
+        move |runtime, _context, _operation| {
+            assert_eq!(runtime.read_blob(blob_id)?, b"published blob");
+            Ok(vec![])
+        },
+    ));
+    application.expect_call(ExpectedCall::default_finalize());
+    let (_, oracle_record) = view
+        .execute_operation(
+            make_operation_context(),
+            Timestamp::from(0),
+            operation.clone(),
+            None,
+            &mut controller,
+        )
+        .await?;
+    assert_eq!(oracle_record.responses, vec![OracleResponse::Blob(blob_id)]);
+    assert_eq!(controller.tracker.bytes_read, 14);
+    assert_eq!(*view.system.balance.get(), Amount::from_tokens(86));
+
+    // Re-executing with a different recorded blob ID fails.
+    application.expect_call(ExpectedCall::execute_operation(
+        move |runtime, _context, _operation| {
+            runtime.read_blob(blob_id)?;
+            Ok(vec![])
+        },
+    ));
+    let result = view
+        .execute_operation(
+            make_operation_context(),
+            Timestamp::from(0),
+            operation.clone(),
+            Some(OracleRecord {
+                responses: vec![OracleResponse::Blob(missing_blob_id)],
+            }),
+            &mut controller,
+        )
+        .await;
+    assert_matches!(result, Err(ExecutionError::OracleResponseMismatch));
+
+    // Reading a blob that was never published fails.
+    application.expect_call(ExpectedCall::execute_operation(
+        move |runtime, _context, _operation| {
+            runtime.read_blob(missing_blob_id)?;
+            Ok(vec![])
+        },
+    ));
+    let result = view
+        .execute_operation(
+            make_operation_context(),
+            Timestamp::from(0),
+            operation,
+            None,
+            &mut controller,
+        )
+        .await;
+    assert_matches!(result, Err(ExecutionError::BlobsNotFound(ids)) if ids == [missing_blob_id]);
+    Ok(())
+}
+
+/// Tests that contracts can read the random seed revealed by an earlier block, that the seed is
+/// recorded as an oracle response, and that the seed of the current block cannot be read.
+#[tokio::test]
+async fn test_random_seed() -> anyhow::Result<()> {
+    let mut state = SystemExecutionState::default();
+    state.description = Some(ChainDescription::Root(0));
+    let mut view = state.into_view().await;
+
+    let seed = CryptoHash::test_hash("seed of block 0");
+    view.context().extra().add_random_seed(BlockHeight(0), seed);
+
+    let mut applications = register_mock_applications(&mut view, 1).await?;
+    let (application_id, application) = applications
+        .next()
+        .expect("Mock application should be registered");
+
+    let context = OperationContext {
+        height: BlockHeight(1),
+        ..make_operation_context()
+    };
+    let operation = Operation::User {
+        application_id,
+        bytes: vec![],
+    };
+    let mut controller = ResourceController::default();
+
+    // The seed of an earlier block is read from storage and recorded.
+    application.expect_call(ExpectedCall::execute_operation(
+        move |runtime, _context, _operation| {
+            assert_eq!(runtime.random_seed(BlockHeight(0))?, seed);
+            Ok(vec![])
+        },
+    ));
+    application.expect_call(ExpectedCall::default_finalize());
+    let (_, oracle_record) = view
+        .execute_operation(
+            context,
+            Timestamp::from(0),
+            operation.clone(),
+            None,
+            &mut controller,
+        )
+        .await?;
+    assert_eq!(
+        oracle_record.responses,
+        vec![OracleResponse::RandomSeed(seed)]
+    );
+
+    // When re-executing the block, the recorded seed is used.
+    let recorded_seed = CryptoHash::test_hash("recorded seed");
+    application.expect_call(ExpectedCall::execute_operation(
+        move |runtime, _context, _operation| {
+            assert_eq!(runtime.random_seed(BlockHeight(0))?, recorded_seed);
+            Ok(vec![])
+        },
+    ));
+    application.expect_call(ExpectedCall::default_finalize());
+    view.execute_operation(
+        context,
+        Timestamp::from(0),
+        operation.clone(),
+        Some(OracleRecord {
+            responses: vec![OracleResponse::RandomSeed(recorded_seed)],
+        }),
+        &mut controller,
+    )
+    .await?;
+
+    // The seed of the current block is not revealed yet.
+    application.expect_call(ExpectedCall::execute_operation(
+        move |runtime, _context, _operation| {
+            runtime.random_seed(BlockHeight(1))?;
+            Ok(vec![])
+        },
+    ));
+    let result = view
+        .execute_operation(
+            context,
+            Timestamp::from(0),
+            operation,
+            None,
+            &mut controller,
+        )
+        .await;
+    assert_matches!(
+        result,
+        Err(ExecutionError::RandomSeedNotRevealed(BlockHeight(1)))
+    );
+    Ok(())
+}
+
+/// Tests that events emitted by a contract are recorded in its execution outcome and paid for.
+#[tokio::test]
+async fn test_emit_events() -> anyhow::Result<()> {
+    let mut state = SystemExecutionState::default();
+    state.description = Some(ChainDescription::Root(0));
+    let mut view = state.into_view().await;
+    view.system.balance.set(Amount::from_tokens(100));
+
+    let mut applications = register_mock_applications(&mut view, 1).await?;
+    let (application_id, application) = applications
+        .next()
+        .expect("Mock application should be registered");
+
+    let policy = ResourceControlPolicy {
+        byte_written: Amount::ONE,
+        ..ResourceControlPolicy::default()
+    };
+    let mut controller = ResourceController {
+        policy: Arc::new(policy),
+        ..ResourceController::default()
+    };
+    let stream_name = StreamName(b"transfers".to_vec());
+
+    application.expect_call(ExpectedCall::execute_operation({
+        let stream_name = stream_name.clone();
+        move |runtime, _context, _operation| {
+            runtime.emit(stream_name.clone(), b"key1".to_vec(), b"first".to_vec())?;
+            runtime.emit(stream_name, b"key2".to_vec(), b"second".to_vec())?;
+            Ok(vec![])
+        }
+    }));
+    application.expect_call(ExpectedCall::default_finalize());
+    let (outcomes, _) = view
+        .execute_operation(
+            make_operation_context(),
+            Timestamp::from(0),
+            Operation::User {
+                application_id,
+                bytes: vec![],
+            },
+            None,
+            &mut controller,
+        )
+        .await?;
+
+    let expected_outcome = RawExecutionOutcome {
+        events: vec![
+            (stream_name.clone(), b"key1".to_vec(), b"first".to_vec()),
+            (stream_name, b"key2".to_vec(), b"second".to_vec()),
+        ],
+        ..RawExecutionOutcome::default()
+    }
+    .with_refund_grant_to(Some(Account::chain(ChainId::root(0))));
+    assert_eq!(
+        outcomes,
+        vec![ExecutionOutcome::User(application_id, expected_outcome)]
+    );
+    // The keys and values of both events, 19 bytes in total, are paid for.
+    assert_eq!(controller.tracker.bytes_written, 19);
+    assert_eq!(*view.system.balance.get(), Amount::from_tokens(81));
+    Ok(())
+}
+
+/// Tests that applications subscribe to event streams of other chains and process the
+/// events delivered to their chain.
+#[tokio::test]
+async fn test_event_subscriptions() -> anyhow::Result<()> {
+    let mut state = SystemExecutionState::default();
+    state.description = Some(ChainDescription::Root(0));
+    let mut view = state.into_view().await;
+
+    let mut applications = register_mock_applications(&mut view, 2).await?;
+    let (first_id, first_application) = applications
+        .next()
+        .expect("Mock application should be registered");
+    let (second_id, second_application) = applications
+        .next()
+        .expect("Mock application should be registered");
+    let publisher_chain_id = ChainId::root(1);
+    let stream_name = StreamName(b"transfers".to_vec());
+    let stream_id = StreamId {
+        application_id: GenericApplicationId::User(first_id),
+        stream_name: stream_name.clone(),
+    };
+    let mut controller = ResourceController::default();
+
+    // Only the first subscriber of the chain sends a subscription message.
+    for (application_id, application, is_first) in [
+        (first_id, &first_application, true),
+        (second_id, &second_application, false),
+    ] {
+        application.expect_call(ExpectedCall::execute_operation({
+            let stream_name = stream_name.clone();
+            move |runtime, _context, _operation| {
+                runtime.subscribe_to_events(publisher_chain_id, first_id, stream_name)?;
+                Ok(vec![])
+            }
+        }));
+        application.expect_call(ExpectedCall::default_finalize());
+        let (outcomes, _) = view
+            .execute_operation(
+                make_operation_context(),
+                Timestamp::from(0),
+                Operation::User {
+                    application_id,
+                    bytes: vec![],
+                },
+                None,
+                &mut controller,
+            )
+            .await?;
+        let subscriptions = outcomes
+            .iter()
+            .filter(|outcome| {
+                matches!(
+                    outcome,
+                    ExecutionOutcome::System(RawExecutionOutcome { messages, .. })
+                        if matches!(
+                            messages.as_slice(),
+                            [RawOutgoingMessage {
+                                destination: Destination::Recipient(chain_id),
+                                message: SystemMessage::Subscribe { .. },
+                                ..
+                            }] if *chain_id == publisher_chain_id
+                        )
+                )
+            })
+            .count();
+        assert_eq!(subscriptions, usize::from(is_first));
+    }
+    assert_eq!(
+        view.system
+            .event_subscriptions
+            .get(&(publisher_chain_id, stream_id.clone()))
+            .await?,
+        Some([first_id, second_id].into_iter().collect())
+    );
+
+    // An event of the stream is processed by both subscribers.
+    let event = StreamEvent {
+        event_id: EventId {
+            chain_id: publisher_chain_id,
+            stream_id,
+            index: 0,
+        },
+        key: b"key".to_vec(),
+        value: b"value".to_vec(),
+    };
+    for application in [&first_application, &second_application] {
+        application.expect_call(ExpectedCall::process_event({
+            let event = event.clone();
+            move |_runtime, _context, bytes| {
+                let delivered_event =
+                    bcs::from_bytes::<StreamEvent>(&bytes).expect("Invalid event");
+                assert_eq!(delivered_event, event);
+                Ok(())
+            }
+        }));
+        application.expect_call(ExpectedCall::default_finalize());
+    }
+    let context = MessageContext {
+        chain_id: ChainId::root(0),
+        is_bouncing: false,
+        authenticated_signer: None,
+        refund_grant_to: None,
+        height: BlockHeight(0),
+        certificate_hash: CryptoHash::default(),
+        message_id: MessageId {
+            chain_id: publisher_chain_id,
+            height: BlockHeight(0),
+            index: 0,
+        },
+        next_message_index: 0,
+    };
+    view.execute_message(
+        context,
+        Timestamp::from(0),
+        Message::System(SystemMessage::NewEvent(event.clone())),
+        None,
+        None,
+        &mut controller,
+    )
+    .await?;
+
+    // Events can only be delivered by the chain that emitted them.
+    let other_context = MessageContext {
+        message_id: MessageId {
+            chain_id: ChainId::root(2),
+            ..context.message_id
+        },
+        ..context
+    };
+    let result = view
+        .execute_message(
+            other_context,
+            Timestamp::from(0),
+            Message::System(SystemMessage::NewEvent(event)),
+            None,
+            None,
+            &mut controller,
+        )
+        .await;
+    assert_matches!(
+        result,
+        Err(ExecutionError::SystemError(
+            SystemExecutionError::IncorrectChainId(chain_id)
+        )) if chain_id == publisher_chain_id
+    );
+    Ok(())
+}
@@ -3,17 +3,26 @@
 
 #![allow(clippy::field_reassign_with_default)]
 
+use std::collections::BTreeMap;
+
+use assert_matches::assert_matches;
 use linera_base::{
-    crypto::CryptoHash,
-    data_types::{Amount, BlockHeight, OracleRecord, Timestamp},
-    identifiers::{Account, ChainDescription, ChainId, MessageId},
+    crypto::{CryptoHash, KeyPair, Signature},
+    data_types::{Amount, BlockHeight, OracleRecord, TimeDelta, Timestamp},
+    identifiers::{Account, ChainDescription, ChainId, Destination, MessageId, Owner},
 };
 use linera_execution::{
-    system::{Recipient, UserData},
-    test_utils::SystemExecutionState,
-    ExecutionOutcome, Message, MessageContext, Operation, OperationContext, Query, QueryContext,
-    RawExecutionOutcome, ResourceController, Response, SystemMessage, SystemOperation, SystemQuery,
-    SystemResponse,
+    committee::{Committee, Epoch, FuelUsage, SignedFuelUsage, ValidatorName},
+    system::{AdminOperation, MessageGrantPolicy, Recipient, StandingOrderOperation, UserData},
+    test_utils::{create_dummy_user_application_description, SystemExecutionState},
+    ExecutionError, ExecutionOutcome, Message, MessageContext, Operation, OperationContext, Query,
+    QueryContext, RawExecutionOutcome, ResourceControlPolicy, ResourceController, Response,
+    SystemExecutionError, SystemMessage, SystemOperation, SystemQuery, SystemResponse,
+    UserApplicationId,
+};
+use linera_views::{
+    register_view::value_hash,
+    views::{CryptoHashView, ProvableView, ViewError},
 };
 
 #[tokio::test]
@@ -105,6 +114,287 @@ async fn test_simple_system_message() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Returns fuel usage reports for `epoch`, signed by the given validators.
+fn fuel_usage_reports(
+    key_pairs: &[KeyPair],
+    epoch: Epoch,
+    fuel_per_block: &[u64],
+) -> Vec<SignedFuelUsage> {
+    key_pairs
+        .iter()
+        .zip(fuel_per_block)
+        .map(|(key_pair, fuel_per_block)| {
+            let usage = FuelUsage {
+                epoch,
+                fuel_per_block: *fuel_per_block,
+            };
+            SignedFuelUsage {
+                validator: ValidatorName(key_pair.public()),
+                usage,
+                signature: Signature::new(&usage, key_pair),
+            }
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_adjust_fees() -> anyhow::Result<()> {
+    let policy = ResourceControlPolicy {
+        block: Amount::ZERO,
+        fuel_unit: Amount::from_attos(800),
+        target_fuel_per_block: 1_000,
+        ..ResourceControlPolicy::default()
+    };
+    let key_pairs = (0..4).map(|_| KeyPair::generate()).collect::<Vec<_>>();
+    let validators = Committee::make_simple(
+        key_pairs
+            .iter()
+            .map(|key_pair| ValidatorName(key_pair.public()))
+            .collect(),
+    )
+    .validators()
+    .clone();
+    let mut state =
+        SystemExecutionState::new(Epoch::ZERO, ChainDescription::Root(0), ChainId::root(0));
+    state.committees = BTreeMap::from([(
+        Epoch::ZERO,
+        Committee::new(validators.clone(), policy.clone()),
+    )]);
+    let mut view = state.into_view().await;
+    let context = OperationContext {
+        chain_id: ChainId::root(0),
+        height: BlockHeight(0),
+        index: Some(0),
+        authenticated_signer: None,
+        authenticated_caller_id: None,
+        next_message_index: 0,
+    };
+    let mut controller = ResourceController::default();
+
+    // Reports from fewer than a quorum of validators are rejected.
+    let operation = AdminOperation::AdjustFees {
+        epoch: Epoch(1),
+        reports: fuel_usage_reports(&key_pairs[..2], Epoch::ZERO, &[5_000, 5_000]),
+    };
+    let result = view
+        .execute_operation(
+            context,
+            Timestamp::from(0),
+            Operation::System(SystemOperation::Admin(operation)),
+            Some(OracleRecord::default()),
+            &mut controller,
+        )
+        .await;
+    assert_matches!(
+        result,
+        Err(ExecutionError::SystemError(
+            SystemExecutionError::InsufficientFuelUsageReports
+        ))
+    );
+
+    // Reports for another epoch are rejected.
+    let operation = AdminOperation::AdjustFees {
+        epoch: Epoch(1),
+        reports: fuel_usage_reports(&key_pairs, Epoch(1), &[5_000, 5_000, 5_000, 5_000]),
+    };
+    let result = view
+        .execute_operation(
+            context,
+            Timestamp::from(0),
+            Operation::System(SystemOperation::Admin(operation)),
+            Some(OracleRecord::default()),
+            &mut controller,
+        )
+        .await;
+    assert_matches!(
+        result,
+        Err(ExecutionError::SystemError(
+            SystemExecutionError::InvalidFuelUsageReport(_)
+        ))
+    );
+
+    // Blocks used more than twice the target according to most validators: prices increase
+    // by 1/8, and by at least one atto. The outlier does not change the result.
+    let operation = AdminOperation::AdjustFees {
+        epoch: Epoch(1),
+        reports: fuel_usage_reports(&key_pairs[..3], Epoch::ZERO, &[5_000, 0, 5_000]),
+    };
+    view.execute_operation(
+        context,
+        Timestamp::from(0),
+        Operation::System(SystemOperation::Admin(operation)),
+        Some(OracleRecord::default()),
+        &mut controller,
+    )
+    .await?;
+    let (epoch, committee) = view.system.current_committee().unwrap();
+    assert_eq!(epoch, Epoch(1));
+    assert_eq!(committee.validators(), &validators);
+    assert_eq!(committee.policy().fuel_unit, Amount::from_attos(900));
+    assert_eq!(committee.policy().block, Amount::from_attos(1));
+
+    // Blocks used a quarter of the target: prices decrease by 3/32.
+    let operation = AdminOperation::AdjustFees {
+        epoch: Epoch(2),
+        reports: fuel_usage_reports(&key_pairs, Epoch(1), &[250, 250, 250, 250]),
+    };
+    view.execute_operation(
+        context,
+        Timestamp::from(0),
+        Operation::System(SystemOperation::Admin(operation)),
+        Some(OracleRecord::default()),
+        &mut controller,
+    )
+    .await?;
+    let (_, committee) = view.system.current_committee().unwrap();
+    assert_eq!(committee.policy().fuel_unit, Amount::from_attos(816));
+    assert_eq!(committee.policy().block, Amount::from_attos(1));
+
+    // Fixed prices cannot be adjusted.
+    let mut state =
+        SystemExecutionState::new(Epoch::ZERO, ChainDescription::Root(0), ChainId::root(0));
+    let policy = ResourceControlPolicy {
+        target_fuel_per_block: 0,
+        ..policy
+    };
+    state.committees = BTreeMap::from([(Epoch::ZERO, Committee::new(validators, policy))]);
+    let mut view = state.into_view().await;
+    let operation = AdminOperation::AdjustFees {
+        epoch: Epoch(1),
+        reports: fuel_usage_reports(&key_pairs, Epoch::ZERO, &[5_000, 5_000, 5_000, 5_000]),
+    };
+    let result = view
+        .execute_operation(
+            context,
+            Timestamp::from(0),
+            Operation::System(SystemOperation::Admin(operation)),
+            Some(OracleRecord::default()),
+            &mut controller,
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(ExecutionError::SystemError(SystemExecutionError::FixedFees))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_standing_orders() -> anyhow::Result<()> {
+    let mut state = SystemExecutionState::default();
+    state.description = Some(ChainDescription::Root(0));
+    state.balance = Amount::from_tokens(10);
+    state.timestamp = Timestamp::from(1_000);
+    let mut view = state.into_view().await;
+    let context = OperationContext {
+        chain_id: ChainId::root(0),
+        height: BlockHeight(0),
+        index: Some(0),
+        authenticated_signer: None,
+        authenticated_caller_id: None,
+        next_message_index: 0,
+    };
+    let recipient = Account::chain(ChainId::root(1));
+    let mut controller = ResourceController::default();
+
+    // Pay one token every 100 µs from the chain balance, and 20 tokens from an owner
+    // account that cannot be debited without that owner's signature.
+    for (owner, amount) in [
+        (None, Amount::ONE),
+        (
+            Some(Owner::from(KeyPair::generate().public())),
+            Amount::from_tokens(20),
+        ),
+    ] {
+        let operation = StandingOrderOperation::Create {
+            owner,
+            recipient: Recipient::Account(recipient),
+            amount,
+            interval: TimeDelta::from_micros(100),
+            end_time: Timestamp::from(1_350),
+        };
+        let result = view
+            .execute_operation(
+                context,
+                Timestamp::from(1_000),
+                Operation::System(SystemOperation::StandingOrder(operation)),
+                Some(OracleRecord::default()),
+                &mut controller,
+            )
+            .await;
+        if owner.is_none() {
+            result?;
+        } else {
+            assert!(matches!(
+                result,
+                Err(ExecutionError::SystemError(
+                    SystemExecutionError::UnauthenticatedStandingOrderOwner
+                ))
+            ));
+        }
+    }
+    let order = view.system.standing_orders.get(&0).await?.unwrap();
+    assert_eq!(order.next_due, Timestamp::from(1_100));
+    assert_eq!(view.system.standing_orders.indices().await?.len(), 1);
+
+    // Nothing is due yet.
+    view.system.timestamp.set(Timestamp::from(1_050));
+    assert!(view.system.execute_standing_orders().await?.is_empty());
+
+    // The transfers due at 1100, 1200 and 1300 are made at once, and the order ends.
+    view.system.timestamp.set(Timestamp::from(1_320));
+    let messages = view.system.execute_standing_orders().await?;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(
+        messages[0].destination,
+        Destination::Recipient(ChainId::root(1))
+    );
+    assert_eq!(
+        messages[0].message,
+        SystemMessage::Credit {
+            amount: Amount::from_tokens(3),
+            source: None,
+            target: None,
+        }
+    );
+    assert_eq!(*view.system.balance.get(), Amount::from_tokens(7));
+    assert_eq!(view.system.standing_orders.indices().await?.len(), 0);
+
+    // Orders that cannot be paid are retried later.
+    let operation = StandingOrderOperation::Create {
+        owner: None,
+        recipient: Recipient::Burn,
+        amount: Amount::from_tokens(8),
+        interval: TimeDelta::from_micros(100),
+        end_time: Timestamp::from(2_000),
+    };
+    view.execute_operation(
+        context,
+        Timestamp::from(1_320),
+        Operation::System(SystemOperation::StandingOrder(operation)),
+        Some(OracleRecord::default()),
+        &mut controller,
+    )
+    .await?;
+    view.system.timestamp.set(Timestamp::from(1_500));
+    assert!(view.system.execute_standing_orders().await?.is_empty());
+    assert_eq!(*view.system.balance.get(), Amount::from_tokens(7));
+    let order = view.system.standing_orders.get(&1).await?.unwrap();
+    assert_eq!(order.next_due, Timestamp::from(1_420));
+
+    let operation = StandingOrderOperation::Cancel { id: 1 };
+    view.execute_operation(
+        context,
+        Timestamp::from(1_500),
+        Operation::System(SystemOperation::StandingOrder(operation)),
+        Some(OracleRecord::default()),
+        &mut controller,
+    )
+    .await?;
+    assert_eq!(view.system.standing_orders.indices().await?.len(), 0);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_simple_system_query() -> anyhow::Result<()> {
     let mut state = SystemExecutionState::default();
@@ -129,3 +419,114 @@ async fn test_simple_system_query() -> anyhow::Result<()> {
     );
     Ok(())
 }
+
+#[tokio::test]
+async fn test_message_grant_policy() -> anyhow::Result<()> {
+    let mut state = SystemExecutionState::default();
+    state.description = Some(ChainDescription::Root(0));
+    let mut view = state.into_view().await;
+    let policy = MessageGrantPolicy {
+        minimum_grant: Amount::from_millis(10),
+        trusted_senders: vec![ChainId::root(2)],
+    };
+    let operation_context = OperationContext {
+        chain_id: ChainId::root(0),
+        height: BlockHeight(0),
+        index: Some(0),
+        authenticated_signer: None,
+        authenticated_caller_id: None,
+        next_message_index: 0,
+    };
+    view.execute_operation(
+        operation_context,
+        Timestamp::from(0),
+        Operation::System(SystemOperation::ChangeMessageGrantPolicy(policy.clone())),
+        Some(OracleRecord::default()),
+        &mut ResourceController::default(),
+    )
+    .await?;
+    assert_eq!(view.system.message_grant_policy.get(), &policy);
+
+    // A user message from an unknown sender without the minimum grant is not executed.
+    let mut context = MessageContext {
+        chain_id: ChainId::root(0),
+        is_bouncing: false,
+        height: BlockHeight(1),
+        certificate_hash: CryptoHash::test_hash("certificate"),
+        message_id: MessageId {
+            chain_id: ChainId::root(1),
+            height: BlockHeight(0),
+            index: 0,
+        },
+        authenticated_signer: None,
+        refund_grant_to: None,
+        next_message_index: 0,
+    };
+    let application_id = UserApplicationId::from(&create_dummy_user_application_description(1));
+    let mut grant = Amount::from_millis(1);
+    let result = view
+        .execute_message(
+            context,
+            Timestamp::from(0),
+            Message::User {
+                application_id,
+                bytes: Vec::new(),
+            },
+            Some(&mut grant),
+            Some(OracleRecord::default()),
+            &mut ResourceController::default(),
+        )
+        .await;
+    assert_matches!(
+        result,
+        Err(ExecutionError::SystemError(
+            SystemExecutionError::InsufficientMessageGrant { .. }
+        ))
+    );
+    assert!(view
+        .system
+        .check_message_grant(&context, Amount::from_millis(10))
+        .is_ok());
+
+    // Trusted senders, the chain itself and bouncing messages don't need a grant.
+    context.message_id.chain_id = ChainId::root(2);
+    assert!(view
+        .system
+        .check_message_grant(&context, Amount::ZERO)
+        .is_ok());
+    context.message_id.chain_id = ChainId::root(0);
+    assert!(view
+        .system
+        .check_message_grant(&context, Amount::ZERO)
+        .is_ok());
+    context.message_id.chain_id = ChainId::root(1);
+    context.is_bouncing = true;
+    assert!(view
+        .system
+        .check_message_grant(&context, Amount::ZERO)
+        .is_ok());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prove_balance_against_state_hash() -> anyhow::Result<()> {
+    let mut state = SystemExecutionState::default();
+    state.description = Some(ChainDescription::Root(0));
+    state.balance = Amount::from_tokens(4);
+    let view = state.into_view().await;
+    let state_hash = view.crypto_hash().await?;
+
+    let system_proof = view.prove_field("system").await?;
+    let balance_proof = view.system.prove_field("balance").await?;
+    let system_hash = balance_proof.view_hash();
+    system_proof.verify_crypto_hash(&state_hash, "ExecutionStateView", &system_hash)?;
+    let balance_hash = value_hash(&Amount::from_tokens(4))?;
+    balance_proof.verify(&system_hash, &balance_hash)?;
+
+    let wrong_hash = value_hash(&Amount::from_tokens(5))?;
+    assert_matches!(
+        balance_proof.verify(&system_hash, &wrong_hash),
+        Err(ViewError::InvalidProof(_))
+    );
+    Ok(())
+}
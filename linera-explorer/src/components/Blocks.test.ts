@@ -60,7 +60,8 @@ test('Blocks mounting', () => {
                   }],
                   messageCounts: [1],
                   stateHash: "5bcd40995283e74798c60e8dc7a93e8c61059440534070673dfb973b2b66f61a",
-                  oracleRecords: []
+                  oracleRecords: [],
+                  events: []
                 }
               }
             }
@@ -1,10 +1,10 @@
 // Copyright (c) Zefchain Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-//! An example of an indexer with the operations plugin.
+//! An example of an indexer with the operations and events plugins.
 
 use linera_indexer::{common::IndexerError, plugin::Plugin, rocks_db::RocksDbRunner};
-use linera_indexer_plugins::operations::OperationsPlugin;
+use linera_indexer_plugins::{events::EventsPlugin, operations::OperationsPlugin};
 
 #[tokio::main]
 async fn main() -> Result<(), IndexerError> {
@@ -20,5 +20,8 @@ async fn main() -> Result<(), IndexerError> {
     runner
         .add_plugin(OperationsPlugin::load(runner.store.clone()).await?)
         .await?;
+    runner
+        .add_plugin(EventsPlugin::load(runner.store.clone()).await?)
+        .await?;
     runner.run().await
 }
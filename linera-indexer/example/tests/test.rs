@@ -98,7 +98,7 @@ async fn test_end_to_end_operations_indexer(config: impl LineraNetConfig) {
     let mut node_service = client.run_node_service(None).await.unwrap();
     let mut indexer = run_indexer(&client.path_provider).await;
 
-    // check operations plugin
+    // check plugins
     let req_client = reqwest_client();
     let plugins = request::<Plugins, _>(&req_client, "http://localhost:8081", plugins::Variables)
         .await
@@ -106,8 +106,8 @@ async fn test_end_to_end_operations_indexer(config: impl LineraNetConfig) {
         .plugins;
     assert_eq!(
         plugins,
-        vec!["operations"],
-        "Indexer plugin 'operations' not loaded",
+        vec!["events", "operations"],
+        "Indexer plugins 'events' and 'operations' not loaded",
     );
 
     // making a few transfers
@@ -0,0 +1,219 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_graphql::{Object, SimpleObject};
+use axum::Router;
+use linera_base::{
+    crypto::CryptoHash,
+    data_types::BlockHeight,
+    doc_scalar,
+    identifiers::{ChainId, StreamId},
+};
+use linera_chain::data_types::HashedCertificateValue;
+use linera_indexer::{
+    common::IndexerError,
+    plugin::{load, route, sdl, Plugin},
+};
+use linera_views::{
+    common::{Context, ContextFromStore, KeyValueStore},
+    map_view::MapView,
+    views::{RootView, ViewError},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::info;
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ChainStream {
+    pub chain_id: ChainId,
+    pub stream_id: StreamId,
+}
+
+doc_scalar!(ChainStream, "An event stream of a particular chain");
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct EventKey {
+    pub chain_stream: ChainStream,
+    pub index: u64,
+}
+
+doc_scalar!(EventKey, "An event key to index the events of a stream");
+
+#[derive(Deserialize, Serialize, Clone, SimpleObject, Debug)]
+pub struct ChainEvent {
+    index: u64,
+    height: BlockHeight,
+    block: CryptoHash,
+    key: Vec<u8>,
+    value: Vec<u8>,
+}
+
+#[derive(RootView)]
+pub struct Events<C> {
+    /// The height of the last block whose events were registered, for each stream.
+    last: MapView<C, ChainStream, BlockHeight>,
+    count: MapView<C, ChainStream, u64>,
+    /// ChainEvent MapView indexed by their stream and position in the stream
+    events: MapView<C, EventKey, ChainEvent>,
+}
+
+/// Implements helper functions on the `RootView`
+impl<C> Events<C>
+where
+    C: Context + Send + Sync + 'static + Clone,
+    ViewError: From<C::Error>,
+{
+    /// Registers the events emitted to a stream by a block, at the indices assigned by the
+    /// chain, and updates the count and last entries for this stream
+    async fn register_events(
+        &mut self,
+        chain_stream: ChainStream,
+        height: BlockHeight,
+        block: CryptoHash,
+        events: Vec<(u32, Vec<u8>, Vec<u8>)>,
+    ) -> Result<(), IndexerError> {
+        if let Some(last_height) = self.last.get(&chain_stream).await? {
+            if last_height >= height {
+                return Ok(());
+            }
+        }
+        let mut count = self.count.get(&chain_stream).await?.unwrap_or(0);
+        for (index, key, value) in events {
+            let index = u64::from(index);
+            let event = ChainEvent {
+                index,
+                height,
+                block,
+                key,
+                value,
+            };
+            info!("register event for {:?}:\n{:?}", chain_stream, event);
+            let event_key = EventKey {
+                chain_stream: chain_stream.clone(),
+                index,
+            };
+            self.events.insert(&event_key, event)?;
+            count = count.max(index + 1);
+        }
+        self.count.insert(&chain_stream, count)?;
+        Ok(self.last.insert(&chain_stream, height)?)
+    }
+}
+
+#[derive(Clone)]
+pub struct EventsPlugin<C>(Arc<Mutex<Events<C>>>);
+
+static NAME: &str = "events";
+
+/// Implements `Plugin`
+#[async_trait::async_trait]
+impl<S> Plugin<S> for EventsPlugin<ContextFromStore<(), S>>
+where
+    S: KeyValueStore + Clone + Send + Sync + 'static,
+    S::Error: From<bcs::Error> + Send + Sync + std::error::Error + 'static,
+    ViewError: From<S::Error>,
+{
+    fn name(&self) -> String {
+        NAME.to_string()
+    }
+
+    async fn load(store: S) -> Result<Self, IndexerError>
+    where
+        Self: Sized,
+    {
+        Ok(Self(load(store, NAME).await?))
+    }
+
+    async fn register(&self, value: &HashedCertificateValue) -> Result<(), IndexerError> {
+        let mut plugin = self.0.lock().await;
+        let Some(executed_block) = value.inner().executed_block() else {
+            return Ok(());
+        };
+        let chain_id = value.inner().chain_id();
+        let mut streams = Vec::<(ChainStream, Vec<(u32, Vec<u8>, Vec<u8>)>)>::new();
+        for event in executed_block.outcome.events.iter().flatten() {
+            let chain_stream = ChainStream {
+                chain_id,
+                stream_id: event.stream_id.clone(),
+            };
+            let entry = (event.index, event.key.clone(), event.value.clone());
+            match streams
+                .iter_mut()
+                .find(|(stream, _)| *stream == chain_stream)
+            {
+                Some((_, events)) => events.push(entry),
+                None => streams.push((chain_stream, vec![entry])),
+            }
+        }
+        for (chain_stream, events) in streams {
+            plugin
+                .register_events(
+                    chain_stream,
+                    executed_block.block.height,
+                    value.hash(),
+                    events,
+                )
+                .await?;
+        }
+        Ok(plugin.save().await?)
+    }
+
+    fn sdl(&self) -> String {
+        sdl(self.clone())
+    }
+
+    fn route(&self, app: Router) -> Router {
+        route(&self.name(), self.clone(), app)
+    }
+}
+
+/// Implements `ObjectType`
+#[Object]
+impl<C> EventsPlugin<C>
+where
+    C: Context + Send + Sync + 'static + Clone,
+    ViewError: From<C::Error>,
+{
+    /// Gets the event at the given position of a stream
+    pub async fn event(&self, key: EventKey) -> Result<Option<ChainEvent>, IndexerError> {
+        let plugin = self.0.lock().await;
+        Ok(plugin.events.get(&key).await?)
+    }
+
+    /// Gets the events of a stream in emission order, starting from the given position
+    pub async fn events(
+        &self,
+        chain_stream: ChainStream,
+        from: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<Vec<ChainEvent>, IndexerError> {
+        let plugin = self.0.lock().await;
+        let count = plugin.count.get(&chain_stream).await?.unwrap_or(0);
+        let from = from.unwrap_or(0);
+        let limit = limit.unwrap_or(20);
+        let mut result = Vec::new();
+        for index in (from..count).take(limit as usize) {
+            let key = EventKey {
+                chain_stream: chain_stream.clone(),
+                index,
+            };
+            match plugin.events.get(&key).await? {
+                None => break,
+                Some(event) => result.push(event),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Gets the number of events registered for a stream
+    pub async fn count(&self, chain_stream: ChainStream) -> Result<u64, IndexerError> {
+        let plugin = self.0.lock().await;
+        Ok(plugin
+            .count
+            .get(&chain_stream)
+            .await
+            .map(|opt| opt.unwrap_or(0))?)
+    }
+}
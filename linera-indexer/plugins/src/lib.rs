@@ -3,4 +3,5 @@
 
 //! Plugins for Linera indexer.
 
+pub mod events;
 pub mod operations;
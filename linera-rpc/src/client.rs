@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use linera_base::{
+    crypto::CryptoHash,
     data_types::{Blob, HashedBlob},
     identifiers::{BlobId, ChainId},
 };
@@ -15,7 +16,7 @@ use linera_core::node::{
 #[cfg(not(web))]
 use linera_core::node::{NotificationStream, ValidatorNode};
 use linera_core::{
-    data_types::{ChainInfoQuery, ChainInfoResponse},
+    data_types::{BlockHeightRange, ChainInfoQuery, ChainInfoResponse},
     node::{CrossChainMessageDelivery, NodeError},
 };
 
@@ -153,4 +154,46 @@ impl ValidatorNode for Client {
             Client::Simple(simple_client) => simple_client.download_blob(blob_id).await?,
         })
     }
+
+    async fn download_certificate(&mut self, hash: CryptoHash) -> Result<Certificate, NodeError> {
+        Ok(match self {
+            Client::Grpc(grpc_client) => grpc_client.download_certificate(hash).await?,
+
+            #[cfg(with_simple_network)]
+            Client::Simple(simple_client) => simple_client.download_certificate(hash).await?,
+        })
+    }
+
+    async fn download_certificates(
+        &mut self,
+        hashes: Vec<CryptoHash>,
+    ) -> Result<Vec<Certificate>, NodeError> {
+        Ok(match self {
+            Client::Grpc(grpc_client) => grpc_client.download_certificates(hashes).await?,
+
+            #[cfg(with_simple_network)]
+            Client::Simple(simple_client) => simple_client.download_certificates(hashes).await?,
+        })
+    }
+
+    async fn download_certificates_by_height(
+        &mut self,
+        chain_id: ChainId,
+        range: BlockHeightRange,
+    ) -> Result<Vec<Certificate>, NodeError> {
+        Ok(match self {
+            Client::Grpc(grpc_client) => {
+                grpc_client
+                    .download_certificates_by_height(chain_id, range)
+                    .await?
+            }
+
+            #[cfg(with_simple_network)]
+            Client::Simple(simple_client) => {
+                simple_client
+                    .download_certificates_by_height(chain_id, range)
+                    .await?
+            }
+        })
+    }
 }
@@ -5,6 +5,7 @@ use std::{iter, time::Duration};
 
 use futures::{future, stream, StreamExt};
 use linera_base::{
+    crypto::CryptoHash,
     data_types::{Blob, HashedBlob},
     identifiers::{BlobId, ChainId},
 };
@@ -274,6 +275,60 @@ impl ValidatorNode for GrpcClient {
             .into_inner()
             .into())
     }
+
+    #[instrument(target = "grpc_client", skip_all, err, fields(address = self.address))]
+    async fn download_certificate(
+        &mut self,
+        hash: CryptoHash,
+    ) -> Result<data_types::Certificate, NodeError> {
+        self.client
+            .download_certificate(<CryptoHash as Into<api::CryptoHash>>::into(hash))
+            .await?
+            .into_inner()
+            .try_into()
+            .map_err(|err| NodeError::GrpcError {
+                error: format!("failed to marshal response: {}", err),
+            })
+    }
+
+    #[instrument(target = "grpc_client", skip_all, err, fields(address = self.address))]
+    async fn download_certificates(
+        &mut self,
+        hashes: Vec<CryptoHash>,
+    ) -> Result<Vec<data_types::Certificate>, NodeError> {
+        self.client
+            .download_certificates(api::CertificatesBatchRequest::from(hashes))
+            .await?
+            .into_inner()
+            .try_into()
+            .map_err(|err| NodeError::GrpcError {
+                error: format!("failed to marshal response: {}", err),
+            })
+    }
+
+    #[instrument(target = "grpc_client", skip_all, err, fields(address = self.address))]
+    async fn download_certificates_by_height(
+        &mut self,
+        chain_id: ChainId,
+        range: linera_core::data_types::BlockHeightRange,
+    ) -> Result<Vec<data_types::Certificate>, NodeError> {
+        let mut pages = self
+            .client
+            .download_certificates_by_height(api::CertificatesByHeightRequest::from((
+                chain_id, range,
+            )))
+            .await?
+            .into_inner();
+        let mut certificates = Vec::new();
+        while let Some(page) = pages.message().await? {
+            let page: Vec<data_types::Certificate> =
+                page.try_into().map_err(|err| NodeError::GrpcError {
+                    error: format!("failed to marshal response: {}", err),
+                })?;
+            certificates.extend(page);
+        }
+        Ok(certificates)
+    }
 }
 
 #[cfg(not(web))]
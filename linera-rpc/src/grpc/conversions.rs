@@ -3,15 +3,16 @@
 
 use linera_base::{
     crypto::{CryptoError, CryptoHash, PublicKey, Signature},
-    data_types::{Blob, BlockHeight},
+    data_types::{Blob, BlockHeight, Round},
     ensure,
     identifiers::{BlobId, ChainId, Owner},
 };
 use linera_chain::data_types::{
-    BlockAndRound, BlockProposal, Certificate, HashedCertificateValue, LiteCertificate, LiteValue,
+    AggregateSignature, BlockAndRound, BlockProposal, Certificate, HashedCertificateValue,
+    LiteCertificate, LiteValue,
 };
 use linera_core::{
-    data_types::{ChainInfoQuery, ChainInfoResponse, CrossChainRequest},
+    data_types::{BlockHeightRange, ChainInfoQuery, ChainInfoResponse, CrossChainRequest},
     node::NodeError,
     worker::Notification,
 };
@@ -169,7 +170,8 @@ impl TryFrom<BlockProposal> for api::BlockProposal {
             chain_id: Some(block_proposal.content.block.chain_id.into()),
             content: bincode::serialize(&block_proposal.content)?,
             owner: Some(block_proposal.owner.into()),
-            signature: Some(block_proposal.signature.into()),
+            signature: bincode::serialize(&block_proposal.signature)?,
+            approvals: bincode::serialize(&block_proposal.approvals)?,
             hashed_certificate_values: bincode::serialize(
                 &block_proposal.hashed_certificate_values,
             )?,
@@ -194,7 +196,8 @@ impl TryFrom<api::BlockProposal> for BlockProposal {
         Ok(Self {
             content,
             owner: try_proto_convert(block_proposal.owner)?,
-            signature: try_proto_convert(block_proposal.signature)?,
+            signature: bincode::deserialize(&block_proposal.signature)?,
+            approvals: bincode::deserialize(&block_proposal.approvals)?,
             hashed_certificate_values: bincode::deserialize(
                 &block_proposal.hashed_certificate_values,
             )?,
@@ -280,8 +283,12 @@ impl<'a> TryFrom<api::LiteCertificate> for HandleLiteCertRequest<'a> {
         };
         let signatures = bincode::deserialize(&certificate.signatures)?;
         let round = bincode::deserialize(&certificate.round)?;
+        let aggregate_signature = bincode::deserialize(&certificate.aggregate_signature)?;
         Ok(Self {
-            certificate: LiteCertificate::new(value, round, signatures),
+            certificate: LiteCertificate {
+                aggregate_signature,
+                ..LiteCertificate::new(value, round, signatures)
+            },
             wait_for_outgoing_messages: certificate.wait_for_outgoing_messages,
         })
     }
@@ -297,6 +304,7 @@ impl<'a> TryFrom<HandleLiteCertRequest<'a>> for api::LiteCertificate {
             chain_id: Some(request.certificate.value.chain_id.into()),
             signatures: bincode::serialize(&request.certificate.signatures)?,
             wait_for_outgoing_messages: request.wait_for_outgoing_messages,
+            aggregate_signature: bincode::serialize(&request.certificate.aggregate_signature)?,
         })
     }
 }
@@ -311,11 +319,12 @@ impl TryFrom<api::Certificate> for HandleCertificateRequest {
             GrpcProtoConversionError::InconsistentChainId
         );
         let signatures = bincode::deserialize(&cert_request.signatures)?;
+        let aggregate_signature = bincode::deserialize(&cert_request.aggregate_signature)?;
         let values = bincode::deserialize(&cert_request.hashed_certificate_values)?;
         let blobs = bincode::deserialize(&cert_request.blobs)?;
         let round = bincode::deserialize(&cert_request.round)?;
         Ok(HandleCertificateRequest {
-            certificate: Certificate::new(value, round, signatures),
+            certificate: make_certificate(value, round, signatures, aggregate_signature),
             wait_for_outgoing_messages: cert_request.wait_for_outgoing_messages,
             hashed_certificate_values: values,
             hashed_blobs: blobs,
@@ -335,6 +344,7 @@ impl TryFrom<HandleCertificateRequest> for api::Certificate {
             hashed_certificate_values: bincode::serialize(&request.hashed_certificate_values)?,
             blobs: bincode::serialize(&request.hashed_blobs)?,
             wait_for_outgoing_messages: request.wait_for_outgoing_messages,
+            aggregate_signature: bincode::serialize(&request.certificate.aggregate_signature())?,
         })
     }
 }
@@ -548,6 +558,133 @@ impl From<api::Blob> for Blob {
     }
 }
 
+impl From<CryptoHash> for api::CryptoHash {
+    fn from(hash: CryptoHash) -> Self {
+        Self {
+            bytes: hash.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<api::CryptoHash> for CryptoHash {
+    type Error = GrpcProtoConversionError;
+
+    fn try_from(hash: api::CryptoHash) -> Result<Self, Self::Error> {
+        Ok(CryptoHash::try_from(hash.bytes.as_slice())?)
+    }
+}
+
+impl From<Vec<CryptoHash>> for api::CertificatesBatchRequest {
+    fn from(hashes: Vec<CryptoHash>) -> Self {
+        Self {
+            hashes: hashes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<api::CertificatesBatchRequest> for Vec<CryptoHash> {
+    type Error = GrpcProtoConversionError;
+
+    fn try_from(request: api::CertificatesBatchRequest) -> Result<Self, Self::Error> {
+        request.hashes.into_iter().map(TryInto::try_into).collect()
+    }
+}
+
+impl From<(ChainId, BlockHeightRange)> for api::CertificatesByHeightRequest {
+    fn from((chain_id, range): (ChainId, BlockHeightRange)) -> Self {
+        Self {
+            chain_id: Some(chain_id.into()),
+            start: Some(range.start.into()),
+            limit: range.limit,
+        }
+    }
+}
+
+impl TryFrom<api::CertificatesByHeightRequest> for (ChainId, BlockHeightRange) {
+    type Error = GrpcProtoConversionError;
+
+    fn try_from(request: api::CertificatesByHeightRequest) -> Result<Self, Self::Error> {
+        let chain_id = try_proto_convert(request.chain_id)?;
+        let start = request
+            .start
+            .ok_or(GrpcProtoConversionError::MissingField)?
+            .into();
+        let range = BlockHeightRange {
+            start,
+            limit: request.limit,
+        };
+        Ok((chain_id, range))
+    }
+}
+
+impl TryFrom<Certificate> for api::DownloadedCertificate {
+    type Error = GrpcProtoConversionError;
+
+    fn try_from(certificate: Certificate) -> Result<Self, Self::Error> {
+        Ok(Self {
+            value: bincode::serialize(&certificate.value)?,
+            round: bincode::serialize(&certificate.round)?,
+            signatures: bincode::serialize(certificate.signatures())?,
+            aggregate_signature: bincode::serialize(&certificate.aggregate_signature())?,
+        })
+    }
+}
+
+impl TryFrom<api::DownloadedCertificate> for Certificate {
+    type Error = GrpcProtoConversionError;
+
+    fn try_from(certificate: api::DownloadedCertificate) -> Result<Self, Self::Error> {
+        let value: HashedCertificateValue = bincode::deserialize(&certificate.value)?;
+        let round = bincode::deserialize(&certificate.round)?;
+        let signatures = bincode::deserialize(&certificate.signatures)?;
+        let aggregate_signature = bincode::deserialize(&certificate.aggregate_signature)?;
+        Ok(make_certificate(
+            value,
+            round,
+            signatures,
+            aggregate_signature,
+        ))
+    }
+}
+
+/// Creates a certificate with either individual signatures or an aggregate signature.
+fn make_certificate(
+    value: HashedCertificateValue,
+    round: Round,
+    signatures: Vec<(ValidatorName, Signature)>,
+    aggregate_signature: Option<AggregateSignature>,
+) -> Certificate {
+    match aggregate_signature {
+        Some(aggregate_signature) => Certificate::new_aggregate(value, round, aggregate_signature),
+        None => Certificate::new(value, round, signatures),
+    }
+}
+
+impl TryFrom<Vec<Certificate>> for api::DownloadedCertificates {
+    type Error = GrpcProtoConversionError;
+
+    fn try_from(certificates: Vec<Certificate>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            certificates: certificates
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<api::DownloadedCertificates> for Vec<Certificate> {
+    type Error = GrpcProtoConversionError;
+
+    fn try_from(certificates: api::DownloadedCertificates) -> Result<Self, Self::Error> {
+        certificates
+            .certificates
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect()
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use std::{borrow::Cow, fmt::Debug};
@@ -700,6 +837,7 @@ pub mod tests {
                 ValidatorName::from(key_pair.public()),
                 Signature::new(&Foo("test".into()), &key_pair),
             )]),
+            aggregate_signature: None,
         };
         let request = HandleLiteCertRequest {
             certificate,
@@ -775,7 +913,11 @@ pub mod tests {
                 round: Round::SingleLeader(4),
             },
             owner: Owner::from(KeyPair::generate().public()),
-            signature: Signature::new(&Foo("test".into()), &KeyPair::generate()),
+            signature: Signature::new(&Foo("test".into()), &KeyPair::generate()).into(),
+            approvals: vec![(
+                Owner::from(KeyPair::generate().public()),
+                Signature::new(&Foo("approval".into()), &KeyPair::generate()).into(),
+            )],
             hashed_certificate_values: vec![HashedCertificateValue::new_confirmed(
                 BlockExecutionOutcome {
                     state_hash: CryptoHash::new(&Foo("execution state".into())),
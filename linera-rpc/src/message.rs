@@ -3,12 +3,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use linera_base::{
+    crypto::CryptoHash,
     data_types::Blob,
     identifiers::{BlobId, ChainId},
 };
-use linera_chain::data_types::{BlockProposal, LiteVote};
+use linera_chain::data_types::{BlockProposal, Certificate, LiteVote};
 use linera_core::{
-    data_types::{ChainInfoQuery, ChainInfoResponse, CrossChainRequest},
+    data_types::{BlockHeightRange, ChainInfoQuery, ChainInfoResponse, CrossChainRequest},
     node::NodeError,
 };
 use linera_version::VersionInfo;
@@ -25,6 +26,9 @@ pub enum RpcMessage {
     LiteCertificate(Box<HandleLiteCertRequest<'static>>),
     ChainInfoQuery(Box<ChainInfoQuery>),
     DownloadBlob(Box<BlobId>),
+    DownloadCertificate(Box<CryptoHash>),
+    DownloadCertificates(Box<Vec<CryptoHash>>),
+    DownloadCertificatesByHeight(Box<(ChainId, BlockHeightRange)>),
     VersionInfoQuery,
 
     // Outbound
@@ -33,6 +37,8 @@ pub enum RpcMessage {
     Error(Box<NodeError>),
     VersionInfoResponse(Box<VersionInfo>),
     DownloadBlobResponse(Box<Blob>),
+    DownloadCertificateResponse(Box<Certificate>),
+    DownloadCertificatesResponse(Box<Vec<Certificate>>),
 
     // Internal to a validator
     CrossChainRequest(Box<CrossChainRequest>),
@@ -57,7 +63,12 @@ impl RpcMessage {
             | VersionInfoQuery
             | VersionInfoResponse(_)
             | DownloadBlob(_)
-            | DownloadBlobResponse(_) => {
+            | DownloadBlobResponse(_)
+            | DownloadCertificate(_)
+            | DownloadCertificates(_)
+            | DownloadCertificatesByHeight(_)
+            | DownloadCertificateResponse(_)
+            | DownloadCertificatesResponse(_) => {
                 return None;
             }
         };
@@ -71,7 +82,11 @@ impl RpcMessage {
         use RpcMessage::*;
 
         match self {
-            VersionInfoQuery | DownloadBlob(_) => true,
+            VersionInfoQuery
+            | DownloadBlob(_)
+            | DownloadCertificate(_)
+            | DownloadCertificates(_)
+            | DownloadCertificatesByHeight(_) => true,
             BlockProposal(_)
             | LiteCertificate(_)
             | Certificate(_)
@@ -81,7 +96,9 @@ impl RpcMessage {
             | Error(_)
             | ChainInfoResponse(_)
             | VersionInfoResponse(_)
-            | DownloadBlobResponse(_) => false,
+            | DownloadBlobResponse(_)
+            | DownloadCertificateResponse(_)
+            | DownloadCertificatesResponse(_) => false,
         }
     }
 }
@@ -122,6 +139,30 @@ impl TryFrom<RpcMessage> for Blob {
     }
 }
 
+impl TryFrom<RpcMessage> for Certificate {
+    type Error = NodeError;
+    fn try_from(message: RpcMessage) -> Result<Self, Self::Error> {
+        use RpcMessage::*;
+        match message {
+            DownloadCertificateResponse(certificate) => Ok(*certificate),
+            Error(error) => Err(*error),
+            _ => Err(NodeError::UnexpectedMessage),
+        }
+    }
+}
+
+impl TryFrom<RpcMessage> for Vec<Certificate> {
+    type Error = NodeError;
+    fn try_from(message: RpcMessage) -> Result<Self, Self::Error> {
+        use RpcMessage::*;
+        match message {
+            DownloadCertificatesResponse(certificates) => Ok(*certificates),
+            Error(error) => Err(*error),
+            _ => Err(NodeError::UnexpectedMessage),
+        }
+    }
+}
+
 impl From<BlockProposal> for RpcMessage {
     fn from(block_proposal: BlockProposal) -> Self {
         RpcMessage::BlockProposal(Box::new(block_proposal))
@@ -181,3 +222,15 @@ impl From<Blob> for RpcMessage {
         RpcMessage::DownloadBlobResponse(Box::new(blob))
     }
 }
+
+impl From<Certificate> for RpcMessage {
+    fn from(certificate: Certificate) -> Self {
+        RpcMessage::DownloadCertificateResponse(Box::new(certificate))
+    }
+}
+
+impl From<Vec<Certificate>> for RpcMessage {
+    fn from(certificates: Vec<Certificate>) -> Self {
+        RpcMessage::DownloadCertificatesResponse(Box::new(certificates))
+    }
+}
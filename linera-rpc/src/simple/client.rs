@@ -7,6 +7,7 @@ use std::{future::Future, time::Duration};
 use async_trait::async_trait;
 use futures::{sink::SinkExt, stream::StreamExt};
 use linera_base::{
+    crypto::CryptoHash,
     data_types::{Blob, HashedBlob},
     identifiers::{BlobId, ChainId},
 };
@@ -14,7 +15,7 @@ use linera_chain::data_types::{
     BlockProposal, Certificate, HashedCertificateValue, LiteCertificate,
 };
 use linera_core::{
-    data_types::{ChainInfoQuery, ChainInfoResponse},
+    data_types::{BlockHeightRange, ChainInfoQuery, ChainInfoResponse},
     node::{CrossChainMessageDelivery, NodeError, NotificationStream, ValidatorNode},
 };
 use linera_version::VersionInfo;
@@ -140,6 +141,30 @@ impl ValidatorNode for SimpleClient {
         self.query(RpcMessage::DownloadBlob(Box::new(blob_id)))
             .await
     }
+
+    async fn download_certificate(&mut self, hash: CryptoHash) -> Result<Certificate, NodeError> {
+        self.query(RpcMessage::DownloadCertificate(Box::new(hash)))
+            .await
+    }
+
+    async fn download_certificates(
+        &mut self,
+        hashes: Vec<CryptoHash>,
+    ) -> Result<Vec<Certificate>, NodeError> {
+        self.query(RpcMessage::DownloadCertificates(Box::new(hashes)))
+            .await
+    }
+
+    async fn download_certificates_by_height(
+        &mut self,
+        chain_id: ChainId,
+        range: BlockHeightRange,
+    ) -> Result<Vec<Certificate>, NodeError> {
+        self.query(RpcMessage::DownloadCertificatesByHeight(Box::new((
+            chain_id, range,
+        ))))
+        .await
+    }
 }
 
 #[derive(Clone)]
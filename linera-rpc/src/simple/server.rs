@@ -294,7 +294,12 @@ where
             | RpcMessage::ChainInfoResponse(_)
             | RpcMessage::VersionInfoResponse(_)
             | RpcMessage::DownloadBlob(_)
-            | RpcMessage::DownloadBlobResponse(_) => Err(NodeError::UnexpectedMessage),
+            | RpcMessage::DownloadBlobResponse(_)
+            | RpcMessage::DownloadCertificate(_)
+            | RpcMessage::DownloadCertificates(_)
+            | RpcMessage::DownloadCertificatesByHeight(_)
+            | RpcMessage::DownloadCertificateResponse(_)
+            | RpcMessage::DownloadCertificatesResponse(_) => Err(NodeError::UnexpectedMessage),
         };
 
         self.server.packets_processed += 1;
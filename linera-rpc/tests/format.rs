@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use linera_base::{
+    crypto::{AccountPublicKey, AccountSignature},
     data_types::{OracleResponse, Round},
     identifiers::{ChainDescription, Destination, GenericApplicationId},
     ownership::ChainOwnership,
@@ -13,7 +14,11 @@ use linera_chain::{
 };
 use linera_core::{data_types::CrossChainRequest, node::NodeError};
 use linera_execution::{
-    system::{AdminOperation, Recipient, SystemChannel, SystemMessage, SystemOperation},
+    committee::SignatureScheme,
+    system::{
+        AdminOperation, Recipient, StandingOrderOperation, SystemChannel, SystemMessage,
+        SystemOperation,
+    },
     Message, MessageKind, Operation,
 };
 use linera_rpc::RpcMessage;
@@ -29,11 +34,15 @@ fn get_registry() -> Result<Registry> {
     // 1. Record samples for types with custom deserializers.
     // 2. Trace the main entry point(s) + every enum separately.
     tracer.trace_type::<Round>(&samples)?;
+    tracer.trace_type::<AccountPublicKey>(&samples)?;
+    tracer.trace_type::<AccountSignature>(&samples)?;
     tracer.trace_type::<OracleResponse>(&samples)?;
+    tracer.trace_type::<SignatureScheme>(&samples)?;
     tracer.trace_type::<Recipient>(&samples)?;
     tracer.trace_type::<SystemChannel>(&samples)?;
     tracer.trace_type::<SystemOperation>(&samples)?;
     tracer.trace_type::<AdminOperation>(&samples)?;
+    tracer.trace_type::<StandingOrderOperation>(&samples)?;
     tracer.trace_type::<SystemMessage>(&samples)?;
     tracer.trace_type::<Operation>(&samples)?;
     tracer.trace_type::<Message>(&samples)?;
@@ -4,7 +4,7 @@
 //! Conversions from types generated by [`wit-bindgen`] to types declared in [`linera-sdk`].
 
 use linera_base::{
-    crypto::{CryptoHash, PublicKey},
+    crypto::{AccountPublicKey, CryptoHash, PublicKey, Secp256k1PublicKey},
     data_types::{Amount, BlockHeight, TimeDelta, Timestamp},
     identifiers::{ApplicationId, BytecodeId, ChainId, MessageId, Owner},
     ownership::{ChainOwnership, CloseChainError, TimeoutConfig},
@@ -86,6 +86,32 @@ impl From<wit_system_api::PublicKey> for PublicKey {
     }
 }
 
+impl From<wit_system_api::Secp256k1PublicKey> for Secp256k1PublicKey {
+    fn from(guest: wit_system_api::Secp256k1PublicKey) -> Secp256k1PublicKey {
+        let wit_system_api::Secp256k1PublicKey {
+            part1,
+            part2,
+            part3,
+            part4,
+            part5,
+        } = guest;
+        Secp256k1PublicKey::from_parts([part1, part2, part3, part4], part5)
+    }
+}
+
+impl From<wit_system_api::AccountPublicKey> for AccountPublicKey {
+    fn from(guest: wit_system_api::AccountPublicKey) -> AccountPublicKey {
+        match guest {
+            wit_system_api::AccountPublicKey::Ed25519(public_key) => {
+                AccountPublicKey::Ed25519(public_key.into())
+            }
+            wit_system_api::AccountPublicKey::Secp256k1(public_key) => {
+                AccountPublicKey::Secp256k1(public_key.into())
+            }
+        }
+    }
+}
+
 impl From<wit_system_api::Owner> for Owner {
     fn from(owner: wit_system_api::Owner) -> Self {
         Owner(owner.inner0.into())
@@ -122,6 +148,7 @@ impl From<wit_system_api::ChainOwnership> for ChainOwnership {
             owners,
             multi_leader_rounds,
             timeout_config,
+            approval_threshold,
         } = guest;
         ChainOwnership {
             super_owners: super_owners
@@ -134,6 +161,7 @@ impl From<wit_system_api::ChainOwnership> for ChainOwnership {
                 .collect(),
             multi_leader_rounds,
             timeout_config: timeout_config.into(),
+            approval_threshold,
         }
     }
 }
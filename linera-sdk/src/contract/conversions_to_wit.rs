@@ -7,7 +7,8 @@ use linera_base::{
     crypto::CryptoHash,
     data_types::{Amount, BlockHeight, Resources, SendMessageRequest, Timestamp},
     identifiers::{
-        Account, ApplicationId, BytecodeId, ChainId, ChannelName, Destination, MessageId, Owner,
+        Account, ApplicationId, BlobId, BytecodeId, ChainId, ChannelName, Destination, MessageId,
+        Owner, StreamName,
     },
 };
 
@@ -73,6 +74,14 @@ impl From<ChainId> for wit_system_api::ChainId {
     }
 }
 
+impl From<BlobId> for wit_system_api::BlobId {
+    fn from(blob_id: BlobId) -> Self {
+        wit_system_api::BlobId {
+            inner0: blob_id.0.into(),
+        }
+    }
+}
+
 impl From<ApplicationId> for wit_system_api::ApplicationId {
     fn from(application_id: ApplicationId) -> Self {
         wit_system_api::ApplicationId {
@@ -129,6 +138,10 @@ impl From<Destination> for wit_system_api::Destination {
             Destination::Subscribers(subscription) => {
                 wit_system_api::Destination::Subscribers(subscription.into())
             }
+            Destination::Scheduled {
+                recipient,
+                not_before,
+            } => wit_system_api::Destination::Scheduled((recipient.into(), not_before.into())),
         }
     }
 }
@@ -141,6 +154,12 @@ impl From<ChannelName> for wit_system_api::ChannelName {
     }
 }
 
+impl From<StreamName> for wit_system_api::StreamName {
+    fn from(name: StreamName) -> Self {
+        wit_system_api::StreamName { inner0: name.0 }
+    }
+}
+
 impl From<Resources> for wit_system_api::Resources {
     fn from(resources: Resources) -> Self {
         wit_system_api::Resources {
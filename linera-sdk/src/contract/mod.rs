@@ -86,6 +86,19 @@ macro_rules! contract {
                 )
             }
 
+            fn process_event(event: Vec<u8>) {
+                use $crate::util::BlockingWait;
+                $crate::contract::run_async_entrypoint::<$contract, _, _>(
+                    unsafe { &mut CONTRACT },
+                    move |contract| {
+                        let event: $crate::StreamEvent =
+                            $crate::bcs::from_bytes(&event).expect("Failed to deserialize event");
+
+                        contract.process_event(event).blocking_wait()
+                    },
+                )
+            }
+
             fn finalize() {
                 use $crate::util::BlockingWait;
 
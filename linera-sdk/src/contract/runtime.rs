@@ -5,8 +5,12 @@
 
 use linera_base::{
     abi::{ContractAbi, ServiceAbi},
+    crypto::CryptoHash,
     data_types::{Amount, BlockHeight, Resources, SendMessageRequest, Timestamp},
-    identifiers::{Account, ApplicationId, ChainId, ChannelName, Destination, MessageId, Owner},
+    identifiers::{
+        Account, ApplicationId, BlobId, ChainId, ChannelName, Destination, MessageId, Owner,
+        StreamName,
+    },
     ownership::{ChainOwnership, CloseChainError},
 };
 use serde::Serialize;
@@ -90,6 +94,10 @@ where
     }
 
     /// Returns the height of the current block that is executing.
+    ///
+    /// Like the [`system_time`](Self::system_time), this is known to the block proposer in
+    /// advance, so it must not be used as a source of randomness. Use
+    /// [`random_seed`](Self::random_seed) instead.
     pub fn block_height(&mut self) -> BlockHeight {
         *self
             .block_height
@@ -121,6 +129,10 @@ where
     }
 
     /// Retrieves the current system time, i.e. the timestamp of the block in which this is called.
+    ///
+    /// The timestamp is chosen by the block proposer, within the validators' tolerance, so it
+    /// must not be used as a source of randomness. Use [`random_seed`](Self::random_seed)
+    /// instead.
     pub fn system_time(&mut self) -> Timestamp {
         *self
             .timestamp
@@ -146,6 +158,25 @@ where
         self.prepare_message(message).send_to(destination)
     }
 
+    /// Schedules a message to be sent to this application on a chain (possibly this one),
+    /// where it can only be received in a block with a timestamp no earlier than
+    /// `not_before`.
+    ///
+    /// The block fails if `not_before` is more than one year after the current block's
+    /// timestamp.
+    pub fn schedule_message(
+        &mut self,
+        recipient: ChainId,
+        not_before: Timestamp,
+        message: Application::Message,
+    ) {
+        self.prepare_message(message)
+            .send_to(Destination::Scheduled {
+                recipient,
+                not_before,
+            })
+    }
+
     /// Returns a `MessageBuilder` to prepare a message to be sent.
     pub fn prepare_message(
         &mut self,
@@ -164,6 +195,46 @@ where
         wit::unsubscribe(chain.into(), &channel.into());
     }
 
+    /// Adds an event with the given `key` and `value` to one of this application's event
+    /// streams. Events are recorded in the block and numbered in emission order within their
+    /// stream. Applications on other chains can subscribe to the stream with
+    /// [`ContractRuntime::subscribe_to_events`].
+    pub fn emit(&mut self, name: StreamName, key: &[u8], value: &[u8]) {
+        wit::emit(&name.into(), key, value);
+    }
+
+    /// Subscribes this application to the event stream `name` of `application_id` on another
+    /// chain. The events added to the stream from then on are sent to this chain, and passed
+    /// to [`Contract::process_event`] when a block receives them. A new subscriber may also
+    /// receive the events that the latest such block sent to the earlier subscribers.
+    pub fn subscribe_to_events<Abi>(
+        &mut self,
+        chain_id: ChainId,
+        application_id: ApplicationId<Abi>,
+        name: StreamName,
+    ) {
+        wit::subscribe_to_events(
+            chain_id.into(),
+            application_id.forget_abi().into(),
+            &name.into(),
+        );
+    }
+
+    /// Unsubscribes this application from the event stream `name` of `application_id` on
+    /// another chain.
+    pub fn unsubscribe_from_events<Abi>(
+        &mut self,
+        chain_id: ChainId,
+        application_id: ApplicationId<Abi>,
+        name: StreamName,
+    ) {
+        wit::unsubscribe_from_events(
+            chain_id.into(),
+            application_id.forget_abi().into(),
+            &name.into(),
+        );
+    }
+
     /// Transfers an `amount` of native tokens from `source` owner account (or the current chain's
     /// balance) to `destination`.
     pub fn transfer(&mut self, source: Option<Owner>, destination: Account, amount: Amount) {
@@ -243,6 +314,27 @@ where
     pub fn assert_before(&mut self, timestamp: Timestamp) {
         wit::assert_before(timestamp.into());
     }
+
+    /// Reads the contents of the published blob with the given ID.
+    ///
+    /// The blob ID is recorded in the block, so validators re-executing it read the same blob.
+    pub fn read_blob(&mut self, blob_id: BlobId) -> Vec<u8> {
+        wit::read_blob(blob_id.into())
+    }
+
+    /// Returns the random seed revealed by the certificate of this chain's block at `height`,
+    /// which must be lower than the current block height.
+    ///
+    /// The seed is derived from the validators' signatures on that block, so it is not known
+    /// to anyone before the block is confirmed. To draw a random value, commit to the draw in
+    /// one block and read the seed of that block's height in a later block. Whoever assembles
+    /// the certificate can still choose between the quorums of validators who signed it.
+    ///
+    /// Cannot be used in fast blocks: A block using this call should be proposed by a regular
+    /// owner, not a super owner.
+    pub fn random_seed(&mut self, height: BlockHeight) -> CryptoHash {
+        wit::random_seed(height.into()).into()
+    }
 }
 
 /// A helper type that uses the builder pattern to configure how a message is sent, and then
@@ -286,6 +378,9 @@ where
     }
 
     /// Forwards a grant of resources so the receiver can use it to pay for receiving the message.
+    ///
+    /// Receivers may require a minimum grant for user messages from unknown senders; see the
+    /// `messageGrantPolicy` of their system state.
     pub fn with_grant(mut self, grant: Resources) -> Self {
         self.grant = grant;
         self
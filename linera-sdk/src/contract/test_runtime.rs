@@ -4,14 +4,18 @@
 //! Runtime types to simulate interfacing with the host executing the contract.
 
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     sync::{Arc, Mutex, MutexGuard},
 };
 
 use linera_base::{
     abi::{ContractAbi, ServiceAbi},
+    crypto::CryptoHash,
     data_types::{Amount, BlockHeight, Resources, SendMessageRequest, Timestamp},
-    identifiers::{Account, ApplicationId, ChainId, ChannelName, Destination, MessageId, Owner},
+    identifiers::{
+        Account, ApplicationId, BlobId, ChainId, ChannelName, Destination, MessageId, Owner,
+        StreamName,
+    },
     ownership::{ChainOwnership, CloseChainError},
 };
 use serde::Serialize;
@@ -40,10 +44,14 @@ where
     send_message_requests: Arc<Mutex<Vec<SendMessageRequest<Application::Message>>>>,
     subscribe_requests: Vec<(ChainId, ChannelName)>,
     unsubscribe_requests: Vec<(ChainId, ChannelName)>,
+    emitted_events: Vec<(StreamName, Vec<u8>, Vec<u8>)>,
+    event_subscriptions: HashSet<(ChainId, ApplicationId, StreamName)>,
     outgoing_transfers: HashMap<Account, Amount>,
     claim_requests: Vec<ClaimRequest>,
     expected_service_queries: VecDeque<(ApplicationId, String, String)>,
     expected_post_requests: VecDeque<(String, Vec<u8>, Vec<u8>)>,
+    blobs: HashMap<BlobId, Vec<u8>>,
+    random_seeds: HashMap<BlockHeight, CryptoHash>,
     key_value_store: KeyValueStore,
 }
 
@@ -80,10 +88,14 @@ where
             send_message_requests: Arc::default(),
             subscribe_requests: Vec::new(),
             unsubscribe_requests: Vec::new(),
+            emitted_events: Vec::new(),
+            event_subscriptions: HashSet::new(),
             outgoing_transfers: HashMap::new(),
             claim_requests: Vec::new(),
             expected_service_queries: VecDeque::new(),
             expected_post_requests: VecDeque::new(),
+            blobs: HashMap::new(),
+            random_seeds: HashMap::new(),
             key_value_store: KeyValueStore::mock().to_mut(),
         }
     }
@@ -392,6 +404,22 @@ where
         self.prepare_message(message).send_to(destination)
     }
 
+    /// Schedules a message to be sent to this application on a chain (possibly this one),
+    /// where it can only be received in a block with a timestamp no earlier than
+    /// `not_before`.
+    pub fn schedule_message(
+        &mut self,
+        recipient: ChainId,
+        not_before: Timestamp,
+        message: Application::Message,
+    ) {
+        self.prepare_message(message)
+            .send_to(Destination::Scheduled {
+                recipient,
+                not_before,
+            })
+    }
+
     /// Returns a `MessageBuilder` to prepare a message to be sent.
     pub fn prepare_message(
         &mut self,
@@ -429,6 +457,47 @@ where
         &self.unsubscribe_requests
     }
 
+    /// Adds an event with the given `key` and `value` to one of this application's event
+    /// streams.
+    pub fn emit(&mut self, name: StreamName, key: &[u8], value: &[u8]) {
+        self.emitted_events
+            .push((name, key.to_vec(), value.to_vec()));
+    }
+
+    /// Returns the list of events emitted in the test so far.
+    pub fn emitted_events(&self) -> &[(StreamName, Vec<u8>, Vec<u8>)] {
+        &self.emitted_events
+    }
+
+    /// Subscribes this application to the event stream `name` of `application_id` on another
+    /// chain.
+    pub fn subscribe_to_events<Abi>(
+        &mut self,
+        chain_id: ChainId,
+        application_id: ApplicationId<Abi>,
+        name: StreamName,
+    ) {
+        self.event_subscriptions
+            .insert((chain_id, application_id.forget_abi(), name));
+    }
+
+    /// Unsubscribes this application from the event stream `name` of `application_id` on
+    /// another chain.
+    pub fn unsubscribe_from_events<Abi>(
+        &mut self,
+        chain_id: ChainId,
+        application_id: ApplicationId<Abi>,
+        name: StreamName,
+    ) {
+        self.event_subscriptions
+            .remove(&(chain_id, application_id.forget_abi(), name));
+    }
+
+    /// Returns the event streams this application is currently subscribed to.
+    pub fn event_subscriptions(&self) -> &HashSet<(ChainId, ApplicationId, StreamName)> {
+        &self.event_subscriptions
+    }
+
     /// Transfers an `amount` of native tokens from `source` owner account (or the current chain's
     /// balance) to `destination`.
     pub fn transfer(&mut self, source: Option<Owner>, destination: Account, amount: Amount) {
@@ -646,6 +715,56 @@ where
     pub fn assert_before(&mut self, timestamp: Timestamp) {
         assert!(self.timestamp.is_some_and(|t| t < timestamp))
     }
+
+    /// Configures the contents of a published blob that can be read during the test.
+    pub fn with_blob(mut self, blob_id: BlobId, bytes: Vec<u8>) -> Self {
+        self.blobs.insert(blob_id, bytes);
+        self
+    }
+
+    /// Configures the contents of a published blob that can be read during the test.
+    pub fn set_blob(&mut self, blob_id: BlobId, bytes: Vec<u8>) -> &mut Self {
+        self.blobs.insert(blob_id, bytes);
+        self
+    }
+
+    /// Reads the contents of the published blob with the given ID.
+    pub fn read_blob(&mut self, blob_id: BlobId) -> Vec<u8> {
+        self.blobs.get(&blob_id).cloned().unwrap_or_else(|| {
+            panic!(
+                "Blob {blob_id} has not been mocked, \
+                please call `MockContractRuntime::set_blob` first"
+            )
+        })
+    }
+
+    /// Configures the random seed revealed by the block at `height`, to be read during the test.
+    pub fn with_random_seed(mut self, height: BlockHeight, seed: CryptoHash) -> Self {
+        self.random_seeds.insert(height, seed);
+        self
+    }
+
+    /// Configures the random seed revealed by the block at `height`, to be read during the test.
+    pub fn set_random_seed(&mut self, height: BlockHeight, seed: CryptoHash) -> &mut Self {
+        self.random_seeds.insert(height, seed);
+        self
+    }
+
+    /// Returns the random seed revealed by the certificate of this chain's block at `height`.
+    ///
+    /// Panics if `height` is not lower than the current block height.
+    pub fn random_seed(&mut self, height: BlockHeight) -> CryptoHash {
+        assert!(
+            height < self.block_height(),
+            "The random seed of block {height} is not revealed yet"
+        );
+        *self.random_seeds.get(&height).unwrap_or_else(|| {
+            panic!(
+                "The random seed of block {height} has not been mocked, \
+                please call `MockContractRuntime::set_random_seed` first"
+            )
+        })
+    }
 }
 
 /// A type alias for the handler for cross-application calls.
@@ -48,7 +48,7 @@ pub use bcs;
 use linera_base::abi::{ContractAbi, ServiceAbi, WithContractAbi, WithServiceAbi};
 pub use linera_base::{
     abi,
-    data_types::{Resources, SendMessageRequest},
+    data_types::{Resources, SendMessageRequest, StreamEvent},
     ensure,
 };
 use serde::{de::DeserializeOwned, Serialize};
@@ -118,6 +118,13 @@ pub trait Contract: WithContractAbi + ContractAbi + Sized {
     /// chain.
     async fn execute_message(&mut self, message: Self::Message);
 
+    /// Processes an event of a stream on another chain that the application subscribed to
+    /// with [`ContractRuntime::subscribe_to_events`].
+    ///
+    /// Events are sent to the subscribed chains as messages, so they are processed when a block
+    /// of this chain receives them. By default, events are ignored.
+    async fn process_event(&mut self, _event: StreamEvent) {}
+
     /// Finishes the execution of the current transaction.
     ///
     /// This is called once at the end of the transaction, to allow all applications that
@@ -8,7 +8,7 @@ use std::cell::Cell;
 use linera_base::{
     abi::ServiceAbi,
     data_types::{Amount, BlockHeight, Timestamp},
-    identifiers::{ApplicationId, ChainId, Owner},
+    identifiers::{ApplicationId, BlobId, ChainId, Owner},
 };
 
 use super::wit::service_system_api as wit;
@@ -135,6 +135,11 @@ where
         wit::fetch_url(url)
     }
 
+    /// Reads the contents of the published blob with the given ID.
+    pub fn read_blob(&self, blob_id: BlobId) -> Vec<u8> {
+        wit::read_blob(blob_id.into())
+    }
+
     /// Loads a value from the `cell` cache or fetches it and stores it in the cache.
     fn fetch_value_through_cache<T>(cell: &Cell<Option<T>>, fetch: impl FnOnce() -> T) -> T
     where
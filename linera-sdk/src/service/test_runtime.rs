@@ -11,7 +11,7 @@ use std::{
 use linera_base::{
     abi::ServiceAbi,
     data_types::{Amount, BlockHeight, Timestamp},
-    identifiers::{ApplicationId, ChainId, Owner},
+    identifiers::{ApplicationId, BlobId, ChainId, Owner},
 };
 
 use crate::{KeyValueStore, Service};
@@ -30,6 +30,7 @@ where
     owner_balances: RefCell<Option<HashMap<Owner, Amount>>>,
     query_application_handler: RefCell<Option<QueryApplicationHandler>>,
     url_blobs: RefCell<Option<HashMap<String, Vec<u8>>>>,
+    blobs: RefCell<Option<HashMap<BlobId, Vec<u8>>>>,
     key_value_store: KeyValueStore,
 }
 
@@ -58,6 +59,7 @@ where
             owner_balances: RefCell::new(None),
             query_application_handler: RefCell::new(None),
             url_blobs: RefCell::new(None),
+            blobs: RefCell::new(None),
             key_value_store: KeyValueStore::mock(),
         }
     }
@@ -357,6 +359,35 @@ where
             })
     }
 
+    /// Configures the contents of a published blob that can be read during the test.
+    pub fn with_blob(self, blob_id: BlobId, bytes: Vec<u8>) -> Self {
+        self.set_blob(blob_id, bytes);
+        self
+    }
+
+    /// Configures the contents of a published blob that can be read during the test.
+    pub fn set_blob(&self, blob_id: BlobId, bytes: Vec<u8>) -> &Self {
+        self.blobs
+            .borrow_mut()
+            .get_or_insert_with(HashMap::new)
+            .insert(blob_id, bytes);
+        self
+    }
+
+    /// Reads the contents of the published blob with the given ID.
+    pub fn read_blob(&self, blob_id: BlobId) -> Vec<u8> {
+        self.blobs
+            .borrow_mut()
+            .as_mut()
+            .and_then(|blobs| blobs.get(&blob_id).cloned())
+            .unwrap_or_else(|| {
+                panic!(
+                    "Blob {blob_id} has not been mocked, \
+                    please call `MockServiceRuntime::set_blob` first"
+                )
+            })
+    }
+
     /// Loads a mocked value from the `cell` cache or panics with a provided `message`.
     fn fetch_mocked_value<T>(cell: &Cell<Option<T>>, message: &str) -> T
     where
@@ -8,7 +8,7 @@
 use std::mem;
 
 use linera_base::{
-    crypto::PublicKey,
+    crypto::{AccountPublicKey, PublicKey},
     data_types::{ApplicationPermissions, Round, Timestamp},
     identifiers::{ApplicationId, ChainId, MessageId, Owner},
     ownership::TimeoutConfig,
@@ -107,10 +107,17 @@ impl BlockBuilder {
         timeout_config: TimeoutConfig,
     ) -> &mut Self {
         self.with_system_operation(SystemOperation::ChangeOwnership {
-            super_owners,
-            owners,
+            super_owners: super_owners
+                .into_iter()
+                .map(AccountPublicKey::from)
+                .collect(),
+            owners: owners
+                .into_iter()
+                .map(|(public_key, weight)| (public_key.into(), weight))
+                .collect(),
             multi_leader_rounds,
             timeout_config,
+            approval_threshold: None,
         })
     }
 
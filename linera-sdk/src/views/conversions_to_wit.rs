@@ -3,9 +3,15 @@
 
 //! Conversions from types declared in [`linera-sdk`] to types generated by [`wit-bindgen`].
 
-use linera_views::batch::WriteOperation;
+use linera_views::{
+    batch::WriteOperation,
+    common::{Direction, KeyBound},
+};
 
-use crate::contract::wit::view_system_api as wit_system_api;
+use crate::{
+    contract::wit::view_system_api as wit_system_api,
+    service::wit::view_system_api as service_wit_system_api,
+};
 
 impl From<WriteOperation> for wit_system_api::WriteOperation {
     fn from(write_operation: WriteOperation) -> Self {
@@ -18,3 +24,41 @@ impl From<WriteOperation> for wit_system_api::WriteOperation {
         }
     }
 }
+
+impl From<KeyBound> for wit_system_api::KeyBound {
+    fn from(bound: KeyBound) -> Self {
+        match bound {
+            KeyBound::Included(key) => wit_system_api::KeyBound::Included(key),
+            KeyBound::Excluded(key) => wit_system_api::KeyBound::Excluded(key),
+            KeyBound::Unbounded => wit_system_api::KeyBound::Unbounded,
+        }
+    }
+}
+
+impl From<Direction> for wit_system_api::Direction {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Forward => wit_system_api::Direction::Forward,
+            Direction::Reverse => wit_system_api::Direction::Reverse,
+        }
+    }
+}
+
+impl From<KeyBound> for service_wit_system_api::KeyBound {
+    fn from(bound: KeyBound) -> Self {
+        match bound {
+            KeyBound::Included(key) => service_wit_system_api::KeyBound::Included(key),
+            KeyBound::Excluded(key) => service_wit_system_api::KeyBound::Excluded(key),
+            KeyBound::Unbounded => service_wit_system_api::KeyBound::Unbounded,
+        }
+    }
+}
+
+impl From<Direction> for service_wit_system_api::Direction {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Forward => service_wit_system_api::Direction::Forward,
+            Direction::Reverse => service_wit_system_api::Direction::Reverse,
+        }
+    }
+}
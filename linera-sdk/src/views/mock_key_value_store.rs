@@ -14,7 +14,7 @@ use std::{
 use futures::FutureExt;
 use linera_views::{
     batch::Batch,
-    common::{ReadableKeyValueStore, WritableKeyValueStore},
+    common::{KeyRange, ReadableKeyValueStore, WritableKeyValueStore},
     memory::{create_memory_store, MemoryStore},
 };
 
@@ -157,6 +157,41 @@ impl MockKeyValueStore {
         self.find_key_values_promises.take(promise)
     }
 
+    /// Finds keys in the storage that start with `key_prefix` and whose remainder lies in
+    /// the `range`, returning a promise to retrieve the final value.
+    pub(crate) fn find_keys_in_range_new(&self, key_prefix: &[u8], range: &KeyRange) -> u32 {
+        self.find_keys_promises.register(
+            self.store
+                .find_keys_in_range(key_prefix, range)
+                .now_or_never()
+                .expect("Memory store should never wait for anything")
+                .expect("Memory store should never fail"),
+        )
+    }
+
+    /// Returns the keys found in storage by the respective [`find_keys_in_range_new`] call.
+    pub(crate) fn find_keys_in_range_wait(&self, promise: u32) -> Vec<Vec<u8>> {
+        self.find_keys_promises.take(promise)
+    }
+
+    /// Finds key-value pairs in the storage in which the key starts with `key_prefix` and
+    /// has a remainder in the `range`, returning a promise to retrieve the final value.
+    pub(crate) fn find_key_values_in_range_new(&self, key_prefix: &[u8], range: &KeyRange) -> u32 {
+        self.find_key_values_promises.register(
+            self.store
+                .find_key_values_in_range(key_prefix, range)
+                .now_or_never()
+                .expect("Memory store should never wait for anything")
+                .expect("Memory store should never fail"),
+        )
+    }
+
+    /// Returns the key-value pairs found in storage by the respective
+    /// [`find_key_values_in_range_new`] call.
+    pub(crate) fn find_key_values_in_range_wait(&self, promise: u32) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.find_key_values_promises.take(promise)
+    }
+
     /// Writes a `batch` of operations to storage.
     pub(crate) fn write_batch(&self, batch: Batch) {
         self.store
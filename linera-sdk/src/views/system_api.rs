@@ -9,7 +9,8 @@ use std::sync::Arc;
 use linera_base::ensure;
 use linera_views::{
     batch::Batch,
-    common::{ContextFromStore, ReadableKeyValueStore, WritableKeyValueStore},
+    common::{ContextFromStore, KeyBound, KeyRange, ReadableKeyValueStore, WritableKeyValueStore},
+    journaling::JournalStatus,
     views::ViewError,
 };
 
@@ -138,6 +139,34 @@ impl ReadableKeyValueStore<ViewError> for KeyValueStore {
         yield_once().await;
         Ok(self.wit_api.find_key_values_wait(promise))
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, ViewError> {
+        ensure!(
+            key_prefix.len() <= Self::MAX_KEY_SIZE,
+            ViewError::KeyTooLong
+        );
+        let promise = self.wit_api.find_keys_in_range_new(key_prefix, range);
+        yield_once().await;
+        Ok(self.wit_api.find_keys_in_range_wait(promise))
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, ViewError> {
+        ensure!(
+            key_prefix.len() <= Self::MAX_KEY_SIZE,
+            ViewError::KeyTooLong
+        );
+        let promise = self.wit_api.find_key_values_in_range_new(key_prefix, range);
+        yield_once().await;
+        Ok(self.wit_api.find_key_values_in_range_wait(promise))
+    }
 }
 
 impl WritableKeyValueStore<ViewError> for KeyValueStore {
@@ -151,6 +180,10 @@ impl WritableKeyValueStore<ViewError> for KeyValueStore {
     async fn clear_journal(&self, _base_key: &[u8]) -> Result<(), ViewError> {
         Ok(())
     }
+
+    async fn inspect_journal(&self, _base_key: &[u8]) -> Result<JournalStatus, ViewError> {
+        Ok(JournalStatus::default())
+    }
 }
 
 /// Which system API should be used to interface with the storage.
@@ -270,6 +303,78 @@ impl WitInterface {
         }
     }
 
+    /// Calls the `find_keys_in_range_new` WIT function.
+    fn find_keys_in_range_new(&self, key_prefix: &[u8], range: &KeyRange) -> u32 {
+        let start = KeyBound::from(range.start.clone());
+        let end = KeyBound::from(range.end.clone());
+        let limit = range.limit.map(|limit| limit as u32);
+        match self {
+            WitInterface::Contract => contract_wit::find_keys_in_range_new(
+                key_prefix,
+                &start.into(),
+                &end.into(),
+                range.direction.into(),
+                limit,
+            ),
+            WitInterface::Service => service_wit::find_keys_in_range_new(
+                key_prefix,
+                &start.into(),
+                &end.into(),
+                range.direction.into(),
+                limit,
+            ),
+            #[cfg(with_testing)]
+            WitInterface::Mock { store, .. } => store.find_keys_in_range_new(key_prefix, range),
+        }
+    }
+
+    /// Calls the `find_keys_in_range_wait` WIT function.
+    fn find_keys_in_range_wait(&self, promise: u32) -> Vec<Vec<u8>> {
+        match self {
+            WitInterface::Contract => contract_wit::find_keys_in_range_wait(promise),
+            WitInterface::Service => service_wit::find_keys_in_range_wait(promise),
+            #[cfg(with_testing)]
+            WitInterface::Mock { store, .. } => store.find_keys_in_range_wait(promise),
+        }
+    }
+
+    /// Calls the `find_key_values_in_range_new` WIT function.
+    fn find_key_values_in_range_new(&self, key_prefix: &[u8], range: &KeyRange) -> u32 {
+        let start = KeyBound::from(range.start.clone());
+        let end = KeyBound::from(range.end.clone());
+        let limit = range.limit.map(|limit| limit as u32);
+        match self {
+            WitInterface::Contract => contract_wit::find_key_values_in_range_new(
+                key_prefix,
+                &start.into(),
+                &end.into(),
+                range.direction.into(),
+                limit,
+            ),
+            WitInterface::Service => service_wit::find_key_values_in_range_new(
+                key_prefix,
+                &start.into(),
+                &end.into(),
+                range.direction.into(),
+                limit,
+            ),
+            #[cfg(with_testing)]
+            WitInterface::Mock { store, .. } => {
+                store.find_key_values_in_range_new(key_prefix, range)
+            }
+        }
+    }
+
+    /// Calls the `find_key_values_in_range_wait` WIT function.
+    fn find_key_values_in_range_wait(&self, promise: u32) -> Vec<(Vec<u8>, Vec<u8>)> {
+        match self {
+            WitInterface::Contract => contract_wit::find_key_values_in_range_wait(promise),
+            WitInterface::Service => service_wit::find_key_values_in_range_wait(promise),
+            #[cfg(with_testing)]
+            WitInterface::Mock { store, .. } => store.find_key_values_in_range_wait(promise),
+        }
+    }
+
     /// Calls the `write_batch` WIT function.
     fn write_batch(&self, batch: Batch) {
         match self {
@@ -5,18 +5,20 @@ use graphql_client::GraphQLQuery;
 use linera_base::{
     crypto::CryptoHash,
     data_types::{Amount, BlockHeight, OracleResponse, Timestamp},
-    identifiers::{Account, ChainDescription, ChainId, ChannelName, Destination, Owner},
+    identifiers::{Account, ChainDescription, ChainId, ChannelName, Destination, Owner, StreamId},
 };
 
 pub type JSONObject = serde_json::Value;
 
 #[cfg(target_arch = "wasm32")]
 mod types {
+    use std::collections::BTreeSet;
+
     use linera_base::data_types::Round;
     use serde::{Deserialize, Serialize};
     use serde_json::Value;
 
-    use super::{BlockHeight, ChainId, CryptoHash};
+    use super::{BlockHeight, ChainId, CryptoHash, StreamId};
 
     pub type ChainManager = Value;
     pub type ChainOwnership = Value;
@@ -52,6 +54,11 @@ mod types {
             height: BlockHeight,
             round: Round,
         },
+        NewEvents {
+            height: BlockHeight,
+            hash: CryptoHash,
+            event_streams: BTreeSet<StreamId>,
+        },
     }
 }
 
@@ -131,7 +138,7 @@ pub struct Transfer;
 mod from {
     use linera_base::data_types::OracleRecord;
     use linera_chain::data_types::{
-        BlockExecutionOutcome, ExecutedBlock, HashedCertificateValue, IncomingMessage,
+        BlockExecutionOutcome, EventRecord, ExecutedBlock, HashedCertificateValue, IncomingMessage,
         OutgoingMessage,
     };
 
@@ -212,6 +219,7 @@ mod from {
                         message_counts,
                         state_hash,
                         oracle_records,
+                        events,
                     },
             } = val;
             let messages = messages
@@ -225,6 +233,10 @@ mod from {
                     message_counts: message_counts.into_iter().map(|c| c as u32).collect(),
                     state_hash,
                     oracle_records: oracle_records.into_iter().map(Into::into).collect(),
+                    events: events
+                        .into_iter()
+                        .map(|events| events.into_iter().map(Into::into).collect())
+                        .collect(),
                 },
             }
         }
@@ -237,6 +249,23 @@ mod from {
         }
     }
 
+    impl From<block::BlockBlockValueExecutedBlockOutcomeEvents> for EventRecord {
+        fn from(val: block::BlockBlockValueExecutedBlockOutcomeEvents) -> Self {
+            let block::BlockBlockValueExecutedBlockOutcomeEvents {
+                stream_id,
+                index,
+                key,
+                value,
+            } = val;
+            EventRecord {
+                stream_id,
+                index: index as u32,
+                key: key.into_iter().map(|byte| byte as u8).collect(),
+                value: value.into_iter().map(|byte| byte as u8).collect(),
+            }
+        }
+    }
+
     impl TryFrom<block::BlockBlock> for HashedCertificateValue {
         type Error = String;
         fn try_from(val: block::BlockBlock) -> Result<Self, Self::Error> {
@@ -10,17 +10,17 @@ use futures::{
     StreamExt,
 };
 use linera_base::{
-    crypto::KeyPair,
-    data_types::Timestamp,
+    crypto::{AccountPublicKey, KeyPair},
+    data_types::{Amount, Timestamp},
     identifiers::{ChainId, Destination},
 };
 use linera_chain::data_types::OutgoingMessage;
 use linera_core::{
-    client::{ArcChainClient, ChainClient},
+    client::{ArcChainClient, BlanketMessagePolicy, ChainClient, MessagePolicy},
     node::{ValidatorNode, ValidatorNodeProvider},
     worker::Reason,
 };
-use linera_execution::{Message, SystemMessage};
+use linera_execution::{Message, MessageKind, SystemMessage, UserApplicationId};
 use linera_storage::Storage;
 use linera_views::views::ViewError;
 use tracing::{error, info, warn};
@@ -40,6 +40,61 @@ pub struct ChainListenerConfig {
     /// Wait after processing any notification (useful for rate limiting).
     #[arg(long = "listener-delay-after-ms", default_value = "0")]
     pub delay_after_ms: u64,
+
+    /// Only accept incoming user messages from these chains, and reject all others. System
+    /// messages are not affected.
+    #[arg(long, num_args(0..))]
+    pub restrict_chain_ids_to: Option<Vec<ChainId>>,
+
+    /// Reject incoming user messages from these chains.
+    #[arg(long, num_args(0..))]
+    pub reject_chain_ids: Vec<ChainId>,
+
+    /// Only accept user messages from these applications, and reject all others. System
+    /// messages are not affected.
+    #[arg(long, num_args(0..))]
+    pub restrict_application_ids_to: Option<Vec<UserApplicationId>>,
+
+    /// Reject user messages from these applications.
+    #[arg(long, num_args(0..))]
+    pub reject_application_ids: Vec<UserApplicationId>,
+
+    /// Only accept incoming messages of these kinds, and reject all others. Protected and
+    /// bouncing messages are always accepted.
+    #[arg(long, num_args(0..), value_enum)]
+    pub restrict_message_kinds_to: Option<Vec<MessageKind>>,
+
+    /// Reject incoming user messages with a smaller grant.
+    #[arg(long, default_value = "0")]
+    pub minimum_message_grant: Amount,
+
+    /// The maximum number of incoming messages from the same chain to include in a block.
+    #[arg(long)]
+    pub max_messages_per_origin: Option<usize>,
+}
+
+impl ChainListenerConfig {
+    /// Returns the policy for handling incoming messages with the given blanket policy and
+    /// the filtering rules of this configuration.
+    pub fn message_policy(&self, blanket: BlanketMessagePolicy) -> MessagePolicy {
+        let mut policy = MessagePolicy::new(blanket)
+            .with_reject_chain_ids(self.reject_chain_ids.iter().copied())
+            .with_reject_application_ids(self.reject_application_ids.iter().copied())
+            .with_minimum_grant(self.minimum_message_grant);
+        if let Some(chain_ids) = &self.restrict_chain_ids_to {
+            policy = policy.with_restrict_chain_ids_to(chain_ids.iter().copied());
+        }
+        if let Some(application_ids) = &self.restrict_application_ids_to {
+            policy = policy.with_restrict_application_ids_to(application_ids.iter().copied());
+        }
+        if let Some(kinds) = &self.restrict_message_kinds_to {
+            policy = policy.with_restrict_kinds_to(kinds.iter().copied());
+        }
+        if let Some(max_messages) = self.max_messages_per_origin {
+            policy = policy.with_max_messages_per_origin(max_messages);
+        }
+        policy
+    }
 }
 
 #[async_trait]
@@ -134,7 +189,9 @@ where
                 // chain, and then process the OpenChain message in the parent.
                 return Ok(());
             };
-            let client = context_guard.make_chain_client(storage.clone(), chain_id);
+            let mut client = context_guard.make_chain_client(storage.clone(), chain_id);
+            let message_policy = config.message_policy(client.message_policy().blanket());
+            client.set_message_policy(message_policy);
             let client = ArcChainClient::new(client);
             entry.insert(client.clone());
             client
@@ -148,11 +205,18 @@ where
                 Either::Left((Some(notification), _)) => notification,
                 Either::Left((None, _)) => break,
                 Either::Right(((), _)) => {
-                    match client.lock().await.process_inbox_if_owned().await {
+                    let mut client = client.lock().await;
+                    match client.process_inbox_if_owned().await {
                         Err(error) => warn!(%error, "Failed to process inbox."),
                         Ok((_, None)) => timeout = Timestamp::from(u64::MAX),
                         Ok((_, Some(new_timeout))) => timeout = new_timeout.timestamp,
                     }
+                    // Wake up again when the next scheduled message can be received.
+                    match client.next_scheduled_message_time().await {
+                        Err(error) => warn!(%error, "Failed to read scheduled messages."),
+                        Ok(Some(time)) => timeout = timeout.min(time),
+                        Ok(None) => {}
+                    }
                     continue;
                 }
             };
@@ -169,6 +233,7 @@ where
                         );
                     }
                 }
+                Reason::NewEvents { .. } => {}
             }
             Self::maybe_sleep(config.delay_after_ms).await;
             let Reason::NewBlock { hash, .. } = notification.reason else {
@@ -210,9 +275,13 @@ where
             }
             let mut context_guard = context.lock().await;
             for (new_id, owners, timestamp) in new_chains {
-                let key_pair = owners
-                    .iter()
-                    .find_map(|public_key| context_guard.wallet().key_pair_for_pk(public_key));
+                // Only Ed25519 keys can be held by the wallet.
+                let key_pair = owners.iter().find_map(|public_key| match public_key {
+                    AccountPublicKey::Ed25519(public_key) => {
+                        context_guard.wallet().key_pair_for_pk(public_key)
+                    }
+                    AccountPublicKey::Secp256k1(_) => None,
+                });
                 context_guard.update_wallet_for_new_chain(new_id, key_pair, timestamp);
                 Self::run_with_chain_id(
                     new_id,
@@ -176,8 +176,17 @@ impl ClientWrapper {
             operation_byte,
             message,
             message_byte,
+            maximum_fuel_per_block,
+            maximum_operations_per_block,
+            maximum_incoming_messages_per_block,
+            maximum_outgoing_messages_per_block,
+            maximum_operation_bytes_per_block,
+            maximum_block_size,
             maximum_bytes_read_per_block,
             maximum_bytes_written_per_block,
+            maximum_bytes_stored_per_application,
+            maximum_bytes_stored_per_chain,
+            target_fuel_per_block,
         } = policy;
         let mut command = self.command().await?;
         command
@@ -199,6 +208,27 @@ impl ClientWrapper {
             .args(["--operation-price", &operation.to_string()])
             .args(["--operation-byte-price", &operation_byte.to_string()])
             .args(["--message-price", &message.to_string()])
+            .args([
+                "--maximum-fuel-per-block",
+                &maximum_fuel_per_block.to_string(),
+            ])
+            .args([
+                "--maximum-operations-per-block",
+                &maximum_operations_per_block.to_string(),
+            ])
+            .args([
+                "--maximum-incoming-messages-per-block",
+                &maximum_incoming_messages_per_block.to_string(),
+            ])
+            .args([
+                "--maximum-outgoing-messages-per-block",
+                &maximum_outgoing_messages_per_block.to_string(),
+            ])
+            .args([
+                "--maximum-operation-bytes-per-block",
+                &maximum_operation_bytes_per_block.to_string(),
+            ])
+            .args(["--maximum-block-size", &maximum_block_size.to_string()])
             .args([
                 "--maximum-bytes-read-per-block",
                 &maximum_bytes_read_per_block.to_string(),
@@ -206,6 +236,18 @@ impl ClientWrapper {
             .args([
                 "--maximum-bytes-written-per-block",
                 &maximum_bytes_written_per_block.to_string(),
+            ])
+            .args([
+                "--maximum-bytes-stored-per-application",
+                &maximum_bytes_stored_per_application.to_string(),
+            ])
+            .args([
+                "--maximum-bytes-stored-per-chain",
+                &maximum_bytes_stored_per_chain.to_string(),
+            ])
+            .args([
+                "--target-fuel-per-block",
+                &target_fuel_per_block.to_string(),
             ]);
         if let Some(seed) = self.testing_prng_seed {
             command.arg("--testing-prng-seed").arg(seed.to_string());
@@ -3,7 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    io::{BufRead, BufReader, BufWriter, Write},
+    collections::BTreeMap,
+    io::{BufRead, BufReader, BufWriter, Read as _, Write},
     iter::IntoIterator,
     path::{Path, PathBuf},
 };
@@ -12,12 +13,16 @@ use anyhow::{bail, Context as _};
 use fs4::FileExt as _;
 use fs_err::{self, File, OpenOptions};
 use linera_base::{
-    crypto::{BcsSignable, CryptoRng, KeyPair, PublicKey},
+    crypto::{
+        AccountSignature, BcsSignable, BlsKeyPair, BlsPublicKey, BlsSignature, CryptoError,
+        CryptoRng, KeyPair, PublicKey,
+    },
     data_types::{Amount, Timestamp},
-    identifiers::{ChainDescription, ChainId},
+    identifiers::{ChainDescription, ChainId, Owner},
 };
+use linera_chain::data_types::{Block, BlockAndRound, BlockProposal};
 use linera_execution::{
-    committee::{Committee, ValidatorName, ValidatorState},
+    committee::{Committee, SignatureScheme, SignedFuelUsage, ValidatorName, ValidatorState},
     ResourceControlPolicy,
 };
 use linera_rpc::config::{ValidatorInternalNetworkConfig, ValidatorPublicNetworkConfig};
@@ -25,7 +30,14 @@ use linera_storage::Storage;
 use linera_views::views::ViewError;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::wallet::{UserChain, Wallet};
+use crate::{
+    encryption::{EncryptedData, KdfParams},
+    wallet::{UserChain, Wallet},
+};
+
+#[cfg(test)]
+#[path = "unit_tests/config.rs"]
+mod tests;
 
 pub trait Import: DeserializeOwned {
     fn read(path: &Path) -> Result<Self, std::io::Error> {
@@ -36,7 +48,11 @@ pub trait Import: DeserializeOwned {
 
 pub trait Export: Serialize {
     fn write(&self, path: &Path) -> Result<(), std::io::Error> {
-        let file = OpenOptions::new().create(true).write(true).open(path)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
         let mut writer = BufWriter::new(file);
         let data = serde_json::to_string_pretty(self).unwrap();
         writer.write_all(data.as_ref())?;
@@ -52,6 +68,13 @@ pub struct ValidatorConfig {
     pub name: ValidatorName,
     /// The network configuration for the validator.
     pub network: ValidatorPublicNetworkConfig,
+    /// The BLS public key of the validator, if it has one.
+    #[serde(default)]
+    pub bls_public_key: Option<BlsPublicKey>,
+    /// The signature of the BLS public key with itself, which the admin checks before
+    /// adding the key to a committee.
+    #[serde(default)]
+    pub bls_proof_of_possession: Option<BlsSignature>,
 }
 
 /// The private configuration of a validator service.
@@ -59,6 +82,8 @@ pub struct ValidatorConfig {
 pub struct ValidatorServerConfig {
     pub validator: ValidatorConfig,
     pub key: KeyPair,
+    #[serde(default)]
+    pub bls_key: Option<BlsKeyPair>,
     pub internal_network: ValidatorInternalNetworkConfig,
 }
 
@@ -69,13 +94,17 @@ impl Export for ValidatorServerConfig {}
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct CommitteeConfig {
     pub validators: Vec<ValidatorConfig>,
+    /// The kind of signatures in the certificates of the first epoch.
+    #[serde(default)]
+    pub signature_scheme: SignatureScheme,
 }
 
 impl Import for CommitteeConfig {}
 impl Export for CommitteeConfig {}
 
 impl CommitteeConfig {
-    pub fn into_committee(self, policy: ResourceControlPolicy) -> Committee {
+    /// Returns the committee, checking the proofs of possession of the BLS public keys.
+    pub fn into_committee(self, policy: ResourceControlPolicy) -> Result<Committee, CryptoError> {
         let validators = self
             .validators
             .into_iter()
@@ -85,11 +114,16 @@ impl CommitteeConfig {
                     ValidatorState {
                         network_address: v.network.to_string(),
                         votes: 1,
+                        bls_public_key: v.bls_public_key,
+                        bls_proof_of_possession: v.bls_proof_of_possession,
                     },
                 )
             })
             .collect();
-        Committee::new(validators, policy)
+        let committee =
+            Committee::new(validators, policy).with_signature_scheme(self.signature_scheme);
+        committee.check_bls_proofs_of_possession()?;
+        Ok(committee)
     }
 }
 
@@ -122,12 +156,21 @@ impl Drop for FileLock {
     }
 }
 
+/// The contents of an encrypted wallet file: the JSON serialization of the [`Wallet`],
+/// encrypted with a passphrase.
+#[derive(Serialize, Deserialize)]
+struct EncryptedWallet {
+    encrypted_wallet: EncryptedData,
+}
+
 /// A wrapper around `Wallet` which owns a [`FileLock`] to prevent
 /// two processes accessing it at the same time.
 pub struct WalletState {
     inner: Wallet,
     prng: Box<dyn CryptoRng>,
     wallet_path: PathBuf,
+    /// The passphrase the wallet is encrypted with, or `None` if it is stored in plain text.
+    passphrase: Option<String>,
     _lock: FileLock,
 }
 
@@ -151,39 +194,90 @@ impl WalletState {
     }
 
     pub fn from_file(path: &Path) -> Result<Self, anyhow::Error> {
+        Self::from_file_with_passphrase(path, None)
+    }
+
+    /// Reads the wallet at `path`, decrypting it with `passphrase` if it is encrypted.
+    ///
+    /// Wallets in the plain format are read regardless of `passphrase`, and stay in the
+    /// plain format until [`WalletState::set_passphrase`] is called.
+    pub fn from_file_with_passphrase(
+        path: &Path,
+        passphrase: Option<String>,
+    ) -> Result<Self, anyhow::Error> {
         let file = OpenOptions::new().read(true).write(true).open(path)?;
         let file_lock = FileLock::new(file, path)?;
-        let inner: Wallet = serde_json::from_reader(BufReader::new(&file_lock.file))?;
+        let mut data = Vec::new();
+        BufReader::new(&file_lock.file).read_to_end(&mut data)?;
+        let (inner, passphrase) = Self::decode(&data, passphrase)?;
         Ok(Self {
             prng: inner.make_prng(),
             inner,
             wallet_path: path.into(),
+            passphrase,
             _lock: file_lock,
         })
     }
 
+    /// Creates a new wallet at `path`, encrypted with `passphrase` if provided. If the file
+    /// already exists and is not empty, the existing wallet is read instead.
     pub fn create(
         path: &Path,
         genesis_config: GenesisConfig,
         testing_prng_seed: Option<u64>,
+        passphrase: Option<String>,
     ) -> Result<Self, anyhow::Error> {
         let file = Self::open_options().read(true).open(path)?;
         let file_lock = FileLock::new(file, path)?;
         let mut reader = BufReader::new(&file_lock.file);
-        let inner = if reader.fill_buf()?.is_empty() {
-            Wallet::new(genesis_config, testing_prng_seed)
+        let (inner, passphrase) = if reader.fill_buf()?.is_empty() {
+            (Wallet::new(genesis_config, testing_prng_seed), passphrase)
         } else {
-            serde_json::from_reader(reader)?
+            let mut data = Vec::new();
+            reader.read_to_end(&mut data)?;
+            Self::decode(&data, passphrase)?
         };
 
         Ok(Self {
             prng: inner.make_prng(),
             inner,
             wallet_path: path.into(),
+            passphrase,
             _lock: file_lock,
         })
     }
 
+    /// Returns whether the wallet is stored encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.passphrase.is_some()
+    }
+
+    /// Sets the passphrase used to encrypt the wallet the next time it is written, or stores
+    /// it in plain text if `passphrase` is `None`.
+    pub fn set_passphrase(&mut self, passphrase: Option<String>) {
+        self.passphrase = passphrase;
+    }
+
+    /// Parses the contents of a wallet file, in either format. Returns the wallet and the
+    /// passphrase to keep using, i.e. `None` for a wallet in the plain format.
+    fn decode(
+        data: &[u8],
+        passphrase: Option<String>,
+    ) -> Result<(Wallet, Option<String>), anyhow::Error> {
+        let Ok(EncryptedWallet { encrypted_wallet }) = serde_json::from_slice(data) else {
+            return Ok((serde_json::from_slice(data)?, None));
+        };
+        let Some(passphrase) = passphrase else {
+            bail!(
+                "The wallet is encrypted: its passphrase must be set in LINERA_WALLET_PASSPHRASE"
+            );
+        };
+        let plaintext = encrypted_wallet
+            .decrypt(&passphrase)
+            .context("Failed to decrypt the wallet")?;
+        Ok((serde_json::from_slice(&plaintext)?, Some(passphrase)))
+    }
+
     /// Writes the wallet to disk.
     ///
     /// The contents of the wallet need to be over-written completely, so
@@ -198,7 +292,15 @@ impl WalletState {
         temp_file_path.set_extension("json.bak");
         let backup_file = Self::open_options().open(&temp_file_path)?;
         let mut temp_file_writer = BufWriter::new(backup_file);
-        if let Err(e) = serde_json::to_writer_pretty(&mut temp_file_writer, &self.inner) {
+        let result = match &self.passphrase {
+            None => serde_json::to_writer_pretty(&mut temp_file_writer, &self.inner)
+                .map_err(anyhow::Error::from),
+            Some(passphrase) => self.encrypt(passphrase).and_then(|encrypted_wallet| {
+                serde_json::to_writer_pretty(&mut temp_file_writer, &encrypted_wallet)
+                    .map_err(anyhow::Error::from)
+            }),
+        };
+        if let Err(e) = result {
             fs_err::remove_file(&temp_file_path)?;
             bail!("failed to serialize the wallet state: {}", e)
         }
@@ -210,6 +312,14 @@ impl WalletState {
         Ok(())
     }
 
+    /// Encrypts the wallet with `passphrase`, using a new random salt.
+    fn encrypt(&self, passphrase: &str) -> Result<EncryptedWallet, anyhow::Error> {
+        let plaintext = serde_json::to_vec(&self.inner)?;
+        let encrypted_wallet =
+            EncryptedData::encrypt(&plaintext, passphrase, KdfParams::default())?;
+        Ok(EncryptedWallet { encrypted_wallet })
+    }
+
     pub fn generate_key_pair(&mut self) -> KeyPair {
         KeyPair::generate_from(&mut self.prng)
     }
@@ -274,7 +384,7 @@ impl GenesisConfig {
         S: Storage + Clone + Send + Sync + 'static,
         ViewError: From<S::ContextError>,
     {
-        let committee = self.create_committee();
+        let committee = self.create_committee()?;
         for (chain_number, (public_key, balance)) in (0..).zip(&self.chains) {
             let description = ChainDescription::Root(chain_number);
             storage
@@ -291,7 +401,25 @@ impl GenesisConfig {
         Ok(())
     }
 
-    pub fn create_committee(&self) -> Committee {
+    pub fn create_committee(&self) -> Result<Committee, CryptoError> {
         self.committee.clone().into_committee(self.policy.clone())
     }
 }
+
+/// A pending block of a chain that requires an approval threshold, together with the owners'
+/// approvals collected so far.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    pub block: Block,
+    pub approvals: BTreeMap<Owner, AccountSignature>,
+}
+
+impl Import for ApprovalRequest {}
+impl Export for ApprovalRequest {}
+
+impl Import for BlockAndRound {}
+impl Export for BlockAndRound {}
+impl Import for BlockProposal {}
+impl Export for BlockProposal {}
+impl Import for SignedFuelUsage {}
+impl Export for SignedFuelUsage {}
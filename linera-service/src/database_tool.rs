@@ -1,9 +1,18 @@
 // Copyright (c) Zefchain Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::process;
+use std::{path::PathBuf, process};
 
-use linera_service::storage::StorageConfigNamespace;
+use linera_base::{crypto::CryptoHash, data_types::BlockHeight, identifiers::ChainId};
+use linera_execution::{WasmRuntime, WithWasmDefault as _};
+use linera_service::{
+    integrity::check_integrity,
+    migration::{MigrationConfig, MigrationJob, MigrationStores},
+    pruning::{PruningConfig, PruningJob},
+    replay::ReplayJob,
+    storage::{run_with_existing_storage, run_with_store, StorageConfigNamespace},
+};
+use linera_storage::ChainSnapshot;
 use linera_views::common::CommonStoreConfig;
 
 #[derive(clap::Parser)]
@@ -71,6 +80,79 @@ enum DatabaseToolCommand {
         #[arg(long = "storage")]
         storage_config: String,
     },
+
+    /// Delete the certificates that are no longer needed, optionally archiving them
+    ///
+    /// The progress of pruning is not persisted: each run examines the blocks of every chain
+    /// starting from height 0, including those whose certificates were already deleted.
+    #[command(name = "prune")]
+    Prune {
+        /// Storage configuration for the blockchain history.
+        #[arg(long = "storage")]
+        storage_config: String,
+
+        /// Retention policy and archive storage
+        #[command(flatten)]
+        pruning_config: PruningConfig,
+    },
+
+    /// Check the integrity of the chains, certificates and journals, and print a JSON report
+    #[command(name = "check_integrity")]
+    CheckIntegrity {
+        /// Storage configuration for the blockchain history.
+        #[arg(long = "storage")]
+        storage_config: String,
+    },
+
+    /// Copy all the keys of a namespace to another database, possibly with another backend
+    #[command(name = "migrate")]
+    Migrate {
+        /// Storage configuration of the namespace to copy.
+        #[arg(long = "source-storage")]
+        source_storage_config: String,
+
+        /// Storage configuration of the namespace to copy to.
+        #[arg(long = "destination-storage")]
+        destination_storage_config: String,
+
+        /// Batch size, checkpoint and verification
+        #[command(flatten)]
+        migration_config: MigrationConfig,
+    },
+
+    /// Re-execute a block on its parent state and print a JSON report of the state differences
+    #[command(name = "replay_block")]
+    ReplayBlock {
+        /// Storage configuration for the blockchain history.
+        #[arg(long = "storage")]
+        storage_config: String,
+
+        /// The chain of the block to replay.
+        #[arg(long)]
+        chain_id: ChainId,
+
+        /// The height of the block to replay.
+        #[arg(long)]
+        height: BlockHeight,
+
+        /// The WebAssembly runtime to use.
+        #[arg(long)]
+        wasm_runtime: Option<WasmRuntime>,
+
+        /// The hash of the block's certificate, if it is stored but not in the chain's
+        /// confirmed log, e.g. because its execution failed with a state hash mismatch.
+        #[arg(long)]
+        certificate_hash: Option<CryptoHash>,
+
+        /// A snapshot of the chain at the previous block, to use instead of the storage.
+        /// Without it, the chain in the storage must not have executed the block yet.
+        #[arg(long)]
+        parent_snapshot: Option<PathBuf>,
+
+        /// A snapshot of the chain at the replayed block, to compare the result with.
+        #[arg(long)]
+        successor_snapshot: Option<PathBuf>,
+    },
 }
 
 async fn evaluate_options(options: DatabaseToolOptions) -> Result<i32, anyhow::Error> {
@@ -121,6 +203,107 @@ async fn evaluate_options(options: DatabaseToolOptions) -> Result<i32, anyhow::E
             let namespaces = full_storage_config.list_all().await?;
             println!("The list of namespaces is {:?}", namespaces);
         }
+        DatabaseToolCommand::Prune {
+            storage_config,
+            pruning_config,
+        } => {
+            let storage_config = storage_config.parse::<StorageConfigNamespace>()?;
+            let full_storage_config = storage_config.add_common_config(common_config).await?;
+            let job = PruningJob {
+                config: pruning_config,
+                period: None,
+            };
+            let summary = run_with_existing_storage(full_storage_config, None, job).await?;
+            println!(
+                "Pruned {} certificates ({} archived, {} retained) from {} chains",
+                summary.pruned, summary.archived, summary.retained, summary.chains
+            );
+        }
+        DatabaseToolCommand::CheckIntegrity { storage_config } => {
+            let storage_config = storage_config.parse::<StorageConfigNamespace>()?;
+            let report = check_integrity(storage_config, common_config).await?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            if report.is_healthy() {
+                tracing::info!("No corruption was found");
+                return Ok(0);
+            } else {
+                tracing::info!("Some corruption was found");
+                return Ok(1);
+            }
+        }
+        DatabaseToolCommand::Migrate {
+            source_storage_config,
+            destination_storage_config,
+            migration_config,
+        } => {
+            let source_storage_config = source_storage_config.parse::<StorageConfigNamespace>()?;
+            let destination_storage_config =
+                destination_storage_config.parse::<StorageConfigNamespace>()?;
+            let stores = MigrationStores {
+                source: source_storage_config.to_string(),
+                destination: destination_storage_config.to_string(),
+            };
+            let source_config = source_storage_config
+                .add_common_config(common_config.clone())
+                .await?;
+            destination_storage_config
+                .add_common_config(common_config.clone())
+                .await?
+                .initialize()
+                .await?;
+            let destination = destination_storage_config
+                .add_common_config(common_config)
+                .await?;
+            let job = MigrationJob {
+                destination,
+                stores,
+                config: migration_config,
+            };
+            let summary = run_with_store(source_config, job).await?;
+            println!(
+                "Copied {} keys ({} bytes)",
+                summary.copied_keys, summary.copied_bytes
+            );
+            if let Some(last_key) = &summary.resumed_after {
+                println!("Resumed a previous run after key {}", hex::encode(last_key));
+            }
+            if let Some(verified_keys) = summary.verified_keys {
+                println!("Verified that both namespaces have the same {verified_keys} keys");
+            }
+        }
+        DatabaseToolCommand::ReplayBlock {
+            storage_config,
+            chain_id,
+            height,
+            wasm_runtime,
+            certificate_hash,
+            parent_snapshot,
+            successor_snapshot,
+        } => {
+            let storage_config = storage_config.parse::<StorageConfigNamespace>()?;
+            let full_storage_config = storage_config.add_common_config(common_config).await?;
+            let read_snapshot = |path: Option<PathBuf>| -> Result<_, anyhow::Error> {
+                path.map(|path| Ok(ChainSnapshot::from_bytes(&fs_err::read(path)?)?))
+                    .transpose()
+            };
+            let job = ReplayJob {
+                chain_id,
+                height,
+                certificate_hash,
+                parent_snapshot: read_snapshot(parent_snapshot)?,
+                successor_snapshot: read_snapshot(successor_snapshot)?,
+            };
+            let wasm_runtime = wasm_runtime.with_wasm_default();
+            let report = run_with_existing_storage(full_storage_config, wasm_runtime, job).await?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            if report.is_consistent() {
+                tracing::info!("The block was reproduced");
+                return Ok(0);
+            } else {
+                tracing::info!("The block was not reproduced");
+                return Ok(1);
+            }
+        }
     }
     tracing::info!("Successful execution of linera-db");
     Ok(0)
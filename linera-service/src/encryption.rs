@@ -0,0 +1,161 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Passphrase-based encryption of files holding secret keys.
+//!
+//! The encryption key is derived from the passphrase with Argon2id, and the data is encrypted
+//! with XChaCha20-Poly1305. The format version and the KDF parameters are stored in clear next
+//! to the ciphertext, and authenticated as associated data.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead as _, KeyInit as _, Payload},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use rand::Rng as _;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The current version of the format of [`EncryptedData`].
+const FORMAT_VERSION: u32 = 1;
+
+/// The size of the salt of the key derivation.
+const SALT_SIZE: usize = 16;
+
+/// The size of an XChaCha20-Poly1305 nonce.
+const NONCE_SIZE: usize = 24;
+
+/// Data encrypted with a passphrase.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedData {
+    /// The version of the format.
+    pub version: u32,
+    /// How the encryption key is derived from the passphrase.
+    pub kdf: KdfParams,
+    /// The nonce of the encryption, in hex.
+    #[serde(with = "hex")]
+    pub nonce: Vec<u8>,
+    /// The encrypted data, followed by the authentication tag, in hex.
+    #[serde(with = "hex")]
+    pub ciphertext: Vec<u8>,
+}
+
+/// The parameters of the Argon2id key derivation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// The memory cost, in KiB.
+    pub memory_kib: u32,
+    /// The number of iterations.
+    pub iterations: u32,
+    /// The degree of parallelism.
+    pub parallelism: u32,
+    /// The random salt, in hex.
+    #[serde(with = "hex")]
+    pub salt: Vec<u8>,
+}
+
+/// Error type for [`EncryptedData`].
+#[derive(Debug, Error)]
+pub enum EncryptionError {
+    #[error("Unsupported format version {0} of encrypted data")]
+    UnsupportedVersion(u32),
+
+    #[error("Invalid key derivation parameters: {0}")]
+    InvalidKdfParams(String),
+
+    #[error("Invalid nonce size {0}")]
+    InvalidNonce(usize),
+
+    #[error("Wrong passphrase or corrupted data")]
+    DecryptionFailed,
+
+    #[error("Failed to encrypt data")]
+    EncryptionFailed,
+}
+
+impl Default for KdfParams {
+    /// Returns the parameters recommended by OWASP, with a new random salt.
+    fn default() -> Self {
+        let mut salt = vec![0; SALT_SIZE];
+        rand::thread_rng().fill(&mut salt[..]);
+        KdfParams {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+            salt,
+        }
+    }
+}
+
+impl KdfParams {
+    /// Derives the encryption key from `passphrase`.
+    fn derive_key(&self, passphrase: &str) -> Result<Key, EncryptionError> {
+        let params = Params::new(
+            self.memory_kib,
+            self.iterations,
+            self.parallelism,
+            Some(Key::default().len()),
+        )
+        .map_err(|error| EncryptionError::InvalidKdfParams(error.to_string()))?;
+        let mut key = Key::default();
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &self.salt, &mut key)
+            .map_err(|error| EncryptionError::InvalidKdfParams(error.to_string()))?;
+        Ok(key)
+    }
+}
+
+impl EncryptedData {
+    /// Encrypts `plaintext` with a key derived from `passphrase`.
+    pub fn encrypt(
+        plaintext: &[u8],
+        passphrase: &str,
+        kdf: KdfParams,
+    ) -> Result<Self, EncryptionError> {
+        let key = kdf.derive_key(passphrase)?;
+        let mut nonce = vec![0; NONCE_SIZE];
+        rand::thread_rng().fill(&mut nonce[..]);
+        let associated_data = Self::associated_data(FORMAT_VERSION, &kdf);
+        let ciphertext = XChaCha20Poly1305::new(&key)
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &associated_data,
+                },
+            )
+            .map_err(|_| EncryptionError::EncryptionFailed)?;
+        Ok(EncryptedData {
+            version: FORMAT_VERSION,
+            kdf,
+            nonce,
+            ciphertext,
+        })
+    }
+
+    /// Decrypts the data with a key derived from `passphrase`.
+    pub fn decrypt(&self, passphrase: &str) -> Result<Vec<u8>, EncryptionError> {
+        if self.version != FORMAT_VERSION {
+            return Err(EncryptionError::UnsupportedVersion(self.version));
+        }
+        if self.nonce.len() != NONCE_SIZE {
+            return Err(EncryptionError::InvalidNonce(self.nonce.len()));
+        }
+        let key = self.kdf.derive_key(passphrase)?;
+        let associated_data = Self::associated_data(self.version, &self.kdf);
+        XChaCha20Poly1305::new(&key)
+            .decrypt(
+                XNonce::from_slice(&self.nonce),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &associated_data,
+                },
+            )
+            .map_err(|_| EncryptionError::DecryptionFailed)
+    }
+
+    /// Returns the header that is authenticated together with the ciphertext.
+    fn associated_data(version: u32, kdf: &KdfParams) -> Vec<u8> {
+        bcs::to_bytes(&(version, kdf)).expect("Serializing the header should not fail")
+    }
+}
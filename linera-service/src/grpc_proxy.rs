@@ -15,9 +15,17 @@ use std::{
 
 use anyhow::Result;
 use async_trait::async_trait;
-use futures::{future::BoxFuture, FutureExt};
+use futures::{
+    future::BoxFuture,
+    stream::{self, BoxStream},
+    FutureExt, StreamExt as _,
+};
 use linera_base::identifiers::ChainId;
-use linera_core::notifier::Notifier;
+use linera_core::{
+    data_types::BlockHeightRange,
+    node::{NodeError, MAX_CERTIFICATES_PER_REQUEST},
+    notifier::Notifier,
+};
 use linera_rpc::{
     config::{
         ShardConfig, TlsConfig, ValidatorInternalNetworkConfig, ValidatorPublicNetworkConfig,
@@ -27,14 +35,17 @@ use linera_rpc::{
             notifier_service_server::{NotifierService, NotifierServiceServer},
             validator_node_server::{ValidatorNode, ValidatorNodeServer},
             validator_worker_client::ValidatorWorkerClient,
-            Blob, BlobId, BlockProposal, Certificate, ChainInfoQuery, ChainInfoResult,
-            LiteCertificate, Notification, SubscriptionRequest, VersionInfo,
+            Blob, BlobId, BlockProposal, Certificate, CertificatesBatchRequest,
+            CertificatesByHeightRequest, ChainInfoQuery, ChainInfoResult, CryptoHash,
+            DownloadedCertificate, DownloadedCertificates, LiteCertificate, Notification,
+            SubscriptionRequest, VersionInfo,
         },
         pool::GrpcConnectionPool,
         GrpcProxyable, GRPC_MAX_MESSAGE_SIZE,
     },
 };
 use linera_storage::Storage;
+use linera_views::views::ViewError;
 use rcgen::generate_simple_self_signed;
 use tokio::select;
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -157,6 +168,7 @@ struct GrpcProxyInner<S> {
 impl<S> GrpcProxy<S>
 where
     S: Storage + Clone + Send + Sync + 'static,
+    ViewError: From<S::ContextError>,
 {
     pub fn new(
         public_config: ValidatorPublicNetworkConfig,
@@ -320,8 +332,11 @@ where
 impl<S> ValidatorNode for GrpcProxy<S>
 where
     S: Storage + Clone + Send + Sync + 'static,
+    ViewError: From<S::ContextError>,
 {
     type SubscribeStream = UnboundedReceiverStream<Result<Notification, Status>>;
+    type DownloadCertificatesByHeightStream =
+        BoxStream<'static, Result<DownloadedCertificates, Status>>;
 
     #[instrument(skip_all, err(Display))]
     async fn handle_block_proposal(
@@ -406,6 +421,68 @@ where
             .map_err(|err| Status::from_error(Box::new(err)))?;
         Ok(Response::new(hashed_blob.into_inner().into()))
     }
+
+    #[instrument(skip_all, err(Display))]
+    async fn download_certificate(
+        &self,
+        request: Request<CryptoHash>,
+    ) -> Result<Response<DownloadedCertificate>, Status> {
+        let hash = request.into_inner().try_into()?;
+        let certificate = self
+            .0
+            .storage
+            .read_certificate(hash)
+            .await
+            .map_err(|err| Status::from_error(Box::new(err)))?;
+        Ok(Response::new(certificate.try_into()?))
+    }
+
+    #[instrument(skip_all, err(Display))]
+    async fn download_certificates(
+        &self,
+        request: Request<CertificatesBatchRequest>,
+    ) -> Result<Response<DownloadedCertificates>, Status> {
+        let hashes: Vec<_> = request.into_inner().try_into()?;
+        let count = hashes.len() as u64;
+        if count > MAX_CERTIFICATES_PER_REQUEST {
+            let error = NodeError::TooManyCertificatesRequested(count);
+            return Err(Status::invalid_argument(error.to_string()));
+        }
+        let certificates = self
+            .0
+            .storage
+            .read_certificates(hashes)
+            .await
+            .map_err(|err| Status::from_error(Box::new(err)))?;
+        Ok(Response::new(certificates.try_into()?))
+    }
+
+    #[instrument(skip_all, err(Display))]
+    async fn download_certificates_by_height(
+        &self,
+        request: Request<CertificatesByHeightRequest>,
+    ) -> Result<Response<Self::DownloadCertificatesByHeightStream>, Status> {
+        let (chain_id, range): (ChainId, BlockHeightRange) = request.into_inner().try_into()?;
+        let storage = self.0.storage.clone();
+        let pages = stream::try_unfold(Some(range), move |range| {
+            let storage = storage.clone();
+            async move {
+                let Some(range) = range else {
+                    return Ok::<_, Status>(None);
+                };
+                let certificates = storage
+                    .read_certificates_by_height(chain_id, range.start, Some(range.page_limit()))
+                    .await
+                    .map_err(|err| Status::from_error(Box::new(err)))?;
+                if certificates.is_empty() {
+                    return Ok(None);
+                }
+                let next_range = range.after_page(certificates.len() as u64);
+                Ok(Some((certificates.try_into()?, next_range)))
+            }
+        });
+        Ok(Response::new(pages.boxed()))
+    }
 }
 
 #[async_trait]
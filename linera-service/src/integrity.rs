@@ -0,0 +1,68 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline verification of a storage, including its key-value store journals.
+
+use async_trait::async_trait;
+use linera_storage::{check_journals, IntegrityChecker, IntegrityReport, Storage};
+use linera_views::{
+    common::{CommonStoreConfig, KeyValueStore},
+    views::ViewError,
+};
+use tracing::info;
+
+use crate::storage::{
+    run_with_existing_storage, run_with_store, Runnable, RunnableWithStore, StorageConfigNamespace,
+};
+
+/// Checks the chains, certificates and journals of the given storage.
+pub async fn check_integrity(
+    storage_config: StorageConfigNamespace,
+    common_config: CommonStoreConfig,
+) -> Result<IntegrityReport, anyhow::Error> {
+    let config = storage_config
+        .add_common_config(common_config.clone())
+        .await?;
+    let report = run_with_existing_storage(config, None, IntegrityJob).await?;
+    let config = storage_config.add_common_config(common_config).await?;
+    run_with_store(config, JournalJob { report }).await
+}
+
+/// A job checking the chains and certificates of a storage.
+struct IntegrityJob;
+
+#[async_trait]
+impl Runnable for IntegrityJob {
+    type Output = IntegrityReport;
+
+    async fn run<S>(self, storage: S) -> Result<IntegrityReport, anyhow::Error>
+    where
+        S: Storage + Clone + Send + Sync + 'static,
+        ViewError: From<S::ContextError>,
+    {
+        let checker = IntegrityChecker::with_admin_committees(storage).await?;
+        Ok(checker.check_all_chains().await?)
+    }
+}
+
+/// A job adding the journals left unresolved in the key-value store to a report.
+struct JournalJob {
+    report: IntegrityReport,
+}
+
+#[async_trait]
+impl RunnableWithStore for JournalJob {
+    type Output = IntegrityReport;
+
+    async fn run<S>(self, store: S) -> Result<IntegrityReport, anyhow::Error>
+    where
+        S: KeyValueStore + Clone + Send + Sync + 'static,
+        S::Error: From<bcs::Error>,
+        ViewError: From<S::Error>,
+    {
+        let mut report = self.report;
+        check_journals(&store, &mut report).await?;
+        info!("Checked the journals of {} chains", report.chains.len());
+        Ok(report)
+    }
+}
@@ -7,12 +7,18 @@
 pub mod chain_listener;
 pub mod cli_wrappers;
 pub mod config;
+pub mod encryption;
 pub mod faucet;
 pub mod grpc_proxy;
+pub mod integrity;
+pub mod migration;
 pub mod node_service;
 pub mod project;
 #[cfg(with_metrics)]
 pub mod prometheus_server;
+pub mod pruning;
+pub mod replay;
+pub mod signer;
 pub mod storage;
 pub mod util;
 pub mod wallet;
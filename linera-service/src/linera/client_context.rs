@@ -4,6 +4,7 @@
 use std::{
     collections::BTreeMap,
     path::PathBuf,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
@@ -22,6 +23,7 @@ use linera_core::{
     client::{ArcChainClient, ChainClient, Client},
     data_types::ClientOutcome,
     node::{CrossChainMessageDelivery, ValidatorNodeProvider},
+    signer::Signer,
 };
 use linera_execution::Bytecode;
 use linera_rpc::node_provider::{NodeOptions, NodeProvider};
@@ -66,6 +68,7 @@ use crate::{client_options::ChainOwnershipConfig, ClientOptions};
 pub struct ClientContext {
     pub(crate) wallet_state: WalletState,
     pub(crate) chain_client_builder: Client<NodeProvider>,
+    pub(crate) signer: Option<Arc<dyn Signer>>,
     pub(crate) send_timeout: Duration,
     pub(crate) recv_timeout: Duration,
     pub(crate) notification_retry_delay: Duration,
@@ -112,7 +115,11 @@ impl ClientContext {
         self.wallet_state.inner_mut()
     }
 
-    pub fn new(options: &ClientOptions, wallet_state: WalletState) -> Self {
+    pub fn new(
+        options: &ClientOptions,
+        wallet_state: WalletState,
+        signer: Option<Arc<dyn Signer>>,
+    ) -> Self {
         let node_options = NodeOptions {
             send_timeout: options.send_timeout,
             recv_timeout: options.recv_timeout,
@@ -122,9 +129,11 @@ impl ClientContext {
         let node_provider = NodeProvider::new(node_options);
         let delivery = CrossChainMessageDelivery::new(options.wait_for_outgoing_messages);
         let chain_client_builder =
-            Client::new(node_provider, options.max_pending_messages, delivery);
+            Client::new(node_provider, options.max_pending_messages, delivery)
+                .with_message_policy(options.message_policy.into());
         ClientContext {
             chain_client_builder,
+            signer,
             wallet_state,
             send_timeout: options.send_timeout,
             recv_timeout: options.recv_timeout,
@@ -157,7 +166,7 @@ impl ClientContext {
             .map(|kp| kp.copy())
             .into_iter()
             .collect();
-        self.chain_client_builder.build(
+        let chain_client = self.chain_client_builder.build(
             chain_id,
             known_key_pairs,
             storage,
@@ -167,7 +176,11 @@ impl ClientContext {
             chain.next_block_height,
             chain.pending_block.clone(),
             chain.pending_blobs.clone(),
-        )
+        );
+        match &self.signer {
+            Some(signer) => chain_client.with_signer(signer.clone()),
+            None => chain_client,
+        }
     }
 
     pub fn make_node_provider(&self) -> NodeProvider {
@@ -672,8 +685,11 @@ impl ClientContext {
     }
 
     /// Tries to aggregate votes into certificates.
-    pub fn make_benchmark_certificates_from_votes(&self, votes: Vec<Vote>) -> Vec<Certificate> {
-        let committee = self.wallet().genesis_config().create_committee();
+    pub fn make_benchmark_certificates_from_votes(
+        &self,
+        votes: Vec<Vote>,
+    ) -> anyhow::Result<Vec<Certificate>> {
+        let committee = self.wallet().genesis_config().create_committee()?;
         let mut aggregators = HashMap::new();
         let mut certificates = Vec::new();
         let mut done_senders = HashSet::new();
@@ -688,6 +704,7 @@ impl ClientContext {
                 chain_id,
                 vote.validator,
             );
+            let lite_vote = vote.lite();
             let aggregator = aggregators.entry(chain_id).or_insert_with(|| {
                 SignatureAggregator::new(
                     vote.value,
@@ -695,7 +712,7 @@ impl ClientContext {
                     &committee,
                 )
             });
-            match aggregator.append(vote.validator, vote.signature) {
+            match aggregator.append_vote(&lite_vote) {
                 Ok(Some(certificate)) => {
                     trace!("Found certificate: {:?}", certificate);
                     certificates.push(certificate);
@@ -709,7 +726,7 @@ impl ClientContext {
                 }
             }
         }
-        certificates
+        Ok(certificates)
     }
 
     /// Broadcasts a bulk of blocks to each validator.
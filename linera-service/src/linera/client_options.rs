@@ -1,23 +1,26 @@
 // Copyright (c) Zefchain Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{env, iter, num::NonZeroU16, path::PathBuf, time::Duration};
+use std::{env, iter, num::NonZeroU16, path::PathBuf, sync::Arc, time::Duration};
 
+use anyhow::Context as _;
 use chrono::{DateTime, Utc};
 use linera_base::{
-    crypto::PublicKey,
-    data_types::{Amount, ApplicationPermissions, TimeDelta},
+    crypto::{AccountPublicKey, BlsPublicKey, BlsSignature, PublicKey},
+    data_types::{Amount, ApplicationPermissions, BlockHeight, TimeDelta},
     identifiers::{Account, ApplicationId, BytecodeId, ChainId, MessageId, Owner},
     ownership::{ChainOwnership, TimeoutConfig},
 };
-use linera_core::client::MessagePolicy;
+use linera_core::{client::BlanketMessagePolicy, signer::Signer};
 use linera_execution::{
-    committee::ValidatorName, system::SystemChannel, UserApplicationId, WasmRuntime,
-    WithWasmDefault as _,
+    committee::{SignatureScheme, ValidatorName},
+    system::SystemChannel,
+    UserApplicationId, WasmRuntime, WithWasmDefault as _,
 };
 use linera_service::{
     chain_listener::ChainListenerConfig,
     config::WalletState,
+    signer::Keystore,
     storage::{full_initialize_storage, run_with_storage, StorageConfigNamespace},
     util,
 };
@@ -36,6 +39,10 @@ pub struct ClientOptions {
     #[arg(long = "wallet")]
     pub wallet_state_path: Option<PathBuf>,
 
+    /// The passphrase of the wallet, if it is encrypted. New wallets are encrypted with it.
+    #[arg(long, env = "LINERA_WALLET_PASSPHRASE", hide_env_values = true)]
+    pub wallet_passphrase: Option<String>,
+
     /// Storage configuration for the blockchain history.
     #[arg(long = "storage")]
     pub storage_config: Option<String>,
@@ -61,6 +68,14 @@ pub struct ClientOptions {
     #[arg(long)]
     pub wasm_runtime: Option<WasmRuntime>,
 
+    /// A directory where compiled WebAssembly modules are kept across restarts.
+    #[arg(long)]
+    pub wasm_cache_dir: Option<PathBuf>,
+
+    /// The maximal size of the compiled WebAssembly modules kept on disk, in MiB.
+    #[arg(long, default_value = "1024")]
+    pub wasm_cache_size_mb: u64,
+
     /// The maximal number of simultaneous queries to the database
     #[arg(long)]
     pub max_concurrent_queries: Option<usize>,
@@ -100,7 +115,20 @@ pub struct ClientOptions {
 
     /// The policy for handling incoming messages.
     #[arg(long, default_value = "accept")]
-    pub message_policy: MessagePolicy,
+    pub message_policy: BlanketMessagePolicy,
+
+    /// An encrypted keystore holding keys of chain owners that are not in the wallet.
+    #[arg(long, conflicts_with = "external_signer")]
+    pub keystore: Option<PathBuf>,
+
+    /// The passphrase of the keystore.
+    #[arg(long, env = "LINERA_KEYSTORE_PASSPHRASE", hide_env_values = true)]
+    pub keystore_passphrase: Option<String>,
+
+    /// The Unix socket of an external signer holding keys of chain owners that are not in
+    /// the wallet.
+    #[arg(long)]
+    pub external_signer: Option<PathBuf>,
 }
 
 impl ClientOptions {
@@ -130,6 +158,12 @@ impl ClientOptions {
     }
 
     pub async fn run_command_with_storage(self) -> anyhow::Result<()> {
+        if let Some(directory) = &self.wasm_cache_dir {
+            linera_execution::enable_module_disk_cache(
+                directory,
+                self.wasm_cache_size_mb * 1024 * 1024,
+            )?;
+        }
         let wallet = self.wallet()?;
         run_with_storage(
             self.storage_config()?
@@ -175,7 +209,58 @@ impl ClientOptions {
     }
 
     pub fn wallet(&self) -> anyhow::Result<WalletState> {
-        WalletState::from_file(&self.wallet_path()?)
+        WalletState::from_file_with_passphrase(&self.wallet_path()?, self.wallet_passphrase.clone())
+    }
+
+    /// Opens the keystore.
+    pub fn keystore(&self) -> anyhow::Result<Keystore> {
+        let (path, passphrase) = self.keystore_path_and_passphrase()?;
+        anyhow::ensure!(
+            path.exists(),
+            "Keystore {} does not exist; create it with `linera signer import-keys`",
+            path.display()
+        );
+        Keystore::read(path, passphrase)
+    }
+
+    /// Opens the keystore, or creates it if the file doesn't exist yet.
+    pub fn keystore_or_create(&self) -> anyhow::Result<Keystore> {
+        let (path, passphrase) = self.keystore_path_and_passphrase()?;
+        if path.exists() {
+            Keystore::read(path, passphrase)
+        } else {
+            Keystore::create(path, passphrase)
+        }
+    }
+
+    fn keystore_path_and_passphrase(&self) -> anyhow::Result<(&PathBuf, String)> {
+        let path = self
+            .keystore
+            .as_ref()
+            .context("A keystore must be specified with --keystore")?;
+        let passphrase = self
+            .keystore_passphrase
+            .clone()
+            .context("The keystore passphrase must be set in LINERA_KEYSTORE_PASSPHRASE")?;
+        Ok((path, passphrase))
+    }
+
+    /// Returns the signer for the keys that are not in the wallet, if any.
+    pub fn signer(&self) -> anyhow::Result<Option<Arc<dyn Signer>>> {
+        if self.keystore.is_some() {
+            return Ok(Some(Arc::new(self.keystore()?.into_signer())));
+        }
+        #[cfg(unix)]
+        if let Some(socket_path) = &self.external_signer {
+            let signer = linera_service::signer::ExternalSigner::new(socket_path.clone());
+            return Ok(Some(Arc::new(signer)));
+        }
+        #[cfg(not(unix))]
+        anyhow::ensure!(
+            self.external_signer.is_none(),
+            "External signers are only supported on Unix"
+        );
+        Ok(None)
     }
 
     pub fn wallet_path(&self) -> anyhow::Result<PathBuf> {
@@ -209,7 +294,12 @@ impl ClientOptions {
             "Wallet already exists at {}. Aborting",
             wallet_path.display()
         );
-        WalletState::create(&wallet_path, genesis_config, testing_prng_seed)
+        WalletState::create(
+            &wallet_path,
+            genesis_config,
+            testing_prng_seed,
+            self.wallet_passphrase.clone(),
+        )
     }
 }
 
@@ -320,6 +410,22 @@ pub enum ClientCommand {
         application_permissions_config: ApplicationPermissionsConfig,
     },
 
+    /// Changes the grant required for incoming user messages, unless they come from one of
+    /// the trusted senders.
+    ChangeMessageGrantPolicy {
+        /// The ID of the chain to which the new policy will be applied.
+        #[arg(long)]
+        chain_id: Option<ChainId>,
+
+        /// The minimum grant of incoming user messages.
+        #[arg(long)]
+        minimum_grant: Amount,
+
+        /// The chains whose messages don't need a minimum grant.
+        #[arg(long, num_args(0..))]
+        trusted_senders: Vec<ChainId>,
+    },
+
     /// Close an existing chain.
     ///
     /// A closed chain cannot execute operations or accept messages anymore.
@@ -401,6 +507,14 @@ pub enum ClientCommand {
         /// Voting power
         #[arg(long, default_value = "1")]
         votes: u64,
+
+        /// The BLS public key of the validator, required if the committee uses BLS signatures.
+        #[arg(long, requires = "bls_proof_of_possession")]
+        bls_public_key: Option<BlsPublicKey>,
+
+        /// The validator's signature of its own BLS public key, proving that it owns the key.
+        #[arg(long, requires = "bls_public_key")]
+        bls_proof_of_possession: Option<BlsSignature>,
     },
 
     /// Remove a validator (admin only)
@@ -456,6 +570,30 @@ pub enum ClientCommand {
         #[arg(long)]
         message_byte: Option<Amount>,
 
+        /// Set the maximum amount of fuel a block can consume.
+        #[arg(long)]
+        maximum_fuel_per_block: Option<u64>,
+
+        /// Set the maximum number of operations in a block.
+        #[arg(long)]
+        maximum_operations_per_block: Option<u64>,
+
+        /// Set the maximum number of incoming messages in a block.
+        #[arg(long)]
+        maximum_incoming_messages_per_block: Option<u64>,
+
+        /// Set the maximum number of outgoing messages created by a block.
+        #[arg(long)]
+        maximum_outgoing_messages_per_block: Option<u64>,
+
+        /// Set the maximum total size of the arguments of the user operations in a block.
+        #[arg(long)]
+        maximum_operation_bytes_per_block: Option<u64>,
+
+        /// Set the maximum size of a serialized block.
+        #[arg(long)]
+        maximum_block_size: Option<u64>,
+
         /// Set the maximum read data per block.
         #[arg(long)]
         maximum_bytes_read_per_block: Option<u64>,
@@ -463,6 +601,32 @@ pub enum ClientCommand {
         /// Set the maximum write data per block.
         #[arg(long)]
         maximum_bytes_written_per_block: Option<u64>,
+
+        /// Set the maximum data stored by a single application on a chain.
+        #[arg(long)]
+        maximum_bytes_stored_per_application: Option<u64>,
+
+        /// Set the maximum data stored by all the applications of a chain.
+        #[arg(long)]
+        maximum_bytes_stored_per_chain: Option<u64>,
+
+        /// Set the fuel per block that prices are adjusted towards, or zero for fixed prices.
+        #[arg(long)]
+        target_fuel_per_block: Option<u64>,
+    },
+
+    /// Set the kind of signatures in the certificates of the next epoch (admin only)
+    SetSignatureScheme {
+        /// Either `ed25519` or `bls`. All validators need a BLS public key for `bls`.
+        scheme: SignatureScheme,
+    },
+
+    /// Adjust the fuel and block prices to the fuel consumption reported by the validators (admin only)
+    AdjustFees {
+        /// Files containing the fuel usage reports of the current epoch, signed by a quorum
+        /// of validators.
+        #[arg(long, num_args(1..))]
+        reports: Vec<PathBuf>,
     },
 
     /// Send one transfer per chain in bulk mode
@@ -562,6 +726,30 @@ pub enum ClientCommand {
         #[arg(long, default_value = "0")]
         message_byte_price: Amount,
 
+        /// Set the maximum amount of fuel a block can consume.
+        #[arg(long)]
+        maximum_fuel_per_block: Option<u64>,
+
+        /// Set the maximum number of operations in a block.
+        #[arg(long)]
+        maximum_operations_per_block: Option<u64>,
+
+        /// Set the maximum number of incoming messages in a block.
+        #[arg(long)]
+        maximum_incoming_messages_per_block: Option<u64>,
+
+        /// Set the maximum number of outgoing messages created by a block.
+        #[arg(long)]
+        maximum_outgoing_messages_per_block: Option<u64>,
+
+        /// Set the maximum total size of the arguments of the user operations in a block.
+        #[arg(long)]
+        maximum_operation_bytes_per_block: Option<u64>,
+
+        /// Set the maximum size of a serialized block.
+        #[arg(long)]
+        maximum_block_size: Option<u64>,
+
         /// Set the maximum read data per block.
         #[arg(long)]
         maximum_bytes_read_per_block: Option<u64>,
@@ -570,6 +758,18 @@ pub enum ClientCommand {
         #[arg(long)]
         maximum_bytes_written_per_block: Option<u64>,
 
+        /// Set the maximum data stored by a single application on a chain.
+        #[arg(long)]
+        maximum_bytes_stored_per_application: Option<u64>,
+
+        /// Set the maximum data stored by all the applications of a chain.
+        #[arg(long)]
+        maximum_bytes_stored_per_chain: Option<u64>,
+
+        /// Set the fuel per block that prices are adjusted towards, or zero for fixed prices.
+        #[arg(long)]
+        target_fuel_per_block: Option<u64>,
+
         /// Force this wallet to generate keys using a PRNG and a given seed. USE FOR
         /// TESTING ONLY.
         #[arg(long)]
@@ -744,14 +944,51 @@ pub enum ClientCommand {
         chain_id: Option<ChainId>,
     },
 
+    /// Write the pending block of a chain that requires an approval threshold to a file, so
+    /// that other owners can approve it.
+    RequestApproval {
+        /// The file to write the block to.
+        path: PathBuf,
+
+        /// The chain with the pending block. If not specified, the wallet's default chain is used.
+        #[arg(long)]
+        chain_id: Option<ChainId>,
+    },
+
+    /// Approve a block written by `request-approval`, as an owner of its chain.
+    ///
+    /// The approval is added to the file, which can then be passed on to the next owner.
+    ApproveBlock {
+        /// The file with the block and the approvals collected so far.
+        path: PathBuf,
+    },
+
+    /// Propose the pending block together with the approvals collected in a file.
+    SubmitApprovals {
+        /// The file with the block and the approvals collected so far.
+        path: PathBuf,
+    },
+
     /// Show the contents of the wallet.
     #[command(subcommand)]
     Wallet(WalletCommand),
 
+    /// Manage the keys kept outside of the wallet.
+    #[command(subcommand)]
+    Signer(SignerCommand),
+
+    /// Build, sign and submit blocks in separate steps, e.g. to sign them offline.
+    #[command(subcommand)]
+    Block(BlockCommand),
+
     /// Manage Linera projects.
     #[command(subcommand)]
     Project(ProjectCommand),
 
+    /// Export or import snapshots of the state of a chain.
+    #[command(subcommand)]
+    Snapshot(SnapshotCommand),
+
     /// Manage a local Linera Network.
     #[command(subcommand)]
     Net(NetCommand),
@@ -849,6 +1086,110 @@ pub enum WalletCommand {
 
     /// Forgets the specified chain, including the associated key pair.
     ForgetChain { chain_id: ChainId },
+
+    /// Encrypt a plain wallet with the passphrase set in `LINERA_WALLET_PASSPHRASE`.
+    Encrypt,
+
+    /// Store an encrypted wallet in plain text.
+    Decrypt,
+
+    /// Re-encrypt the wallet with a new passphrase.
+    ChangePassphrase {
+        /// The new passphrase of the wallet.
+        #[arg(long, env = "LINERA_WALLET_NEW_PASSPHRASE", hide_env_values = true)]
+        new_passphrase: String,
+    },
+}
+
+#[derive(Clone, clap::Subcommand)]
+pub enum SignerCommand {
+    /// Move the keys of the wallet's chains to the keystore, creating it if needed.
+    ImportKeys {
+        /// The chain whose key to move. If omitted, the keys of all the wallet's chains are
+        /// moved.
+        chain_id: Option<ChainId>,
+    },
+
+    /// List the public keys in the keystore.
+    List,
+
+    /// Sign block proposals and approvals with the keys of the keystore on behalf of clients
+    /// using `--external-signer`. Only the current user can connect to the socket.
+    Serve {
+        /// The Unix socket to listen on.
+        #[arg(long)]
+        socket: PathBuf,
+
+        /// Ask for confirmation on the terminal before signing each block.
+        #[arg(long)]
+        confirm: bool,
+    },
+}
+
+#[derive(Clone, clap::Subcommand)]
+pub enum BlockCommand {
+    /// Build an unsigned block with the incoming messages of the chain's inbox and write it to
+    /// a file.
+    Build {
+        /// The file to write the block to.
+        path: PathBuf,
+
+        /// The chain to build the block for. If not specified, the wallet's default chain is
+        /// used.
+        #[arg(long)]
+        chain_id: Option<ChainId>,
+
+        /// The owner who will sign the block. If not specified, the chain must have a single
+        /// owner.
+        #[arg(long)]
+        owner: Option<Owner>,
+
+        /// A JSON file with a list of operations to include in the block.
+        #[arg(long)]
+        operations: Option<PathBuf>,
+    },
+
+    /// Sign a block written by `block build`. This only needs the owner's key, so it can run
+    /// on a machine without network access.
+    Sign {
+        /// The file with the unsigned block.
+        path: PathBuf,
+
+        /// The file to write the signed block proposal to.
+        #[arg(long)]
+        output: PathBuf,
+    },
+
+    /// Submit a block proposal written by `block sign` to the validators and wait for the
+    /// block to be committed.
+    Submit {
+        /// The file with the signed block proposal.
+        path: PathBuf,
+    },
+}
+
+#[derive(Clone, clap::Subcommand)]
+pub enum SnapshotCommand {
+    /// Export the state of a chain from the local storage to a snapshot file.
+    Export {
+        /// The chain to export. If not specified, the wallet's default chain is used.
+        chain_id: Option<ChainId>,
+
+        /// The expected height of the latest block of the chain.
+        #[arg(long)]
+        height: Option<BlockHeight>,
+
+        /// The path of the snapshot file to write.
+        #[arg(long)]
+        output: PathBuf,
+    },
+
+    /// Import a snapshot file into the local storage, after verifying it against the
+    /// committees known by the admin chain.
+    Import {
+        /// The path of the snapshot file to read.
+        input: PathBuf,
+    },
 }
 
 #[derive(Clone, clap::Parser)]
@@ -910,13 +1251,15 @@ pub enum ProjectCommand {
 
 #[derive(Debug, Clone, clap::Args)]
 pub struct ChainOwnershipConfig {
-    /// Public keys of the new super owners.
+    /// Public keys of the new super owners: Ed25519 keys, or compressed secp256k1 keys of
+    /// Ethereum accounts.
     #[arg(long, num_args(0..))]
-    super_owner_public_keys: Vec<PublicKey>,
+    super_owner_public_keys: Vec<AccountPublicKey>,
 
-    /// Public keys of the new regular owners.
+    /// Public keys of the new regular owners: Ed25519 keys, or compressed secp256k1 keys of
+    /// Ethereum accounts.
     #[arg(long, num_args(0..))]
-    owner_public_keys: Vec<PublicKey>,
+    owner_public_keys: Vec<AccountPublicKey>,
 
     /// Weights for the new owners.
     ///
@@ -959,6 +1302,11 @@ pub struct ChainOwnershipConfig {
         value_parser = util::parse_millis_delta
     )]
     pub fallback_duration: TimeDelta,
+
+    /// If set, blocks proposed by regular owners must be approved by owners whose weights add
+    /// up to at least this threshold. The proposer's own weight counts towards it.
+    #[arg(long)]
+    approval_threshold: Option<u64>,
 }
 
 impl TryFrom<ChainOwnershipConfig> for ChainOwnership {
@@ -974,6 +1322,7 @@ impl TryFrom<ChainOwnershipConfig> for ChainOwnership {
             base_timeout,
             timeout_increment,
             fallback_duration,
+            approval_threshold,
         } = config;
         anyhow::ensure!(
             owner_weights.is_empty() || owner_weights.len() == owner_public_keys.len(),
@@ -997,12 +1346,22 @@ impl TryFrom<ChainOwnershipConfig> for ChainOwnership {
             timeout_increment,
             fallback_duration,
         };
-        Ok(ChainOwnership {
+        let ownership = ChainOwnership {
             super_owners,
             owners,
             multi_leader_rounds,
             timeout_config,
-        })
+            approval_threshold,
+        };
+        if let Some(threshold) = approval_threshold {
+            let total_weight = ownership.owners_weight(ownership.owners.keys());
+            anyhow::ensure!(
+                threshold > 0 && threshold <= total_weight,
+                "The approval threshold {threshold} must be positive and at most the owners' \
+                 total weight {total_weight}."
+            );
+        }
+        Ok(ownership)
     }
 }
 
@@ -17,30 +17,31 @@ use linera_base::{
     identifiers::{ChainDescription, ChainId, MessageId, Owner},
     ownership::ChainOwnership,
 };
-use linera_chain::data_types::{CertificateValue, ExecutedBlock};
+use linera_chain::data_types::{BlockAndRound, BlockProposal, CertificateValue, ExecutedBlock};
 use linera_core::{
     client::ChainClientError,
     data_types::{ChainInfoQuery, ClientOutcome},
     local_node::LocalNodeClient,
     node::LocalValidatorNodeProvider,
+    signer::{InMemorySigner, Signer},
     worker::{Reason, WorkerState},
 };
 use linera_execution::{
-    committee::{Committee, ValidatorName, ValidatorState},
-    system::{SystemChannel, UserData},
-    Message, ResourceControlPolicy, SystemMessage,
+    committee::{Committee, Epoch, SignedFuelUsage, ValidatorName, ValidatorState},
+    system::{MessageGrantPolicy, SystemChannel, UserData},
+    Message, Operation, ResourceControlPolicy, SystemMessage,
 };
 use linera_service::{
     chain_listener::ClientContext as _,
     cli_wrappers,
-    config::{CommitteeConfig, Export, GenesisConfig, Import, WalletState},
+    config::{ApprovalRequest, CommitteeConfig, Export, GenesisConfig, Import, WalletState},
     faucet::FaucetService,
     node_service::NodeService,
     project::{self, Project},
     storage::Runnable,
     wallet::UserChain,
 };
-use linera_storage::Storage;
+use linera_storage::{ChainSnapshot, Storage};
 use linera_views::views::ViewError;
 use serde_json::Value;
 use tracing::{debug, info, warn};
@@ -58,7 +59,10 @@ use {
     tracing::error,
 };
 
-use crate::client_options::{ClientCommand, NetCommand, ProjectCommand, WalletCommand};
+use crate::client_options::{
+    BlockCommand, ClientCommand, NetCommand, ProjectCommand, SignerCommand, SnapshotCommand,
+    WalletCommand,
+};
 
 #[cfg(feature = "benchmark")]
 fn deserialize_response(response: RpcMessage) -> Option<ChainInfoResponse> {
@@ -100,7 +104,8 @@ impl Runnable for Job {
         ViewError: From<S::ContextError>,
     {
         let Job(options, wallet) = self;
-        let mut context = ClientContext::new(&options, wallet);
+        let signer = options.signer()?;
+        let mut context = ClientContext::new(&options, wallet, signer);
         let command = options.command;
 
         use ClientCommand::*;
@@ -260,6 +265,35 @@ impl Runnable for Job {
                 debug!("{:?}", certificate);
             }
 
+            ChangeMessageGrantPolicy {
+                chain_id,
+                minimum_grant,
+                trusted_senders,
+            } => {
+                let chain_id = chain_id.unwrap_or_else(|| context.default_chain());
+                let chain_client = context.make_chain_client(storage, chain_id).into_arc();
+                info!("Changing the message grant policy of chain {}", chain_id);
+                let time_start = Instant::now();
+                let message_grant_policy = MessageGrantPolicy {
+                    minimum_grant,
+                    trusted_senders,
+                };
+                let certificate = context
+                    .apply_client_command(&chain_client, |mut chain_client| {
+                        let message_grant_policy = message_grant_policy.clone();
+                        async move {
+                            chain_client
+                                .change_message_grant_policy(message_grant_policy)
+                                .await
+                        }
+                    })
+                    .await
+                    .context("Failed to change the message grant policy")?;
+                let time_total = time_start.elapsed();
+                info!("Operation confirmed after {} ms", time_total.as_millis());
+                debug!("{:?}", certificate);
+            }
+
             CloseChain { chain_id } => {
                 let chain_client = context.make_chain_client(storage, chain_id).into_arc();
                 info!("Closing chain {}", chain_id);
@@ -454,10 +488,31 @@ impl Runnable for Job {
 
             command @ (SetValidator { .. }
             | RemoveValidator { .. }
-            | ResourceControlPolicy { .. }) => {
+            | ResourceControlPolicy { .. }
+            | SetSignatureScheme { .. }
+            | AdjustFees { .. }) => {
                 info!("Starting operations to change validator set");
                 let time_start = Instant::now();
 
+                if let SetValidator {
+                    bls_public_key: Some(public_key),
+                    bls_proof_of_possession: Some(proof),
+                    ..
+                } = &command
+                {
+                    public_key
+                        .check_proof_of_possession(proof)
+                        .context("Invalid proof of possession of the BLS public key")?;
+                }
+                let fuel_usage_reports = match &command {
+                    AdjustFees { reports } => reports
+                        .iter()
+                        .map(|path| SignedFuelUsage::read(path))
+                        .collect::<Result<Vec<_>, _>>()
+                        .context("Failed to read the fuel usage reports")?,
+                    _ => Vec::new(),
+                };
+
                 // Make sure genesis chains are subscribed to the admin chain.
                 let context = Arc::new(Mutex::new(context));
                 let mut context = context.lock().await;
@@ -475,22 +530,28 @@ impl Runnable for Job {
                 let maybe_certificate = context
                     .apply_client_command(&chain_client, |mut chain_client| {
                         let command = command.clone();
+                        let fuel_usage_reports = fuel_usage_reports.clone();
                         async move {
                             // Create the new committee.
                             let mut committee = chain_client.local_committee().await.unwrap();
                             let mut policy = committee.policy().clone();
                             let mut validators = committee.validators().clone();
+                            let mut signature_scheme = committee.signature_scheme();
                             match command {
                                 SetValidator {
                                     name,
                                     address,
                                     votes,
+                                    bls_public_key,
+                                    bls_proof_of_possession,
                                 } => {
                                     validators.insert(
                                         name,
                                         ValidatorState {
                                             network_address: address,
                                             votes,
+                                            bls_public_key,
+                                            bls_proof_of_possession,
                                         },
                                     );
                                 }
@@ -512,8 +573,17 @@ impl Runnable for Job {
                                     operation_byte,
                                     message,
                                     message_byte,
+                                    maximum_fuel_per_block,
+                                    maximum_operations_per_block,
+                                    maximum_incoming_messages_per_block,
+                                    maximum_outgoing_messages_per_block,
+                                    maximum_operation_bytes_per_block,
+                                    maximum_block_size,
                                     maximum_bytes_read_per_block,
                                     maximum_bytes_written_per_block,
+                                    maximum_bytes_stored_per_application,
+                                    maximum_bytes_stored_per_chain,
+                                    target_fuel_per_block,
                                 } => {
                                     if let Some(block) = block {
                                         policy.block = block;
@@ -548,6 +618,36 @@ impl Runnable for Job {
                                     if let Some(message_byte) = message_byte {
                                         policy.message_byte = message_byte;
                                     }
+                                    if let Some(maximum_fuel_per_block) = maximum_fuel_per_block {
+                                        policy.maximum_fuel_per_block = maximum_fuel_per_block;
+                                    }
+                                    if let Some(maximum_operations_per_block) =
+                                        maximum_operations_per_block
+                                    {
+                                        policy.maximum_operations_per_block =
+                                            maximum_operations_per_block;
+                                    }
+                                    if let Some(maximum_incoming_messages_per_block) =
+                                        maximum_incoming_messages_per_block
+                                    {
+                                        policy.maximum_incoming_messages_per_block =
+                                            maximum_incoming_messages_per_block;
+                                    }
+                                    if let Some(maximum_outgoing_messages_per_block) =
+                                        maximum_outgoing_messages_per_block
+                                    {
+                                        policy.maximum_outgoing_messages_per_block =
+                                            maximum_outgoing_messages_per_block;
+                                    }
+                                    if let Some(maximum_operation_bytes_per_block) =
+                                        maximum_operation_bytes_per_block
+                                    {
+                                        policy.maximum_operation_bytes_per_block =
+                                            maximum_operation_bytes_per_block;
+                                    }
+                                    if let Some(maximum_block_size) = maximum_block_size {
+                                        policy.maximum_block_size = maximum_block_size;
+                                    }
                                     if let Some(maximum_bytes_read_per_block) =
                                         maximum_bytes_read_per_block
                                     {
@@ -560,6 +660,21 @@ impl Runnable for Job {
                                         policy.maximum_bytes_written_per_block =
                                             maximum_bytes_written_per_block;
                                     }
+                                    if let Some(maximum_bytes_stored_per_application) =
+                                        maximum_bytes_stored_per_application
+                                    {
+                                        policy.maximum_bytes_stored_per_application =
+                                            maximum_bytes_stored_per_application;
+                                    }
+                                    if let Some(maximum_bytes_stored_per_chain) =
+                                        maximum_bytes_stored_per_chain
+                                    {
+                                        policy.maximum_bytes_stored_per_chain =
+                                            maximum_bytes_stored_per_chain;
+                                    }
+                                    if let Some(target_fuel_per_block) = target_fuel_per_block {
+                                        policy.target_fuel_per_block = target_fuel_per_block;
+                                    }
                                     info!(
                                         "ResourceControlPolicy:\n\
                             {:.2} base cost per block\n\
@@ -573,8 +688,17 @@ impl Runnable for Job {
                             {:.2} per byte in the argument of an operation\n\
                             {:.2} per outgoing messages\n\
                             {:.2} per byte in the argument of an outgoing messages\n\
+                            {:.2} maximum fuel per block\n\
+                            {:.2} maximum number of operations per block\n\
+                            {:.2} maximum number of incoming messages per block\n\
+                            {:.2} maximum number of outgoing messages per block\n\
+                            {:.2} maximum number of operation bytes per block\n\
+                            {:.2} maximum block size\n\
                             {:.2} maximum number bytes read per block\n\
-                            {:.2} maximum number bytes written per block",
+                            {:.2} maximum number bytes written per block\n\
+                            {:.2} maximum number bytes stored per application\n\
+                            {:.2} maximum number bytes stored per chain\n\
+                            {:.2} target fuel per block",
                                         policy.block,
                                         policy.fuel_unit,
                                         policy.read_operation,
@@ -586,8 +710,17 @@ impl Runnable for Job {
                                         policy.operation_byte,
                                         policy.message,
                                         policy.message_byte,
+                                        policy.maximum_fuel_per_block,
+                                        policy.maximum_operations_per_block,
+                                        policy.maximum_incoming_messages_per_block,
+                                        policy.maximum_outgoing_messages_per_block,
+                                        policy.maximum_operation_bytes_per_block,
+                                        policy.maximum_block_size,
                                         policy.maximum_bytes_read_per_block,
-                                        policy.maximum_bytes_written_per_block
+                                        policy.maximum_bytes_written_per_block,
+                                        policy.maximum_bytes_stored_per_application,
+                                        policy.maximum_bytes_stored_per_chain,
+                                        policy.target_fuel_per_block
                                     );
                                     if block.is_none()
                                         && fuel_unit.is_none()
@@ -600,15 +733,34 @@ impl Runnable for Job {
                                         && operation_byte.is_none()
                                         && message.is_none()
                                         && message_byte.is_none()
+                                        && maximum_fuel_per_block.is_none()
+                                        && maximum_operations_per_block.is_none()
+                                        && maximum_incoming_messages_per_block.is_none()
+                                        && maximum_outgoing_messages_per_block.is_none()
+                                        && maximum_operation_bytes_per_block.is_none()
+                                        && maximum_block_size.is_none()
                                         && maximum_bytes_read_per_block.is_none()
                                         && maximum_bytes_written_per_block.is_none()
+                                        && maximum_bytes_stored_per_application.is_none()
+                                        && maximum_bytes_stored_per_chain.is_none()
+                                        && target_fuel_per_block.is_none()
                                     {
                                         return Ok(ClientOutcome::Committed(None));
                                     }
                                 }
+                                SetSignatureScheme { scheme } => {
+                                    signature_scheme = scheme;
+                                }
+                                AdjustFees { .. } => {
+                                    return chain_client
+                                        .adjust_fees(fuel_usage_reports)
+                                        .await
+                                        .map(|outcome| outcome.map(Some));
+                                }
                                 _ => unreachable!(),
                             }
-                            committee = Committee::new(validators, policy);
+                            committee = Committee::new(validators, policy)
+                                .with_signature_scheme(signature_scheme);
                             chain_client
                                 .stage_new_committee(committee)
                                 .await
@@ -700,7 +852,7 @@ impl Runnable for Job {
                 info!("Received {} valid votes.", votes.len());
 
                 info!("Starting benchmark phase 2 (certified blocks)");
-                let certificates = context.make_benchmark_certificates_from_votes(votes);
+                let certificates = context.make_benchmark_certificates_from_votes(votes)?;
                 assert_eq!(
                     num_proposal,
                     certificates.len(),
@@ -1027,6 +1179,128 @@ impl Runnable for Job {
                 context.update_and_save_wallet(&mut chain_client).await;
             }
 
+            RequestApproval { path, chain_id } => {
+                let chain_id = chain_id.unwrap_or_else(|| context.default_chain());
+                let chain_client = context.make_chain_client(storage, chain_id);
+                let block = chain_client
+                    .pending_block()
+                    .clone()
+                    .with_context(|| format!("Chain {chain_id} has no pending block"))?;
+                let request = ApprovalRequest {
+                    block,
+                    approvals: Default::default(),
+                };
+                request.write(&path)?;
+                info!(
+                    "Pending block of chain {} written to {}. Pass it on to the other owners.",
+                    chain_id,
+                    path.display()
+                );
+            }
+
+            ApproveBlock { path } => {
+                let mut request = ApprovalRequest::read(&path)?;
+                let chain_id = request.block.chain_id;
+                info!(
+                    "Approving block {} of chain {} with {} incoming messages and operations {:#?}",
+                    request.block.height,
+                    chain_id,
+                    request.block.incoming_messages.len(),
+                    request.block.operations
+                );
+                let mut chain_client = context.make_chain_client(storage, chain_id);
+                chain_client.synchronize_from_validators().await?;
+                let (owner, signature) = chain_client.approve_block(&request.block).await?;
+                request.approvals.insert(owner, signature);
+                request.write(&path)?;
+                info!("Approval by {} added to {}", owner, path.display());
+            }
+
+            SubmitApprovals { path } => {
+                let request = ApprovalRequest::read(&path)?;
+                let chain_id = request.block.chain_id;
+                let mut chain_client = context.make_chain_client(storage, chain_id);
+                ensure!(
+                    chain_client.pending_block().as_ref() == Some(&request.block),
+                    "The block in {} is not the pending block of chain {}",
+                    path.display(),
+                    chain_id
+                );
+                for (owner, signature) in request.approvals {
+                    chain_client.add_approval(owner, signature).await?;
+                }
+                let chain_client = chain_client.into_arc();
+                let certificate = context
+                    .apply_client_command(&chain_client, |mut chain_client| async move {
+                        chain_client.process_pending_block().await
+                    })
+                    .await
+                    .context("Failed to submit the approved block")?;
+                match certificate {
+                    Some(certificate) => {
+                        info!("Approved block committed successfully.");
+                        println!("{}", certificate.hash());
+                    }
+                    None => info!("No block is currently pending."),
+                }
+            }
+
+            Block(BlockCommand::Build {
+                path,
+                chain_id,
+                owner,
+                operations,
+            }) => {
+                let chain_id = chain_id.unwrap_or_else(|| context.default_chain());
+                let operations: Vec<Operation> = match &operations {
+                    Some(operations_path) => {
+                        serde_json::from_slice(&fs_err::read(operations_path)?).with_context(
+                            || format!("Invalid operations in {}", operations_path.display()),
+                        )?
+                    }
+                    None => Vec::new(),
+                };
+                let mut chain_client = context.make_chain_client(storage, chain_id);
+                chain_client.synchronize_from_validators().await?;
+                let owner = match owner {
+                    Some(owner) => owner,
+                    None => {
+                        let ownership = chain_client
+                            .chain_info_with_manager_values()
+                            .await?
+                            .manager
+                            .ownership;
+                        let owners = ownership.all_owners().copied().collect::<Vec<_>>();
+                        ensure!(
+                            owners.len() == 1,
+                            "Chain {chain_id} has {} owners; please specify one with --owner",
+                            owners.len()
+                        );
+                        owners[0]
+                    }
+                };
+                let content = chain_client.build_block(operations, owner).await?;
+                content.write(&path)?;
+                info!(
+                    "Block {} of chain {} with {} incoming messages written to {}",
+                    content.block.height,
+                    chain_id,
+                    content.block.incoming_messages.len(),
+                    path.display()
+                );
+            }
+
+            Block(BlockCommand::Submit { path }) => {
+                let proposal = BlockProposal::read(&path)?;
+                let chain_id = proposal.content.block.chain_id;
+                let mut chain_client = context.make_chain_client(storage, chain_id);
+                let certificate = chain_client.submit_signed_proposal(proposal).await;
+                context.update_and_save_wallet(&mut chain_client).await;
+                let certificate = certificate.context("Failed to submit the block proposal")?;
+                info!("Block committed successfully.");
+                println!("{}", certificate.hash());
+            }
+
             Wallet(WalletCommand::Init {
                 faucet: Some(faucet_url),
                 with_new_chain: true,
@@ -1065,7 +1339,71 @@ impl Runnable for Job {
                 context.save_wallet();
             }
 
-            CreateGenesisConfig { .. } | Keygen | Net(_) | Wallet(_) | HelpMarkdown => {
+            Snapshot(SnapshotCommand::Export {
+                chain_id,
+                height,
+                output,
+            }) => {
+                let chain_id = chain_id.unwrap_or_else(|| context.default_chain());
+                info!("Exporting a snapshot of chain {}", chain_id);
+                let snapshot = ChainSnapshot::export(&storage, chain_id, height).await?;
+                fs_err::write(&output, snapshot.to_bytes()?)?;
+                info!(
+                    "Exported chain {} at height {} to {}",
+                    chain_id,
+                    snapshot.height(),
+                    output.display()
+                );
+            }
+
+            Snapshot(SnapshotCommand::Import { input }) => {
+                let snapshot = ChainSnapshot::from_bytes(&fs_err::read(&input)?)?;
+                let chain_id = snapshot.chain_id();
+                info!(
+                    "Importing chain {} at height {}",
+                    chain_id,
+                    snapshot.height()
+                );
+                let admin_id = context.wallet().genesis_admin_chain();
+                let genesis_committee = context.wallet().genesis_config().create_committee()?;
+                let mut committees = storage
+                    .load_chain(admin_id)
+                    .await?
+                    .execution_state
+                    .system
+                    .committees
+                    .get()
+                    .clone();
+                committees.entry(Epoch::ZERO).or_insert(genesis_committee);
+                snapshot.import(&storage, &committees).await?;
+                let chain = storage.load_chain(chain_id).await?;
+                let timestamp = *chain.execution_state.system.timestamp.get();
+                if context.wallet().get(chain_id).is_none() {
+                    context
+                        .wallet_mut()
+                        .insert(UserChain::make_other(chain_id, timestamp));
+                }
+                if let Some(user_chain) = context
+                    .wallet_mut()
+                    .chains_mut()
+                    .find(|user_chain| user_chain.chain_id == chain_id)
+                {
+                    let tip = chain.tip_state.get();
+                    user_chain.block_hash = tip.block_hash;
+                    user_chain.next_block_height = tip.next_block_height;
+                    user_chain.timestamp = timestamp;
+                }
+                context.save_wallet();
+                println!("{}", chain_id);
+            }
+
+            CreateGenesisConfig { .. }
+            | Keygen
+            | Net(_)
+            | Wallet(_)
+            | Signer(_)
+            | Block(BlockCommand::Sign { .. })
+            | HelpMarkdown => {
                 unreachable!()
             }
         }
@@ -1134,7 +1472,7 @@ impl Job {
             );
         };
         anyhow::ensure!(
-            config.ownership.verify_owner(&Owner::from(public_key)) == Some(public_key),
+            config.ownership.verify_owner(&Owner::from(public_key)) == Some(public_key.into()),
             "The chain with the ID returned by the faucet is not owned by you. \
             Please make sure you are connecting to a genuine faucet."
         );
@@ -1260,13 +1598,31 @@ async fn run(options: ClientOptions) -> anyhow::Result<()> {
             operation_byte_price,
             message_price,
             message_byte_price,
+            maximum_fuel_per_block,
+            maximum_operations_per_block,
+            maximum_incoming_messages_per_block,
+            maximum_outgoing_messages_per_block,
+            maximum_operation_bytes_per_block,
+            maximum_block_size,
             maximum_bytes_read_per_block,
             maximum_bytes_written_per_block,
+            maximum_bytes_stored_per_application,
+            maximum_bytes_stored_per_chain,
+            target_fuel_per_block,
             testing_prng_seed,
             network_name,
         } => {
             let committee_config = CommitteeConfig::read(committee_config_path)
                 .expect("Unable to read committee config file");
+            let maximum_fuel_per_block = maximum_fuel_per_block.unwrap_or(u64::MAX);
+            let maximum_operations_per_block = maximum_operations_per_block.unwrap_or(u64::MAX);
+            let maximum_incoming_messages_per_block =
+                maximum_incoming_messages_per_block.unwrap_or(u64::MAX);
+            let maximum_outgoing_messages_per_block =
+                maximum_outgoing_messages_per_block.unwrap_or(u64::MAX);
+            let maximum_operation_bytes_per_block =
+                maximum_operation_bytes_per_block.unwrap_or(u64::MAX);
+            let maximum_block_size = maximum_block_size.unwrap_or(u64::MAX);
             let maximum_bytes_read_per_block = match *maximum_bytes_read_per_block {
                 Some(value) => value,
                 None => u64::MAX,
@@ -1275,6 +1631,14 @@ async fn run(options: ClientOptions) -> anyhow::Result<()> {
                 Some(value) => value,
                 None => u64::MAX,
             };
+            let maximum_bytes_stored_per_application = match *maximum_bytes_stored_per_application {
+                Some(value) => value,
+                None => u64::MAX,
+            };
+            let maximum_bytes_stored_per_chain = match *maximum_bytes_stored_per_chain {
+                Some(value) => value,
+                None => u64::MAX,
+            };
             let policy = ResourceControlPolicy {
                 block: *block_price,
                 fuel_unit: *fuel_unit_price,
@@ -1287,8 +1651,17 @@ async fn run(options: ClientOptions) -> anyhow::Result<()> {
                 operation: *operation_price,
                 message_byte: *message_byte_price,
                 message: *message_price,
+                maximum_fuel_per_block,
+                maximum_operations_per_block,
+                maximum_incoming_messages_per_block,
+                maximum_outgoing_messages_per_block,
+                maximum_operation_bytes_per_block,
+                maximum_block_size,
                 maximum_bytes_read_per_block,
                 maximum_bytes_written_per_block,
+                maximum_bytes_stored_per_application,
+                maximum_bytes_stored_per_chain,
+                target_fuel_per_block: target_fuel_per_block.unwrap_or(0),
             };
             let timestamp = start_timestamp
                 .map(|st| {
@@ -1304,6 +1677,9 @@ async fn run(options: ClientOptions) -> anyhow::Result<()> {
             });
             let mut genesis_config =
                 GenesisConfig::new(committee_config, admin_id, timestamp, policy, network_name);
+            genesis_config
+                .create_committee()
+                .context("Invalid committee configuration")?;
             let mut rng = Box::<dyn CryptoRng>::from(*testing_prng_seed);
             let mut chains = vec![];
             for i in 0..*num_other_initial_chains {
@@ -1432,6 +1808,37 @@ async fn run(options: ClientOptions) -> anyhow::Result<()> {
                 Ok(())
             }
 
+            WalletCommand::Encrypt => {
+                let passphrase = options
+                    .wallet_passphrase
+                    .clone()
+                    .context("The new passphrase must be set in LINERA_WALLET_PASSPHRASE")?;
+                let mut wallet = options.wallet()?;
+                ensure!(!wallet.is_encrypted(), "The wallet is already encrypted");
+                wallet.set_passphrase(Some(passphrase));
+                wallet.save()?;
+                info!("Encrypted the wallet");
+                Ok(())
+            }
+
+            WalletCommand::Decrypt => {
+                let mut wallet = options.wallet()?;
+                ensure!(wallet.is_encrypted(), "The wallet is not encrypted");
+                wallet.set_passphrase(None);
+                wallet.save()?;
+                info!("Decrypted the wallet");
+                Ok(())
+            }
+
+            WalletCommand::ChangePassphrase { new_passphrase } => {
+                let mut wallet = options.wallet()?;
+                ensure!(wallet.is_encrypted(), "The wallet is not encrypted");
+                wallet.set_passphrase(Some(new_passphrase.clone()));
+                wallet.save()?;
+                info!("Changed the passphrase of the wallet");
+                Ok(())
+            }
+
             WalletCommand::Init {
                 genesis_config_path,
                 faucet,
@@ -1488,6 +1895,79 @@ Make sure to use a Linera client compatible with this network.
             }
         },
 
+        ClientCommand::Signer(signer_command) => match signer_command {
+            SignerCommand::ImportKeys { chain_id } => {
+                let mut wallet = options.wallet()?;
+                let mut keystore = options.keystore_or_create()?;
+                let chain_ids = match chain_id {
+                    Some(chain_id) => vec![*chain_id],
+                    None => wallet.inner().own_chain_ids(),
+                };
+                let mut key_pairs = Vec::new();
+                for chain_id in chain_ids {
+                    key_pairs.push(wallet.inner_mut().forget_keys(&chain_id)?);
+                }
+                let count = key_pairs.len();
+                for key_pair in key_pairs {
+                    keystore.insert(key_pair);
+                }
+                // Write the keystore first, so that no key is lost if saving the wallet fails.
+                keystore.write()?;
+                wallet.save()?;
+                info!("Moved {count} keys to the keystore");
+                Ok(())
+            }
+
+            SignerCommand::List => {
+                for public_key in options.keystore()?.public_keys() {
+                    println!("{public_key}");
+                }
+                Ok(())
+            }
+
+            #[cfg(unix)]
+            SignerCommand::Serve { socket, confirm } => {
+                let signer = Arc::new(options.keystore()?.into_signer());
+                info!(
+                    "Signing with the keys of the keystore on {}",
+                    socket.display()
+                );
+                linera_service::signer::serve_signer(socket, signer, *confirm).await
+            }
+
+            #[cfg(not(unix))]
+            SignerCommand::Serve { .. } => bail!("External signers are only supported on Unix"),
+        },
+
+        ClientCommand::Block(BlockCommand::Sign { path, output }) => {
+            let content = BlockAndRound::read(path)?;
+            let owner = content
+                .block
+                .authenticated_signer
+                .context("The block has no authenticated signer")?;
+            let signer: Arc<dyn Signer> = match options.signer()? {
+                Some(signer) => signer,
+                None => Arc::new(InMemorySigner::new(options.wallet()?.inner().key_pairs())),
+            };
+            let public_key = signer
+                .public_key(&owner)
+                .await?
+                .with_context(|| format!("No key found for owner {owner}"))?;
+            let message = BlockProposal::signing_message(&content, None);
+            let signature = signer.sign(&owner, &message).await?;
+            let proposal = BlockProposal::new_with_signature(
+                content,
+                public_key,
+                signature,
+                vec![],
+                vec![],
+                None,
+            );
+            proposal.write(output)?;
+            info!("Signed block proposal written to {}", output.display());
+            Ok(())
+        }
+
         _ => options.run_command_with_storage().await,
     }
 }
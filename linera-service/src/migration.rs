@@ -0,0 +1,346 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Migration of the content of a key-value store to another one, possibly using a
+//! different backend.
+
+use std::{
+    ops::Bound::{Excluded, Unbounded},
+    path::PathBuf,
+};
+
+use anyhow::{bail, ensure, Context as _};
+use async_trait::async_trait;
+use linera_views::{
+    batch::Batch,
+    common::{KeyRange, KeyValueIterable, KeyValueStore},
+    views::ViewError,
+};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest as _, Sha3_256};
+use tracing::{debug, info};
+
+use crate::storage::{run_with_store, RunnableWithStore, StoreConfig};
+
+#[cfg(test)]
+#[path = "unit_tests/migration.rs"]
+mod tests;
+
+/// The options of a migration between two key-value stores.
+#[derive(Debug, Clone, clap::Args)]
+pub struct MigrationConfig {
+    /// The number of key-value pairs written to the destination in each batch.
+    #[arg(long, default_value = "1000")]
+    pub batch_size: usize,
+
+    /// A file recording the progress of the migration, used to resume it if interrupted.
+    #[arg(long = "checkpoint")]
+    pub checkpoint_path: Option<PathBuf>,
+
+    /// Skip the final pass comparing the content of both stores.
+    #[arg(long)]
+    pub skip_verification: bool,
+}
+
+impl Default for MigrationConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 1000,
+            checkpoint_path: None,
+            skip_verification: false,
+        }
+    }
+}
+
+/// The source and destination of a migration, e.g. as given on the command line.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationStores {
+    pub source: String,
+    pub destination: String,
+}
+
+/// The progress of a migration, as recorded in the checkpoint file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Checkpoint {
+    /// The stores of the migration. A checkpoint is only used for the same stores.
+    #[serde(default)]
+    stores: MigrationStores,
+    /// The last key written to the destination. Keys are copied in increasing order.
+    last_key: Option<Vec<u8>>,
+}
+
+impl Checkpoint {
+    fn read(path: Option<&PathBuf>, stores: &MigrationStores) -> Result<Self, anyhow::Error> {
+        match path {
+            Some(path) if path.exists() => {
+                let bytes = fs_err::read(path)?;
+                let checkpoint = serde_json::from_slice::<Self>(&bytes)
+                    .context("Failed to read the checkpoint file")?;
+                ensure!(
+                    checkpoint.stores == *stores,
+                    "The checkpoint file {} records a migration from {:?} to {:?}",
+                    path.display(),
+                    checkpoint.stores.source,
+                    checkpoint.stores.destination
+                );
+                Ok(checkpoint)
+            }
+            _ => Ok(Self {
+                stores: stores.clone(),
+                last_key: None,
+            }),
+        }
+    }
+
+    fn write(&self, path: Option<&PathBuf>) -> Result<(), anyhow::Error> {
+        if let Some(path) = path {
+            fs_err::write(path, serde_json::to_vec(self)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// What was copied by a migration.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MigrationSummary {
+    /// The number of key-value pairs copied by this run.
+    pub copied_keys: u64,
+    /// The total size of the keys and values copied by this run.
+    pub copied_bytes: u64,
+    /// The last key copied by a previous run, if this run resumed it.
+    pub resumed_after: Option<Vec<u8>>,
+    /// The number of key-value pairs found identical in both stores, if they were compared.
+    pub verified_keys: Option<u64>,
+}
+
+/// The number of key-value pairs with a given first byte, and a hash of their content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixDigest {
+    pub count: u64,
+    pub hash: Vec<u8>,
+}
+
+/// Reads at most `limit` key-value pairs of `store` whose key starts with `first_byte`, in
+/// increasing order of keys, starting after the key with the given suffix if any.
+async fn read_page<S>(
+    store: &S,
+    first_byte: u8,
+    after: Option<&[u8]>,
+    limit: usize,
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ViewError>
+where
+    S: KeyValueStore + Sync,
+    ViewError: From<S::Error>,
+{
+    let prefix = [first_byte];
+    let start = match after {
+        Some(suffix) => Excluded(suffix.to_vec()),
+        None => Unbounded,
+    };
+    let range = KeyRange::new(start, Unbounded).with_limit(limit);
+    let key_values = store.find_key_values_in_range(&prefix, &range).await?;
+    let mut page = Vec::new();
+    for key_value in key_values.into_iterator_owned() {
+        let (suffix, value) = key_value?;
+        page.push(([&prefix[..], &suffix].concat(), value));
+    }
+    Ok(page)
+}
+
+/// Copies every key-value pair of `source` into `destination`, in increasing order of
+/// keys. Progress is recorded in the checkpoint file, if any, after each batch, so that an
+/// interrupted migration resumes after the last key written. The checkpoint file can only
+/// be used again for the same `stores`. Keys must not be empty.
+pub async fn copy_store<S, D>(
+    source: &S,
+    destination: &D,
+    stores: &MigrationStores,
+    config: &MigrationConfig,
+) -> Result<MigrationSummary, anyhow::Error>
+where
+    S: KeyValueStore + Sync,
+    D: KeyValueStore + Sync,
+    ViewError: From<S::Error> + From<D::Error>,
+{
+    ensure!(config.batch_size > 0, "The batch size must not be zero");
+    let checkpoint_path = config.checkpoint_path.as_ref();
+    let mut checkpoint = Checkpoint::read(checkpoint_path, stores)?;
+    if let Some(last_key) = &checkpoint.last_key {
+        info!("Resuming the migration after key {}", hex::encode(last_key));
+    }
+    let mut summary = MigrationSummary {
+        resumed_after: checkpoint.last_key.clone(),
+        ..MigrationSummary::default()
+    };
+    let first_prefix = match &checkpoint.last_key {
+        Some(last_key) => last_key.first().copied().unwrap_or_default(),
+        None => 0,
+    };
+    // Keys are read by pages of one batch, so that the source is never loaded in memory.
+    for first_byte in first_prefix..=u8::MAX {
+        let mut after = checkpoint
+            .last_key
+            .as_ref()
+            .filter(|last_key| last_key.first() == Some(&first_byte))
+            .map(|last_key| last_key[1..].to_vec());
+        loop {
+            let key_values =
+                read_page(source, first_byte, after.as_deref(), config.batch_size).await?;
+            let Some((last_key, _)) = key_values.last() else {
+                break;
+            };
+            let is_last_page = key_values.len() < config.batch_size;
+            after = Some(last_key[1..].to_vec());
+            checkpoint.last_key = Some(last_key.clone());
+            let mut batch = Batch::new();
+            for (key, value) in key_values {
+                summary.copied_keys += 1;
+                summary.copied_bytes += (key.len() + value.len()) as u64;
+                batch.put_key_value_bytes(key, value);
+            }
+            destination
+                .write_batch(batch, &[])
+                .await
+                .map_err(ViewError::from)?;
+            checkpoint.write(checkpoint_path)?;
+            debug!("Copied {} keys", summary.copied_keys);
+            if is_last_page {
+                break;
+            }
+        }
+        info!(
+            "Copied the keys starting with {:#04x}: {} keys and {} bytes so far",
+            first_byte, summary.copied_keys, summary.copied_bytes
+        );
+    }
+    Ok(summary)
+}
+
+/// Computes the digest of the key-value pairs of `store` whose key starts with `first_byte`,
+/// reading them by pages of `page_size`.
+pub async fn prefix_digest<S>(
+    store: &S,
+    first_byte: u8,
+    page_size: usize,
+) -> Result<PrefixDigest, ViewError>
+where
+    S: KeyValueStore + Sync,
+    ViewError: From<S::Error>,
+{
+    let mut hasher = Sha3_256::new();
+    let mut count = 0;
+    let mut after = None;
+    loop {
+        let key_values = read_page(store, first_byte, after.as_deref(), page_size).await?;
+        let page_len = key_values.len();
+        for (key, value) in &key_values {
+            // Lengths are included so that different splits of the same bytes are distinguished.
+            hasher.update((key.len() as u64).to_le_bytes());
+            hasher.update(key);
+            hasher.update((value.len() as u64).to_le_bytes());
+            hasher.update(value);
+            count += 1;
+        }
+        match key_values.into_iter().last() {
+            Some((last_key, _)) if page_len == page_size => after = Some(last_key[1..].to_vec()),
+            _ => break,
+        }
+    }
+    Ok(PrefixDigest {
+        count,
+        hash: hasher.finalize().to_vec(),
+    })
+}
+
+/// Checks that both stores have the same key-value pairs, and returns their number.
+pub async fn verify_stores<S, D>(
+    source: &S,
+    destination: &D,
+    page_size: usize,
+) -> Result<u64, anyhow::Error>
+where
+    S: KeyValueStore + Sync,
+    D: KeyValueStore + Sync,
+    ViewError: From<S::Error> + From<D::Error>,
+{
+    let mut total = 0;
+    let mut mismatches = Vec::new();
+    for first_byte in 0..=u8::MAX {
+        let expected = prefix_digest(source, first_byte, page_size).await?;
+        let found = prefix_digest(destination, first_byte, page_size).await?;
+        if expected != found {
+            mismatches.push(format!(
+                "{:#04x} ({} keys in the source, {} in the destination)",
+                first_byte, expected.count, found.count
+            ));
+        }
+        total += expected.count;
+    }
+    if !mismatches.is_empty() {
+        bail!(
+            "The stores differ for the keys starting with {}",
+            mismatches.join(", ")
+        );
+    }
+    Ok(total)
+}
+
+/// A job copying its store to the destination store.
+pub struct MigrationJob {
+    pub destination: StoreConfig,
+    pub stores: MigrationStores,
+    pub config: MigrationConfig,
+}
+
+#[async_trait]
+impl RunnableWithStore for MigrationJob {
+    type Output = MigrationSummary;
+
+    async fn run<S>(self, source: S) -> Result<MigrationSummary, anyhow::Error>
+    where
+        S: KeyValueStore + Clone + Send + Sync + 'static,
+        S::Error: From<bcs::Error>,
+        ViewError: From<S::Error>,
+    {
+        let job = CopyJob {
+            source,
+            stores: self.stores,
+            config: self.config,
+        };
+        run_with_store(self.destination, job).await
+    }
+}
+
+/// A [`MigrationJob`] from the given source, run once the destination store is available.
+struct CopyJob<S> {
+    source: S,
+    stores: MigrationStores,
+    config: MigrationConfig,
+}
+
+#[async_trait]
+impl<S> RunnableWithStore for CopyJob<S>
+where
+    S: KeyValueStore + Clone + Send + Sync + 'static,
+    S::Error: From<bcs::Error>,
+    ViewError: From<S::Error>,
+{
+    type Output = MigrationSummary;
+
+    async fn run<D>(self, destination: D) -> Result<MigrationSummary, anyhow::Error>
+    where
+        D: KeyValueStore + Clone + Send + Sync + 'static,
+        D::Error: From<bcs::Error>,
+        ViewError: From<D::Error>,
+    {
+        let mut summary =
+            copy_store(&self.source, &destination, &self.stores, &self.config).await?;
+        if !self.config.skip_verification {
+            info!("Comparing the content of both stores");
+            let verified_keys =
+                verify_stores(&self.source, &destination, self.config.batch_size).await?;
+            summary.verified_keys = Some(verified_keys);
+        }
+        Ok(summary)
+    }
+}
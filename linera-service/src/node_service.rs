@@ -17,13 +17,16 @@ use futures::{
     Future,
 };
 use linera_base::{
-    crypto::{CryptoError, CryptoHash, PublicKey},
+    crypto::{AccountPublicKey, CryptoError, CryptoHash, PublicKey},
     data_types::{Amount, ApplicationPermissions, Blob, TimeDelta, Timestamp},
-    identifiers::{ApplicationId, BlobId, BytecodeId, ChainId, Owner},
+    identifiers::{ApplicationId, BlobId, BytecodeId, ChainId, EventId, Owner, StreamId},
     ownership::{ChainOwnership, TimeoutConfig},
     BcsHexParseError,
 };
-use linera_chain::{data_types::HashedCertificateValue, ChainStateView};
+use linera_chain::{
+    data_types::{EventRecord, HashedCertificateValue},
+    ChainStateView,
+};
 use linera_core::{
     client::{ArcChainClient, ChainClient, ChainClientError},
     data_types::{ClientOutcome, RoundTimeout},
@@ -31,8 +34,11 @@ use linera_core::{
     worker::{Notification, Reason},
 };
 use linera_execution::{
-    committee::{Committee, Epoch},
-    system::{AdminOperation, Recipient, SystemChannel, UserData},
+    committee::{Committee, Epoch, SignedFuelUsage},
+    system::{
+        AdminOperation, MessageGrantPolicy, Recipient, StandingOrder, StandingOrderOperation,
+        SystemChannel, UserData,
+    },
     Bytecode, Operation, Query, Response, SystemOperation, UserApplicationDescription,
     UserApplicationId,
 };
@@ -50,6 +56,9 @@ use crate::{
     util,
 };
 
+/// The maximum number of events returned by a single `events` query.
+const MAX_EVENTS_PER_QUERY: u32 = 1000;
+
 #[derive(SimpleObject, Serialize, Deserialize, Clone)]
 pub struct Chains {
     pub list: Vec<ChainId>,
@@ -208,6 +217,23 @@ where
         let mut client = self.clients.try_client_lock(&chain_id).await?;
         Ok(client.subscribe().await?)
     }
+
+    /// Subscribes to notifications of new events in the given stream of the specified chain.
+    /// Applications on other chains subscribe to event streams through their contract runtime.
+    async fn events(
+        &self,
+        chain_id: ChainId,
+        stream_id: StreamId,
+    ) -> Result<impl Stream<Item = Notification>, Error> {
+        let mut client = self.clients.try_client_lock(&chain_id).await?;
+        let notifications = client.subscribe().await?;
+        Ok(notifications.filter(move |notification| {
+            matches!(
+                &notification.reason,
+                Reason::NewEvents { event_streams, .. } if event_streams.contains(&stream_id)
+            )
+        }))
+    }
 }
 
 impl<P, S, C> MutationRoot<P, S, C>
@@ -370,6 +396,52 @@ where
         .await
     }
 
+    /// Creates a standing order transferring `amount` from the given owner's account (or
+    /// from the chain balance) every `interval`, until `end_time`. The first transfer is
+    /// made one interval after the current block.
+    async fn create_standing_order(
+        &self,
+        chain_id: ChainId,
+        owner: Option<Owner>,
+        recipient: Recipient,
+        amount: Amount,
+        interval: TimeDelta,
+        end_time: Timestamp,
+    ) -> Result<CryptoHash, Error> {
+        let operation = SystemOperation::StandingOrder(StandingOrderOperation::Create {
+            owner,
+            recipient,
+            amount,
+            interval,
+            end_time,
+        });
+        self.execute_system_operation(operation, chain_id).await
+    }
+
+    /// Changes the amount, interval and end time of a standing order.
+    async fn update_standing_order(
+        &self,
+        chain_id: ChainId,
+        id: u64,
+        amount: Amount,
+        interval: TimeDelta,
+        end_time: Timestamp,
+    ) -> Result<CryptoHash, Error> {
+        let operation = SystemOperation::StandingOrder(StandingOrderOperation::Update {
+            id,
+            amount,
+            interval,
+            end_time,
+        });
+        self.execute_system_operation(operation, chain_id).await
+    }
+
+    /// Cancels a standing order.
+    async fn cancel_standing_order(&self, chain_id: ChainId, id: u64) -> Result<CryptoHash, Error> {
+        let operation = SystemOperation::StandingOrder(StandingOrderOperation::Cancel { id });
+        self.execute_system_operation(operation, chain_id).await
+    }
+
     /// Creates (or activates) a new chain by installing the given authentication key.
     /// This will automatically subscribe to the future committees created by `admin_id`.
     async fn open_chain(
@@ -486,10 +558,11 @@ where
         new_public_key: PublicKey,
     ) -> Result<CryptoHash, Error> {
         let operation = SystemOperation::ChangeOwnership {
-            super_owners: vec![new_public_key],
+            super_owners: vec![new_public_key.into()],
             owners: Vec::new(),
             multi_leader_rounds: 2,
             timeout_config: TimeoutConfig::default(),
+            approval_threshold: None,
         };
         self.execute_system_operation(operation, chain_id).await
     }
@@ -521,10 +594,19 @@ where
             default = 86_400_000
         )]
         fallback_duration_ms: u64,
+        #[graphql(
+            desc = "If set, blocks must be approved by owners whose weights add up to at least \
+                    this threshold"
+        )]
+        approval_threshold: Option<u64>,
     ) -> Result<CryptoHash, Error> {
         let operation = SystemOperation::ChangeOwnership {
             super_owners: Vec::new(),
-            owners: new_public_keys.into_iter().zip(new_weights).collect(),
+            owners: new_public_keys
+                .into_iter()
+                .map(AccountPublicKey::from)
+                .zip(new_weights)
+                .collect(),
             multi_leader_rounds,
             timeout_config: TimeoutConfig {
                 fast_round_duration: fast_round_ms.map(TimeDelta::from_millis),
@@ -532,6 +614,7 @@ where
                 timeout_increment: TimeDelta::from_millis(timeout_increment_ms),
                 fallback_duration: TimeDelta::from_millis(fallback_duration_ms),
             },
+            approval_threshold,
         };
         self.execute_system_operation(operation, chain_id).await
     }
@@ -552,6 +635,21 @@ where
         self.execute_system_operation(operation, chain_id).await
     }
 
+    /// Changes the grant required for incoming user messages on this chain, unless they
+    /// come from one of the trusted senders.
+    async fn change_message_grant_policy(
+        &self,
+        chain_id: ChainId,
+        minimum_grant: Amount,
+        trusted_senders: Vec<ChainId>,
+    ) -> Result<CryptoHash, Error> {
+        let operation = SystemOperation::ChangeMessageGrantPolicy(MessageGrantPolicy {
+            minimum_grant,
+            trusted_senders,
+        });
+        self.execute_system_operation(operation, chain_id).await
+    }
+
     /// (admin chain only) Registers a new committee. This will notify the subscribers of
     /// the admin chain so that they can migrate to the new epoch (by accepting the
     /// notification as an "incoming message" in a next block).
@@ -604,6 +702,19 @@ where
         self.execute_system_operation(operation, chain_id).await
     }
 
+    /// (admin chain only) Registers a new committee with the same validators as the
+    /// current one, and with prices adjusted to the fuel consumption per block reported by
+    /// a quorum of validators.
+    async fn adjust_fees(
+        &self,
+        chain_id: ChainId,
+        epoch: Epoch,
+        reports: Vec<SignedFuelUsage>,
+    ) -> Result<CryptoHash, Error> {
+        let operation = SystemOperation::Admin(AdminOperation::AdjustFees { epoch, reports });
+        self.execute_system_operation(operation, chain_id).await
+    }
+
     /// Publishes a new application bytecode.
     async fn publish_bytecode(
         &self,
@@ -727,6 +838,24 @@ where
         Ok(overviews)
     }
 
+    async fn standing_orders(
+        &self,
+        chain_id: ChainId,
+    ) -> Result<Vec<StandingOrderOverview>, Error> {
+        let client = self.clients.try_client_lock(&chain_id).await?;
+        let view = client.chain_state_view().await?;
+        let mut overviews = Vec::new();
+        view.execution_state
+            .system
+            .standing_orders
+            .for_each_index_value(|id, order| {
+                overviews.push(StandingOrderOverview { id, order });
+                Ok(())
+            })
+            .await?;
+        Ok(overviews)
+    }
+
     async fn chains(&self) -> Result<Chains, Error> {
         Ok(Chains {
             list: self.clients.0.lock().await.keys().cloned().collect(),
@@ -780,6 +909,46 @@ where
         }
     }
 
+    /// Returns the event at the given index in a stream of the specified chain.
+    async fn event(
+        &self,
+        chain_id: ChainId,
+        stream_id: StreamId,
+        index: u32,
+    ) -> Result<Option<EventRecord>, Error> {
+        let client = self.clients.try_client_lock(&chain_id).await?;
+        let event_id = EventId {
+            chain_id,
+            stream_id,
+            index,
+        };
+        match client.storage_client().await.read_event(event_id).await {
+            Ok(event) => Ok(Some(event)),
+            Err(ViewError::NotFound(_)) => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Returns the events of a stream of the specified chain in emission order, starting
+    /// with the event at index `from`. At most `limit` events are returned, and no more than
+    /// 1000.
+    async fn events(
+        &self,
+        chain_id: ChainId,
+        stream_id: StreamId,
+        from: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Vec<EventRecord>, Error> {
+        let client = self.clients.try_client_lock(&chain_id).await?;
+        let from = from.unwrap_or(0);
+        let limit = limit.unwrap_or(100).min(MAX_EVENTS_PER_QUERY);
+        Ok(client
+            .storage_client()
+            .await
+            .read_events_from_stream(chain_id, &stream_id, from, limit)
+            .await?)
+    }
+
     /// Returns the version information on this node service.
     async fn version(&self) -> linera_version::VersionInfo {
         linera_version::VersionInfo::default()
@@ -843,6 +1012,12 @@ impl ApplicationOverview {
     }
 }
 
+#[derive(SimpleObject)]
+pub struct StandingOrderOverview {
+    id: u64,
+    order: StandingOrder,
+}
+
 /// Given a parsed GraphQL query (or `ExecutableDocument`), returns the `OperationType`.
 ///
 /// Errors:
@@ -1122,7 +1297,7 @@ pub async fn wait_for_next_round(stream: &mut NotificationStream, timeout: Round
     let mut stream = stream.filter(|notification| match &notification.reason {
         Reason::NewBlock { height, .. } => *height >= timeout.next_block_height,
         Reason::NewRound { round, .. } => *round > timeout.current_round,
-        Reason::NewIncomingMessage { .. } => false,
+        Reason::NewIncomingMessage { .. } | Reason::NewEvents { .. } => false,
     });
     future::select(
         Box::pin(stream.next()),
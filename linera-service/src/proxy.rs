@@ -6,7 +6,7 @@ use std::{net::SocketAddr, path::PathBuf, time::Duration};
 use anyhow::{bail, Result};
 use async_trait::async_trait;
 use futures::{SinkExt, StreamExt};
-use linera_core::node::NodeError;
+use linera_core::node::{NodeError, MAX_CERTIFICATES_PER_REQUEST};
 use linera_rpc::{
     config::{
         NetworkProtocol, ShardConfig, ValidatorInternalNetworkPreConfig,
@@ -122,6 +122,7 @@ impl Runnable for ProxyContext {
 impl<S> Proxy<S>
 where
     S: Storage + Clone + Send + Sync + 'static,
+    ViewError: From<S::ContextError>,
 {
     /// Constructs and configures the [`Proxy`] given [`ProxyContext`].
     async fn from_context(context: ProxyContext, storage: S) -> Result<Self> {
@@ -184,6 +185,7 @@ where
 impl<S> MessageHandler for SimpleProxy<S>
 where
     S: Storage + Clone + Send + Sync + 'static,
+    ViewError: From<S::ContextError>,
 {
     #[instrument(skip_all, fields(chain_id = ?message.target_chain_id()))]
     async fn handle_message(&mut self, message: RpcMessage) -> Option<RpcMessage> {
@@ -228,6 +230,7 @@ where
 impl<S> SimpleProxy<S>
 where
     S: Storage + Clone + Send + Sync + 'static,
+    ViewError: From<S::ContextError>,
 {
     #[instrument(skip_all, fields(port = self.public_config.port, metrics_port = self.internal_config.metrics_port), err)]
     async fn run(self, shutdown_signal: CancellationToken) -> Result<()> {
@@ -285,6 +288,24 @@ where
                     .into_inner()
                     .into(),
             )),
+            RpcMessage::DownloadCertificate(hash) => {
+                Ok(Some(self.storage.read_certificate(*hash).await?.into()))
+            }
+            RpcMessage::DownloadCertificates(hashes) => {
+                let count = hashes.len() as u64;
+                if count > MAX_CERTIFICATES_PER_REQUEST {
+                    return Ok(Some(NodeError::TooManyCertificatesRequested(count).into()));
+                }
+                Ok(Some(self.storage.read_certificates(*hashes).await?.into()))
+            }
+            RpcMessage::DownloadCertificatesByHeight(request) => {
+                let (chain_id, range) = *request;
+                let certificates = self
+                    .storage
+                    .read_certificates_by_height(chain_id, range.start, Some(range.page_limit()))
+                    .await?;
+                Ok(Some(certificates.into()))
+            }
             _ => Err(anyhow::Error::from(NodeError::UnexpectedMessage)),
         }
     }
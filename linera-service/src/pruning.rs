@@ -0,0 +1,131 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pruning of the certificates that are no longer needed by a validator.
+
+use std::time::Duration;
+
+use anyhow::bail;
+use async_trait::async_trait;
+use linera_base::data_types::{TimeDelta, Timestamp};
+use linera_storage::{Pruner, PruningSummary, RetentionPolicy, Storage};
+use linera_views::{common::CommonStoreConfig, views::ViewError};
+use tracing::{error, info};
+
+use crate::storage::{run_with_existing_storage, Runnable, StorageConfigNamespace};
+
+/// The retention policy and the archive to use when pruning the storage.
+#[derive(Debug, Default, Clone, clap::Args)]
+pub struct PruningConfig {
+    /// Keep this number of most recent blocks of each chain when pruning.
+    #[arg(long = "prune-keep-last-blocks", conflicts_with = "keep_max_age_secs")]
+    pub keep_last_blocks: Option<u64>,
+
+    /// Keep the blocks that are at most this number of seconds old when pruning.
+    #[arg(long = "prune-keep-max-age-secs")]
+    pub keep_max_age_secs: Option<u64>,
+
+    /// Storage configuration where pruned certificates are archived before being deleted.
+    #[arg(long = "prune-archive-storage")]
+    pub archive_storage_config: Option<StorageConfigNamespace>,
+}
+
+impl PruningConfig {
+    /// Returns whether a retention policy was configured.
+    pub fn has_retention_policy(&self) -> bool {
+        self.keep_last_blocks.is_some() || self.keep_max_age_secs.is_some()
+    }
+
+    /// Returns the retention policy to apply at the given time, if one was configured.
+    pub fn retention_policy(&self, now: Timestamp) -> Option<RetentionPolicy> {
+        if let Some(count) = self.keep_last_blocks {
+            return Some(RetentionPolicy::KeepLastBlocks(count));
+        }
+        let max_age = TimeDelta::from_secs(self.keep_max_age_secs?);
+        Some(RetentionPolicy::KeepNewerThan(now.saturating_sub(max_age)))
+    }
+}
+
+/// A job pruning the storage once, or periodically if a period is set.
+pub struct PruningJob {
+    pub config: PruningConfig,
+    pub period: Option<Duration>,
+}
+
+impl PruningJob {
+    async fn run_pruner<S, A>(
+        &self,
+        mut pruner: Pruner<S, A>,
+    ) -> Result<PruningSummary, anyhow::Error>
+    where
+        S: Storage + Clone + Send + Sync + 'static,
+        A: Storage + Clone + Send + Sync + 'static,
+        ViewError: From<S::ContextError>,
+    {
+        loop {
+            let Some(policy) = self.config.retention_policy(Timestamp::now()) else {
+                bail!("A retention policy is required to prune the storage");
+            };
+            let result = pruner.prune_all_chains(policy).await;
+            match &result {
+                Ok(summary) => info!(
+                    "Pruned {} certificates ({} archived, {} retained) from {} chains",
+                    summary.pruned, summary.archived, summary.retained, summary.chains
+                ),
+                Err(error) => error!("Failed to prune the storage: {error}"),
+            }
+            let Some(period) = self.period else {
+                return Ok(result?);
+            };
+            tokio::time::sleep(period).await;
+        }
+    }
+}
+
+#[async_trait]
+impl Runnable for PruningJob {
+    type Output = PruningSummary;
+
+    async fn run<S>(self, storage: S) -> Result<PruningSummary, anyhow::Error>
+    where
+        S: Storage + Clone + Send + Sync + 'static,
+        ViewError: From<S::ContextError>,
+    {
+        let Some(archive_config) = self.config.archive_storage_config.clone() else {
+            return self.run_pruner(Pruner::new(storage)).await;
+        };
+        let common_config = CommonStoreConfig::default();
+        archive_config
+            .add_common_config(common_config.clone())
+            .await?
+            .initialize()
+            .await?;
+        let archive_config = archive_config.add_common_config(common_config).await?;
+        let job = ArchivingJob { job: self, storage };
+        run_with_existing_storage(archive_config, None, job).await
+    }
+}
+
+/// A [`PruningJob`] on the given storage, run once the archive storage is available.
+struct ArchivingJob<S> {
+    job: PruningJob,
+    storage: S,
+}
+
+#[async_trait]
+impl<S> Runnable for ArchivingJob<S>
+where
+    S: Storage + Clone + Send + Sync + 'static,
+    ViewError: From<S::ContextError>,
+{
+    type Output = PruningSummary;
+
+    async fn run<A>(self, archive: A) -> Result<PruningSummary, anyhow::Error>
+    where
+        A: Storage + Clone + Send + Sync + 'static,
+        ViewError: From<A::ContextError>,
+    {
+        let pruner = Pruner::new(self.storage).with_archive(archive);
+        self.job.run_pruner(pruner).await
+    }
+}
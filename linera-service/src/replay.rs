@@ -0,0 +1,120 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Re-execution of a block from a storage or from snapshots, to investigate state hash
+//! mismatches.
+
+use anyhow::{bail, ensure, Context as _};
+use async_trait::async_trait;
+use linera_base::{crypto::CryptoHash, data_types::BlockHeight, identifiers::ChainId};
+use linera_storage::{read_chain_state, replay_block, ChainSnapshot, ReplayReport, Storage};
+use linera_views::views::ViewError;
+use tracing::info;
+
+use crate::storage::Runnable;
+
+#[cfg(test)]
+#[path = "unit_tests/replay.rs"]
+mod tests;
+
+/// A job re-executing the block of a chain at a given height.
+///
+/// The parent state is read from the parent snapshot if provided, and from the storage
+/// otherwise, in which case the chain must not have executed the block yet. The certificate
+/// is read from the storage, by its hash if provided and from the chain's confirmed log
+/// otherwise, and from the successor snapshot if neither has it. The resulting state is
+/// compared with the successor snapshot if provided, with the storage if it is at the
+/// block's height and the parent state came from a snapshot, and with the parent state
+/// otherwise.
+///
+/// A certificate that failed to execute, e.g. because of a state hash mismatch, is
+/// stored but not in the confirmed log: replaying it from the storage alone requires its
+/// hash.
+pub struct ReplayJob {
+    pub chain_id: ChainId,
+    pub height: BlockHeight,
+    pub certificate_hash: Option<CryptoHash>,
+    pub parent_snapshot: Option<ChainSnapshot>,
+    pub successor_snapshot: Option<ChainSnapshot>,
+}
+
+#[async_trait]
+impl Runnable for ReplayJob {
+    type Output = ReplayReport;
+
+    async fn run<S>(self, storage: S) -> Result<ReplayReport, anyhow::Error>
+    where
+        S: Storage + Clone + Send + Sync + 'static,
+        ViewError: From<S::ContextError>,
+    {
+        let (next_block_height, stored_state) = read_chain_state(&storage, self.chain_id).await?;
+        let hash = match self.certificate_hash {
+            Some(hash) => Some(hash),
+            None => {
+                let index = usize::try_from(self.height)?;
+                storage
+                    .load_chain(self.chain_id)
+                    .await?
+                    .confirmed_log
+                    .get(index)
+                    .await?
+            }
+        };
+        let certificate = match (hash, &self.successor_snapshot) {
+            (Some(hash), _) => storage.read_certificate(hash).await?,
+            (None, Some(successor)) => successor.certificate.clone(),
+            (None, None) => bail!(
+                "The certificate of block {} of chain {} is not in the confirmed log and \
+                 neither its hash nor a successor snapshot was provided",
+                self.height,
+                self.chain_id
+            ),
+        };
+        let value = certificate.value();
+        ensure!(
+            value.chain_id() == self.chain_id && value.height() == self.height,
+            "The certificate is for block {} of chain {}",
+            value.height(),
+            value.chain_id()
+        );
+        if let Some(successor) = &self.successor_snapshot {
+            ensure!(
+                successor.chain_id() == self.chain_id && successor.height() == self.height,
+                "The successor snapshot is for block {} of chain {}",
+                successor.height(),
+                successor.chain_id()
+            );
+        }
+
+        let (parent, successor) = match (self.parent_snapshot, self.successor_snapshot) {
+            (Some(parent), Some(successor)) => (parent.key_values, Some(successor.key_values)),
+            (Some(parent), None) => {
+                let successor =
+                    (next_block_height == self.height.try_add_one()?).then_some(stored_state);
+                (parent.key_values, successor)
+            }
+            (None, successor) => {
+                ensure!(
+                    next_block_height == self.height,
+                    "Chain {} is at height {next_block_height} in the storage; a parent \
+                     snapshot is required to replay block {}",
+                    self.chain_id,
+                    self.height
+                );
+                (
+                    stored_state,
+                    successor.map(|successor| successor.key_values),
+                )
+            }
+        };
+        info!(
+            "Replaying block {} of chain {} on a state of {} keys",
+            self.height,
+            self.chain_id,
+            parent.len()
+        );
+        replay_block(&storage, &parent, &certificate, successor.as_deref())
+            .await
+            .context("Failed to replay the block")
+    }
+}
@@ -3,7 +3,7 @@
 
 use async_trait::async_trait;
 use linera_base::{
-    crypto::KeyPair,
+    crypto::{CryptoHash, KeyPair},
     data_types::{Blob, HashedBlob, Timestamp},
     identifiers::{BlobId, ChainId},
 };
@@ -12,7 +12,7 @@ use linera_chain::data_types::{
 };
 use linera_core::{
     client::ChainClient,
-    data_types::{ChainInfoQuery, ChainInfoResponse},
+    data_types::{BlockHeightRange, ChainInfoQuery, ChainInfoResponse},
     node::{
         CrossChainMessageDelivery, LocalValidatorNodeProvider, NodeError, NotificationStream,
         ValidatorNode,
@@ -80,6 +80,25 @@ impl ValidatorNode for DummyValidatorNode {
     async fn download_blob(&mut self, _: BlobId) -> Result<Blob, NodeError> {
         Err(NodeError::UnexpectedMessage)
     }
+
+    async fn download_certificate(&mut self, _: CryptoHash) -> Result<Certificate, NodeError> {
+        Err(NodeError::UnexpectedMessage)
+    }
+
+    async fn download_certificates(
+        &mut self,
+        _: Vec<CryptoHash>,
+    ) -> Result<Vec<Certificate>, NodeError> {
+        Err(NodeError::UnexpectedMessage)
+    }
+
+    async fn download_certificates_by_height(
+        &mut self,
+        _: ChainId,
+        _: BlockHeightRange,
+    ) -> Result<Vec<Certificate>, NodeError> {
+        Err(NodeError::UnexpectedMessage)
+    }
 }
 
 struct DummyValidatorNodeProvider;
@@ -137,10 +156,7 @@ async fn main() -> std::io::Result<()> {
     let storage = MemoryStorage::new(store_config, namespace, None)
         .await
         .expect("storage");
-    let config = ChainListenerConfig {
-        delay_before_ms: 0,
-        delay_after_ms: 0,
-    };
+    let config = ChainListenerConfig::default();
     let context = DummyContext;
     let service = NodeService::<DummyValidatorNodeProvider, _, _>::new(
         config,
@@ -4,12 +4,15 @@
 
 use std::{path::PathBuf, time::Duration};
 
-use anyhow::bail;
+use anyhow::{bail, Context as _};
 use async_trait::async_trait;
 use futures::{stream::FuturesUnordered, FutureExt, StreamExt, TryFutureExt};
-use linera_base::crypto::{CryptoRng, KeyPair};
+use linera_base::crypto::{BlsKeyPair, CryptoRng, KeyPair, Signature};
 use linera_core::worker::WorkerState;
-use linera_execution::{committee::ValidatorName, WasmRuntime, WithWasmDefault};
+use linera_execution::{
+    committee::{Epoch, FuelUsage, SignatureScheme, SignedFuelUsage, ValidatorName},
+    WasmRuntime, WithWasmDefault,
+};
 use linera_rpc::{
     config::{
         CrossChainConfig, NetworkProtocol, NotificationConfig, ShardConfig, ShardId, TlsConfig,
@@ -23,7 +26,11 @@ use linera_service::{
     config::{
         CommitteeConfig, Export, GenesisConfig, Import, ValidatorConfig, ValidatorServerConfig,
     },
-    storage::{full_initialize_storage, run_with_storage, Runnable, StorageConfigNamespace},
+    pruning::{PruningConfig, PruningJob},
+    storage::{
+        full_initialize_storage, run_with_existing_storage, run_with_storage, Runnable,
+        StorageConfigNamespace,
+    },
     util,
 };
 use linera_storage::Storage;
@@ -38,6 +45,8 @@ struct ServerContext {
     notification_config: NotificationConfig,
     shard: Option<usize>,
     grace_period: Duration,
+    pruning_config: PruningConfig,
+    prune_interval: Option<Duration>,
 }
 
 impl ServerContext {
@@ -57,6 +66,7 @@ impl ServerContext {
             Some(self.server_config.key.copy()),
             storage,
         )
+        .with_bls_key_pair(self.server_config.bls_key.as_ref().map(BlsKeyPair::copy))
         .with_allow_inactive_chains(false)
         .with_allow_messages_from_deprecated_epochs(false)
         .with_grace_period(self.grace_period);
@@ -164,6 +174,25 @@ impl ServerContext {
     }
 }
 
+/// A job reading the average fuel consumed per block by the blocks of an epoch that this
+/// validator executed.
+struct AverageBlockFuelJob {
+    epoch: Epoch,
+}
+
+#[async_trait]
+impl Runnable for AverageBlockFuelJob {
+    type Output = Option<u64>;
+
+    async fn run<S>(self, storage: S) -> Result<Option<u64>, anyhow::Error>
+    where
+        S: Storage + Clone + Send + Sync + 'static,
+        ViewError: From<S::ContextError>,
+    {
+        Ok(storage.read_average_block_fuel(self.epoch).await?)
+    }
+}
+
 #[async_trait]
 impl Runnable for ServerContext {
     type Output = ();
@@ -178,6 +207,19 @@ impl Runnable for ServerContext {
 
         tokio::spawn(util::listen_for_shutdown_signals(shutdown_notifier.clone()));
 
+        if let Some(period) = self.prune_interval {
+            let job = PruningJob {
+                config: self.pruning_config.clone(),
+                period: Some(period),
+            };
+            let storage = storage.clone();
+            tokio::spawn(async move {
+                if let Err(error) = job.run(storage).await {
+                    error!("Pruning task failed: {error}");
+                }
+            });
+        }
+
         // Run the server
         let states = match self.shard {
             Some(shard) => {
@@ -278,11 +320,18 @@ fn make_server_config<R: CryptoRng>(
         metrics_port: options.metrics_port,
     };
     let key = KeyPair::generate_from(rng);
+    let bls_key = BlsKeyPair::generate_from(rng);
     let name = ValidatorName(key.public());
-    let validator = ValidatorConfig { network, name };
+    let validator = ValidatorConfig {
+        network,
+        name,
+        bls_public_key: Some(bls_key.public()),
+        bls_proof_of_possession: Some(bls_key.proof_of_possession()),
+    };
     ValidatorServerConfig {
         validator,
         key,
+        bls_key: Some(bls_key),
         internal_network,
     }
 }
@@ -325,6 +374,14 @@ enum ServerCommand {
         #[arg(long)]
         wasm_runtime: Option<WasmRuntime>,
 
+        /// A directory where compiled WebAssembly modules are kept across restarts.
+        #[arg(long)]
+        wasm_cache_dir: Option<PathBuf>,
+
+        /// The maximal size of the compiled WebAssembly modules kept on disk, in MiB.
+        #[arg(long, default_value = "1024")]
+        wasm_cache_size_mb: u64,
+
         /// The maximal number of simultaneous queries to the database
         #[arg(long)]
         max_concurrent_queries: Option<usize>,
@@ -336,6 +393,14 @@ enum ServerCommand {
         /// The maximal number of entries in the storage cache.
         #[arg(long, default_value = "1000")]
         cache_size: usize,
+
+        /// Prune the certificates that are no longer needed with this period, in milliseconds.
+        #[arg(long = "prune-interval-ms", value_parser = util::parse_millis)]
+        prune_interval: Option<Duration>,
+
+        /// Retention policy and archive storage used when pruning
+        #[command(flatten)]
+        pruning_config: PruningConfig,
     },
 
     /// Act as a trusted third-party and generate all server configurations
@@ -353,6 +418,37 @@ enum ServerCommand {
         /// TESTING ONLY.
         #[arg(long)]
         testing_prng_seed: Option<u64>,
+
+        /// The kind of signatures in the certificates of the first epoch: `ed25519` or `bls`
+        #[arg(long, default_value = "ed25519")]
+        signature_scheme: SignatureScheme,
+    },
+
+    /// Sign a report of the fuel consumed per block during an epoch, for the admin chain
+    /// to adjust the prices of the next epoch
+    #[command(name = "report-fuel-usage")]
+    ReportFuelUsage {
+        /// Path to the file containing the server configuration of this Linera validator (including its secret key)
+        #[arg(long = "server")]
+        server_config_path: PathBuf,
+
+        /// Storage configuration of this validator, where the fuel consumed by the blocks it
+        /// executed is recorded.
+        #[arg(long = "storage", required_unless_present = "fuel_per_block")]
+        storage_config: Option<StorageConfigNamespace>,
+
+        /// The epoch in which the fuel consumption was observed
+        #[arg(long)]
+        epoch: Epoch,
+
+        /// Report this average fuel per block instead of the one measured by this validator.
+        /// USE FOR TESTING ONLY.
+        #[arg(long)]
+        fuel_per_block: Option<u64>,
+
+        /// Path where to write the signed report
+        #[arg(long)]
+        output: PathBuf,
     },
 
     /// Initialize the database
@@ -380,7 +476,7 @@ enum ServerCommand {
     },
 }
 
-fn main() {
+fn main() -> anyhow::Result<()> {
     let env_filter = tracing_subscriber::EnvFilter::builder()
         .with_default_directive(tracing_subscriber::filter::LevelFilter::INFO.into())
         .from_env_lossy();
@@ -410,7 +506,7 @@ fn main() {
         .block_on(run(options))
 }
 
-async fn run(options: ServerOptions) {
+async fn run(options: ServerOptions) -> anyhow::Result<()> {
     linera_version::VERSION_INFO.log();
 
     match options.command {
@@ -423,15 +519,26 @@ async fn run(options: ServerOptions) {
             shard,
             grace_period,
             wasm_runtime,
+            wasm_cache_dir,
+            wasm_cache_size_mb,
             max_concurrent_queries,
             max_stream_queries,
             cache_size,
+            prune_interval,
+            pruning_config,
         } => {
             let genesis_config = GenesisConfig::read(&genesis_config_path)
                 .expect("Fail to read initial chain config");
             let server_config = ValidatorServerConfig::read(&server_config_path)
                 .expect("Fail to read server config");
 
+            if prune_interval.is_some() && !pruning_config.has_retention_policy() {
+                panic!(
+                    "--prune-interval-ms requires --prune-keep-last-blocks or \
+                    --prune-keep-max-age-secs"
+                );
+            }
+
             #[cfg(feature = "rocksdb")]
             if server_config.internal_network.shards.len() > 1
                 && storage_config.storage_config.is_rocks_db()
@@ -445,8 +552,17 @@ async fn run(options: ServerOptions) {
                 notification_config,
                 shard,
                 grace_period,
+                pruning_config,
+                prune_interval,
             };
             let wasm_runtime = wasm_runtime.with_wasm_default();
+            if let Some(directory) = wasm_cache_dir {
+                linera_execution::enable_module_disk_cache(
+                    directory,
+                    wasm_cache_size_mb * 1024 * 1024,
+                )
+                .context("Failed to create the WebAssembly module cache")?;
+            }
             let common_config = CommonStoreConfig {
                 max_concurrent_queries,
                 max_stream_queries,
@@ -465,6 +581,7 @@ async fn run(options: ServerOptions) {
             validators,
             committee,
             testing_prng_seed,
+            signature_scheme,
         } => {
             let mut config_validators = Vec::new();
             let mut rng = Box::<dyn CryptoRng>::from(testing_prng_seed);
@@ -486,6 +603,7 @@ async fn run(options: ServerOptions) {
             if let Some(committee) = committee {
                 let config = CommitteeConfig {
                     validators: config_validators,
+                    signature_scheme,
                 };
                 config
                     .write(&committee)
@@ -494,6 +612,45 @@ async fn run(options: ServerOptions) {
             }
         }
 
+        ServerCommand::ReportFuelUsage {
+            server_config_path,
+            storage_config,
+            epoch,
+            fuel_per_block,
+            output,
+        } => {
+            let server_config = ValidatorServerConfig::read(&server_config_path)
+                .expect("Fail to read server config");
+            let fuel_per_block = match (fuel_per_block, storage_config) {
+                (Some(fuel_per_block), _) => fuel_per_block,
+                (None, Some(storage_config)) => {
+                    let full_storage_config = storage_config
+                        .add_common_config(CommonStoreConfig::default())
+                        .await?;
+                    let job = AverageBlockFuelJob { epoch };
+                    run_with_existing_storage(full_storage_config, None, job)
+                        .await?
+                        .with_context(|| {
+                            format!("This validator executed no blocks in epoch {epoch}")
+                        })?
+                }
+                (None, None) => bail!("Either --storage or --fuel-per-block is required"),
+            };
+            let usage = FuelUsage {
+                epoch,
+                fuel_per_block,
+            };
+            let report = SignedFuelUsage {
+                validator: server_config.validator.name,
+                usage,
+                signature: Signature::new(&usage, &server_config.key),
+            };
+            report
+                .write(&output)
+                .expect("Unable to write fuel usage report");
+            info!("Wrote fuel usage report {}", output.to_str().unwrap());
+        }
+
         ServerCommand::Initialize {
             storage_config,
             genesis_config_path,
@@ -517,6 +674,7 @@ async fn run(options: ServerOptions) {
                 .unwrap();
         }
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -0,0 +1,339 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! [`Signer`] backends keeping the keys of the chain owners out of the wallet: an encrypted
+//! keystore file, and an external signer process.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use linera_base::{
+    crypto::{KeyPair, PublicKey, Signature},
+    identifiers::Owner,
+};
+#[cfg(unix)]
+use linera_chain::data_types::{Block, BlockProposal};
+use linera_core::signer::{InMemorySigner, Signer, SignerError};
+use serde::{Deserialize, Serialize};
+
+use crate::encryption::{EncryptedData, KdfParams};
+
+#[cfg(test)]
+#[path = "unit_tests/signer.rs"]
+mod tests;
+
+/// A file holding key pairs encrypted with a passphrase.
+pub struct Keystore {
+    path: PathBuf,
+    passphrase: String,
+    key_pairs: Vec<KeyPair>,
+}
+
+impl Keystore {
+    /// Creates an empty keystore, to be written at `path`.
+    pub fn create(path: &Path, passphrase: String) -> Result<Self, anyhow::Error> {
+        anyhow::ensure!(!path.exists(), "Keystore {} already exists", path.display());
+        Ok(Keystore {
+            path: path.to_owned(),
+            passphrase,
+            key_pairs: Vec::new(),
+        })
+    }
+
+    /// Reads and decrypts the keystore at `path`.
+    pub fn read(path: &Path, passphrase: String) -> Result<Self, anyhow::Error> {
+        let data = fs_err::read(path)?;
+        let encrypted: EncryptedData = serde_json::from_slice(&data)
+            .with_context(|| format!("Invalid keystore {}", path.display()))?;
+        let plaintext = encrypted.decrypt(&passphrase)?;
+        let key_pairs = serde_json::from_slice(&plaintext)?;
+        Ok(Keystore {
+            path: path.to_owned(),
+            passphrase,
+            key_pairs,
+        })
+    }
+
+    /// Encrypts the keystore and writes it to its file.
+    pub fn write(&self) -> Result<(), anyhow::Error> {
+        let plaintext = serde_json::to_vec(&self.key_pairs)?;
+        let encrypted = EncryptedData::encrypt(&plaintext, &self.passphrase, KdfParams::default())?;
+        let temporary_path = self.path.with_extension("tmp");
+        fs_err::write(&temporary_path, serde_json::to_vec_pretty(&encrypted)?)?;
+        fs_err::rename(&temporary_path, &self.path)?;
+        Ok(())
+    }
+
+    /// Adds a key pair to the keystore, unless it is already present.
+    pub fn insert(&mut self, key_pair: KeyPair) {
+        let public_key = key_pair.public();
+        if self
+            .key_pairs
+            .iter()
+            .all(|known| known.public() != public_key)
+        {
+            self.key_pairs.push(key_pair);
+        }
+    }
+
+    /// Returns the public keys of the key pairs in the keystore.
+    pub fn public_keys(&self) -> impl Iterator<Item = PublicKey> + '_ {
+        self.key_pairs.iter().map(KeyPair::public)
+    }
+
+    /// Returns a signer using the key pairs of the keystore.
+    pub fn into_signer(self) -> InMemorySigner {
+        InMemorySigner::new(self.key_pairs)
+    }
+}
+
+/// A request to an external signer.
+///
+/// The client connects to the signer's Unix socket and writes one request as a line of JSON.
+/// The signer answers with one [`SignerResponse`] as a line of JSON.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum SignerRequest {
+    /// Asks for the public key of an owner.
+    PublicKey { owner: Owner },
+    /// Asks for a signature of a message, in hex, on behalf of an owner.
+    Sign {
+        owner: Owner,
+        #[serde(with = "hex")]
+        message: Vec<u8>,
+    },
+}
+
+/// The response of an external signer to a [`SignerRequest`].
+#[derive(Debug, Serialize, Deserialize)]
+pub enum SignerResponse {
+    /// The public key of the owner, or `None` if the signer doesn't hold its key.
+    PublicKey(Option<PublicKey>),
+    /// The signature of the message.
+    Signature(Signature),
+    /// The request failed.
+    Error(String),
+}
+
+/// A [`Signer`] forwarding the requests to another process over a Unix socket.
+#[cfg(unix)]
+pub struct ExternalSigner {
+    socket_path: PathBuf,
+}
+
+#[cfg(unix)]
+impl ExternalSigner {
+    /// Creates a signer connecting to the socket at `socket_path` for each request.
+    pub fn new(socket_path: PathBuf) -> Self {
+        ExternalSigner { socket_path }
+    }
+
+    /// Sends `request` to the signer and returns its response.
+    async fn send(&self, request: &SignerRequest) -> Result<SignerResponse, SignerError> {
+        use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader};
+
+        let mut stream = tokio::net::UnixStream::connect(&self.socket_path).await?;
+        let mut line = serde_json::to_vec(request).map_err(backend_error)?;
+        line.push(b'\n');
+        stream.write_all(&line).await?;
+        let mut response = String::new();
+        BufReader::new(stream).read_line(&mut response).await?;
+        match serde_json::from_str(&response).map_err(backend_error)? {
+            SignerResponse::Error(error) => Err(SignerError::Backend(error)),
+            response => Ok(response),
+        }
+    }
+}
+
+#[cfg(unix)]
+#[async_trait]
+impl Signer for ExternalSigner {
+    async fn public_key(&self, owner: &Owner) -> Result<Option<PublicKey>, SignerError> {
+        match self
+            .send(&SignerRequest::PublicKey { owner: *owner })
+            .await?
+        {
+            SignerResponse::PublicKey(public_key) => Ok(public_key),
+            response => Err(unexpected_response(response)),
+        }
+    }
+
+    async fn sign(&self, owner: &Owner, message: &[u8]) -> Result<Signature, SignerError> {
+        let request = SignerRequest::Sign {
+            owner: *owner,
+            message: message.to_vec(),
+        };
+        match self.send(&request).await? {
+            SignerResponse::Signature(signature) => Ok(signature),
+            response => Err(unexpected_response(response)),
+        }
+    }
+}
+
+/// Answers the requests of [`ExternalSigner`] clients connecting to `socket_path`, using
+/// `signer`. This runs until an I/O error occurs on the socket.
+///
+/// The socket is only accessible to the user running the signer, and connections from other
+/// users are refused. A socket left over at `socket_path` by a previous signer of the same user
+/// is replaced. Only block proposals and approvals are signed, and if `confirm` is set, each of
+/// them must first be approved by the operator on the terminal.
+#[cfg(unix)]
+pub async fn serve_signer(
+    socket_path: &Path,
+    signer: std::sync::Arc<dyn Signer>,
+    confirm: bool,
+) -> Result<(), anyhow::Error> {
+    use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader};
+
+    let (listener, owner_uid) = bind_private_socket(socket_path)
+        .with_context(|| format!("Failed to listen on {}", socket_path.display()))?;
+    // Confirmations are asked one at a time.
+    let terminal = std::sync::Arc::new(tokio::sync::Mutex::new(()));
+    loop {
+        let (stream, _) = listener.accept().await?;
+        match stream.peer_cred() {
+            Ok(credentials) if credentials.uid() == owner_uid => {}
+            Ok(credentials) => {
+                tracing::warn!("Refusing signer connection from user {}", credentials.uid());
+                continue;
+            }
+            Err(error) => {
+                tracing::warn!("Failed to identify signer client: {error}");
+                continue;
+            }
+        }
+        let signer = signer.clone();
+        let terminal = terminal.clone();
+        tokio::spawn(async move {
+            let (reader, mut writer) = stream.into_split();
+            let mut request = String::new();
+            if let Err(error) = BufReader::new(reader).read_line(&mut request).await {
+                tracing::warn!("Failed to read signer request: {error}");
+                return;
+            }
+            let response = match serde_json::from_str(&request) {
+                Ok(SignerRequest::PublicKey { owner }) => {
+                    signer.public_key(&owner).await.map_or_else(
+                        |error| SignerResponse::Error(error.to_string()),
+                        SignerResponse::PublicKey,
+                    )
+                }
+                Ok(SignerRequest::Sign { owner, message }) => {
+                    match BlockProposal::signed_block(&message) {
+                        None => SignerResponse::Error(
+                            "Only block proposals and approvals are signed".to_string(),
+                        ),
+                        Some(block)
+                            if confirm && !ask_confirmation(&terminal, owner, block).await =>
+                        {
+                            SignerResponse::Error("The operator declined to sign".to_string())
+                        }
+                        Some(_) => signer.sign(&owner, &message).await.map_or_else(
+                            |error| SignerResponse::Error(error.to_string()),
+                            SignerResponse::Signature,
+                        ),
+                    }
+                }
+                Err(error) => SignerResponse::Error(format!("Invalid request: {error}")),
+            };
+            let mut line = serde_json::to_vec(&response).expect("Serialization should not fail");
+            line.push(b'\n');
+            if let Err(error) = writer.write_all(&line).await {
+                tracing::warn!("Failed to write signer response: {error}");
+            }
+        });
+    }
+}
+
+/// Binds a Unix socket at `socket_path` that only the current user can access, and returns
+/// it with the ID of that user.
+///
+/// The socket is created with restricted permissions inside a fresh private directory and
+/// then moved into place, so that it is never accessible to other users. An existing file at
+/// `socket_path` is only removed if it is a socket owned by the same user.
+#[cfg(unix)]
+fn bind_private_socket(
+    socket_path: &Path,
+) -> Result<(tokio::net::UnixListener, u32), anyhow::Error> {
+    use std::os::unix::fs::{
+        DirBuilderExt as _, FileTypeExt as _, MetadataExt as _, PermissionsExt as _,
+    };
+
+    let file_name = socket_path
+        .file_name()
+        .context("The socket path has no file name")?;
+    let parent = socket_path.parent().unwrap_or(Path::new("."));
+    let private_dir = parent.join(format!(
+        ".{}.{}",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&private_dir)
+        .with_context(|| format!("Failed to create {}", private_dir.display()))?;
+    let result = (|| -> Result<_, anyhow::Error> {
+        let owner_uid = fs_err::metadata(&private_dir)?.uid();
+        match std::fs::symlink_metadata(socket_path) {
+            Ok(metadata) => {
+                anyhow::ensure!(
+                    metadata.file_type().is_socket() && metadata.uid() == owner_uid,
+                    "{} exists and is not a socket of the current user",
+                    socket_path.display()
+                );
+                anyhow::ensure!(
+                    std::os::unix::net::UnixStream::connect(socket_path).is_err(),
+                    "Another signer is listening on {}",
+                    socket_path.display()
+                );
+                fs_err::remove_file(socket_path)?;
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error.into()),
+        }
+        let private_path = private_dir.join(file_name);
+        let listener = tokio::net::UnixListener::bind(&private_path)?;
+        fs_err::set_permissions(&private_path, std::fs::Permissions::from_mode(0o600))?;
+        fs_err::rename(&private_path, socket_path)?;
+        Ok((listener, owner_uid))
+    })();
+    fs_err::remove_dir_all(&private_dir)?;
+    result
+}
+
+/// Asks the operator on the terminal whether to sign `block` on behalf of `owner`.
+#[cfg(unix)]
+async fn ask_confirmation(terminal: &tokio::sync::Mutex<()>, owner: Owner, block: Block) -> bool {
+    let _guard = terminal.lock().await;
+    let prompt = format!(
+        "Sign block {} of chain {} for owner {owner}, with {} incoming messages and {} \
+        operations? [y/N] ",
+        block.height,
+        block.chain_id,
+        block.incoming_messages.len(),
+        block.operations.len(),
+    );
+    tokio::task::spawn_blocking(move || {
+        use std::io::Write as _;
+
+        eprint!("{prompt}");
+        std::io::stderr().flush().ok()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer).ok()?;
+        Some(matches!(answer.trim(), "y" | "Y" | "yes"))
+    })
+    .await
+    .ok()
+    .flatten()
+    .unwrap_or(false)
+}
+
+#[cfg(unix)]
+fn backend_error(error: serde_json::Error) -> SignerError {
+    SignerError::Backend(error.to_string())
+}
+
+#[cfg(unix)]
+fn unexpected_response(response: SignerResponse) -> SignerError {
+    SignerError::Backend(format!("Unexpected response from signer: {response:?}"))
+}
@@ -9,7 +9,7 @@ use linera_execution::WasmRuntime;
 use linera_storage::{MemoryStorage, ServiceStorage, Storage};
 use linera_storage_service::{client::ServiceStoreClient, common::ServiceStoreConfig};
 use linera_views::{
-    common::{AdminKeyValueStore, CommonStoreConfig},
+    common::{AdminKeyValueStore, CommonStoreConfig, KeyValueStore},
     memory::MemoryStoreConfig,
     views::ViewError,
 };
@@ -546,6 +546,88 @@ where
     }
 }
 
+/// Runs a job on a storage that has already been initialized, e.g. by `linera-db` or
+/// `linera-server initialize`. No genesis configuration is needed.
+pub async fn run_with_existing_storage<Job>(
+    config: StoreConfig,
+    wasm_runtime: Option<WasmRuntime>,
+    job: Job,
+) -> Result<Job::Output, anyhow::Error>
+where
+    Job: Runnable,
+{
+    match config {
+        StoreConfig::Memory(_, _) => {
+            bail!("The memory storage cannot be reused across processes");
+        }
+        StoreConfig::Service(config, namespace) => {
+            let storage = ServiceStorage::new(config, &namespace, wasm_runtime).await?;
+            job.run(storage).await
+        }
+        #[cfg(feature = "rocksdb")]
+        StoreConfig::RocksDb(config, namespace) => {
+            let storage = RocksDbStorage::new(config, &namespace, wasm_runtime).await?;
+            job.run(storage).await
+        }
+        #[cfg(feature = "dynamodb")]
+        StoreConfig::DynamoDb(config, namespace) => {
+            let storage = DynamoDbStorage::new(config, &namespace, wasm_runtime).await?;
+            job.run(storage).await
+        }
+        #[cfg(feature = "scylladb")]
+        StoreConfig::ScyllaDb(config, namespace) => {
+            let storage = ScyllaDbStorage::new(config, &namespace, wasm_runtime).await?;
+            job.run(storage).await
+        }
+    }
+}
+
+/// A job operating directly on the key-value store of a storage configuration.
+#[async_trait]
+pub trait RunnableWithStore {
+    type Output;
+
+    async fn run<S>(self, store: S) -> Result<Self::Output, anyhow::Error>
+    where
+        S: KeyValueStore + Clone + Send + Sync + 'static,
+        S::Error: From<bcs::Error>,
+        ViewError: From<S::Error>;
+}
+
+/// Runs a job on the key-value store of a storage that has already been initialized.
+pub async fn run_with_store<Job>(
+    config: StoreConfig,
+    job: Job,
+) -> Result<Job::Output, anyhow::Error>
+where
+    Job: RunnableWithStore,
+{
+    match config {
+        StoreConfig::Memory(_, _) => {
+            bail!("The memory storage cannot be reused across processes");
+        }
+        StoreConfig::Service(config, namespace) => {
+            let store = ServiceStoreClient::connect(&config, &namespace).await?;
+            job.run(store).await
+        }
+        #[cfg(feature = "rocksdb")]
+        StoreConfig::RocksDb(config, namespace) => {
+            let store = RocksDbStore::connect(&config, &namespace).await?;
+            job.run(store).await
+        }
+        #[cfg(feature = "dynamodb")]
+        StoreConfig::DynamoDb(config, namespace) => {
+            let store = DynamoDbStore::connect(&config, &namespace).await?;
+            job.run(store).await
+        }
+        #[cfg(feature = "scylladb")]
+        StoreConfig::ScyllaDb(config, namespace) => {
+            let store = ScyllaDbStore::connect(&config, &namespace).await?;
+            job.run(store).await
+        }
+    }
+}
+
 #[allow(unused_variables)]
 pub async fn full_initialize_storage(
     config: StoreConfig,
@@ -17,6 +17,7 @@ use linera_core::{
     test_utils::{MemoryStorageBuilder, NodeProvider, StorageBuilder as _, TestBuilder},
 };
 use linera_execution::{
+    committee::SignatureScheme,
     system::{Recipient, UserData},
     ResourceControlPolicy,
 };
@@ -114,10 +115,15 @@ fn make_genesis_config(builder: &TestBuilder<MemoryStorageBuilder>) -> GenesisCo
         .map(|name| ValidatorConfig {
             name: *name,
             network: network.clone(),
+            bls_public_key: None,
+            bls_proof_of_possession: None,
         })
         .collect();
     let mut genesis_config = GenesisConfig::new(
-        CommitteeConfig { validators },
+        CommitteeConfig {
+            validators,
+            signature_scheme: SignatureScheme::Ed25519,
+        },
         builder.admin_id(),
         Timestamp::from(0),
         ResourceControlPolicy::default(),
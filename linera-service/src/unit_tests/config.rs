@@ -0,0 +1,60 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use linera_base::{data_types::Timestamp, identifiers::ChainId};
+use linera_execution::ResourceControlPolicy;
+
+use super::{CommitteeConfig, GenesisConfig, WalletState};
+
+fn make_genesis_config() -> GenesisConfig {
+    GenesisConfig::new(
+        CommitteeConfig::default(),
+        ChainId::root(0),
+        Timestamp::from(0),
+        ResourceControlPolicy::default(),
+        "test".to_string(),
+    )
+}
+
+#[test]
+fn test_encrypted_wallet() -> anyhow::Result<()> {
+    let directory = tempfile::tempdir()?;
+    let path = directory.path().join("wallet.json");
+    let passphrase = Some("secret".to_string());
+
+    let mut wallet = WalletState::create(&path, make_genesis_config(), Some(37), None)?;
+    wallet.save()?;
+    drop(wallet);
+    let contents = std::fs::read_to_string(&path)?;
+    assert!(contents.contains("testing_prng_seed"));
+
+    // Migrate the plain wallet.
+    let mut wallet = WalletState::from_file_with_passphrase(&path, passphrase.clone())?;
+    assert!(!wallet.is_encrypted());
+    wallet.set_passphrase(passphrase.clone());
+    wallet.save()?;
+    let expected = serde_json::to_value(wallet.inner())?;
+    drop(wallet);
+    let contents = std::fs::read_to_string(&path)?;
+    assert!(contents.contains("encrypted_wallet"));
+    assert!(!contents.contains("testing_prng_seed"));
+
+    assert!(WalletState::from_file(&path).is_err());
+    assert!(WalletState::from_file_with_passphrase(&path, Some("wrong".to_string())).is_err());
+    let mut wallet = WalletState::from_file_with_passphrase(&path, passphrase.clone())?;
+    assert!(wallet.is_encrypted());
+    assert_eq!(serde_json::to_value(wallet.inner())?, expected);
+
+    // Change the passphrase, then go back to the plain format.
+    wallet.set_passphrase(Some("other".to_string()));
+    wallet.save()?;
+    drop(wallet);
+    assert!(WalletState::from_file_with_passphrase(&path, passphrase).is_err());
+    let mut wallet = WalletState::from_file_with_passphrase(&path, Some("other".to_string()))?;
+    wallet.set_passphrase(None);
+    wallet.save()?;
+    drop(wallet);
+    let wallet = WalletState::from_file(&path)?;
+    assert!(!wallet.is_encrypted());
+    Ok(())
+}
@@ -0,0 +1,88 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use linera_views::{
+    batch::Batch,
+    common::{ReadableKeyValueStore as _, WritableKeyValueStore as _},
+    memory::{create_memory_store, MemoryStore},
+};
+
+use super::{copy_store, verify_stores, MigrationConfig, MigrationStores};
+
+/// Creates a store with `count` keys spread over several first bytes.
+async fn make_source(count: u8) -> anyhow::Result<MemoryStore> {
+    let store = create_memory_store();
+    let mut batch = Batch::new();
+    for i in 0..count {
+        batch.put_key_value_bytes(vec![i % 3, i], vec![i; usize::from(i)]);
+    }
+    store.write_batch(batch, &[]).await?;
+    Ok(store)
+}
+
+fn make_stores(destination: &str) -> MigrationStores {
+    MigrationStores {
+        source: "memory:source".to_string(),
+        destination: destination.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_copy_and_verify_store() -> anyhow::Result<()> {
+    let source = make_source(20).await?;
+    let destination = create_memory_store();
+    let config = MigrationConfig {
+        batch_size: 3,
+        ..MigrationConfig::default()
+    };
+    let stores = make_stores("memory:destination");
+    let summary = copy_store(&source, &destination, &stores, &config).await?;
+    assert_eq!(summary.copied_keys, 20);
+    assert_eq!(summary.resumed_after, None);
+    assert_eq!(verify_stores(&source, &destination, 3).await?, 20);
+
+    // A modified value is detected.
+    let mut batch = Batch::new();
+    batch.put_key_value_bytes(vec![1, 4], vec![0]);
+    destination.write_batch(batch, &[]).await?;
+    assert!(verify_stores(&source, &destination, 3).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resume_copy_from_checkpoint() -> anyhow::Result<()> {
+    let directory = tempfile::tempdir()?;
+    let checkpoint_path = directory.path().join("checkpoint.json");
+    let config = MigrationConfig {
+        batch_size: 4,
+        checkpoint_path: Some(checkpoint_path.clone()),
+        ..MigrationConfig::default()
+    };
+    let source = make_source(20).await?;
+
+    // A previous run was interrupted after copying the keys up to `[1, 10]`.
+    let destination = create_memory_store();
+    let mut batch = Batch::new();
+    for i in (0..20).filter(|i| i % 3 == 0 || (i % 3 == 1 && *i <= 10)) {
+        batch.put_key_value_bytes(vec![i % 3, i], vec![i; usize::from(i)]);
+    }
+    destination.write_batch(batch, &[]).await?;
+    fs_err::write(
+        &checkpoint_path,
+        r#"{"stores":{"source":"memory:source","destination":"memory:destination"},"last_key":[1,10]}"#,
+    )?;
+    assert!(verify_stores(&source, &destination, 4).await.is_err());
+
+    // The checkpoint cannot be used for another destination.
+    let other_stores = make_stores("memory:other");
+    let result = copy_store(&source, &destination, &other_stores, &config).await;
+    assert!(result.is_err());
+
+    let stores = make_stores("memory:destination");
+    let summary = copy_store(&source, &destination, &stores, &config).await?;
+    assert_eq!(summary.resumed_after, Some(vec![1, 10]));
+    assert_eq!(summary.copied_keys, 9);
+    assert_eq!(verify_stores(&source, &destination, 4).await?, 20);
+    assert!(destination.read_value_bytes(&[2, 17]).await?.is_some());
+    Ok(())
+}
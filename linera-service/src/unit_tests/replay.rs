@@ -0,0 +1,68 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use linera_base::{
+    crypto::KeyPair,
+    data_types::{Amount, BlockHeight, Round, Timestamp},
+    identifiers::{ChainDescription, ChainId},
+};
+use linera_chain::{
+    data_types::{Certificate, ExecutedBlock, HashedCertificateValue},
+    test::{make_first_block, BlockTestExt as _},
+};
+use linera_execution::committee::{Committee, ValidatorName};
+use linera_storage::{MemoryStorage, Storage};
+
+use super::ReplayJob;
+use crate::storage::Runnable as _;
+
+#[tokio::test]
+async fn test_replay_from_storage() -> anyhow::Result<()> {
+    let storage = MemoryStorage::make_test_storage(None).await;
+    let chain_id = ChainId::root(0);
+    let key_pair = KeyPair::generate();
+    let committee = Committee::make_simple(vec![ValidatorName(key_pair.public())]);
+    storage
+        .create_chain(
+            committee,
+            chain_id,
+            ChainDescription::Root(0),
+            key_pair.public(),
+            Amount::from_tokens(10),
+            Timestamp::from(0),
+        )
+        .await?;
+
+    // Store the certificate without executing it, as after a failed execution.
+    let block = make_first_block(chain_id)
+        .with_simple_transfer(ChainId::root(1), Amount::ONE)
+        .with_timestamp(1);
+    let mut chain = storage.load_chain(chain_id).await?;
+    let outcome = chain.execute_block(&block, block.timestamp, None).await?;
+    drop(chain);
+    let value = HashedCertificateValue::new_confirmed(ExecutedBlock { block, outcome });
+    let certificate = Certificate::new(value, Round::Fast, vec![]);
+    storage.write_certificate(&certificate).await?;
+
+    let job = ReplayJob {
+        chain_id,
+        height: BlockHeight::ZERO,
+        certificate_hash: Some(certificate.hash()),
+        parent_snapshot: None,
+        successor_snapshot: None,
+    };
+    let report = job.run(storage.clone()).await?;
+    assert!(report.is_consistent(), "{report:?}");
+    assert!(!report.compared_to_successor);
+
+    // Without the hash, the certificate cannot be found.
+    let job = ReplayJob {
+        chain_id,
+        height: BlockHeight::ZERO,
+        certificate_hash: None,
+        parent_snapshot: None,
+        successor_snapshot: None,
+    };
+    assert!(job.run(storage).await.is_err());
+    Ok(())
+}
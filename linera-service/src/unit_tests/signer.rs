@@ -0,0 +1,106 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{os::unix::fs::PermissionsExt as _, sync::Arc};
+
+use linera_base::{
+    crypto::{KeyPair, Signature},
+    data_types::Round,
+    identifiers::{ChainId, Owner},
+};
+use linera_chain::{
+    data_types::{BlockAndRound, BlockProposal},
+    test::make_first_block,
+};
+use linera_core::signer::{InMemorySigner, Signer as _};
+
+use super::{bind_private_socket, ExternalSigner, Keystore};
+
+#[test]
+fn test_keystore_round_trip() -> anyhow::Result<()> {
+    let directory = tempfile::tempdir()?;
+    let path = directory.path().join("keystore.json");
+    let key_pair = KeyPair::generate();
+    let public_key = key_pair.public();
+
+    let mut keystore = Keystore::create(&path, "secret".to_string())?;
+    keystore.insert(key_pair.copy());
+    keystore.insert(key_pair);
+    keystore.write()?;
+    assert!(Keystore::create(&path, "secret".to_string()).is_err());
+
+    let contents = std::fs::read_to_string(&path)?;
+    assert!(!contents.contains(&public_key.to_string()));
+
+    let keystore = Keystore::read(&path, "secret".to_string())?;
+    assert_eq!(keystore.public_keys().collect::<Vec<_>>(), vec![public_key]);
+    assert!(Keystore::read(&path, "wrong".to_string()).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_external_signer() -> anyhow::Result<()> {
+    let directory = tempfile::tempdir()?;
+    let socket_path = directory.path().join("signer.sock");
+    let key_pair = KeyPair::generate();
+    let public_key = key_pair.public();
+    let owner = Owner::from(public_key);
+    let content = BlockAndRound {
+        block: make_first_block(ChainId::root(1)),
+        round: Round::Fast,
+    };
+    let message = BlockProposal::signing_message(&content, None);
+    let expected_signature = Signature::new_from_message(&message, &key_pair);
+
+    let signer = Arc::new(InMemorySigner::new([key_pair]));
+    let server_path = socket_path.clone();
+    tokio::spawn(async move { super::serve_signer(&server_path, signer, false).await });
+    while !socket_path.exists() {
+        tokio::task::yield_now().await;
+    }
+    let permissions = std::fs::metadata(&socket_path)?.permissions();
+    assert_eq!(permissions.mode() & 0o777, 0o600);
+
+    let external_signer = ExternalSigner::new(socket_path);
+    assert_eq!(external_signer.public_key(&owner).await?, Some(public_key));
+    assert_eq!(
+        external_signer.sign(&owner, &message).await?,
+        expected_signature
+    );
+    let other_owner = Owner::from(KeyPair::generate().public());
+    assert_eq!(external_signer.public_key(&other_owner).await?, None);
+    assert!(external_signer.sign(&other_owner, &message).await.is_err());
+
+    // Messages other than block proposals and approvals are not signed.
+    let approval = BlockProposal::approval_message(&content.block);
+    assert!(external_signer.sign(&owner, &approval).await.is_ok());
+    assert!(external_signer.sign(&owner, b"message").await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bind_private_socket_replaces_only_stale_sockets() -> anyhow::Result<()> {
+    let directory = tempfile::tempdir()?;
+    let socket_path = directory.path().join("signer.sock");
+
+    // Other files are left alone.
+    std::fs::write(&socket_path, "data")?;
+    assert!(bind_private_socket(&socket_path).is_err());
+    assert_eq!(std::fs::read_to_string(&socket_path)?, "data");
+    std::fs::remove_file(&socket_path)?;
+
+    // A socket that nobody listens on any more is replaced.
+    drop(std::os::unix::net::UnixListener::bind(&socket_path)?);
+    let (listener, _) = bind_private_socket(&socket_path)?;
+    let permissions = std::fs::metadata(&socket_path)?.permissions();
+    assert_eq!(permissions.mode() & 0o777, 0o600);
+
+    // The socket of a running signer is not.
+    assert!(bind_private_socket(&socket_path).is_err());
+    drop(listener);
+
+    // No private directory is left behind.
+    let entries = std::fs::read_dir(directory.path())?.count();
+    assert_eq!(entries, 1);
+    Ok(())
+}
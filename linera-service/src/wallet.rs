@@ -132,6 +132,19 @@ impl Wallet {
             .map(|key_pair| key_pair.copy())
     }
 
+    /// Returns copies of all key pairs in the wallet, assigned to a chain or not.
+    pub fn key_pairs(&self) -> Vec<KeyPair> {
+        self.unassigned_key_pairs
+            .values()
+            .chain(
+                self.chains
+                    .values()
+                    .filter_map(|user_chain| user_chain.key_pair.as_ref()),
+            )
+            .map(KeyPair::copy)
+            .collect()
+    }
+
     pub fn assign_new_chain_to_key(
         &mut self,
         key: PublicKey,
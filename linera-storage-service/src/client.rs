@@ -1,7 +1,7 @@
 // Copyright (c) Zefchain Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{mem, sync::Arc};
+use std::{mem, ops::Bound, sync::Arc};
 
 use async_lock::{RwLock, RwLockWriteGuard, Semaphore, SemaphoreGuard};
 use linera_base::ensure;
@@ -12,9 +12,10 @@ use linera_views::test_utils::generate_test_namespace;
 use linera_views::{
     batch::{Batch, WriteOperation},
     common::{
-        AdminKeyValueStore, CommonStoreConfig, KeyValueStore, ReadableKeyValueStore,
-        WritableKeyValueStore,
+        AdminKeyValueStore, CommonStoreConfig, Direction, KeyRange, KeyValueStore,
+        ReadableKeyValueStore, WritableKeyValueStore,
     },
+    journaling::JournalStatus,
 };
 use serde::de::DeserializeOwned;
 use tonic::transport::{Channel, Endpoint};
@@ -24,13 +25,15 @@ use crate::common::STORAGE_SERVICE_METRICS;
 use crate::{
     common::{KeyTag, ServiceContextError, ServiceStoreConfig, MAX_PAYLOAD_SIZE},
     key_value_store::{
-        statement::Operation, store_processor_client::StoreProcessorClient, KeyValue,
-        KeyValueAppend, ReplyContainsKey, ReplyExistsNamespace, ReplyFindKeyValuesByPrefix,
-        ReplyFindKeysByPrefix, ReplyListAll, ReplyReadMultiValues, ReplyReadValue,
+        key_bound, statement::Operation, store_processor_client::StoreProcessorClient, KeyBound,
+        KeyValue, KeyValueAppend, RangeQuery, ReplyContainsKey, ReplyExistsNamespace,
+        ReplyFindKeyValuesByPrefix, ReplyFindKeyValuesInRange, ReplyFindKeysByPrefix,
+        ReplyFindKeysInRange, ReplyListAll, ReplyReadMultiValues, ReplyReadValue,
         ReplySpecificChunk, RequestContainsKey, RequestCreateNamespace, RequestDeleteAll,
         RequestDeleteNamespace, RequestExistsNamespace, RequestFindKeyValuesByPrefix,
-        RequestFindKeysByPrefix, RequestListAll, RequestReadMultiValues, RequestReadValue,
-        RequestSpecificChunk, RequestWriteBatchExtended, Statement,
+        RequestFindKeyValuesInRange, RequestFindKeysByPrefix, RequestFindKeysInRange,
+        RequestListAll, RequestReadMultiValues, RequestReadValue, RequestSpecificChunk,
+        RequestWriteBatchExtended, Statement,
     },
 };
 
@@ -199,6 +202,92 @@ impl ReadableKeyValueStore<ServiceContextError> for ServiceStoreClientInternal {
             Self::read_entries(client, message_index, num_chunks).await
         }
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<Vec<u8>>, ServiceContextError> {
+        ensure!(
+            key_prefix.len() <= MAX_KEY_SIZE,
+            ServiceContextError::KeyTooLong
+        );
+        let mut full_key_prefix = self.namespace.clone();
+        full_key_prefix.extend(key_prefix);
+        let query = RequestFindKeysInRange {
+            key_prefix: full_key_prefix,
+            range: Some(range_to_query(range)),
+        };
+        let request = tonic::Request::new(query);
+        let mut client = self.client.write().await;
+        let _guard = self.acquire().await;
+        let response = client.process_find_keys_in_range(request).await?;
+        let response = response.into_inner();
+        let ReplyFindKeysInRange {
+            keys,
+            message_index,
+            num_chunks,
+        } = response;
+        if num_chunks == 0 {
+            Ok(keys)
+        } else {
+            Self::read_entries(client, message_index, num_chunks).await
+        }
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ServiceContextError> {
+        ensure!(
+            key_prefix.len() <= MAX_KEY_SIZE,
+            ServiceContextError::KeyTooLong
+        );
+        let mut full_key_prefix = self.namespace.clone();
+        full_key_prefix.extend(key_prefix);
+        let query = RequestFindKeyValuesInRange {
+            key_prefix: full_key_prefix,
+            range: Some(range_to_query(range)),
+        };
+        let request = tonic::Request::new(query);
+        let mut client = self.client.write().await;
+        let _guard = self.acquire().await;
+        let response = client.process_find_key_values_in_range(request).await?;
+        let response = response.into_inner();
+        let ReplyFindKeyValuesInRange {
+            key_values,
+            message_index,
+            num_chunks,
+        } = response;
+        if num_chunks == 0 {
+            let key_values = key_values
+                .into_iter()
+                .map(|x| (x.key, x.value))
+                .collect::<Vec<_>>();
+            Ok(key_values)
+        } else {
+            Self::read_entries(client, message_index, num_chunks).await
+        }
+    }
+}
+
+fn bound_to_key_bound(bound: &Bound<Vec<u8>>) -> Option<KeyBound> {
+    let bound = match bound {
+        Bound::Included(key) => key_bound::Bound::Included(key.clone()),
+        Bound::Excluded(key) => key_bound::Bound::Excluded(key.clone()),
+        Bound::Unbounded => return None,
+    };
+    Some(KeyBound { bound: Some(bound) })
+}
+
+fn range_to_query(range: &KeyRange) -> RangeQuery {
+    RangeQuery {
+        start: bound_to_key_bound(&range.start),
+        end: bound_to_key_bound(&range.end),
+        reverse: range.direction == Direction::Reverse,
+        limit: range.limit.map(|limit| limit as u64),
+    }
 }
 
 impl WritableKeyValueStore<ServiceContextError> for ServiceStoreClientInternal {
@@ -261,6 +350,13 @@ impl WritableKeyValueStore<ServiceContextError> for ServiceStoreClientInternal {
     async fn clear_journal(&self, _base_key: &[u8]) -> Result<(), ServiceContextError> {
         Ok(())
     }
+
+    async fn inspect_journal(
+        &self,
+        _base_key: &[u8],
+    ) -> Result<JournalStatus, ServiceContextError> {
+        Ok(JournalStatus::default())
+    }
 }
 
 impl KeyValueStore for ServiceStoreClientInternal {
@@ -522,6 +618,22 @@ impl ReadableKeyValueStore<ServiceContextError> for ServiceStoreClient {
     ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ServiceContextError> {
         self.store.find_key_values_by_prefix(key_prefix).await
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<Vec<u8>>, ServiceContextError> {
+        self.store.find_keys_in_range(key_prefix, range).await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ServiceContextError> {
+        self.store.find_key_values_in_range(key_prefix, range).await
+    }
 }
 
 impl WritableKeyValueStore<ServiceContextError> for ServiceStoreClient {
@@ -534,6 +646,10 @@ impl WritableKeyValueStore<ServiceContextError> for ServiceStoreClient {
     async fn clear_journal(&self, base_key: &[u8]) -> Result<(), ServiceContextError> {
         self.store.clear_journal(base_key).await
     }
+
+    async fn inspect_journal(&self, base_key: &[u8]) -> Result<JournalStatus, ServiceContextError> {
+        self.store.inspect_journal(base_key).await
+    }
 }
 
 impl KeyValueStore for ServiceStoreClient {
@@ -1,13 +1,15 @@
 // Copyright (c) Zefchain Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::BTreeMap, sync::Arc};
+use std::{collections::BTreeMap, ops::Bound, sync::Arc};
 
 use async_lock::RwLock;
 use linera_storage_service::common::{KeyTag, MAX_PAYLOAD_SIZE};
 use linera_views::{
     batch::Batch,
-    common::{CommonStoreConfig, ReadableKeyValueStore, WritableKeyValueStore},
+    common::{
+        CommonStoreConfig, Direction, KeyRange, ReadableKeyValueStore, WritableKeyValueStore,
+    },
     memory::{create_memory_store_stream_queries, MemoryStore},
 };
 #[cfg(feature = "rocksdb")]
@@ -19,15 +21,17 @@ use serde::Serialize;
 use tonic::{transport::Server, Request, Response, Status};
 
 use crate::key_value_store::{
+    key_bound,
     statement::Operation,
     store_processor_server::{StoreProcessor, StoreProcessorServer},
-    KeyValue, OptValue, ReplyContainsKey, ReplyCreateNamespace, ReplyDeleteAll,
-    ReplyDeleteNamespace, ReplyExistsNamespace, ReplyFindKeyValuesByPrefix, ReplyFindKeysByPrefix,
-    ReplyListAll, ReplyReadMultiValues, ReplyReadValue, ReplySpecificChunk,
-    ReplyWriteBatchExtended, RequestContainsKey, RequestCreateNamespace, RequestDeleteAll,
-    RequestDeleteNamespace, RequestExistsNamespace, RequestFindKeyValuesByPrefix,
-    RequestFindKeysByPrefix, RequestListAll, RequestReadMultiValues, RequestReadValue,
-    RequestSpecificChunk, RequestWriteBatchExtended,
+    KeyBound, KeyValue, OptValue, RangeQuery, ReplyContainsKey, ReplyCreateNamespace,
+    ReplyDeleteAll, ReplyDeleteNamespace, ReplyExistsNamespace, ReplyFindKeyValuesByPrefix,
+    ReplyFindKeyValuesInRange, ReplyFindKeysByPrefix, ReplyFindKeysInRange, ReplyListAll,
+    ReplyReadMultiValues, ReplyReadValue, ReplySpecificChunk, ReplyWriteBatchExtended,
+    RequestContainsKey, RequestCreateNamespace, RequestDeleteAll, RequestDeleteNamespace,
+    RequestExistsNamespace, RequestFindKeyValuesByPrefix, RequestFindKeyValuesInRange,
+    RequestFindKeysByPrefix, RequestFindKeysInRange, RequestListAll, RequestReadMultiValues,
+    RequestReadValue, RequestSpecificChunk, RequestWriteBatchExtended,
 };
 
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -36,6 +40,33 @@ pub mod key_value_store {
     tonic::include_proto!("key_value_store.v1");
 }
 
+fn key_bound_to_bound(key_bound: Option<KeyBound>) -> Bound<Vec<u8>> {
+    match key_bound.and_then(|key_bound| key_bound.bound) {
+        Some(key_bound::Bound::Included(key)) => Bound::Included(key),
+        Some(key_bound::Bound::Excluded(key)) => Bound::Excluded(key),
+        None => Bound::Unbounded,
+    }
+}
+
+fn query_to_range(query: Option<RangeQuery>) -> Result<KeyRange, Status> {
+    let query = query.ok_or_else(|| Status::invalid_argument("missing range"))?;
+    let limit = query
+        .limit
+        .map(usize::try_from)
+        .transpose()
+        .map_err(|_e| Status::invalid_argument("limit"))?;
+    Ok(KeyRange {
+        start: key_bound_to_bound(query.start),
+        end: key_bound_to_bound(query.end),
+        direction: if query.reverse {
+            Direction::Reverse
+        } else {
+            Direction::Forward
+        },
+        limit,
+    })
+}
+
 enum ServiceStoreServerInternal {
     Memory(MemoryStore),
     /// The RocksDb key value store
@@ -132,6 +163,42 @@ impl ServiceStoreServer {
         }
     }
 
+    pub async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<Vec<u8>>, Status> {
+        match &self.store {
+            ServiceStoreServerInternal::Memory(store) => store
+                .find_keys_in_range(key_prefix, range)
+                .await
+                .map_err(|_e| Status::not_found("find_keys_in_range")),
+            #[cfg(feature = "rocksdb")]
+            ServiceStoreServerInternal::RocksDb(store) => store
+                .find_keys_in_range(key_prefix, range)
+                .await
+                .map_err(|_e| Status::not_found("find_keys_in_range")),
+        }
+    }
+
+    pub async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Status> {
+        match &self.store {
+            ServiceStoreServerInternal::Memory(store) => store
+                .find_key_values_in_range(key_prefix, range)
+                .await
+                .map_err(|_e| Status::not_found("find_key_values_in_range")),
+            #[cfg(feature = "rocksdb")]
+            ServiceStoreServerInternal::RocksDb(store) => store
+                .find_key_values_in_range(key_prefix, range)
+                .await
+                .map_err(|_e| Status::not_found("find_key_values_in_range")),
+        }
+    }
+
     pub async fn write_batch(&self, batch: Batch) -> Result<(), Status> {
         match &self.store {
             ServiceStoreServerInternal::Memory(store) => store
@@ -358,6 +425,68 @@ impl StoreProcessor for ServiceStoreServer {
         Ok(Response::new(response))
     }
 
+    async fn process_find_keys_in_range(
+        &self,
+        request: Request<RequestFindKeysInRange>,
+    ) -> Result<Response<ReplyFindKeysInRange>, Status> {
+        let request = request.into_inner();
+        let RequestFindKeysInRange { key_prefix, range } = request;
+        let range = query_to_range(range)?;
+        let keys = self.find_keys_in_range(&key_prefix, &range).await?;
+        let size = keys.iter().map(|x| x.len()).sum::<usize>();
+        let response = if size < MAX_PAYLOAD_SIZE {
+            ReplyFindKeysInRange {
+                keys,
+                message_index: 0,
+                num_chunks: 0,
+            }
+        } else {
+            let (message_index, num_chunks) = self.insert_pending_read(keys).await;
+            ReplyFindKeysInRange {
+                keys: Vec::default(),
+                message_index,
+                num_chunks,
+            }
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn process_find_key_values_in_range(
+        &self,
+        request: Request<RequestFindKeyValuesInRange>,
+    ) -> Result<Response<ReplyFindKeyValuesInRange>, Status> {
+        let request = request.into_inner();
+        let RequestFindKeyValuesInRange { key_prefix, range } = request;
+        let range = query_to_range(range)?;
+        let key_values = self.find_key_values_in_range(&key_prefix, &range).await?;
+        let size = key_values
+            .iter()
+            .map(|x| x.0.len() + x.1.len())
+            .sum::<usize>();
+        let response = if size < MAX_PAYLOAD_SIZE {
+            let key_values = key_values
+                .into_iter()
+                .map(|x| KeyValue {
+                    key: x.0,
+                    value: x.1,
+                })
+                .collect::<Vec<_>>();
+            ReplyFindKeyValuesInRange {
+                key_values,
+                message_index: 0,
+                num_chunks: 0,
+            }
+        } else {
+            let (message_index, num_chunks) = self.insert_pending_read(key_values).await;
+            ReplyFindKeyValuesInRange {
+                key_values: Vec::default(),
+                message_index,
+                num_chunks,
+            }
+        };
+        Ok(Response::new(response))
+    }
+
     async fn process_write_batch_extended(
         &self,
         request: Request<RequestWriteBatchExtended>,
@@ -1,25 +1,28 @@
 // Copyright (c) Zefchain Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{fmt::Debug, sync::Arc};
+use std::{collections::BTreeSet, fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
 use dashmap::DashMap;
 use linera_base::{
     crypto::CryptoHash,
-    data_types::{Blob, HashedBlob, TimeDelta, Timestamp},
-    identifiers::{BlobId, ChainId},
+    data_types::{Blob, BlockHeight, HashedBlob, TimeDelta, Timestamp},
+    identifiers::{BlobId, ChainId, EventId, StreamId},
 };
 use linera_chain::{
-    data_types::{Certificate, CertificateValue, HashedCertificateValue, LiteCertificate},
+    data_types::{
+        Certificate, CertificateValue, EventRecord, HashedCertificateValue, LiteCertificate,
+    },
     ChainStateView,
 };
 use linera_execution::{
-    ExecutionRuntimeConfig, UserApplicationId, UserContractCode, UserServiceCode, WasmRuntime,
+    committee::Epoch, ExecutionRuntimeConfig, UserApplicationId, UserContractCode, UserServiceCode,
+    WasmRuntime,
 };
 use linera_views::{
     batch::Batch,
-    common::{AdminKeyValueStore, ContextFromStore, KeyValueStore},
+    common::{AdminKeyValueStore, ContextFromStore, KeyIterable, KeyValueIterable, KeyValueStore},
     value_splitting::DatabaseConsistencyError,
     views::{View, ViewError},
 };
@@ -97,6 +100,30 @@ pub static READ_BLOB_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
     .expect("Counter creation should not fail")
 });
 
+/// The metric counting how often an event is read from storage.
+#[cfg(with_metrics)]
+#[doc(hidden)]
+pub static READ_EVENT_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
+    prometheus_util::register_int_counter_vec(
+        "read_event",
+        "The metric counting how often an event is read from storage",
+        &[],
+    )
+    .expect("Counter creation should not fail")
+});
+
+/// The metric counting how often a random seed is read from storage.
+#[cfg(with_metrics)]
+#[doc(hidden)]
+pub static READ_RANDOM_SEED_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
+    prometheus_util::register_int_counter_vec(
+        "read_random_seed",
+        "The metric counting how often a random seed is read from storage",
+        &[],
+    )
+    .expect("Counter creation should not fail")
+});
+
 /// The metric counting how often a hashed certificate value is written to storage.
 #[cfg(with_metrics)]
 #[doc(hidden)]
@@ -121,6 +148,30 @@ pub static WRITE_BLOB_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
     .expect("Counter creation should not fail")
 });
 
+/// The metric counting how often an event is written to storage.
+#[cfg(with_metrics)]
+#[doc(hidden)]
+pub static WRITE_EVENT_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
+    prometheus_util::register_int_counter_vec(
+        "write_event",
+        "The metric counting how often an event is written to storage",
+        &[],
+    )
+    .expect("Counter creation should not fail")
+});
+
+/// The metric counting how often a random seed is written to storage.
+#[cfg(with_metrics)]
+#[doc(hidden)]
+pub static WRITE_RANDOM_SEED_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
+    prometheus_util::register_int_counter_vec(
+        "write_random_seed",
+        "The metric counting how often a random seed is written to storage",
+        &[],
+    )
+    .expect("Counter creation should not fail")
+});
+
 /// The metric counting how often a certificate is read from storage.
 #[cfg(with_metrics)]
 #[doc(hidden)]
@@ -145,6 +196,18 @@ pub static WRITE_CERTIFICATE_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
     .expect("Counter creation should not fail")
 });
 
+/// The metric counting how often a certificate is deleted from storage.
+#[cfg(with_metrics)]
+#[doc(hidden)]
+pub static DELETE_CERTIFICATE_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
+    prometheus_util::register_int_counter_vec(
+        "delete_certificate",
+        "The metric counting how often a certificate is deleted from storage",
+        &[],
+    )
+    .expect("Counter creation should not fail")
+});
+
 /// The latency to load a chain state.
 #[cfg(with_metrics)]
 #[doc(hidden)]
@@ -237,8 +300,20 @@ enum BaseKey {
     Certificate(CryptoHash),
     Value(CryptoHash),
     BlobId(BlobId),
+    Event(EventId),
+    RandomSeed(ChainId, BlockHeight),
+    BlockFuel(Epoch, ChainId, BlockHeight),
 }
 
+/// The serialized tag of [`BaseKey::ChainState`], i.e. the prefix of all chain states.
+const CHAIN_STATE_TAG: u8 = 0;
+
+/// The serialized tag of [`BaseKey::BlockFuel`], i.e. the prefix of all fuel records.
+const BLOCK_FUEL_TAG: u8 = 6;
+
+/// The length of a serialized [`ChainId`].
+const CHAIN_ID_LENGTH: usize = 32;
+
 /// A clock that can be used to get the current `Timestamp`.
 #[async_trait]
 pub trait Clock {
@@ -389,6 +464,7 @@ where
             execution_runtime_config: self.execution_runtime_config,
             user_contracts: self.client.user_contracts.clone(),
             user_services: self.client.user_services.clone(),
+            blobs_in_block: Arc::default(),
             _chain_guard: Arc::new(guard),
         };
         let client = self.client.client.clone();
@@ -498,6 +574,125 @@ where
         self.write_batch(batch).await
     }
 
+    async fn read_event(&self, event_id: EventId) -> Result<EventRecord, ViewError> {
+        let event_key = bcs::to_bytes(&BaseKey::Event(event_id.clone()))?;
+        let maybe_event = self
+            .client
+            .client
+            .read_value::<EventRecord>(&event_key)
+            .await?;
+        #[cfg(with_metrics)]
+        READ_EVENT_COUNTER.with_label_values(&[]).inc();
+        maybe_event.ok_or_else(|| ViewError::not_found("value for event ID", event_id))
+    }
+
+    async fn read_events_from_stream(
+        &self,
+        chain_id: ChainId,
+        stream_id: &StreamId,
+        from: u32,
+        limit: u32,
+    ) -> Result<Vec<EventRecord>, ViewError> {
+        // Streams are append-only, so their events have consecutive indices.
+        let keys = (from..from.saturating_add(limit))
+            .map(|index| {
+                bcs::to_bytes(&BaseKey::Event(EventId {
+                    chain_id,
+                    stream_id: stream_id.clone(),
+                    index,
+                }))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let maybe_events = self
+            .client
+            .client
+            .read_multi_values::<EventRecord>(keys)
+            .await?;
+        #[cfg(with_metrics)]
+        READ_EVENT_COUNTER
+            .with_label_values(&[])
+            .inc_by(maybe_events.len() as u64);
+        Ok(maybe_events.into_iter().map_while(|event| event).collect())
+    }
+
+    async fn write_events(
+        &self,
+        chain_id: ChainId,
+        events: &[EventRecord],
+    ) -> Result<(), ViewError> {
+        let mut batch = Batch::new();
+        for event in events {
+            self.add_event_to_batch(chain_id, event, &mut batch)?;
+        }
+        self.write_batch(batch).await
+    }
+
+    async fn read_random_seed(
+        &self,
+        chain_id: ChainId,
+        height: BlockHeight,
+    ) -> Result<CryptoHash, ViewError> {
+        let seed_key = bcs::to_bytes(&BaseKey::RandomSeed(chain_id, height))?;
+        let maybe_seed = self
+            .client
+            .client
+            .read_value::<CryptoHash>(&seed_key)
+            .await?;
+        #[cfg(with_metrics)]
+        READ_RANDOM_SEED_COUNTER.with_label_values(&[]).inc();
+        maybe_seed.ok_or_else(|| ViewError::not_found("random seed of block", (chain_id, height)))
+    }
+
+    async fn write_random_seed(
+        &self,
+        chain_id: ChainId,
+        height: BlockHeight,
+        seed: CryptoHash,
+    ) -> Result<(), ViewError> {
+        #[cfg(with_metrics)]
+        WRITE_RANDOM_SEED_COUNTER.with_label_values(&[]).inc();
+        let mut batch = Batch::new();
+        let seed_key = bcs::to_bytes(&BaseKey::RandomSeed(chain_id, height))?;
+        batch.put_key_value(seed_key, &seed)?;
+        self.write_batch(batch).await
+    }
+
+    async fn write_block_fuel(
+        &self,
+        epoch: Epoch,
+        chain_id: ChainId,
+        height: BlockHeight,
+        fuel: u64,
+    ) -> Result<(), ViewError> {
+        let mut batch = Batch::new();
+        let fuel_key = bcs::to_bytes(&BaseKey::BlockFuel(epoch, chain_id, height))?;
+        batch.put_key_value(fuel_key, &fuel)?;
+        self.write_batch(batch).await
+    }
+
+    async fn read_average_block_fuel(&self, epoch: Epoch) -> Result<Option<u64>, ViewError> {
+        let mut prefix = vec![BLOCK_FUEL_TAG];
+        prefix.extend(bcs::to_bytes(&epoch)?);
+        let key_values = self
+            .client
+            .client
+            .find_key_values_by_prefix(&prefix)
+            .await?;
+        let mut blocks = 0u128;
+        let mut total_fuel = 0u128;
+        for key_value in key_values.iterator() {
+            let (_, value) = key_value?;
+            total_fuel += u128::from(bcs::from_bytes::<u64>(value)?);
+            blocks += 1;
+        }
+        if blocks == 0 {
+            return Ok(None);
+        }
+        let average =
+            u64::try_from(total_fuel / blocks).expect("The average of u64 values fits in a u64");
+        Ok(Some(average))
+    }
+
     async fn contains_certificate(&self, hash: CryptoHash) -> Result<bool, ViewError> {
         let cert_key = bcs::to_bytes(&BaseKey::Certificate(hash))?;
         let value_key = bcs::to_bytes(&BaseKey::Value(hash))?;
@@ -546,6 +741,31 @@ where
         self.write_batch(batch).await
     }
 
+    async fn delete_certificates(&self, hashes: &[CryptoHash]) -> Result<(), ViewError> {
+        let mut batch = Batch::new();
+        for hash in hashes {
+            self.add_certificate_deletion_to_batch(*hash, &mut batch)?;
+        }
+        self.write_batch(batch).await
+    }
+
+    async fn list_chain_ids(&self) -> Result<Vec<ChainId>, ViewError> {
+        let keys = self
+            .client
+            .client
+            .find_keys_by_prefix(&[CHAIN_STATE_TAG])
+            .await?;
+        let mut chain_ids = BTreeSet::new();
+        for key in keys.iterator() {
+            // Every key of a chain state starts with the serialized chain ID.
+            let chain_id_bytes = key?
+                .get(..CHAIN_ID_LENGTH)
+                .ok_or(ViewError::InconsistentEntries)?;
+            chain_ids.insert(bcs::from_bytes::<ChainId>(chain_id_bytes)?);
+        }
+        Ok(chain_ids.into_iter().collect())
+    }
+
     fn wasm_runtime(&self) -> Option<WasmRuntime> {
         self.client.wasm_runtime
     }
@@ -585,6 +805,24 @@ where
         Ok(())
     }
 
+    fn add_event_to_batch(
+        &self,
+        chain_id: ChainId,
+        event: &EventRecord,
+        batch: &mut Batch,
+    ) -> Result<(), ViewError> {
+        #[cfg(with_metrics)]
+        WRITE_EVENT_COUNTER.with_label_values(&[]).inc();
+        let event_id = EventId {
+            chain_id,
+            stream_id: event.stream_id.clone(),
+            index: event.index,
+        };
+        let event_key = bcs::to_bytes(&BaseKey::Event(event_id))?;
+        batch.put_key_value(event_key, event)?;
+        Ok(())
+    }
+
     fn add_certificate_to_batch(
         &self,
         certificate: &Certificate,
@@ -600,6 +838,20 @@ where
         Ok(())
     }
 
+    fn add_certificate_deletion_to_batch(
+        &self,
+        hash: CryptoHash,
+        batch: &mut Batch,
+    ) -> Result<(), ViewError> {
+        #[cfg(with_metrics)]
+        DELETE_CERTIFICATE_COUNTER.with_label_values(&[]).inc();
+        let cert_key = bcs::to_bytes(&BaseKey::Certificate(hash))?;
+        let value_key = bcs::to_bytes(&BaseKey::Value(hash))?;
+        batch.delete_key(cert_key);
+        batch.delete_key(value_key);
+        Ok(())
+    }
+
     async fn write_batch(&self, batch: Batch) -> Result<(), ViewError> {
         self.client.client.write_batch(batch, &[]).await?;
         Ok(())
@@ -0,0 +1,332 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline verification of the chains and certificates present in a storage.
+
+use std::collections::BTreeMap;
+
+use linera_base::{crypto::CryptoHash, data_types::BlockHeight, identifiers::ChainId};
+use linera_chain::data_types::{Certificate, CertificateValue};
+use linera_execution::committee::{Committee, Epoch};
+use linera_views::{
+    common::{Context, KeyValueStore, WritableKeyValueStore as _},
+    journaling::JournalStatus,
+    views::{CryptoHashView, ViewError},
+};
+use serde::Serialize;
+
+use crate::Storage;
+
+#[cfg(test)]
+#[path = "unit_tests/integrity.rs"]
+mod tests;
+
+/// A problem found while checking the integrity of a chain.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IntegrityIssue {
+    /// The chain state could not be loaded.
+    UnreadableChain { error: String },
+    /// The certificate of a block in the confirmed log is not in the storage. This is
+    /// expected for blocks that were pruned.
+    MissingCertificate {
+        height: BlockHeight,
+        hash: CryptoHash,
+    },
+    /// The certificate of a block could not be read.
+    UnreadableCertificate {
+        height: BlockHeight,
+        hash: CryptoHash,
+        error: String,
+    },
+    /// The certificate does not contain the confirmed block expected at this position of
+    /// the confirmed log.
+    UnexpectedCertificate {
+        height: BlockHeight,
+        hash: CryptoHash,
+    },
+    /// The block does not point to the previous block of the confirmed log.
+    BrokenLink {
+        height: BlockHeight,
+        expected: Option<CryptoHash>,
+        found: Option<CryptoHash>,
+    },
+    /// The signatures of the certificate are not valid for the committee of its epoch.
+    InvalidSignatures {
+        height: BlockHeight,
+        hash: CryptoHash,
+        error: String,
+    },
+    /// The tip of the chain does not match its confirmed log.
+    InconsistentTip {
+        block_hash: Option<CryptoHash>,
+        next_block_height: BlockHeight,
+        log_length: usize,
+    },
+    /// The hash of the execution state does not match the stored or certified state hash.
+    StateHashMismatch {
+        computed: CryptoHash,
+        stored: Option<CryptoHash>,
+        certified: Option<CryptoHash>,
+    },
+    /// A journal was left unresolved in the underlying key-value store.
+    DanglingJournal {
+        pending_blocks: Option<u32>,
+        stored_blocks: usize,
+    },
+}
+
+/// The result of checking a single chain.
+#[derive(Clone, Debug, Serialize)]
+pub struct ChainReport {
+    pub chain_id: ChainId,
+    pub next_block_height: BlockHeight,
+    /// The number of certificates whose signatures were checked.
+    pub verified_certificates: usize,
+    /// The number of certificates from epochs whose committee is no longer known.
+    pub unverified_certificates: usize,
+    pub issues: Vec<IntegrityIssue>,
+    /// The base key of the chain state in the key-value store.
+    #[serde(skip)]
+    pub base_key: Vec<u8>,
+}
+
+/// The result of checking a whole storage.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct IntegrityReport {
+    /// The epochs of the committees used to check signatures.
+    pub known_epochs: Vec<Epoch>,
+    pub chains: Vec<ChainReport>,
+    /// Issues that do not belong to a particular chain.
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityIssue {
+    /// Returns whether the issue indicates a corruption, as opposed to pruned data.
+    pub fn is_error(&self) -> bool {
+        !matches!(self, IntegrityIssue::MissingCertificate { .. })
+    }
+}
+
+impl IntegrityReport {
+    /// Returns whether no corruption was found.
+    pub fn is_healthy(&self) -> bool {
+        self.issues
+            .iter()
+            .chain(self.chains.iter().flat_map(|chain| &chain.issues))
+            .all(|issue| !issue.is_error())
+    }
+}
+
+/// Adds the journals left unresolved in the key-value store of a storage to the report,
+/// for the chains of the report and for the keys without a base key, e.g. certificates.
+pub async fn check_journals<S>(store: &S, report: &mut IntegrityReport) -> Result<(), ViewError>
+where
+    S: KeyValueStore + Sync,
+    ViewError: From<S::Error>,
+{
+    let status = store.inspect_journal(&[]).await?;
+    if let Some(issue) = dangling_journal(status) {
+        report.issues.push(issue);
+    }
+    for chain in &mut report.chains {
+        let status = store.inspect_journal(&chain.base_key).await?;
+        if let Some(issue) = dangling_journal(status) {
+            chain.issues.push(issue);
+        }
+    }
+    Ok(())
+}
+
+fn dangling_journal(status: JournalStatus) -> Option<IntegrityIssue> {
+    status
+        .is_dangling()
+        .then_some(IntegrityIssue::DanglingJournal {
+            pending_blocks: status.pending_blocks,
+            stored_blocks: status.stored_blocks,
+        })
+}
+
+/// Checks the chains and certificates of a storage: the confirmed log of every chain is
+/// walked through the `previous_block_hash` links, certificates are checked against the
+/// committees known to the admin chain, and execution state hashes are recomputed.
+pub struct IntegrityChecker<S> {
+    storage: S,
+    committees: BTreeMap<Epoch, Committee>,
+}
+
+impl<S> IntegrityChecker<S>
+where
+    S: Storage + Clone + Send + Sync + 'static,
+    ViewError: From<S::ContextError>,
+{
+    /// Creates a new [`IntegrityChecker`] using the given committees to check signatures.
+    pub fn new(storage: S, committees: BTreeMap<Epoch, Committee>) -> Self {
+        IntegrityChecker {
+            storage,
+            committees,
+        }
+    }
+
+    /// Creates a new [`IntegrityChecker`] using the committees of the admin chain of the
+    /// chains present in the storage.
+    pub async fn with_admin_committees(storage: S) -> Result<Self, ViewError> {
+        let mut committees = BTreeMap::new();
+        for chain_id in storage.list_chain_ids().await? {
+            let admin_id = *storage
+                .load_chain(chain_id)
+                .await?
+                .execution_state
+                .system
+                .admin_id
+                .get();
+            if let Some(admin_id) = admin_id {
+                let admin_chain = storage.load_chain(admin_id).await?;
+                committees = admin_chain.execution_state.system.committees.get().clone();
+                break;
+            }
+        }
+        Ok(Self::new(storage, committees))
+    }
+
+    /// Checks every chain present in the storage.
+    pub async fn check_all_chains(&self) -> Result<IntegrityReport, ViewError> {
+        let mut report = IntegrityReport {
+            known_epochs: self.committees.keys().copied().collect(),
+            ..IntegrityReport::default()
+        };
+        for chain_id in self.storage.list_chain_ids().await? {
+            report.chains.push(self.check_chain(chain_id).await);
+        }
+        Ok(report)
+    }
+
+    /// Checks a single chain. Failures to read the storage are reported as issues.
+    pub async fn check_chain(&self, chain_id: ChainId) -> ChainReport {
+        let mut report = ChainReport {
+            chain_id,
+            next_block_height: BlockHeight::ZERO,
+            verified_certificates: 0,
+            unverified_certificates: 0,
+            issues: Vec::new(),
+            base_key: Vec::new(),
+        };
+        if let Err(error) = self.check_chain_into(chain_id, &mut report).await {
+            report.issues.push(IntegrityIssue::UnreadableChain {
+                error: error.to_string(),
+            });
+        }
+        report
+    }
+
+    async fn check_chain_into(
+        &self,
+        chain_id: ChainId,
+        report: &mut ChainReport,
+    ) -> Result<(), ViewError> {
+        let chain = self.storage.load_chain(chain_id).await?;
+        report.base_key = chain.context().base_key();
+        let tip = chain.tip_state.get().clone();
+        report.next_block_height = tip.next_block_height;
+        let hashes = chain.confirmed_log.read(..).await?;
+        if tip.block_hash != hashes.last().copied()
+            || usize::try_from(tip.next_block_height)? != hashes.len()
+        {
+            report.issues.push(IntegrityIssue::InconsistentTip {
+                block_hash: tip.block_hash,
+                next_block_height: tip.next_block_height,
+                log_length: hashes.len(),
+            });
+        }
+
+        let mut tip_certificate = None;
+        for (index, hash) in hashes.iter().enumerate() {
+            let height = BlockHeight::from(index as u64);
+            let certificate = match self.storage.read_certificate(*hash).await {
+                Ok(certificate) => certificate,
+                Err(ViewError::NotFound(_)) => {
+                    report.issues.push(IntegrityIssue::MissingCertificate {
+                        height,
+                        hash: *hash,
+                    });
+                    continue;
+                }
+                Err(error) => {
+                    report.issues.push(IntegrityIssue::UnreadableCertificate {
+                        height,
+                        hash: *hash,
+                        error: error.to_string(),
+                    });
+                    continue;
+                }
+            };
+            let previous = index.checked_sub(1).map(|index| hashes[index]);
+            self.check_certificate(chain_id, height, *hash, previous, &certificate, report);
+            if index + 1 == hashes.len() {
+                tip_certificate = Some(certificate);
+            }
+        }
+
+        let computed = chain.execution_state.crypto_hash().await?;
+        let stored = *chain.execution_state_hash.get();
+        let certified = tip_certificate
+            .as_ref()
+            .and_then(|certificate| certificate.value().executed_block())
+            .map(|executed_block| executed_block.outcome.state_hash);
+        if stored.is_some_and(|stored| stored != computed)
+            || certified.is_some_and(|certified| certified != computed)
+        {
+            report.issues.push(IntegrityIssue::StateHashMismatch {
+                computed,
+                stored,
+                certified,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_certificate(
+        &self,
+        chain_id: ChainId,
+        height: BlockHeight,
+        hash: CryptoHash,
+        previous: Option<CryptoHash>,
+        certificate: &Certificate,
+        report: &mut ChainReport,
+    ) {
+        let value = certificate.value();
+        let CertificateValue::ConfirmedBlock { executed_block } = value else {
+            report
+                .issues
+                .push(IntegrityIssue::UnexpectedCertificate { height, hash });
+            return;
+        };
+        let block = &executed_block.block;
+        // Stored values are not re-hashed when they are read.
+        if CryptoHash::new(value) != hash || block.chain_id != chain_id || block.height != height {
+            report
+                .issues
+                .push(IntegrityIssue::UnexpectedCertificate { height, hash });
+            return;
+        }
+        if block.previous_block_hash != previous {
+            report.issues.push(IntegrityIssue::BrokenLink {
+                height,
+                expected: previous,
+                found: block.previous_block_hash,
+            });
+        }
+        let Some(committee) = self.committees.get(&block.epoch) else {
+            report.unverified_certificates += 1;
+            return;
+        };
+        match certificate.clone().check(committee) {
+            Ok(_) => report.verified_certificates += 1,
+            Err(error) => report.issues.push(IntegrityIssue::InvalidSignatures {
+                height,
+                hash,
+                error: error.to_string(),
+            }),
+        }
+    }
+}
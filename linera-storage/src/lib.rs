@@ -7,13 +7,17 @@ mod chain_guards;
 mod db_storage;
 #[cfg(with_dynamodb)]
 mod dynamo_db;
+mod integrity;
 mod memory;
+mod pruning;
+mod replay;
 #[cfg(with_rocksdb)]
 mod rocks_db;
 #[cfg(with_scylladb)]
 mod scylla_db;
 #[cfg(not(target_arch = "wasm32"))]
 mod service;
+mod snapshot;
 
 use std::{fmt::Debug, sync::Arc};
 
@@ -24,11 +28,11 @@ use futures::future;
 use linera_base::{
     crypto::{CryptoHash, PublicKey},
     data_types::{Amount, BlockHeight, HashedBlob, Timestamp},
-    identifiers::{BlobId, ChainDescription, ChainId, GenericApplicationId},
+    identifiers::{BlobId, ChainDescription, ChainId, EventId, GenericApplicationId, StreamId},
     ownership::ChainOwnership,
 };
 use linera_chain::{
-    data_types::{Certificate, ChannelFullName, HashedCertificateValue},
+    data_types::{Certificate, ChannelFullName, EventRecord, HashedCertificateValue},
     ChainError, ChainStateView,
 };
 use linera_execution::{
@@ -64,7 +68,14 @@ pub use crate::scylla_db::ScyllaDbStorage;
 pub use crate::service::ServiceStorage;
 pub use crate::{
     db_storage::{Clock, DbStorage, WallClock},
+    integrity::{check_journals, ChainReport, IntegrityChecker, IntegrityIssue, IntegrityReport},
     memory::MemoryStorage,
+    pruning::{Pruner, PruningSummary, RetentionPolicy},
+    replay::{
+        read_chain_state, replay_block, ComponentDiff, KeyDifference, ReplayError, ReplayReport,
+        StateComponent,
+    },
+    snapshot::{ChainSnapshot, SnapshotError},
 };
 
 /// Communicate with a persistent storage using the "views" abstraction.
@@ -128,6 +139,57 @@ pub trait Storage: Sized {
     /// Writes several blobs
     async fn write_hashed_blobs(&self, blobs: &[HashedBlob]) -> Result<(), ViewError>;
 
+    /// Reads the event with the given ID.
+    async fn read_event(&self, event_id: EventId) -> Result<EventRecord, ViewError>;
+
+    /// Reads at most `limit` events of a stream on the given chain, in emission order,
+    /// starting with the event at index `from`.
+    async fn read_events_from_stream(
+        &self,
+        chain_id: ChainId,
+        stream_id: &StreamId,
+        from: u32,
+        limit: u32,
+    ) -> Result<Vec<EventRecord>, ViewError>;
+
+    /// Writes several events emitted on the given chain.
+    async fn write_events(
+        &self,
+        chain_id: ChainId,
+        events: &[EventRecord],
+    ) -> Result<(), ViewError>;
+
+    /// Reads the random seed revealed by the certificate of a chain's block at the given
+    /// height.
+    async fn read_random_seed(
+        &self,
+        chain_id: ChainId,
+        height: BlockHeight,
+    ) -> Result<CryptoHash, ViewError>;
+
+    /// Writes the random seed revealed by the certificate of a chain's block at the given
+    /// height.
+    async fn write_random_seed(
+        &self,
+        chain_id: ChainId,
+        height: BlockHeight,
+        seed: CryptoHash,
+    ) -> Result<(), ViewError>;
+
+    /// Records the fuel consumed by a chain's block of the given epoch, as executed by this
+    /// validator.
+    async fn write_block_fuel(
+        &self,
+        epoch: Epoch,
+        chain_id: ChainId,
+        height: BlockHeight,
+        fuel: u64,
+    ) -> Result<(), ViewError>;
+
+    /// Returns the average fuel consumed per block by the blocks of the given epoch that this
+    /// validator executed, or `None` if it executed none.
+    async fn read_average_block_fuel(&self, epoch: Epoch) -> Result<Option<u64>, ViewError>;
+
     /// Tests existence of the certificate with the given hash.
     async fn contains_certificate(&self, hash: CryptoHash) -> Result<bool, ViewError>;
 
@@ -140,6 +202,12 @@ pub trait Storage: Sized {
     /// Writes a vector of certificates.
     async fn write_certificates(&self, certificate: &[Certificate]) -> Result<(), ViewError>;
 
+    /// Deletes the certificates and the hashed certificate values with the given hashes.
+    async fn delete_certificates(&self, hashes: &[CryptoHash]) -> Result<(), ViewError>;
+
+    /// Lists the IDs of all the chains with a state in storage.
+    async fn list_chain_ids(&self) -> Result<Vec<ChainId>, ViewError>;
+
     /// Loads the view of a chain state and checks that it is active.
     async fn load_active_chain(
         &self,
@@ -178,6 +246,31 @@ pub trait Storage: Sized {
         Ok(certs)
     }
 
+    /// Reads the confirmed certificates of a chain, starting at block height `start` and
+    /// returning at most `limit` certificates.
+    async fn read_certificates_by_height(
+        &self,
+        chain_id: ChainId,
+        start: BlockHeight,
+        limit: Option<u64>,
+    ) -> Result<Vec<Certificate>, ViewError>
+    where
+        Self: Clone + Send + 'static,
+        ViewError: From<Self::ContextError>,
+    {
+        let chain = self.load_chain(chain_id).await?;
+        let count = chain.confirmed_log.count();
+        let start = usize::try_from(start.0).unwrap_or(usize::MAX).min(count);
+        let end = match limit {
+            Some(limit) => start
+                .saturating_add(usize::try_from(limit).unwrap_or(usize::MAX))
+                .min(count),
+            None => count,
+        };
+        let hashes = chain.confirmed_log.read(start..end).await?;
+        self.read_certificates(hashes).await
+    }
+
     /// Initializes a chain in a simple way (used for testing and to create a genesis state).
     async fn create_chain(
         &self,
@@ -350,9 +443,27 @@ pub struct ChainRuntimeContext<S> {
     execution_runtime_config: ExecutionRuntimeConfig,
     user_contracts: Arc<DashMap<UserApplicationId, UserContractCode>>,
     user_services: Arc<DashMap<UserApplicationId, UserServiceCode>>,
+    /// The blobs declared by a block that is being executed but not confirmed yet.
+    blobs_in_block: Arc<DashMap<BlobId, HashedBlob>>,
     _chain_guard: Arc<ChainGuard>,
 }
 
+impl<S> ChainRuntimeContext<S> {
+    /// Makes the blobs declared by a block readable during its execution, without writing
+    /// them to the storage. Replaces any blobs set for a previous block.
+    pub fn set_blobs_in_block(&self, blobs: impl IntoIterator<Item = HashedBlob>) {
+        self.blobs_in_block.clear();
+        for blob in blobs {
+            self.blobs_in_block.insert(blob.id(), blob);
+        }
+    }
+
+    /// Forgets the blobs set with [`Self::set_blobs_in_block`].
+    pub fn clear_blobs_in_block(&self) {
+        self.blobs_in_block.clear();
+    }
+}
+
 #[async_trait]
 impl<S> ExecutionRuntimeContext for ChainRuntimeContext<S>
 where
@@ -401,4 +512,23 @@ where
             }
         }
     }
+
+    async fn get_blob(&self, blob_id: BlobId) -> Result<HashedBlob, ExecutionError> {
+        if let Some(blob) = self.blobs_in_block.get(&blob_id) {
+            return Ok(blob.clone());
+        }
+        match self.storage.read_hashed_blob(blob_id).await {
+            Ok(blob) => Ok(blob),
+            Err(ViewError::NotFound(_)) => Err(ExecutionError::BlobsNotFound(vec![blob_id])),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn get_random_seed(&self, height: BlockHeight) -> Result<CryptoHash, ExecutionError> {
+        match self.storage.read_random_seed(self.chain_id, height).await {
+            Ok(seed) => Ok(seed),
+            Err(ViewError::NotFound(_)) => Err(ExecutionError::RandomSeedNotFound(height)),
+            Err(error) => Err(error.into()),
+        }
+    }
 }
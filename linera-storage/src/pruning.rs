@@ -0,0 +1,219 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pruning of old certificates and hashed certificate values.
+
+use std::collections::{BTreeSet, HashMap};
+
+use linera_base::{
+    data_types::{BlockHeight, Timestamp},
+    identifiers::ChainId,
+};
+use linera_chain::data_types::ExecutedBlock;
+use linera_execution::{Operation, SystemOperation};
+use linera_views::views::ViewError;
+
+use crate::Storage;
+
+#[cfg(test)]
+#[path = "unit_tests/pruning.rs"]
+mod tests;
+
+/// Which certified blocks of each chain must be kept when pruning.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RetentionPolicy {
+    /// Keeps the given number of most recent blocks of each chain.
+    KeepLastBlocks(u64),
+    /// Keeps the blocks with a timestamp not older than the given one.
+    KeepNewerThan(Timestamp),
+}
+
+/// The outcome of a pruning pass.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PruningSummary {
+    /// The number of chains that were examined.
+    pub chains: usize,
+    /// The number of certificates that were deleted.
+    pub pruned: usize,
+    /// The number of certificates that were written to the archive before being deleted.
+    pub archived: usize,
+    /// The number of certificates outside of the retention policy that are still needed.
+    pub retained: usize,
+}
+
+/// The progress of the pruning of a single chain.
+#[derive(Debug, Default)]
+struct ChainPruningState {
+    /// All certificates below this height have been examined already.
+    next_height: BlockHeight,
+    /// Heights below `next_height` that were kept because their messages were not delivered
+    /// yet.
+    in_flight: BTreeSet<BlockHeight>,
+}
+
+/// Deletes the certificates that are no longer needed, optionally exporting them to an
+/// archive storage first.
+///
+/// A certificate is never deleted if it is the latest block of its chain, if some of its
+/// outgoing messages are still waiting to be delivered, or if it publishes bytecode.
+///
+/// The progress of each chain is only kept in memory, so that a new `Pruner` examines
+/// every chain again from height 0. Reusing the same `Pruner` for periodic passes avoids
+/// this.
+pub struct Pruner<S, A = S> {
+    storage: S,
+    archive: Option<A>,
+    chains: HashMap<ChainId, ChainPruningState>,
+}
+
+impl<S> Pruner<S>
+where
+    S: Storage + Clone + Send + Sync + 'static,
+    ViewError: From<S::ContextError>,
+{
+    /// Creates a new [`Pruner`] deleting certificates from the given storage.
+    pub fn new(storage: S) -> Self {
+        Pruner {
+            storage,
+            archive: None,
+            chains: HashMap::new(),
+        }
+    }
+
+    /// Writes the certificates to the `archive` storage before deleting them.
+    pub fn with_archive<A>(self, archive: A) -> Pruner<S, A> {
+        Pruner {
+            storage: self.storage,
+            archive: Some(archive),
+            chains: self.chains,
+        }
+    }
+}
+
+impl<S, A> Pruner<S, A>
+where
+    S: Storage + Clone + Send + Sync + 'static,
+    A: Storage + Clone + Send + Sync + 'static,
+    ViewError: From<S::ContextError>,
+{
+    /// Prunes every chain present in the storage.
+    pub async fn prune_all_chains(
+        &mut self,
+        policy: RetentionPolicy,
+    ) -> Result<PruningSummary, ViewError> {
+        let mut summary = PruningSummary::default();
+        for chain_id in self.storage.list_chain_ids().await? {
+            let chain_summary = self.prune_chain(chain_id, policy).await?;
+            summary.chains += 1;
+            summary.pruned += chain_summary.pruned;
+            summary.archived += chain_summary.archived;
+            summary.retained += chain_summary.retained;
+        }
+        Ok(summary)
+    }
+
+    /// Prunes the certificates of a single chain.
+    pub async fn prune_chain(
+        &mut self,
+        chain_id: ChainId,
+        policy: RetentionPolicy,
+    ) -> Result<PruningSummary, ViewError> {
+        let mut summary = PruningSummary {
+            chains: 1,
+            ..PruningSummary::default()
+        };
+        let state = self.chains.entry(chain_id).or_default();
+        let (candidates, in_flight) = {
+            let chain = self.storage.load_chain(chain_id).await?;
+            let next_block_height = chain.tip_state.get().next_block_height;
+            // The latest block is always kept.
+            let mut end = next_block_height.saturating_sub(BlockHeight(1));
+            if let RetentionPolicy::KeepLastBlocks(count) = policy {
+                end = end.min(next_block_height.saturating_sub(BlockHeight(count)));
+            }
+            let heights = state
+                .in_flight
+                .iter()
+                .copied()
+                .chain((state.next_height.0..end.0).map(BlockHeight))
+                .collect::<Vec<_>>();
+            let indices = heights
+                .iter()
+                .map(|height| usize::try_from(*height))
+                .collect::<Result<Vec<_>, _>>()?;
+            let hashes = chain.confirmed_log.multi_get(indices).await?;
+            let in_flight = chain
+                .outbox_counters
+                .get()
+                .keys()
+                .copied()
+                .collect::<BTreeSet<_>>();
+            let candidates = heights
+                .into_iter()
+                .zip(hashes)
+                .filter_map(|(height, hash)| Some((height, hash?)))
+                .collect::<Vec<_>>();
+            (candidates, in_flight)
+        }; // Release the chain so that it is not locked while certificates are deleted.
+
+        // The progress is only recorded once the certificates have been deleted.
+        let mut next_height = state.next_height;
+        let mut still_in_flight = BTreeSet::new();
+        let mut hashes = Vec::new();
+        for (height, hash) in candidates {
+            let value = match self.storage.read_hashed_certificate_value(hash).await {
+                Ok(value) => value,
+                Err(ViewError::NotFound(_)) => {
+                    // Already pruned, e.g. by another process.
+                    next_height = next_height.max(height.try_add_one()?);
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+            let Some(executed_block) = value.inner().executed_block() else {
+                continue;
+            };
+            if height >= next_height {
+                if let RetentionPolicy::KeepNewerThan(timestamp) = policy {
+                    if executed_block.block.timestamp >= timestamp {
+                        break;
+                    }
+                }
+                next_height = height.try_add_one()?;
+            }
+            if in_flight.contains(&height) {
+                still_in_flight.insert(height);
+                summary.retained += 1;
+            } else if publishes_bytecode(executed_block) {
+                summary.retained += 1;
+            } else {
+                hashes.push(hash);
+            }
+        }
+
+        if !hashes.is_empty() {
+            if let Some(archive) = &self.archive {
+                let certificates = self.storage.read_certificates(hashes.clone()).await?;
+                archive.write_certificates(&certificates).await?;
+                summary.archived = certificates.len();
+            }
+            self.storage.delete_certificates(&hashes).await?;
+            summary.pruned = hashes.len();
+            tracing::debug!("Pruned {} certificates of chain {chain_id}", summary.pruned);
+        }
+        state.next_height = next_height;
+        state.in_flight = still_in_flight;
+        Ok(summary)
+    }
+}
+
+/// Returns whether the block publishes bytecode. The certificate is then needed to load the
+/// applications using it.
+fn publishes_bytecode(executed_block: &ExecutedBlock) -> bool {
+    executed_block.block.operations.iter().any(|operation| {
+        matches!(
+            operation,
+            Operation::System(SystemOperation::PublishBytecode { .. })
+        )
+    })
+}
@@ -0,0 +1,227 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deterministic re-execution of a block on a copy of its parent state, to find which
+//! application diverged when a block produces an unexpected state hash.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use linera_base::{crypto::CryptoHash, data_types::BlockHeight, ensure, identifiers::ChainId};
+use linera_chain::{
+    data_types::{Certificate, CertificateValue},
+    ChainError, ChainStateView,
+};
+use linera_execution::UserApplicationId;
+use linera_views::{
+    common::Context,
+    views::{RootView, View, ViewError},
+};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{
+    snapshot::{load_key_values, read_key_values},
+    Storage,
+};
+
+#[cfg(test)]
+#[path = "unit_tests/replay.rs"]
+mod tests;
+
+/// An error when replaying a block.
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error(transparent)]
+    ViewError(#[from] ViewError),
+    #[error(transparent)]
+    ChainError(#[from] ChainError),
+    #[error("The certificate is not for a confirmed block")]
+    InvalidCertificate,
+    #[error("The parent state of chain {chain_id} is at height {found}, not {expected}")]
+    UnexpectedHeight {
+        chain_id: ChainId,
+        expected: BlockHeight,
+        found: BlockHeight,
+    },
+}
+
+/// The part of a chain state that a key belongs to.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateComponent {
+    /// The state of the system application.
+    System,
+    /// The state of a user application.
+    User(UserApplicationId),
+    /// The rest of the chain state, e.g. inboxes, outboxes and consensus state.
+    Chain,
+}
+
+/// A key whose value differs between the re-executed state and the reference state.
+/// Keys and values are hex-encoded, and keys are relative to the chain's base key.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct KeyDifference {
+    pub key: String,
+    pub expected: Option<String>,
+    pub computed: Option<String>,
+}
+
+/// The differences found in one part of the chain state.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ComponentDiff {
+    pub component: StateComponent,
+    pub differences: Vec<KeyDifference>,
+}
+
+/// The result of replaying a block.
+#[derive(Clone, Debug, Serialize)]
+pub struct ReplayReport {
+    pub chain_id: ChainId,
+    pub height: BlockHeight,
+    pub certified_state_hash: CryptoHash,
+    pub computed_state_hash: CryptoHash,
+    /// Whether the whole execution outcome, including messages, matches the certificate.
+    pub outcome_matches: bool,
+    /// Whether the state was compared to the successor state, or else to the parent state.
+    pub compared_to_successor: bool,
+    pub diffs: Vec<ComponentDiff>,
+}
+
+impl ReplayReport {
+    /// Returns whether the re-execution reproduced the certified outcome.
+    pub fn is_consistent(&self) -> bool {
+        self.outcome_matches && self.certified_state_hash == self.computed_state_hash
+    }
+}
+
+/// Re-executes the block of `certificate` on the given parent state, using the recorded
+/// oracle responses, and compares the resulting key-value pairs with the successor state
+/// if provided, or else with the parent state to show every key touched by the block.
+///
+/// States are given as the key-value pairs of the chain state, relative to the chain's
+/// base key, e.g. as found in a [`ChainSnapshot`](crate::ChainSnapshot). The storage is
+/// only used to load the bytecode of user applications.
+pub async fn replay_block<S>(
+    storage: &S,
+    parent: &[(Vec<u8>, Vec<u8>)],
+    certificate: &Certificate,
+    successor: Option<&[(Vec<u8>, Vec<u8>)]>,
+) -> Result<ReplayReport, ReplayError>
+where
+    S: Storage + Clone + Send + Sync + 'static,
+    ViewError: From<S::ContextError>,
+{
+    let CertificateValue::ConfirmedBlock { executed_block } = certificate.value() else {
+        return Err(ReplayError::InvalidCertificate);
+    };
+    let block = &executed_block.block;
+    let chain_id = block.chain_id;
+    let extra = storage
+        .load_chain(chain_id)
+        .await?
+        .context()
+        .extra()
+        .clone();
+    let mut chain = load_key_values(parent, extra).await?;
+    let found = chain.tip_state.get().next_block_height;
+    ensure!(
+        found == block.height,
+        ReplayError::UnexpectedHeight {
+            chain_id,
+            expected: block.height,
+            found,
+        }
+    );
+    let oracle_records = executed_block.outcome.oracle_records.clone();
+    let outcome = chain
+        .execute_block(block, block.timestamp, Some(oracle_records))
+        .await?;
+    chain.save().await?;
+    let computed = read_key_values(&chain).await?;
+    let components = component_prefixes(&chain).await?;
+    let reference = successor.unwrap_or(parent);
+    Ok(ReplayReport {
+        chain_id,
+        height: block.height,
+        certified_state_hash: executed_block.outcome.state_hash,
+        computed_state_hash: outcome.state_hash,
+        outcome_matches: outcome == executed_block.outcome,
+        compared_to_successor: successor.is_some(),
+        diffs: diff_key_values(reference, &computed, &components),
+    })
+}
+
+/// Reads the current key-value pairs of a chain state from storage, together with the
+/// height of the next block, in the format expected by [`replay_block`].
+pub async fn read_chain_state<S>(
+    storage: &S,
+    chain_id: ChainId,
+) -> Result<(BlockHeight, Vec<(Vec<u8>, Vec<u8>)>), ViewError>
+where
+    S: Storage + Clone + Send + Sync + 'static,
+    ViewError: From<S::ContextError>,
+{
+    let chain = storage.load_chain(chain_id).await?;
+    let next_block_height = chain.tip_state.get().next_block_height;
+    Ok((next_block_height, read_key_values(&chain).await?))
+}
+
+/// Returns the key prefix of the system application and of each user application.
+async fn component_prefixes<C>(
+    chain: &ChainStateView<C>,
+) -> Result<Vec<(Vec<u8>, StateComponent)>, ViewError>
+where
+    C: Context + Clone + Send + Sync + 'static,
+    ViewError: From<C::Error>,
+{
+    let system = &chain.execution_state.system;
+    let mut prefixes = vec![(system.context().base_key(), StateComponent::System)];
+    let users = &chain.execution_state.users;
+    for application_id in users.indices().await? {
+        if let Some(view) = users.try_load_entry(&application_id).await? {
+            let prefix = view.context().base_key();
+            prefixes.push((prefix, StateComponent::User(application_id)));
+        }
+    }
+    Ok(prefixes)
+}
+
+/// Compares two sets of key-value pairs, grouping the differences by component.
+fn diff_key_values(
+    expected: &[(Vec<u8>, Vec<u8>)],
+    computed: &[(Vec<u8>, Vec<u8>)],
+    components: &[(Vec<u8>, StateComponent)],
+) -> Vec<ComponentDiff> {
+    let expected = expected.iter().cloned().collect::<BTreeMap<_, _>>();
+    let computed = computed.iter().cloned().collect::<BTreeMap<_, _>>();
+    let keys = expected
+        .keys()
+        .chain(computed.keys())
+        .collect::<BTreeSet<_>>();
+    let mut diffs = BTreeMap::<StateComponent, Vec<KeyDifference>>::new();
+    for key in keys {
+        let expected_value = expected.get(key);
+        let computed_value = computed.get(key);
+        if expected_value == computed_value {
+            continue;
+        }
+        // The longest matching prefix is the most specific component.
+        let component = components
+            .iter()
+            .filter(|(prefix, _)| key.starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(StateComponent::Chain, |(_, component)| *component);
+        diffs.entry(component).or_default().push(KeyDifference {
+            key: hex::encode(key),
+            expected: expected_value.map(hex::encode),
+            computed: computed_value.map(hex::encode),
+        });
+    }
+    diffs
+        .into_iter()
+        .map(|(component, differences)| ComponentDiff {
+            component,
+            differences,
+        })
+        .collect()
+}
@@ -0,0 +1,426 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Portable snapshots of the full state of a chain, to bootstrap new nodes without
+//! re-executing every block.
+//!
+//! Only the parts of a snapshot that can be checked against the certified blocks are
+//! imported: the execution state is verified against the certified state hash, and the
+//! block-chaining state, the log of confirmed blocks, the event streams and the consensus
+//! state are rebuilt from the certificates of the chain. The cross-chain state (inboxes,
+//! outboxes, channels) and the log of received certificates cannot be checked against the
+//! certificates of the chain alone, so snapshots of chains with any such state are
+//! rejected.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use linera_base::{
+    crypto::{BcsHashable, CryptoHash},
+    data_types::{ArithmeticError, BlockHeight},
+    ensure,
+    identifiers::ChainId,
+};
+use linera_chain::{
+    data_types::{Certificate, CertificateValue},
+    ChainError, ChainStateView,
+};
+use linera_execution::committee::{Committee, Epoch};
+use linera_views::{
+    batch::Batch,
+    common::{Context, KeyValueIterable},
+    memory::MemoryContext,
+    views::{CryptoHashView, View, ViewError},
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::Storage;
+
+#[cfg(test)]
+#[path = "unit_tests/snapshot.rs"]
+mod tests;
+
+/// The version of the snapshot file format.
+const SNAPSHOT_FORMAT_VERSION: u32 = 2;
+
+/// The number of stream queries used when loading a chain state in memory.
+const MAX_STREAM_QUERIES: usize = 10;
+
+/// A portable snapshot of the full state of a chain after one of its certified blocks.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChainSnapshot {
+    /// The certificate of the latest block included in the snapshot.
+    pub certificate: Certificate,
+    /// The certificates of the earlier blocks of the chain, in order of height.
+    pub block_certificates: Vec<Certificate>,
+    /// The certificates publishing the bytecode of the applications known by the chain.
+    pub bytecode_certificates: Vec<Certificate>,
+    /// The key-value pairs of the chain state, relative to the chain's base key.
+    pub key_values: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl BcsHashable for ChainSnapshot {}
+
+/// The content of a snapshot file.
+#[derive(Serialize, Deserialize)]
+struct SnapshotFile {
+    version: u32,
+    hash: CryptoHash,
+    snapshot: ChainSnapshot,
+}
+
+/// An error when exporting or importing a [`ChainSnapshot`].
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error(transparent)]
+    ViewError(#[from] ViewError),
+    #[error(transparent)]
+    ChainError(#[from] ChainError),
+    #[error(transparent)]
+    ArithmeticError(#[from] ArithmeticError),
+    #[error("Failed to (de)serialize the snapshot: {0}")]
+    Serialization(#[from] bcs::Error),
+    #[error("Unsupported snapshot format version {0}")]
+    UnsupportedVersion(u32),
+    #[error("The snapshot does not match its hash")]
+    CorruptedSnapshot,
+    #[error("Chain {0} does not have any block yet")]
+    EmptyChain(ChainId),
+    #[error("The latest block of chain {chain_id} is at height {found}, not {expected}")]
+    UnexpectedHeight {
+        chain_id: ChainId,
+        expected: BlockHeight,
+        found: BlockHeight,
+    },
+    #[error("The local state of chain {chain_id} is already at height {local_height}")]
+    OutdatedSnapshot {
+        chain_id: ChainId,
+        local_height: BlockHeight,
+    },
+    #[error("The snapshot certificate is not for a confirmed block")]
+    InvalidCertificate,
+    #[error("No committee is known for epoch {0:?}")]
+    UnknownEpoch(Epoch),
+    #[error("The snapshot state does not belong to the certified block")]
+    BlockMismatch,
+    #[error("The state hash {computed} does not match the certified state hash {certified}")]
+    StateHashMismatch {
+        computed: CryptoHash,
+        certified: CryptoHash,
+    },
+    #[error("The snapshot contains {0} that cannot be verified")]
+    UnverifiableState(&'static str),
+}
+
+/// Reads the key-value pairs of a chain state, relative to the chain's base key.
+pub(crate) async fn read_key_values<C>(
+    chain: &ChainStateView<C>,
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ViewError>
+where
+    C: Context + Clone + Send + Sync + 'static,
+    ViewError: From<C::Error>,
+{
+    // The chain is loaded, so no other task is modifying its state meanwhile.
+    let context = chain.context();
+    let key_values = context
+        .find_key_values_by_prefix(&context.base_key())
+        .await?
+        .into_iterator_owned()
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(key_values)
+}
+
+/// Loads a chain state in memory from the key-value pairs returned by [`read_key_values`].
+pub(crate) async fn load_key_values<E>(
+    key_values: &[(Vec<u8>, Vec<u8>)],
+    extra: E,
+) -> Result<ChainStateView<MemoryContext<E>>, ViewError>
+where
+    E: Clone + Send + Sync + 'static,
+{
+    let context = MemoryContext::new(MAX_STREAM_QUERIES, extra);
+    let mut batch = Batch::new();
+    for (key, value) in key_values {
+        batch.put_key_value_bytes(key.clone(), value.clone());
+    }
+    context.write_batch(batch).await?;
+    ChainStateView::load(context).await
+}
+
+impl ChainSnapshot {
+    /// Exports the current state of a chain from storage. If a height is provided, the
+    /// latest block of the chain must be at that height.
+    pub async fn export<S>(
+        storage: &S,
+        chain_id: ChainId,
+        height: Option<BlockHeight>,
+    ) -> Result<Self, SnapshotError>
+    where
+        S: Storage + Clone + Send + Sync + 'static,
+        ViewError: From<S::ContextError>,
+    {
+        let (block_hashes, bytecode_hashes, key_values) = {
+            let chain = storage.load_chain(chain_id).await?;
+            let tip = chain.tip_state.get();
+            tip.block_hash.ok_or(SnapshotError::EmptyChain(chain_id))?;
+            let tip_height = tip.next_block_height.try_sub_one()?;
+            if let Some(expected) = height {
+                ensure!(
+                    expected == tip_height,
+                    SnapshotError::UnexpectedHeight {
+                        chain_id,
+                        expected,
+                        found: tip_height,
+                    }
+                );
+            }
+            let mut bytecode_hashes = BTreeSet::new();
+            chain
+                .execution_state
+                .system
+                .registry
+                .known_applications
+                .for_each_index_value(|_, description| {
+                    bytecode_hashes.insert(description.bytecode_location.certificate_hash);
+                    Ok(())
+                })
+                .await?;
+            let block_hashes = chain.confirmed_log.read(..).await?;
+            let key_values = read_key_values(&chain).await?;
+            (block_hashes, bytecode_hashes, key_values)
+        };
+        // The certificates of pruned blocks are missing, so such chains cannot be exported.
+        let mut block_certificates = storage.read_certificates(block_hashes).await?;
+        let certificate = block_certificates
+            .pop()
+            .ok_or(SnapshotError::EmptyChain(chain_id))?;
+        let bytecode_certificates = storage.read_certificates(bytecode_hashes).await?;
+        Ok(ChainSnapshot {
+            certificate,
+            block_certificates,
+            bytecode_certificates,
+            key_values,
+        })
+    }
+
+    /// Returns the ID of the chain in the snapshot.
+    pub fn chain_id(&self) -> ChainId {
+        self.certificate.value().chain_id()
+    }
+
+    /// Returns the height of the latest block included in the snapshot.
+    pub fn height(&self) -> BlockHeight {
+        self.certificate.value().height()
+    }
+
+    /// Verifies the snapshot and writes it to storage, replacing the local state of the
+    /// chain. The certificate must be signed by one of the given trusted committees, the
+    /// earlier certificates must form a chain of blocks leading to it, and the hash of the
+    /// execution state must match the certified state hash.
+    ///
+    /// Snapshots of chains that have received or sent messages, or that have channel
+    /// subscribers, are rejected, since this state cannot be verified.
+    pub async fn import<S>(
+        &self,
+        storage: &S,
+        committees: &BTreeMap<Epoch, Committee>,
+    ) -> Result<(), SnapshotError>
+    where
+        S: Storage + Clone + Send + Sync + 'static,
+        ViewError: From<S::ContextError>,
+    {
+        let execution_key_values = self.verify(committees).await?;
+        let chain_id = self.chain_id();
+        let chain = storage.load_chain(chain_id).await?;
+        let local_height = chain.tip_state.get().next_block_height;
+        ensure!(
+            local_height <= self.height(),
+            SnapshotError::OutdatedSnapshot {
+                chain_id,
+                local_height,
+            }
+        );
+        storage
+            .write_certificates(&self.bytecode_certificates)
+            .await?;
+        storage.write_certificates(&self.block_certificates).await?;
+        storage.write_certificate(&self.certificate).await?;
+
+        // Only the verified execution state is copied from the snapshot.
+        let context = chain.context().clone();
+        let execution_key = chain.execution_state.context().base_key();
+        let mut batch = Batch::new();
+        batch.delete_key_prefix(context.base_key());
+        for (key, value) in execution_key_values {
+            let key = execution_key.iter().chain(key).copied().collect();
+            batch.put_key_value_bytes(key, value);
+        }
+        context.write_batch(batch).await.map_err(ViewError::from)?;
+        drop(chain);
+
+        // The rest of the chain state is rebuilt from the certified blocks. The context
+        // still holds the lock on the chain.
+        let mut chain = ChainStateView::load(context).await?;
+        let add_count = |counter: u32, count: usize| -> Result<u32, ArithmeticError> {
+            let count = u32::try_from(count).map_err(|_| ArithmeticError::Overflow)?;
+            counter.checked_add(count).ok_or(ArithmeticError::Overflow)
+        };
+        let certificates = self.block_certificates.iter().chain([&self.certificate]);
+        for certificate in certificates {
+            let executed_block = certificate
+                .value()
+                .executed_block()
+                .ok_or(SnapshotError::InvalidCertificate)?;
+            let block = &executed_block.block;
+            let tip = chain.tip_state.get_mut();
+            tip.block_hash = Some(certificate.hash());
+            tip.next_block_height.try_add_assign_one()?;
+            tip.num_incoming_messages =
+                add_count(tip.num_incoming_messages, block.incoming_messages.len())?;
+            tip.num_operations = add_count(tip.num_operations, block.operations.len())?;
+            tip.num_outgoing_messages =
+                add_count(tip.num_outgoing_messages, executed_block.messages().len())?;
+            chain.confirmed_log.push(certificate.hash());
+            // Streams are append-only, so the count of each stream is one past the index of
+            // its latest event.
+            for event in executed_block.outcome.events.iter().flatten() {
+                let count = chain
+                    .event_counts
+                    .get_mut_or_default(&event.stream_id)
+                    .await?;
+                *count = event
+                    .index
+                    .checked_add(1)
+                    .ok_or(ArithmeticError::Overflow)?;
+            }
+            let events = executed_block
+                .outcome
+                .events
+                .iter()
+                .flatten()
+                .cloned()
+                .collect::<Vec<_>>();
+            storage.write_events(chain_id, &events).await?;
+            storage
+                .write_random_seed(chain_id, block.height, certificate.random_seed())
+                .await?;
+        }
+        let state_hash = chain.execution_state.crypto_hash().await?;
+        chain.execution_state_hash.set(Some(state_hash));
+        let maybe_committee = chain.execution_state.system.current_committee().into_iter();
+        chain.manager.get_mut().reset(
+            chain.execution_state.system.ownership.get(),
+            self.height().try_add_one()?,
+            storage.clock().current_time(),
+            maybe_committee.flat_map(|(_, committee)| committee.keys_and_weights()),
+        )?;
+        chain.save().await?;
+        Ok(())
+    }
+
+    /// Checks the certificates and recomputes the state hash of the snapshot in memory.
+    /// Returns the key-value pairs of the execution state, relative to its base key.
+    async fn verify(
+        &self,
+        committees: &BTreeMap<Epoch, Committee>,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, SnapshotError> {
+        let value = self.certificate.value();
+        let CertificateValue::ConfirmedBlock { executed_block } = value else {
+            return Err(SnapshotError::InvalidCertificate);
+        };
+        let epoch = value.epoch();
+        let committee = committees
+            .get(&epoch)
+            .ok_or(SnapshotError::UnknownEpoch(epoch))?;
+        self.certificate.clone().check(committee)?;
+
+        // The earlier blocks are authenticated by the hashes chaining them to the
+        // certified one.
+        let chain_id = self.chain_id();
+        let mut next_block = &executed_block.block;
+        for certificate in self.block_certificates.iter().rev() {
+            let CertificateValue::ConfirmedBlock { executed_block } = certificate.value() else {
+                return Err(SnapshotError::InvalidCertificate);
+            };
+            let block = &executed_block.block;
+            ensure!(
+                next_block.previous_block_hash == Some(certificate.hash())
+                    && block.chain_id == chain_id
+                    && block.height.try_add_one()? == next_block.height,
+                SnapshotError::BlockMismatch
+            );
+            next_block = block;
+        }
+        ensure!(
+            next_block.height == BlockHeight::ZERO && next_block.previous_block_hash.is_none(),
+            SnapshotError::BlockMismatch
+        );
+
+        let mut chain = load_key_values(&self.key_values, ()).await?;
+        let tip = chain.tip_state.get();
+        let description = *chain.execution_state.system.description.get();
+        ensure!(
+            tip.block_hash == Some(self.certificate.hash())
+                && tip.next_block_height == self.height().try_add_one()?
+                && description.map(ChainId::from) == Some(chain_id),
+            SnapshotError::BlockMismatch
+        );
+        ensure!(
+            chain.inboxes.indices().await?.is_empty() && chain.received_log.count() == 0,
+            SnapshotError::UnverifiableState("received messages")
+        );
+        ensure!(
+            chain.outboxes.indices().await?.is_empty() && chain.outbox_counters.get().is_empty(),
+            SnapshotError::UnverifiableState("sent messages")
+        );
+        ensure!(
+            chain.channels.indices().await?.is_empty(),
+            SnapshotError::UnverifiableState("channel subscribers")
+        );
+        let computed = chain.execution_state.crypto_hash().await?;
+        let certified = executed_block.outcome.state_hash;
+        ensure!(
+            computed == certified,
+            SnapshotError::StateHashMismatch {
+                computed,
+                certified,
+            }
+        );
+        let base_key = chain.context().base_key();
+        let execution_key = chain.execution_state.context().base_key();
+        let execution_key = &execution_key[base_key.len()..];
+        let key_values = self
+            .key_values
+            .iter()
+            .filter_map(|(key, value)| {
+                let key = key.strip_prefix(execution_key)?;
+                Some((key.to_vec(), value.clone()))
+            })
+            .collect();
+        Ok(key_values)
+    }
+
+    /// Serializes the snapshot, together with its hash, to be written to a file.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SnapshotError> {
+        let file = SnapshotFile {
+            version: SNAPSHOT_FORMAT_VERSION,
+            hash: CryptoHash::new(self),
+            snapshot: self.clone(),
+        };
+        Ok(bcs::to_bytes(&file)?)
+    }
+
+    /// Deserializes a snapshot file, checking its hash.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let file = bcs::from_bytes::<SnapshotFile>(bytes)?;
+        ensure!(
+            file.version == SNAPSHOT_FORMAT_VERSION,
+            SnapshotError::UnsupportedVersion(file.version)
+        );
+        ensure!(
+            CryptoHash::new(&file.snapshot) == file.hash,
+            SnapshotError::CorruptedSnapshot
+        );
+        Ok(file.snapshot)
+    }
+}
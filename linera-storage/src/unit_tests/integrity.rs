@@ -0,0 +1,177 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use linera_base::{
+    crypto::{CryptoHash, KeyPair},
+    data_types::{Amount, BlockHeight, Round, Timestamp},
+    identifiers::{ChainDescription, ChainId},
+};
+use linera_chain::{
+    data_types::{
+        BlockExecutionOutcome, ExecutedBlock, HashedCertificateValue, SignatureAggregator, Vote,
+    },
+    test::{make_child_block, make_first_block},
+};
+use linera_execution::committee::{Committee, Epoch, ValidatorName};
+use linera_views::views::{CryptoHashView, RootView};
+
+use super::{IntegrityChecker, IntegrityIssue};
+use crate::{MemoryStorage, Storage, TestClock};
+
+/// Creates a chain with `count` blocks signed by a single validator, and returns the
+/// committee and the hashes of the blocks.
+async fn make_chain(
+    storage: &MemoryStorage<TestClock>,
+    chain_id: ChainId,
+    count: u64,
+) -> anyhow::Result<(Committee, Vec<CryptoHash>)> {
+    let key_pair = KeyPair::generate();
+    let committee = Committee::make_simple(vec![ValidatorName(key_pair.public())]);
+    storage
+        .create_chain(
+            committee.clone(),
+            chain_id,
+            ChainDescription::Root(0),
+            key_pair.public(),
+            Amount::from_tokens(10),
+            Timestamp::from(0),
+        )
+        .await?;
+    let mut chain = storage.load_chain(chain_id).await?;
+    let state_hash = chain.execution_state.crypto_hash().await?;
+    let mut hashes = Vec::new();
+    let mut previous: Option<HashedCertificateValue> = None;
+    for _ in 0..count {
+        let block = match &previous {
+            None => make_first_block(chain_id),
+            Some(parent) => make_child_block(parent),
+        };
+        let value = HashedCertificateValue::new_confirmed(ExecutedBlock {
+            block,
+            outcome: BlockExecutionOutcome {
+                state_hash,
+                ..BlockExecutionOutcome::default()
+            },
+        });
+        let vote = Vote::new(value.clone(), Round::Fast, &key_pair);
+        let certificate = SignatureAggregator::new(value.clone(), Round::Fast, &committee)
+            .append(vote.validator, vote.signature)?
+            .expect("a single validator forms a quorum");
+        storage.write_certificate(&certificate).await?;
+        chain.confirmed_log.push(value.hash());
+        hashes.push(value.hash());
+        previous = Some(value);
+    }
+    let tip = chain.tip_state.get_mut();
+    tip.block_hash = hashes.last().copied();
+    tip.next_block_height = BlockHeight(count);
+    chain.save().await?;
+    Ok((committee, hashes))
+}
+
+#[tokio::test]
+async fn test_integrity_of_healthy_storage() -> anyhow::Result<()> {
+    let storage = MemoryStorage::make_test_storage(None).await;
+    let chain_id = ChainId::root(0);
+    make_chain(&storage, chain_id, 3).await?;
+
+    let checker = IntegrityChecker::with_admin_committees(storage).await?;
+    let report = checker.check_all_chains().await?;
+    assert!(report.is_healthy(), "{report:?}");
+    assert_eq!(report.known_epochs, vec![Epoch::ZERO]);
+    assert_eq!(report.chains.len(), 1);
+    assert_eq!(report.chains[0].next_block_height, BlockHeight(3));
+    assert_eq!(report.chains[0].verified_certificates, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_integrity_issues() -> anyhow::Result<()> {
+    let storage = MemoryStorage::make_test_storage(None).await;
+    let chain_id = ChainId::root(0);
+    let (committee, hashes) = make_chain(&storage, chain_id, 3).await?;
+
+    // A pruned certificate is reported, but is not an error.
+    storage.delete_certificates(&hashes[..1]).await?;
+    let committees = BTreeMap::from([(Epoch::ZERO, committee)]);
+    let checker = IntegrityChecker::new(storage.clone(), committees);
+    let report = checker.check_chain(chain_id).await;
+    assert_eq!(
+        report.issues,
+        vec![IntegrityIssue::MissingCertificate {
+            height: BlockHeight(0),
+            hash: hashes[0],
+        }]
+    );
+    assert!(!report.issues[0].is_error());
+
+    // The state was modified without executing a block.
+    let mut chain = storage.load_chain(chain_id).await?;
+    chain
+        .execution_state
+        .system
+        .balance
+        .set(Amount::from_tokens(1));
+    chain.save().await?;
+    drop(chain);
+    let report = checker.check_chain(chain_id).await;
+    assert!(report
+        .issues
+        .iter()
+        .any(|issue| matches!(issue, IntegrityIssue::StateHashMismatch { .. })));
+
+    // Certificates signed by an unknown committee are rejected.
+    let other_committee = Committee::make_simple(vec![ValidatorName(KeyPair::generate().public())]);
+    let checker = IntegrityChecker::new(storage, BTreeMap::from([(Epoch::ZERO, other_committee)]));
+    let report = checker.check_chain(chain_id).await;
+    let invalid_signatures = report
+        .issues
+        .iter()
+        .filter(|issue| matches!(issue, IntegrityIssue::InvalidSignatures { .. }))
+        .count();
+    assert_eq!(invalid_signatures, 2);
+    Ok(())
+}
+
+/// Tests that a journal left below the value splitting layer of DynamoDB is reported.
+#[cfg(with_dynamodb)]
+#[tokio::test]
+async fn test_dangling_journal_below_value_splitting() -> anyhow::Result<()> {
+    use linera_views::{
+        batch::{SimpleUnorderedBatch, SimplifiedBatch as _},
+        common::AdminKeyValueStore as _,
+        dynamo_db::{create_dynamo_db_test_config, DynamoDbStore, DynamoDbStoreInternal},
+        journaling::DirectWritableKeyValueStore as _,
+        test_utils::generate_test_namespace,
+    };
+
+    use super::{check_journals, IntegrityReport};
+
+    let config = create_dynamo_db_test_config().await;
+    let namespace = generate_test_namespace();
+    let store = DynamoDbStore::recreate_and_connect(&config, &namespace).await?;
+    let mut report = IntegrityReport::default();
+    check_journals(&store, &mut report).await?;
+    assert!(report.is_healthy());
+
+    // Simulate a crash after the journal header was written, by writing it directly to
+    // the inner store: the header of the journal without base key says one block remains.
+    let inner_store = DynamoDbStoreInternal::connect(&config, &namespace).await?;
+    let header_key = [&[0, 1][..], &bcs::to_bytes(&0u32)?].concat();
+    let mut batch = SimpleUnorderedBatch::default();
+    batch.add_insert(header_key, bcs::to_bytes(&1u32)?);
+    inner_store.write_batch(batch).await?;
+
+    check_journals(&store, &mut report).await?;
+    assert_eq!(
+        report.issues,
+        vec![IntegrityIssue::DanglingJournal {
+            pending_blocks: Some(1),
+            stored_blocks: 0,
+        }]
+    );
+    assert!(!report.is_healthy());
+    Ok(())
+}
@@ -0,0 +1,118 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use linera_base::{
+    crypto::CryptoHash,
+    data_types::{BlockHeight, Round, Timestamp},
+    identifiers::ChainId,
+};
+use linera_chain::{
+    data_types::{BlockExecutionOutcome, Certificate, ExecutedBlock, HashedCertificateValue},
+    test::{make_child_block, make_first_block, BlockTestExt},
+};
+use linera_views::views::RootView;
+
+use super::{Pruner, PruningSummary, RetentionPolicy};
+use crate::{MemoryStorage, Storage, TestClock};
+
+/// Writes a chain of `count` certified blocks, one per second, and returns their hashes.
+async fn write_blocks(
+    storage: &MemoryStorage<TestClock>,
+    chain_id: ChainId,
+    count: u64,
+) -> anyhow::Result<Vec<CryptoHash>> {
+    let mut chain = storage.load_chain(chain_id).await?;
+    let mut hashes = Vec::new();
+    let mut previous: Option<HashedCertificateValue> = None;
+    for height in 0..count {
+        let block = match &previous {
+            None => make_first_block(chain_id),
+            Some(parent) => make_child_block(parent),
+        }
+        .with_timestamp(Timestamp::from(height * 1_000_000));
+        let value = HashedCertificateValue::new_confirmed(ExecutedBlock {
+            block,
+            outcome: BlockExecutionOutcome::default(),
+        });
+        let certificate = Certificate::new(value.clone(), Round::Fast, vec![]);
+        storage.write_certificate(&certificate).await?;
+        chain.confirmed_log.push(value.hash());
+        hashes.push(value.hash());
+        previous = Some(value);
+    }
+    chain.tip_state.get_mut().next_block_height = BlockHeight(count);
+    chain.save().await?;
+    Ok(hashes)
+}
+
+#[tokio::test]
+async fn test_prune_keep_last_blocks() -> anyhow::Result<()> {
+    let storage = MemoryStorage::make_test_storage(None).await;
+    let chain_id = ChainId::root(0);
+    let hashes = write_blocks(&storage, chain_id, 5).await?;
+    assert_eq!(storage.list_chain_ids().await?, vec![chain_id]);
+
+    // The messages of block 1 have not been delivered yet.
+    let mut chain = storage.load_chain(chain_id).await?;
+    chain.outbox_counters.get_mut().insert(BlockHeight(1), 1);
+    chain.save().await?;
+    drop(chain);
+
+    let mut pruner = Pruner::new(storage.clone());
+    let policy = RetentionPolicy::KeepLastBlocks(2);
+    let summary = pruner.prune_chain(chain_id, policy).await?;
+    assert_eq!(
+        summary,
+        PruningSummary {
+            chains: 1,
+            pruned: 2,
+            archived: 0,
+            retained: 1,
+        }
+    );
+    for (height, hash) in hashes.iter().enumerate() {
+        let expected = ![0, 2].contains(&height);
+        assert_eq!(storage.contains_certificate(*hash).await?, expected);
+    }
+
+    // Once the messages are delivered, the block is pruned as well.
+    let mut chain = storage.load_chain(chain_id).await?;
+    chain.outbox_counters.get_mut().clear();
+    chain.save().await?;
+    drop(chain);
+
+    let summary = pruner.prune_all_chains(policy).await?;
+    assert_eq!(summary.pruned, 1);
+    assert!(!storage.contains_certificate(hashes[1]).await?);
+    assert!(storage.contains_certificate(hashes[3]).await?);
+    assert!(storage.contains_certificate(hashes[4]).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prune_keep_newer_than_with_archive() -> anyhow::Result<()> {
+    let storage = MemoryStorage::make_test_storage(None).await;
+    let archive = MemoryStorage::make_test_storage(None).await;
+    let chain_id = ChainId::root(0);
+    let hashes = write_blocks(&storage, chain_id, 4).await?;
+
+    let mut pruner = Pruner::new(storage.clone()).with_archive(archive.clone());
+    let policy = RetentionPolicy::KeepNewerThan(Timestamp::from(2_000_000));
+    let summary = pruner.prune_all_chains(policy).await?;
+    assert_eq!(summary.pruned, 2);
+    assert_eq!(summary.archived, 2);
+    for hash in &hashes[..2] {
+        assert!(!storage.contains_certificate(*hash).await?);
+        assert_eq!(archive.read_certificate(*hash).await?.hash(), *hash);
+    }
+    for hash in &hashes[2..] {
+        assert!(storage.contains_certificate(*hash).await?);
+    }
+
+    // Even if everything is outside the retention window, the latest block is kept.
+    let policy = RetentionPolicy::KeepNewerThan(Timestamp::from(u64::MAX));
+    let summary = pruner.prune_all_chains(policy).await?;
+    assert_eq!(summary.pruned, 1);
+    assert!(storage.contains_certificate(hashes[3]).await?);
+    Ok(())
+}
@@ -0,0 +1,69 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use linera_base::{
+    crypto::{CryptoHash, KeyPair},
+    data_types::{Amount, Round, Timestamp},
+    identifiers::{ChainDescription, ChainId},
+};
+use linera_chain::{
+    data_types::{Certificate, ExecutedBlock, HashedCertificateValue},
+    test::{make_first_block, BlockTestExt as _},
+};
+use linera_execution::committee::{Committee, ValidatorName};
+
+use super::{replay_block, StateComponent};
+use crate::{snapshot::read_key_values, MemoryStorage, Storage};
+
+#[tokio::test]
+async fn test_replay_block() -> anyhow::Result<()> {
+    let storage = MemoryStorage::make_test_storage(None).await;
+    let chain_id = ChainId::root(0);
+    let key_pair = KeyPair::generate();
+    let committee = Committee::make_simple(vec![ValidatorName(key_pair.public())]);
+    storage
+        .create_chain(
+            committee,
+            chain_id,
+            ChainDescription::Root(0),
+            key_pair.public(),
+            Amount::from_tokens(10),
+            Timestamp::from(0),
+        )
+        .await?;
+
+    // Execute the block once, without saving the result, to obtain the certified outcome.
+    let block = make_first_block(chain_id)
+        .with_simple_transfer(ChainId::root(1), Amount::ONE)
+        .with_timestamp(1);
+    let mut chain = storage.load_chain(chain_id).await?;
+    let parent = read_key_values(&chain).await?;
+    let outcome = chain.execute_block(&block, block.timestamp, None).await?;
+    drop(chain);
+
+    let value = HashedCertificateValue::new_confirmed(ExecutedBlock {
+        block: block.clone(),
+        outcome: outcome.clone(),
+    });
+    let certificate = Certificate::new(value, Round::Fast, vec![]);
+    let report = replay_block(&storage, &parent, &certificate, None).await?;
+    assert!(report.is_consistent(), "{report:?}");
+    assert!(!report.compared_to_successor);
+    assert!(report
+        .diffs
+        .iter()
+        .any(|diff| diff.component == StateComponent::System));
+
+    // A certificate with a different state hash is reported.
+    let mut tampered = outcome;
+    tampered.state_hash = CryptoHash::test_hash("tampered");
+    let value = HashedCertificateValue::new_confirmed(ExecutedBlock {
+        block,
+        outcome: tampered,
+    });
+    let certificate = Certificate::new(value, Round::Fast, vec![]);
+    let report = replay_block(&storage, &parent, &certificate, None).await?;
+    assert!(!report.is_consistent());
+    assert!(!report.outcome_matches);
+    Ok(())
+}
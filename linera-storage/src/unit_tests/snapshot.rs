@@ -0,0 +1,251 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use linera_base::{
+    crypto::{CryptoHash, KeyPair},
+    data_types::{Amount, BlockHeight, Round, Timestamp},
+    identifiers::{ChainDescription, ChainId, EventId, GenericApplicationId, StreamId, StreamName},
+};
+use linera_chain::{
+    data_types::{
+        BlockExecutionOutcome, Certificate, ChainAndHeight, ChannelFullName, EventRecord,
+        ExecutedBlock, HashedCertificateValue, Origin, SignatureAggregator, Target, Vote,
+    },
+    test::{make_child_block, make_first_block},
+};
+use linera_execution::{
+    committee::{Committee, Epoch, ValidatorName},
+    system::SystemChannel,
+};
+use linera_views::views::{CryptoHashView, RootView};
+
+use super::{ChainSnapshot, SnapshotError, SnapshotFile, SNAPSHOT_FORMAT_VERSION};
+use crate::{MemoryStorage, Storage, TestClock};
+
+/// The stream of the events emitted by the test blocks.
+fn test_stream_id() -> StreamId {
+    StreamId {
+        application_id: GenericApplicationId::System,
+        stream_name: StreamName(b"test".to_vec()),
+    }
+}
+
+/// Creates a chain with the given number of certified blocks, each emitting one event, and
+/// returns the committee that signed them.
+async fn make_chain_with_blocks(
+    storage: &MemoryStorage<TestClock>,
+    chain_id: ChainId,
+    count: u64,
+) -> anyhow::Result<Committee> {
+    let key_pair = KeyPair::generate();
+    let committee = Committee::make_simple(vec![ValidatorName(key_pair.public())]);
+    storage
+        .create_chain(
+            committee.clone(),
+            chain_id,
+            ChainDescription::Root(0),
+            key_pair.public(),
+            Amount::from_tokens(10),
+            Timestamp::from(0),
+        )
+        .await?;
+    let mut chain = storage.load_chain(chain_id).await?;
+    let state_hash = chain.execution_state.crypto_hash().await?;
+    let mut block = make_first_block(chain_id);
+    for index in 0..count {
+        let event = EventRecord {
+            stream_id: test_stream_id(),
+            index: u32::try_from(index)?,
+            key: Vec::new(),
+            value: vec![index as u8],
+        };
+        storage.write_events(chain_id, &[event.clone()]).await?;
+        chain
+            .event_counts
+            .insert(&event.stream_id, event.index + 1)?;
+        let value = HashedCertificateValue::new_confirmed(ExecutedBlock {
+            block,
+            outcome: BlockExecutionOutcome {
+                state_hash,
+                events: vec![vec![event]],
+                ..BlockExecutionOutcome::default()
+            },
+        });
+        let vote = Vote::new(value.clone(), Round::Fast, &key_pair);
+        let certificate = SignatureAggregator::new(value.clone(), Round::Fast, &committee)
+            .append(vote.validator, vote.signature)?
+            .expect("a single validator forms a quorum");
+        storage.write_certificate(&certificate).await?;
+        chain.confirmed_log.push(value.hash());
+        let tip = chain.tip_state.get_mut();
+        tip.block_hash = Some(value.hash());
+        tip.next_block_height.try_add_assign_one()?;
+        block = make_child_block(&value);
+    }
+    chain.save().await?;
+    Ok(committee)
+}
+
+#[tokio::test]
+async fn test_snapshot_export_import() -> anyhow::Result<()> {
+    let storage = MemoryStorage::make_test_storage(None).await;
+    let chain_id = ChainId::root(0);
+    let committee = make_chain_with_blocks(&storage, chain_id, 3).await?;
+
+    let snapshot = ChainSnapshot::export(&storage, chain_id, Some(BlockHeight(2))).await?;
+    assert_eq!(snapshot.chain_id(), chain_id);
+    assert_eq!(snapshot.height(), BlockHeight(2));
+    assert_eq!(snapshot.block_certificates.len(), 2);
+    let snapshot = ChainSnapshot::from_bytes(&snapshot.to_bytes()?)?;
+
+    let other_storage = MemoryStorage::make_test_storage(None).await;
+    let committees = BTreeMap::from([(Epoch::ZERO, committee)]);
+    snapshot.import(&other_storage, &committees).await?;
+
+    let chain = storage.load_chain(chain_id).await?;
+    let other_chain = other_storage.load_chain(chain_id).await?;
+    assert_eq!(other_chain.tip_state.get(), chain.tip_state.get());
+    assert_eq!(
+        other_chain.confirmed_log.read(..).await?,
+        chain.confirmed_log.read(..).await?
+    );
+    assert_eq!(
+        other_chain.execution_state_hash.get(),
+        chain.execution_state_hash.get()
+    );
+    assert_eq!(
+        other_chain.execution_state.crypto_hash().await?,
+        chain.execution_state.crypto_hash().await?
+    );
+    assert!(
+        other_storage
+            .contains_certificate(snapshot.certificate.hash())
+            .await?
+    );
+    // The event streams are rebuilt from the certified blocks.
+    let stream_id = test_stream_id();
+    assert_eq!(other_chain.event_counts.get(&stream_id).await?, Some(3));
+    let event_id = EventId {
+        chain_id,
+        stream_id,
+        index: 2,
+    };
+    assert_eq!(
+        other_storage.read_event(event_id.clone()).await?,
+        storage.read_event(event_id).await?
+    );
+    drop(other_chain);
+
+    // A snapshot cannot roll back a chain that is already at that height.
+    let result = snapshot.import(&other_storage, &committees).await;
+    assert!(matches!(
+        result,
+        Err(SnapshotError::OutdatedSnapshot { .. })
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_verification() -> anyhow::Result<()> {
+    let storage = MemoryStorage::make_test_storage(None).await;
+    let chain_id = ChainId::root(0);
+    let committee = make_chain_with_blocks(&storage, chain_id, 2).await?;
+    let snapshot = ChainSnapshot::export(&storage, chain_id, None).await?;
+
+    let result = ChainSnapshot::export(&storage, chain_id, Some(BlockHeight(0))).await;
+    assert!(matches!(
+        result,
+        Err(SnapshotError::UnexpectedHeight { .. })
+    ));
+
+    // The certificate must be signed by a trusted committee.
+    let other_storage = MemoryStorage::make_test_storage(None).await;
+    let result = snapshot.import(&other_storage, &BTreeMap::new()).await;
+    assert!(matches!(result, Err(SnapshotError::UnknownEpoch(_))));
+
+    // The file must match its hash.
+    let file = SnapshotFile {
+        version: SNAPSHOT_FORMAT_VERSION,
+        hash: CryptoHash::test_hash("corrupted"),
+        snapshot: snapshot.clone(),
+    };
+    let result = ChainSnapshot::from_bytes(&bcs::to_bytes(&file)?);
+    assert!(matches!(result, Err(SnapshotError::CorruptedSnapshot)));
+
+    // The certificate must be for a confirmed block.
+    let validated = Certificate::new(
+        HashedCertificateValue::new_validated(
+            snapshot
+                .certificate
+                .value()
+                .executed_block()
+                .unwrap()
+                .clone(),
+        ),
+        Round::Fast,
+        vec![],
+    );
+    let tampered = ChainSnapshot {
+        certificate: validated,
+        ..snapshot.clone()
+    };
+    let result = tampered.import(&other_storage, &BTreeMap::new()).await;
+    assert!(matches!(result, Err(SnapshotError::InvalidCertificate)));
+
+    // The earlier blocks must lead to the certified one.
+    let committees = BTreeMap::from([(Epoch::ZERO, committee)]);
+    let tampered = ChainSnapshot {
+        block_certificates: Vec::new(),
+        ..snapshot
+    };
+    let result = tampered.import(&other_storage, &committees).await;
+    assert!(matches!(result, Err(SnapshotError::BlockMismatch)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_rejects_cross_chain_state() -> anyhow::Result<()> {
+    let chain_id = ChainId::root(0);
+    let other_chain_id = ChainId::root(1);
+    // Messages and subscribers cannot be checked against the certificates of the chain,
+    // so snapshots of chains with a received log, an inbox, an outbox or a channel are
+    // rejected.
+    for case in 0..4 {
+        let storage = MemoryStorage::make_test_storage(None).await;
+        let committee = make_chain_with_blocks(&storage, chain_id, 1).await?;
+        let committees = BTreeMap::from([(Epoch::ZERO, committee)]);
+        let mut chain = storage.load_chain(chain_id).await?;
+        match case {
+            0 => chain.received_log.push(ChainAndHeight {
+                chain_id: other_chain_id,
+                height: BlockHeight(0),
+            }),
+            1 => {
+                let origin = Origin::chain(other_chain_id);
+                chain.inboxes.try_load_entry_mut(&origin).await?;
+            }
+            2 => {
+                let target = Target::chain(other_chain_id);
+                chain.outboxes.try_load_entry_mut(&target).await?;
+                chain.outbox_counters.get_mut().insert(BlockHeight(0), 1);
+            }
+            _ => {
+                let name = ChannelFullName {
+                    application_id: GenericApplicationId::System,
+                    name: SystemChannel::Admin.name(),
+                };
+                chain.channels.try_load_entry_mut(&name).await?;
+            }
+        }
+        chain.save().await?;
+        drop(chain);
+
+        let snapshot = ChainSnapshot::export(&storage, chain_id, None).await?;
+        let other_storage = MemoryStorage::make_test_storage(None).await;
+        let result = snapshot.import(&other_storage, &committees).await;
+        assert!(matches!(result, Err(SnapshotError::UnverifiableState(_))));
+    }
+    Ok(())
+}
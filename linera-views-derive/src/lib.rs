@@ -331,6 +331,45 @@ fn generate_crypto_hash_code(input: ItemStruct) -> TokenStream2 {
     }
 }
 
+fn generate_provable_view_code(input: ItemStruct) -> TokenStream2 {
+    let struct_name = input.ident;
+    let (impl_generics, type_generics, maybe_where_clause) = input.generics.split_for_impl();
+    let template_vect = get_seq_parameter(input.generics.clone());
+
+    let (context, context_constraints) = context_and_constraints(&input.attrs, &template_vect);
+
+    let mut where_clause = maybe_where_clause
+        .cloned()
+        .unwrap_or_else(empty_where_clause);
+    where_clause
+        .predicates
+        .extend(context_constraints.predicates);
+
+    let mut field_names = Vec::new();
+    let mut field_hashes = Vec::new();
+    for e in input.fields {
+        let name = e.clone().ident.unwrap();
+        field_names.push(name.to_string());
+        field_hashes.push(quote! { self.#name.hash().await? });
+    }
+
+    quote! {
+        #[linera_views::async_trait]
+        impl #impl_generics linera_views::views::ProvableView<#context> for #struct_name #type_generics
+        #where_clause
+        {
+            fn field_names() -> &'static [&'static str] {
+                &[#(#field_names),*]
+            }
+
+            async fn field_hashes(&self) -> Result<Vec<linera_views::common::HasherOutput>, linera_views::views::ViewError> {
+                use linera_views::views::HashableView;
+                Ok(vec![#(#field_hashes),*])
+            }
+        }
+    }
+}
+
 fn generate_clonable_view_code(input: ItemStruct) -> TokenStream2 {
     let struct_name = input.ident;
     let generics = input.generics;
@@ -407,6 +446,12 @@ pub fn derive_hashable_root_view(input: TokenStream) -> TokenStream {
     stream.into()
 }
 
+#[proc_macro_derive(ProvableView, attributes(view))]
+pub fn derive_provable_view(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ItemStruct);
+    generate_provable_view_code(input).into()
+}
+
 #[proc_macro_derive(ClonableView, attributes(view))]
 pub fn derive_clonable_view(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as ItemStruct);
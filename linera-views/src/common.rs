@@ -22,9 +22,10 @@ use std::{
 };
 
 use async_trait::async_trait;
+use linera_witty::{WitLoad, WitStore, WitType};
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::{batch::Batch, views::ViewError};
+use crate::{batch::Batch, journaling::JournalStatus, views::ViewError};
 
 #[cfg(test)]
 #[path = "unit_tests/common_tests.rs"]
@@ -101,6 +102,172 @@ pub fn get_interval(key_prefix: Vec<u8>) -> (Bound<Vec<u8>>, Bound<Vec<u8>>) {
     (Included(key_prefix), upper_bound)
 }
 
+/// Tests whether `key` is above the lower bound `start` of an interval.
+pub(crate) fn is_above_start(start: &Bound<Vec<u8>>, key: &[u8]) -> bool {
+    match start {
+        Included(start) => key >= start.as_slice(),
+        Excluded(start) => key > start.as_slice(),
+        Unbounded => true,
+    }
+}
+
+/// Tests whether `key` is below the upper bound `end` of an interval.
+pub(crate) fn is_below_end(end: &Bound<Vec<u8>>, key: &[u8]) -> bool {
+    match end {
+        Included(end) => key <= end.as_slice(),
+        Excluded(end) => key < end.as_slice(),
+        Unbounded => true,
+    }
+}
+
+/// The order in which a range query returns its keys.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, WitType, WitLoad, WitStore)]
+pub enum Direction {
+    /// The keys are returned in increasing lexicographic order.
+    #[default]
+    Forward,
+    /// The keys are returned in decreasing lexicographic order.
+    Reverse,
+}
+
+/// A bound of a [`KeyRange`], in a form that can be passed to and from applications.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, WitType, WitLoad, WitStore)]
+pub enum KeyBound {
+    /// The key is included in the range.
+    Included(Vec<u8>),
+    /// The key is excluded from the range.
+    Excluded(Vec<u8>),
+    /// The range is not bounded on this side.
+    Unbounded,
+}
+
+impl From<Bound<Vec<u8>>> for KeyBound {
+    fn from(bound: Bound<Vec<u8>>) -> Self {
+        match bound {
+            Included(key) => KeyBound::Included(key),
+            Excluded(key) => KeyBound::Excluded(key),
+            Unbounded => KeyBound::Unbounded,
+        }
+    }
+}
+
+impl From<KeyBound> for Bound<Vec<u8>> {
+    fn from(bound: KeyBound) -> Self {
+        match bound {
+            KeyBound::Included(key) => Included(key),
+            KeyBound::Excluded(key) => Excluded(key),
+            KeyBound::Unbounded => Unbounded,
+        }
+    }
+}
+
+/// A range query over the keys sharing a common prefix.
+///
+/// The bounds are expressed relative to the prefix, i.e. they apply to the keys once the
+/// prefix is removed. At most `limit` keys are returned, starting from the lower bound
+/// for a [`Direction::Forward`] query and from the upper bound for a
+/// [`Direction::Reverse`] one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyRange {
+    /// The lower bound of the range.
+    pub start: Bound<Vec<u8>>,
+    /// The upper bound of the range.
+    pub end: Bound<Vec<u8>>,
+    /// The order in which the keys are returned.
+    pub direction: Direction,
+    /// The maximal number of keys returned, if any.
+    pub limit: Option<usize>,
+}
+
+impl Default for KeyRange {
+    fn default() -> Self {
+        Self::new(Unbounded, Unbounded)
+    }
+}
+
+impl KeyRange {
+    /// Creates a forward query without limit for the keys between `start` and `end`.
+    pub fn new(start: Bound<Vec<u8>>, end: Bound<Vec<u8>>) -> Self {
+        KeyRange {
+            start,
+            end,
+            direction: Direction::Forward,
+            limit: None,
+        }
+    }
+
+    /// Returns the same query with the keys in decreasing order.
+    pub fn reverse(mut self) -> Self {
+        self.direction = Direction::Reverse;
+        self
+    }
+
+    /// Returns the same query returning at most `limit` keys.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Tests whether `key`, relative to the prefix, lies between the bounds of the range.
+    pub fn contains(&self, key: &[u8]) -> bool {
+        is_above_start(&self.start, key) && is_below_end(&self.end, key)
+    }
+
+    /// Tests whether the bounds of the range are such that the query returns nothing.
+    pub fn is_empty(&self) -> bool {
+        if self.limit == Some(0) {
+            return true;
+        }
+        match (&self.start, &self.end) {
+            (Included(start), Included(end)) => start > end,
+            (Included(start) | Excluded(start), Included(end) | Excluded(end)) => start >= end,
+            _ => false,
+        }
+    }
+
+    /// Returns the bounds of the range for the full keys, i.e. including `key_prefix`.
+    pub fn bounds_with_prefix(&self, key_prefix: &[u8]) -> (Bound<Vec<u8>>, Bound<Vec<u8>>) {
+        let with_prefix = |key: &Vec<u8>| {
+            let mut full_key = key_prefix.to_vec();
+            full_key.extend_from_slice(key);
+            full_key
+        };
+        let start = match &self.start {
+            Included(start) => Included(with_prefix(start)),
+            Excluded(start) => Excluded(with_prefix(start)),
+            Unbounded => Included(key_prefix.to_vec()),
+        };
+        let end = match &self.end {
+            Included(end) => Included(with_prefix(end)),
+            Excluded(end) => Excluded(with_prefix(end)),
+            Unbounded => get_upper_bound(key_prefix),
+        };
+        (start, end)
+    }
+
+    /// Selects the entries returned by the query from `entries`, which are the entries
+    /// within the bounds of the range in increasing order.
+    pub fn select<T>(&self, entries: impl DoubleEndedIterator<Item = T>) -> Vec<T> {
+        let limit = self.limit.unwrap_or(usize::MAX);
+        match self.direction {
+            Direction::Forward => entries.take(limit).collect(),
+            Direction::Reverse => entries.rev().take(limit).collect(),
+        }
+    }
+
+    /// Returns the query for the keys following `key` in the direction of the range,
+    /// with `count` fewer keys to return. This is used to fetch a range by pages.
+    pub fn after(&self, key: &[u8], count: usize) -> Self {
+        let mut range = self.clone();
+        match self.direction {
+            Direction::Forward => range.start = Excluded(key.to_vec()),
+            Direction::Reverse => range.end = Excluded(key.to_vec()),
+        }
+        range.limit = self.limit.map(|limit| limit.saturating_sub(count));
+        range
+    }
+}
+
 pub(crate) fn from_bytes_opt<V: DeserializeOwned, E>(
     key_opt: &Option<Vec<u8>>,
 ) -> Result<Option<V>, E>
@@ -324,6 +491,24 @@ pub trait LocalReadableKeyValueStore<E> {
     /// Finds the `(key,value)` pairs matching the prefix. The prefix is not included in the returned keys.
     async fn find_key_values_by_prefix(&self, key_prefix: &[u8]) -> Result<Self::KeyValues, E>;
 
+    /// Finds the keys matching the prefix whose remainder lies in the `range`, in the
+    /// direction of the range and up to its limit. The prefix is not included in the
+    /// returned keys.
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, E>;
+
+    /// Finds the `(key,value)` pairs matching the prefix whose key remainder lies in the
+    /// `range`, in the direction of the range and up to its limit. The prefix is not
+    /// included in the returned keys.
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, E>;
+
     // We can't use `async fn` here in the below implementations due to
     // https://github.com/rust-lang/impl-trait-utils/issues/17, but once that bug is fixed
     // we can revert them to `async fn` syntax, which is neater.
@@ -371,6 +556,9 @@ pub trait LocalWritableKeyValueStore<E> {
     /// Clears any journal entry that may remain.
     /// The journal is located at the `base_key`.
     async fn clear_journal(&self, base_key: &[u8]) -> Result<(), E>;
+
+    /// Reads the journal located at the `base_key`, if any, without resolving it.
+    async fn inspect_journal(&self, base_key: &[u8]) -> Result<JournalStatus, E>;
 }
 
 /// Low-level trait for the administration of stores and their namespaces.
@@ -582,6 +770,22 @@ pub trait Context: Clone {
         key_prefix: &[u8],
     ) -> Result<Self::KeyValues, Self::Error>;
 
+    /// Finds the keys matching the `key_prefix` whose remainder lies in the `range`. The
+    /// `key_prefix` is not included in the returned keys.
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, Self::Error>;
+
+    /// Finds the `(key,value)` pairs matching the `key_prefix` whose key remainder lies in
+    /// the `range`. The `key_prefix` is not included in the returned keys.
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, Self::Error>;
+
     /// Applies the operations from the `batch`, persisting the changes.
     async fn write_batch(&self, batch: Batch) -> Result<(), Self::Error>;
 
@@ -799,6 +1003,30 @@ where
         .await
     }
 
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, Self::Error> {
+        log_time_async(
+            self.store.find_keys_in_range(key_prefix, range),
+            "find_keys_in_range",
+        )
+        .await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, Self::Error> {
+        log_time_async(
+            self.store.find_key_values_in_range(key_prefix, range),
+            "find_key_values_in_range",
+        )
+        .await
+    }
+
     async fn write_batch(&self, batch: Batch) -> Result<(), Self::Error> {
         log_time_async(self.store.write_batch(batch, &self.base_key), "write_batch").await
     }
@@ -1,7 +1,12 @@
 // Copyright (c) Zefchain Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashMap, env, sync::Arc};
+use std::{
+    collections::HashMap,
+    env,
+    ops::Bound::{Excluded, Included, Unbounded},
+    sync::Arc,
+};
 
 use async_lock::{Semaphore, SemaphoreGuard};
 use async_trait::async_trait;
@@ -42,11 +47,12 @@ use crate::metering::{
 use crate::{
     batch::{Batch, SimpleUnorderedBatch},
     common::{
-        AdminKeyValueStore, CommonStoreConfig, ContextFromStore, KeyIterable, KeyValueIterable,
-        KeyValueStore, ReadableKeyValueStore, WritableKeyValueStore,
+        is_above_start, is_below_end, AdminKeyValueStore, CommonStoreConfig, ContextFromStore,
+        Direction, KeyIterable, KeyRange, KeyValueIterable, KeyValueStore, ReadableKeyValueStore,
+        WritableKeyValueStore,
     },
     journaling::{
-        DirectKeyValueStore, DirectWritableKeyValueStore, JournalConsistencyError,
+        DirectKeyValueStore, DirectWritableKeyValueStore, JournalConsistencyError, JournalStatus,
         JournalingKeyValueStore,
     },
     lru_caching::LruCachingStore,
@@ -619,6 +625,125 @@ impl DynamoDbStoreInternal {
             responses,
         })
     }
+
+    async fn get_range_query_output(
+        &self,
+        attribute_str: &str,
+        start: &[u8],
+        end: Option<&[u8]>,
+        direction: Direction,
+        limit: Option<usize>,
+        start_key_map: Option<HashMap<String, AttributeValue>>,
+    ) -> Result<QueryOutput, DynamoDbContextError> {
+        let _guard = self.acquire().await;
+        // DynamoDB accepts a single condition on the sort key, so the excluded endpoints
+        // of the range are removed afterwards.
+        let key_condition = match end {
+            Some(_) => format!(
+                "{PARTITION_ATTRIBUTE} = :partition and {KEY_ATTRIBUTE} BETWEEN :start AND :end"
+            ),
+            None => format!("{PARTITION_ATTRIBUTE} = :partition and {KEY_ATTRIBUTE} >= :start"),
+        };
+        let mut query = self
+            .client
+            .query()
+            .table_name(&self.namespace)
+            .projection_expression(attribute_str)
+            .key_condition_expression(key_condition)
+            .expression_attribute_values(
+                ":partition",
+                AttributeValue::B(Blob::new(DUMMY_PARTITION_KEY)),
+            )
+            .expression_attribute_values(":start", AttributeValue::B(Blob::new(start)))
+            .scan_index_forward(direction == Direction::Forward)
+            .set_limit(limit.map(|limit| i32::try_from(limit).unwrap_or(i32::MAX)))
+            .set_exclusive_start_key(start_key_map);
+        if let Some(end) = end {
+            query = query.expression_attribute_values(":end", AttributeValue::B(Blob::new(end)));
+        }
+        Ok(query.send().boxed().await?)
+    }
+
+    async fn get_range_responses(
+        &self,
+        attribute: &str,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<QueryResponses, DynamoDbContextError> {
+        ensure!(
+            !key_prefix.is_empty(),
+            DynamoDbContextError::ZeroLengthKeyPrefix
+        );
+        ensure!(
+            key_prefix.len() <= MAX_KEY_SIZE,
+            DynamoDbContextError::KeyPrefixTooLong
+        );
+        let prefix_len = key_prefix.len();
+        if range.is_empty() {
+            // The iterators over the responses expect at least one response.
+            return Ok(QueryResponses {
+                prefix_len,
+                responses: vec![QueryOutput::builder().build()],
+            });
+        }
+        let (start, end) = range.bounds_with_prefix(key_prefix);
+        let start_key = match &start {
+            Included(start) | Excluded(start) => start.clone(),
+            Unbounded => key_prefix.to_vec(),
+        };
+        let end_key = match &end {
+            Included(end) | Excluded(end) => Some(end.clone()),
+            Unbounded => None,
+        };
+        let mut responses = Vec::new();
+        let mut count = 0;
+        let mut start_key_map = None;
+        loop {
+            // Two more items are requested in case the endpoints are excluded.
+            let remaining = range.limit.map(|limit| limit - count);
+            let mut response = self
+                .get_range_query_output(
+                    attribute,
+                    &start_key,
+                    end_key.as_deref(),
+                    range.direction,
+                    remaining.map(|remaining| remaining + 2),
+                    start_key_map,
+                )
+                .await?;
+            let items = response.items.take().unwrap_or_default();
+            let mut retained = Vec::new();
+            for item in items {
+                let key = extract_key(0, &item)?;
+                let in_range = is_above_start(&start, key) && is_below_end(&end, key);
+                if in_range {
+                    retained.push(item);
+                }
+            }
+            if let Some(remaining) = remaining {
+                retained.truncate(remaining);
+            }
+            count += retained.len();
+            response.items = Some(retained);
+            let last_evaluated = response.last_evaluated_key.clone();
+            responses.push(response);
+            if range.limit == Some(count) {
+                break;
+            }
+            match last_evaluated {
+                None => {
+                    break;
+                }
+                Some(value) => {
+                    start_key_map = Some(value);
+                }
+            }
+        }
+        Ok(QueryResponses {
+            prefix_len,
+            responses,
+        })
+    }
 }
 
 struct QueryResponses {
@@ -839,6 +964,28 @@ impl ReadableKeyValueStore<DynamoDbContextError> for DynamoDbStoreInternal {
             .await?;
         Ok(DynamoDbKeyValues { result_queries })
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<DynamoDbKeys, DynamoDbContextError> {
+        let result_queries = self
+            .get_range_responses(KEY_ATTRIBUTE, key_prefix, range)
+            .await?;
+        Ok(DynamoDbKeys { result_queries })
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<DynamoDbKeyValues, DynamoDbContextError> {
+        let result_queries = self
+            .get_range_responses(KEY_VALUE_ATTRIBUTE, key_prefix, range)
+            .await?;
+        Ok(DynamoDbKeyValues { result_queries })
+    }
 }
 
 #[async_trait]
@@ -927,6 +1074,22 @@ impl ReadableKeyValueStore<DynamoDbContextError> for DynamoDbStore {
     ) -> Result<Self::KeyValues, DynamoDbContextError> {
         self.store.find_key_values_by_prefix(key_prefix).await
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, DynamoDbContextError> {
+        self.store.find_keys_in_range(key_prefix, range).await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, DynamoDbContextError> {
+        self.store.find_key_values_in_range(key_prefix, range).await
+    }
 }
 
 impl WritableKeyValueStore<DynamoDbContextError> for DynamoDbStore {
@@ -939,6 +1102,13 @@ impl WritableKeyValueStore<DynamoDbContextError> for DynamoDbStore {
     async fn clear_journal(&self, base_key: &[u8]) -> Result<(), DynamoDbContextError> {
         self.store.clear_journal(base_key).await
     }
+
+    async fn inspect_journal(
+        &self,
+        base_key: &[u8],
+    ) -> Result<JournalStatus, DynamoDbContextError> {
+        self.store.inspect_journal(base_key).await
+    }
 }
 
 impl KeyValueStore for DynamoDbStore {
@@ -1,7 +1,13 @@
 // Copyright (c) Zefchain Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    ops::{
+        Bound,
+        Bound::{Excluded, Included, Unbounded},
+    },
+};
 
 use futures::future;
 use indexed_db_futures::{js_sys, prelude::*, web_sys};
@@ -10,9 +16,11 @@ use thiserror::Error;
 use crate::{
     batch::{Batch, WriteOperation},
     common::{
-        get_upper_bound_option, CommonStoreConfig, ContextFromStore, LocalAdminKeyValueStore,
-        LocalKeyValueStore, LocalReadableKeyValueStore, LocalWritableKeyValueStore,
+        get_upper_bound_option, CommonStoreConfig, ContextFromStore, Direction, KeyRange,
+        LocalAdminKeyValueStore, LocalKeyValueStore, LocalReadableKeyValueStore,
+        LocalWritableKeyValueStore,
     },
+    journaling::JournalStatus,
     value_splitting::DatabaseConsistencyError,
     views::ViewError,
 };
@@ -79,6 +87,80 @@ fn prefix_to_range(prefix: &[u8]) -> Result<web_sys::IdbKeyRange, wasm_bindgen::
     }
 }
 
+fn bounds_to_range(
+    start: &Bound<Vec<u8>>,
+    end: &Bound<Vec<u8>>,
+) -> Result<web_sys::IdbKeyRange, wasm_bindgen::JsValue> {
+    let (lower, lower_open) = match start {
+        Included(start) => (start, false),
+        Excluded(start) => (start, true),
+        Unbounded => unreachable!("the start of a range with a prefix is bounded"),
+    };
+    let lower = js_sys::Uint8Array::from(&lower[..]);
+    match end {
+        Included(upper) | Excluded(upper) => {
+            let upper_open = matches!(end, Excluded(_));
+            let upper = js_sys::Uint8Array::from(&upper[..]);
+            web_sys::IdbKeyRange::bound_with_lower_open_and_upper_open(
+                &lower.into(),
+                &upper.into(),
+                lower_open,
+                upper_open,
+            )
+        }
+        Unbounded => web_sys::IdbKeyRange::lower_bound_with_open(&lower.into(), lower_open),
+    }
+}
+
+impl IndexedDbStore {
+    async fn find_in_range_internal(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+        with_values: bool,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, IndexedDbContextError> {
+        let mut key_values = vec![];
+        if range.is_empty() {
+            return Ok(key_values);
+        }
+        let (start, end) = range.bounds_with_prefix(key_prefix);
+        let key_range = bounds_to_range(&start, &end)?;
+        let direction = match range.direction {
+            Direction::Forward => web_sys::IdbCursorDirection::Next,
+            Direction::Reverse => web_sys::IdbCursorDirection::Prev,
+        };
+        let limit = range.limit.unwrap_or(usize::MAX);
+        let transaction = self.database.transaction_on_one(&self.object_store_name)?;
+        let object_store = transaction.object_store(&self.object_store_name)?;
+        let Some(cursor) = object_store
+            .open_cursor_with_range_and_direction_owned(key_range, direction)?
+            .await?
+        else {
+            return Ok(key_values);
+        };
+
+        while key_values.len() < limit {
+            let Some(key) = cursor.primary_key() else {
+                break;
+            };
+            let key = js_sys::Uint8Array::new(&key);
+            let value = match with_values {
+                true => js_sys::Uint8Array::new(&cursor.value()).to_vec(),
+                false => Vec::new(),
+            };
+            key_values.push((
+                key.subarray(key_prefix.len() as u32, key.length()).to_vec(),
+                value,
+            ));
+            if !cursor.continue_cursor()?.await? {
+                break;
+            }
+        }
+
+        Ok(key_values)
+    }
+}
+
 impl LocalReadableKeyValueStore<IndexedDbContextError> for IndexedDbStore {
     const MAX_KEY_SIZE: usize = usize::MAX;
     type Keys = Vec<Vec<u8>>;
@@ -156,6 +238,25 @@ impl LocalReadableKeyValueStore<IndexedDbContextError> for IndexedDbStore {
 
         Ok(key_values)
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<Vec<u8>>, IndexedDbContextError> {
+        let key_values = self
+            .find_in_range_internal(key_prefix, range, false)
+            .await?;
+        Ok(key_values.into_iter().map(|(key, _)| key).collect())
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, IndexedDbContextError> {
+        self.find_in_range_internal(key_prefix, range, true).await
+    }
 }
 
 impl LocalWritableKeyValueStore<IndexedDbContextError> for IndexedDbStore {
@@ -200,6 +301,13 @@ impl LocalWritableKeyValueStore<IndexedDbContextError> for IndexedDbStore {
     async fn clear_journal(&self, _base_key: &[u8]) -> Result<(), IndexedDbContextError> {
         Ok(())
     }
+
+    async fn inspect_journal(
+        &self,
+        _base_key: &[u8],
+    ) -> Result<JournalStatus, IndexedDbContextError> {
+        Ok(JournalStatus::default())
+    }
 }
 
 impl LocalAdminKeyValueStore for IndexedDbStore {
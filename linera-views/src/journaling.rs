@@ -27,7 +27,7 @@ use thiserror::Error;
 use crate::{
     batch::{Batch, BatchValueWriter, DeletePrefixExpander, SimplifiedBatch},
     common::{
-        AdminKeyValueStore, KeyIterable, KeyValueStore, ReadableKeyValueStore,
+        AdminKeyValueStore, KeyIterable, KeyRange, KeyValueStore, ReadableKeyValueStore,
         WritableKeyValueStore, MIN_VIEW_TAG,
     },
 };
@@ -157,6 +157,22 @@ where
     ) -> Result<Self::KeyValues, K::Error> {
         self.store.find_key_values_by_prefix(key_prefix).await
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, K::Error> {
+        self.store.find_keys_in_range(key_prefix, range).await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, K::Error> {
+        self.store.find_key_values_in_range(key_prefix, range).await
+    }
 }
 
 impl<K> AdminKeyValueStore for JournalingKeyValueStore<K>
@@ -218,6 +234,11 @@ where
         }
         Ok(())
     }
+
+    async fn inspect_journal(&self, base_key: &[u8]) -> Result<JournalStatus, K::Error> {
+        // Reads go directly to the inner store, where the journal is written.
+        inspect_journal(&self.store, base_key).await
+    }
 }
 
 impl<K> KeyValueStore for JournalingKeyValueStore<K>
@@ -398,3 +419,43 @@ impl<K> JournalingKeyValueStore<K> {
         Self { store }
     }
 }
+
+/// The journal found in a store for a given base key.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct JournalStatus {
+    /// The number of blocks still to be processed according to the journal header, if any.
+    pub pending_blocks: Option<u32>,
+    /// The number of journal blocks present in the store.
+    pub stored_blocks: usize,
+}
+
+impl JournalStatus {
+    /// Returns whether a journal was left unresolved, e.g. after a crash.
+    pub fn is_dangling(&self) -> bool {
+        self.pending_blocks.is_some() || self.stored_blocks > 0
+    }
+}
+
+/// Reads the journal for the given base key from the inner store of a
+/// [`JournalingKeyValueStore`], without resolving it.
+async fn inspect_journal<S, E>(store: &S, base_key: &[u8]) -> Result<JournalStatus, E>
+where
+    S: ReadableKeyValueStore<E> + Sync,
+    E: From<bcs::Error>,
+{
+    let header_key = get_journaling_key(base_key, KeyTag::Journal as u8, 0)?;
+    let header = store.read_value::<JournalHeader>(&header_key).await?;
+    let mut entry_prefix = base_key.to_vec();
+    entry_prefix.extend([JOURNAL_TAG, KeyTag::Entry as u8]);
+    let mut stored_blocks = 0;
+    for key in store.find_keys_by_prefix(&entry_prefix).await?.iterator() {
+        // Only keys made of a block position can be journal blocks.
+        if key?.len() == std::mem::size_of::<u32>() {
+            stored_blocks += 1;
+        }
+    }
+    Ok(JournalStatus {
+        pending_blocks: header.map(|header| header.block_count),
+        stored_blocks,
+    })
+}
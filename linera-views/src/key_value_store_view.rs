@@ -23,7 +23,7 @@ use crate::{
     batch::{Batch, WriteOperation},
     common::{
         contains_key, get_interval, get_upper_bound, insert_key_prefix, Context, HasherOutput,
-        KeyIterable, KeyValueIterable, SuffixClosedSetIterator, Update, MIN_VIEW_TAG,
+        KeyIterable, KeyRange, KeyValueIterable, SuffixClosedSetIterator, Update, MIN_VIEW_TAG,
     },
     map_view::ByteMapView,
     views::{ClonableView, HashableView, Hasher, View, ViewError},
@@ -48,6 +48,7 @@ use {
     crate::common::{
         ContextFromStore, KeyValueStore, ReadableKeyValueStore, WritableKeyValueStore,
     },
+    crate::journaling::JournalStatus,
     crate::memory::{MemoryContext, TEST_MEMORY_MAX_STREAM_QUERIES},
     async_lock::RwLock,
     std::sync::Arc,
@@ -958,6 +959,144 @@ where
         Ok(key_values)
     }
 
+    /// Returns the range to query in storage so that, once the pending updates are
+    /// applied, enough entries remain to answer the query for `range`.
+    fn stored_range(&self, key_prefix: &[u8], range: &KeyRange) -> KeyRange {
+        let mut stored_range = range.clone();
+        if !self.deleted_prefixes.is_empty() {
+            // A deleted prefix may hide any number of stored keys.
+            stored_range.limit = None;
+        } else if let Some(limit) = range.limit {
+            // Each update hides at most one stored key.
+            let bounds = range.bounds_with_prefix(key_prefix);
+            stored_range.limit = Some(limit + self.updates.range(bounds).count());
+        }
+        stored_range
+    }
+
+    /// Iterates over the keys matching the given prefix whose remainder lies in the
+    /// `range`, in the direction of the range and up to its limit. The prefix is not
+    /// included in the returned keys.
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// # use std::ops::Bound::{Included, Unbounded};
+    /// # use linera_views::common::KeyRange;
+    /// # use linera_views::memory::create_memory_context;
+    /// # use linera_views::key_value_store_view::KeyValueStoreView;
+    /// # use crate::linera_views::views::View;
+    /// # let context = create_memory_context();
+    ///   let mut view = KeyValueStoreView::load(context).await.unwrap();
+    ///   view.insert(vec![0,1], vec![34]).await.unwrap();
+    ///   view.insert(vec![0,2], vec![42]).await.unwrap();
+    ///   view.insert(vec![0,3], vec![57]).await.unwrap();
+    ///   let range = KeyRange::new(Included(vec![2]), Unbounded).reverse();
+    ///   let keys = view.find_keys_in_range(&[0], &range).await.unwrap();
+    ///   assert_eq!(keys, vec![vec![3], vec![2]]);
+    /// # })
+    /// ```
+    pub async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<Vec<u8>>, ViewError> {
+        ensure!(
+            key_prefix.len() <= self.max_key_size(),
+            ViewError::KeyTooLong
+        );
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        let len = key_prefix.len();
+        let mut keys = BTreeSet::new();
+        if !self.delete_storage_first {
+            let key_prefix_full = self.context.base_tag_index(KeyTag::Index as u8, key_prefix);
+            let stored_range = self.stored_range(key_prefix, range);
+            for key in self
+                .context
+                .find_keys_in_range(&key_prefix_full, &stored_range)
+                .await?
+                .iterator()
+            {
+                let key = key?;
+                let mut key_with_prefix = key_prefix.to_vec();
+                key_with_prefix.extend_from_slice(key);
+                if !contains_key(&self.deleted_prefixes, &key_with_prefix) {
+                    keys.insert(key.to_vec());
+                }
+            }
+        }
+        for (update_key, update) in self.updates.range(range.bounds_with_prefix(key_prefix)) {
+            match update {
+                Update::Set(_) => keys.insert(update_key[len..].to_vec()),
+                Update::Removed => keys.remove(&update_key[len..]),
+            };
+        }
+        Ok(range.select(keys.into_iter()))
+    }
+
+    /// Iterates over the key-value pairs, for keys matching the given prefix whose
+    /// remainder lies in the `range`, in the direction of the range and up to its limit.
+    /// The prefix is not included in the returned keys.
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// # use std::ops::Bound::Unbounded;
+    /// # use linera_views::common::KeyRange;
+    /// # use linera_views::memory::create_memory_context;
+    /// # use linera_views::key_value_store_view::KeyValueStoreView;
+    /// # use crate::linera_views::views::View;
+    /// # let context = create_memory_context();
+    ///   let mut view = KeyValueStoreView::load(context).await.unwrap();
+    ///   view.insert(vec![0,1], vec![34]).await.unwrap();
+    ///   view.insert(vec![0,2], vec![42]).await.unwrap();
+    ///   let range = KeyRange::new(Unbounded, Unbounded).with_limit(1);
+    ///   let key_values = view.find_key_values_in_range(&[0], &range).await.unwrap();
+    ///   assert_eq!(key_values, vec![(vec![1], vec![34])]);
+    /// # })
+    /// ```
+    pub async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ViewError> {
+        ensure!(
+            key_prefix.len() <= self.max_key_size(),
+            ViewError::KeyTooLong
+        );
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        let len = key_prefix.len();
+        let mut key_values = BTreeMap::new();
+        if !self.delete_storage_first {
+            let key_prefix_full = self.context.base_tag_index(KeyTag::Index as u8, key_prefix);
+            let stored_range = self.stored_range(key_prefix, range);
+            for entry in self
+                .context
+                .find_key_values_in_range(&key_prefix_full, &stored_range)
+                .await?
+                .into_iterator_owned()
+            {
+                let (key, value) = entry?;
+                let mut key_with_prefix = key_prefix.to_vec();
+                key_with_prefix.extend_from_slice(&key);
+                if !contains_key(&self.deleted_prefixes, &key_with_prefix) {
+                    key_values.insert(key, value);
+                }
+            }
+        }
+        for (update_key, update) in self.updates.range(range.bounds_with_prefix(key_prefix)) {
+            match update {
+                Update::Set(value) => {
+                    key_values.insert(update_key[len..].to_vec(), value.clone());
+                }
+                Update::Removed => {
+                    key_values.remove(&update_key[len..]);
+                }
+            }
+        }
+        Ok(range.select(key_values.into_iter()))
+    }
+
     async fn compute_hash(&self) -> Result<<sha3::Sha3_256 as Hasher>::Output, ViewError> {
         #[cfg(with_metrics)]
         let _hash_latency = KEY_VALUE_STORE_VIEW_HASH_RUNTIME.measure_latency();
@@ -1060,6 +1199,24 @@ where
         let view = self.view.read().await;
         view.find_key_values_by_prefix(key_prefix).await
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, ViewError> {
+        let view = self.view.read().await;
+        view.find_keys_in_range(key_prefix, range).await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, ViewError> {
+        let view = self.view.read().await;
+        view.find_key_values_in_range(key_prefix, range).await
+    }
 }
 
 #[cfg(with_testing)]
@@ -1082,6 +1239,10 @@ where
     async fn clear_journal(&self, _base_key: &[u8]) -> Result<(), ViewError> {
         Ok(())
     }
+
+    async fn inspect_journal(&self, _base_key: &[u8]) -> Result<JournalStatus, ViewError> {
+        Ok(JournalStatus::default())
+    }
 }
 
 #[cfg(with_testing)]
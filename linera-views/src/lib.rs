@@ -48,12 +48,17 @@ The following views implement the `View` trait:
 * `LogView` implements a log, which is a list of entries that can be expanded.
 * `QueueView` implements a queue, which is a list of entries that can be expanded and reduced.
 * `MapView` implements a map with keys and values.
+* `MerkleMapView` implements a map whose entries, or their absence, can be proven against its hash.
 * `SetView` implements a set with keys.
 * `CollectionView` implements a map whose values are views themselves.
 * `ReentrantCollectionView` implements a map for which different keys can be accessed independently.
 * `ViewContainer<C>` implements a `KeyValueStore` and is used internally.
 
 The `LogView` can be seen as an analog of `VecDeque` while `MapView` is an analog of `BTreeMap`.
+
+Only the entries of a `MerkleMapView` can be proven against its hash, with a `MapProof`. The
+fields of a view deriving `ProvableView` are proven with a `FieldProof`. The other views only
+have a hash of their whole content.
 */
 
 #![deny(missing_docs)]
@@ -95,6 +100,9 @@ pub mod queue_view;
 /// The `MapView` implements a map with ordered keys.
 pub mod map_view;
 
+/// The `MerkleMapView` implements a map whose entries can be proven against its hash.
+pub mod merkle_map_view;
+
 /// The `SetView` implements a set with ordered entries.
 pub mod set_view;
 
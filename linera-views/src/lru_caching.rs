@@ -25,7 +25,8 @@ use {
 
 use crate::{
     batch::{Batch, WriteOperation},
-    common::{get_interval, KeyValueStore, ReadableKeyValueStore, WritableKeyValueStore},
+    common::{get_interval, KeyRange, KeyValueStore, ReadableKeyValueStore, WritableKeyValueStore},
+    journaling::JournalStatus,
 };
 
 #[cfg(with_metrics)]
@@ -205,6 +206,22 @@ where
     ) -> Result<Self::KeyValues, K::Error> {
         self.store.find_key_values_by_prefix(key_prefix).await
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, K::Error> {
+        self.store.find_keys_in_range(key_prefix, range).await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, K::Error> {
+        self.store.find_key_values_in_range(key_prefix, range).await
+    }
 }
 
 impl<K> WritableKeyValueStore<K::Error> for LruCachingStore<K>
@@ -240,6 +257,10 @@ where
     async fn clear_journal(&self, base_key: &[u8]) -> Result<(), K::Error> {
         self.store.clear_journal(base_key).await
     }
+
+    async fn inspect_journal(&self, base_key: &[u8]) -> Result<JournalStatus, K::Error> {
+        self.store.inspect_journal(base_key).await
+    }
 }
 
 impl<K> KeyValueStore for LruCachingStore<K>
@@ -43,6 +43,7 @@ use std::{
     fmt::Debug,
     marker::PhantomData,
     mem,
+    ops::Bound,
 };
 
 use async_trait::async_trait;
@@ -51,13 +52,25 @@ use serde::{de::DeserializeOwned, Serialize};
 use crate::{
     batch::Batch,
     common::{
-        contains_key, get_interval, insert_key_prefix, Context, CustomSerialize, HasherOutput,
-        KeyIterable, KeyValueIterable, SuffixClosedSetIterator, Update,
+        contains_key, get_interval, insert_key_prefix, Context, CustomSerialize, Direction,
+        HasherOutput, KeyIterable, KeyRange, KeyValueIterable, SuffixClosedSetIterator, Update,
     },
     hashable_wrapper::WrappedHashableContainerView,
     views::{ClonableView, HashableView, Hasher, View, ViewError},
 };
 
+/// Serializes the index of a bound of a range of indices.
+fn serialize_bound<Q: ?Sized>(
+    bound: Bound<&Q>,
+    serialize: impl Fn(&Q) -> Result<Vec<u8>, ViewError>,
+) -> Result<Bound<Vec<u8>>, ViewError> {
+    Ok(match bound {
+        Bound::Included(index) => Bound::Included(serialize(index)?),
+        Bound::Excluded(index) => Bound::Excluded(serialize(index)?),
+        Bound::Unbounded => Bound::Unbounded,
+    })
+}
+
 /// A view that supports inserting and removing values indexed by `Vec<u8>`.
 #[derive(Debug)]
 pub struct ByteMapView<C, V> {
@@ -697,6 +710,131 @@ where
     }
 }
 
+impl<C, V> ByteMapView<C, V>
+where
+    C: Context + Sync,
+    ViewError: From<C::Error>,
+    V: Clone + DeserializeOwned + 'static,
+{
+    /// Returns the range to query in storage so that, once the pending updates are
+    /// applied, enough keys remain to answer the query for `range`.
+    fn stored_range(&self, range: &KeyRange) -> KeyRange {
+        let mut stored_range = range.clone();
+        if !self.deleted_prefixes.is_empty() {
+            // A deleted prefix may hide any number of stored keys.
+            stored_range.limit = None;
+        } else if let Some(limit) = range.limit {
+            // Each update hides at most one stored key.
+            let bounds = (range.start.clone(), range.end.clone());
+            stored_range.limit = Some(limit + self.updates.range(bounds).count());
+        }
+        stored_range
+    }
+
+    /// Returns the keys of the map in the `range`, in the direction of the range and up
+    /// to its limit. The next page of a forward query is obtained by excluding the last
+    /// key returned, see [`KeyRange::after`].
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// # use std::ops::Bound::{Included, Unbounded};
+    /// # use linera_views::common::KeyRange;
+    /// # use linera_views::memory::create_memory_context;
+    /// # use linera_views::map_view::ByteMapView;
+    /// # use crate::linera_views::views::View;
+    /// # let context = create_memory_context();
+    ///   let mut map = ByteMapView::load(context).await.unwrap();
+    ///   map.insert(vec![0,1], String::from("Hello"));
+    ///   map.insert(vec![1,2], String::from("Bonjour"));
+    ///   map.insert(vec![2,2], String::from("Hallo"));
+    ///   let range = KeyRange::new(Included(vec![1]), Unbounded).with_limit(1);
+    ///   assert_eq!(map.keys_in_range(&range).await.unwrap(), vec![vec![1,2]]);
+    ///   let range = range.after(&[1,2], 1).with_limit(1);
+    ///   assert_eq!(map.keys_in_range(&range).await.unwrap(), vec![vec![2,2]]);
+    /// # })
+    /// ```
+    pub async fn keys_in_range(&self, range: &KeyRange) -> Result<Vec<Vec<u8>>, ViewError> {
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut keys = BTreeSet::new();
+        if !self.delete_storage_first {
+            let base = self.context.base_key();
+            let stored_range = self.stored_range(range);
+            for key in self
+                .context
+                .find_keys_in_range(&base, &stored_range)
+                .await?
+                .iterator()
+            {
+                let key = key?;
+                if !contains_key(&self.deleted_prefixes, key) {
+                    keys.insert(key.to_vec());
+                }
+            }
+        }
+        let bounds = (range.start.clone(), range.end.clone());
+        for (key, update) in self.updates.range(bounds) {
+            match update {
+                Update::Set(_) => keys.insert(key.clone()),
+                Update::Removed => keys.remove(key),
+            };
+        }
+        Ok(range.select(keys.into_iter()))
+    }
+
+    /// Returns the keys and values of the map in the `range`, in the direction of the
+    /// range and up to its limit.
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// # use std::ops::Bound::Unbounded;
+    /// # use linera_views::common::KeyRange;
+    /// # use linera_views::memory::create_memory_context;
+    /// # use linera_views::map_view::ByteMapView;
+    /// # use crate::linera_views::views::View;
+    /// # let context = create_memory_context();
+    ///   let mut map = ByteMapView::load(context).await.unwrap();
+    ///   map.insert(vec![0,1], String::from("Hello"));
+    ///   map.insert(vec![1,2], String::from("Bonjour"));
+    ///   let range = KeyRange::new(Unbounded, Unbounded).reverse().with_limit(1);
+    ///   assert_eq!(map.entries_in_range(&range).await.unwrap(), vec![(vec![1,2], String::from("Bonjour"))]);
+    /// # })
+    /// ```
+    pub async fn entries_in_range(&self, range: &KeyRange) -> Result<Vec<(Vec<u8>, V)>, ViewError> {
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut entries = BTreeMap::new();
+        if !self.delete_storage_first {
+            let base = self.context.base_key();
+            let stored_range = self.stored_range(range);
+            for entry in self
+                .context
+                .find_key_values_in_range(&base, &stored_range)
+                .await?
+                .iterator()
+            {
+                let (key, bytes) = entry?;
+                if !contains_key(&self.deleted_prefixes, key) {
+                    let value = C::deserialize_value(bytes)?;
+                    entries.insert(key.to_vec(), value);
+                }
+            }
+        }
+        let bounds = (range.start.clone(), range.end.clone());
+        for (key, update) in self.updates.range(bounds) {
+            match update {
+                Update::Set(value) => {
+                    entries.insert(key.clone(), value.clone());
+                }
+                Update::Removed => {
+                    entries.remove(key);
+                }
+            }
+        }
+        Ok(range.select(entries.into_iter()))
+    }
+}
+
 impl<C, V> ByteMapView<C, V>
 where
     C: Context + Sync,
@@ -838,6 +976,13 @@ where
     }
 }
 
+impl<C, I, V> MapView<C, I, V> {
+    /// Returns the underlying map, indexed by the serialized indices.
+    pub(crate) fn byte_map(&self) -> &ByteMapView<C, V> {
+        &self.map
+    }
+}
+
 impl<C, I, V> MapView<C, I, V>
 where
     C: Context + Sync,
@@ -1148,6 +1293,103 @@ where
     }
 }
 
+impl<C, I, V> MapView<C, I, V>
+where
+    C: Context + Sync,
+    ViewError: From<C::Error>,
+    I: Serialize + DeserializeOwned,
+    V: Clone + DeserializeOwned + 'static,
+{
+    /// Returns the indices of the map between `start` and `end`, in the order determined
+    /// by serialization, or the reverse order for [`Direction::Reverse`], and at most
+    /// `limit` of them. The next page is obtained by excluding the last index returned.
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// # use std::ops::Bound::{Excluded, Included, Unbounded};
+    /// # use linera_views::common::Direction;
+    /// # use linera_views::memory::create_memory_context;
+    /// # use linera_views::map_view::MapView;
+    /// # use crate::linera_views::views::View;
+    /// # let context = create_memory_context();
+    ///   let mut map : MapView<_,u8,String> = MapView::load(context).await.unwrap();
+    ///   map.insert(&1, String::from("Hello"));
+    ///   map.insert(&2, String::from("Bonjour"));
+    ///   map.insert(&3, String::from("Hallo"));
+    ///   let indices = map.keys_in_range(Included(&2), Unbounded, Direction::Forward, Some(1)).await.unwrap();
+    ///   assert_eq!(indices, vec![2]);
+    ///   let indices = map.keys_in_range(Excluded(&2), Unbounded, Direction::Forward, Some(1)).await.unwrap();
+    ///   assert_eq!(indices, vec![3]);
+    /// # })
+    /// ```
+    pub async fn keys_in_range<Q>(
+        &self,
+        start: Bound<&Q>,
+        end: Bound<&Q>,
+        direction: Direction,
+        limit: Option<usize>,
+    ) -> Result<Vec<I>, ViewError>
+    where
+        I: Borrow<Q>,
+        Q: Serialize + ?Sized,
+    {
+        let range = KeyRange {
+            start: serialize_bound(start, |index| Ok(C::derive_short_key(index)?))?,
+            end: serialize_bound(end, |index| Ok(C::derive_short_key(index)?))?,
+            direction,
+            limit,
+        };
+        self.map
+            .keys_in_range(&range)
+            .await?
+            .iter()
+            .map(|key| Ok(C::deserialize_value(key)?))
+            .collect()
+    }
+
+    /// Returns the indices and values of the map between `start` and `end`, in the order
+    /// determined by serialization, or the reverse order for [`Direction::Reverse`], and
+    /// at most `limit` of them.
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// # use std::ops::Bound::{Included, Unbounded};
+    /// # use linera_views::common::Direction;
+    /// # use linera_views::memory::create_memory_context;
+    /// # use linera_views::map_view::MapView;
+    /// # use crate::linera_views::views::View;
+    /// # let context = create_memory_context();
+    ///   let mut map : MapView<_,u8,String> = MapView::load(context).await.unwrap();
+    ///   map.insert(&1, String::from("Hello"));
+    ///   map.insert(&2, String::from("Bonjour"));
+    ///   let entries = map.entries_in_range(Unbounded, Included(&2), Direction::Reverse, None).await.unwrap();
+    ///   assert_eq!(entries, vec![(2, String::from("Bonjour")), (1, String::from("Hello"))]);
+    /// # })
+    /// ```
+    pub async fn entries_in_range<Q>(
+        &self,
+        start: Bound<&Q>,
+        end: Bound<&Q>,
+        direction: Direction,
+        limit: Option<usize>,
+    ) -> Result<Vec<(I, V)>, ViewError>
+    where
+        I: Borrow<Q>,
+        Q: Serialize + ?Sized,
+    {
+        let range = KeyRange {
+            start: serialize_bound(start, |index| Ok(C::derive_short_key(index)?))?,
+            end: serialize_bound(end, |index| Ok(C::derive_short_key(index)?))?,
+            direction,
+            limit,
+        };
+        self.map
+            .entries_in_range(&range)
+            .await?
+            .into_iter()
+            .map(|(key, value)| Ok((C::deserialize_value(&key)?, value)))
+            .collect()
+    }
+}
+
 impl<C, I, V> MapView<C, I, V>
 where
     C: Context + Sync,
@@ -1563,6 +1805,104 @@ where
     }
 }
 
+impl<C, I, V> CustomMapView<C, I, V>
+where
+    C: Context + Sync,
+    ViewError: From<C::Error>,
+    I: CustomSerialize,
+    V: Clone + DeserializeOwned + 'static,
+{
+    /// Returns the indices of the map between `start` and `end`, in the order determined
+    /// by the custom serialization, or the reverse order for [`Direction::Reverse`], and
+    /// at most `limit` of them. The next page is obtained by excluding the last index
+    /// returned.
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// # use std::ops::Bound::{Excluded, Unbounded};
+    /// # use linera_views::common::Direction;
+    /// # use linera_views::memory::create_memory_context;
+    /// # use linera_views::map_view::CustomMapView;
+    /// # use crate::linera_views::views::View;
+    /// # let context = create_memory_context();
+    ///   let mut map : CustomMapView<_,u128,String> = CustomMapView::load(context).await.unwrap();
+    ///   map.insert(&(34 as u128), String::from("Hello"));
+    ///   map.insert(&(256 as u128), String::from("Hola"));
+    ///   map.insert(&(37 as u128), String::from("Bonjour"));
+    ///   let indices = map.keys_in_range(Unbounded, Unbounded, Direction::Reverse, Some(2)).await.unwrap();
+    ///   assert_eq!(indices, vec![256 as u128, 37 as u128]);
+    ///   let indices = map.keys_in_range(Unbounded, Excluded(&(37 as u128)), Direction::Reverse, Some(2)).await.unwrap();
+    ///   assert_eq!(indices, vec![34 as u128]);
+    /// # })
+    /// ```
+    pub async fn keys_in_range<Q>(
+        &self,
+        start: Bound<&Q>,
+        end: Bound<&Q>,
+        direction: Direction,
+        limit: Option<usize>,
+    ) -> Result<Vec<I>, ViewError>
+    where
+        I: Borrow<Q>,
+        Q: CustomSerialize,
+    {
+        let range = KeyRange {
+            start: serialize_bound(start, Q::to_custom_bytes)?,
+            end: serialize_bound(end, Q::to_custom_bytes)?,
+            direction,
+            limit,
+        };
+        self.map
+            .keys_in_range(&range)
+            .await?
+            .iter()
+            .map(|key| I::from_custom_bytes(key))
+            .collect()
+    }
+
+    /// Returns the indices and values of the map between `start` and `end`, in the order
+    /// determined by the custom serialization, or the reverse order for
+    /// [`Direction::Reverse`], and at most `limit` of them.
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// # use std::ops::Bound::{Included, Unbounded};
+    /// # use linera_views::common::Direction;
+    /// # use linera_views::memory::create_memory_context;
+    /// # use linera_views::map_view::CustomMapView;
+    /// # use crate::linera_views::views::View;
+    /// # let context = create_memory_context();
+    ///   let mut map : CustomMapView<_,u128,String> = CustomMapView::load(context).await.unwrap();
+    ///   map.insert(&(34 as u128), String::from("Hello"));
+    ///   map.insert(&(37 as u128), String::from("Bonjour"));
+    ///   let entries = map.entries_in_range(Included(&(35 as u128)), Unbounded, Direction::Forward, None).await.unwrap();
+    ///   assert_eq!(entries, vec![(37 as u128, String::from("Bonjour"))]);
+    /// # })
+    /// ```
+    pub async fn entries_in_range<Q>(
+        &self,
+        start: Bound<&Q>,
+        end: Bound<&Q>,
+        direction: Direction,
+        limit: Option<usize>,
+    ) -> Result<Vec<(I, V)>, ViewError>
+    where
+        I: Borrow<Q>,
+        Q: CustomSerialize,
+    {
+        let range = KeyRange {
+            start: serialize_bound(start, Q::to_custom_bytes)?,
+            end: serialize_bound(end, Q::to_custom_bytes)?,
+            direction,
+            limit,
+        };
+        self.map
+            .entries_in_range(&range)
+            .await?
+            .into_iter()
+            .map(|(key, value)| Ok((I::from_custom_bytes(&key)?, value)))
+            .collect()
+    }
+}
+
 impl<C, I, V> CustomMapView<C, I, V>
 where
     C: Context + Sync,
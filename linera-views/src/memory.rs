@@ -11,8 +11,9 @@ use crate::{
     batch::{Batch, DeletePrefixExpander, WriteOperation},
     common::{
         get_interval, AdminKeyValueStore, CommonStoreConfig, Context, ContextFromStore,
-        KeyIterable, KeyValueStore, ReadableKeyValueStore, WritableKeyValueStore,
+        KeyIterable, KeyRange, KeyValueStore, ReadableKeyValueStore, WritableKeyValueStore,
     },
+    journaling::JournalStatus,
     value_splitting::DatabaseConsistencyError,
     views::ViewError,
 };
@@ -109,6 +110,34 @@ impl ReadableKeyValueStore<MemoryContextError> for MemoryStore {
         }
         Ok(key_values)
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<Vec<u8>>, MemoryContextError> {
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        let map = self.map.read().await;
+        let len = key_prefix.len();
+        let entries = map.range(range.bounds_with_prefix(key_prefix));
+        Ok(range.select(entries.map(|(key, _value)| key[len..].to_vec())))
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, MemoryContextError> {
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        let map = self.map.read().await;
+        let len = key_prefix.len();
+        let entries = map.range(range.bounds_with_prefix(key_prefix));
+        Ok(range.select(entries.map(|(key, value)| (key[len..].to_vec(), value.to_vec()))))
+    }
 }
 
 impl WritableKeyValueStore<MemoryContextError> for MemoryStore {
@@ -141,6 +170,10 @@ impl WritableKeyValueStore<MemoryContextError> for MemoryStore {
     async fn clear_journal(&self, _base_key: &[u8]) -> Result<(), MemoryContextError> {
         Ok(())
     }
+
+    async fn inspect_journal(&self, _base_key: &[u8]) -> Result<JournalStatus, MemoryContextError> {
+        Ok(JournalStatus::default())
+    }
 }
 
 impl AdminKeyValueStore for MemoryStore {
@@ -0,0 +1,422 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `MerkleMapView` is a [`MapView`] whose hash is the root of a Merkle tree over its
+//! entries, so that the presence or the absence of an index can be proven with a
+//! [`MapProof`] whose size is logarithmic in the number of entries.
+//!
+//! The leaves of the tree are the entries of the map, in the order of their serialized
+//! indices. As in RFC 6962, the left subtree of a node with `n > 1` leaves holds the
+//! largest power of two smaller than `n` leaves. The hash of the view combines the root
+//! of the tree with the number of entries.
+
+#[cfg(with_metrics)]
+use {
+    linera_base::prometheus_util::{self, MeasureLatency},
+    linera_base::sync::Lazy,
+    prometheus::HistogramVec,
+};
+
+#[cfg(with_metrics)]
+/// The runtime of hash computation
+static MERKLE_MAP_VIEW_HASH_RUNTIME: Lazy<HistogramVec> = Lazy::new(|| {
+    prometheus_util::register_histogram_vec(
+        "merkle_map_view_hash_runtime",
+        "MerkleMapView hash runtime",
+        &[],
+        Some(vec![
+            0.001, 0.003, 0.01, 0.03, 0.1, 0.2, 0.3, 0.4, 0.5, 0.75, 1.0, 2.0, 5.0,
+        ]),
+    )
+    .expect("Histogram can be created")
+});
+
+use std::{
+    borrow::Borrow,
+    ops::{Deref, DerefMut},
+};
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    batch::Batch,
+    common::{Context, HasherOutput},
+    hashable_wrapper::WrappedHashableContainerView,
+    map_view::MapView,
+    views::{ClonableView, HashableView, Hasher, View, ViewError},
+};
+
+/// The tags separating the hashes of the leaves, of the inner nodes and of the root.
+const LEAF_TAG: u8 = 0;
+const NODE_TAG: u8 = 1;
+const ROOT_TAG: u8 = 2;
+
+fn leaf_hash(key: &[u8], value: &[u8]) -> Result<HasherOutput, ViewError> {
+    let mut hasher = sha3::Sha3_256::default();
+    hasher.update_with_bcs_bytes(&LEAF_TAG)?;
+    hasher.update_with_bcs_bytes(&key)?;
+    hasher.update_with_bcs_bytes(&value)?;
+    Ok(hasher.finalize())
+}
+
+fn node_hash(left: &HasherOutput, right: &HasherOutput) -> Result<HasherOutput, ViewError> {
+    let mut hasher = sha3::Sha3_256::default();
+    hasher.update_with_bcs_bytes(&NODE_TAG)?;
+    hasher.update_with_bytes(left)?;
+    hasher.update_with_bytes(right)?;
+    Ok(hasher.finalize())
+}
+
+fn root_hash(count: u64, merkle_root: Option<&HasherOutput>) -> Result<HasherOutput, ViewError> {
+    let mut hasher = sha3::Sha3_256::default();
+    hasher.update_with_bcs_bytes(&ROOT_TAG)?;
+    hasher.update_with_bcs_bytes(&count)?;
+    if let Some(merkle_root) = merkle_root {
+        hasher.update_with_bytes(merkle_root)?;
+    }
+    Ok(hasher.finalize())
+}
+
+/// Returns the number of leaves of the left subtree of a tree with `count > 1` leaves.
+fn split_point(count: u64) -> u64 {
+    let mut split = 1;
+    while 2 * split < count {
+        split *= 2;
+    }
+    split
+}
+
+/// Computes the root of the tree over the non-empty list of `leaves`.
+fn merkle_root(leaves: &[HasherOutput]) -> Result<HasherOutput, ViewError> {
+    if leaves.len() == 1 {
+        return Ok(leaves[0]);
+    }
+    let split = split_point(leaves.len() as u64) as usize;
+    node_hash(
+        &merkle_root(&leaves[..split])?,
+        &merkle_root(&leaves[split..])?,
+    )
+}
+
+/// Computes the hashes of the siblings of the nodes on the path from the leaf at
+/// `position` to the root, from the bottom to the top.
+fn audit_path(leaves: &[HasherOutput], position: usize) -> Result<Vec<HasherOutput>, ViewError> {
+    if leaves.len() == 1 {
+        return Ok(Vec::new());
+    }
+    let split = split_point(leaves.len() as u64) as usize;
+    let (mut path, sibling) = if position < split {
+        let path = audit_path(&leaves[..split], position)?;
+        (path, merkle_root(&leaves[split..])?)
+    } else {
+        let path = audit_path(&leaves[split..], position - split)?;
+        (path, merkle_root(&leaves[..split])?)
+    };
+    path.push(sibling);
+    Ok(path)
+}
+
+/// Computes the root of a tree with `count` leaves from the `leaf` at `position` and its
+/// audit path. Returns `None` if the path does not have the length required by the tree.
+fn merkle_root_from_path(
+    leaf: HasherOutput,
+    position: u64,
+    count: u64,
+    path: &[HasherOutput],
+) -> Result<Option<HasherOutput>, ViewError> {
+    if count == 1 {
+        return Ok(path.is_empty().then_some(leaf));
+    }
+    let Some((sibling, path)) = path.split_last() else {
+        return Ok(None);
+    };
+    let split = split_point(count);
+    if position < split {
+        let Some(left) = merkle_root_from_path(leaf, position, split, path)? else {
+            return Ok(None);
+        };
+        Ok(Some(node_hash(&left, sibling)?))
+    } else {
+        let Some(right) = merkle_root_from_path(leaf, position - split, count - split, path)?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(node_hash(sibling, &right)?))
+    }
+}
+
+/// A proof that an entry is one of the leaves of the tree of a [`MerkleMapView`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryProof {
+    /// The position of the entry in the map.
+    pub position: u64,
+    /// The serialized index of the entry.
+    pub key: Vec<u8>,
+    /// The serialized value of the entry.
+    pub value: Vec<u8>,
+    /// The hashes of the siblings of the nodes on the path from the entry to the root,
+    /// from the bottom to the top.
+    pub path: Vec<HasherOutput>,
+}
+
+/// A proof that an index is present in a [`MerkleMapView`] with a given value, or that it
+/// is absent from it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapProof {
+    /// The number of entries of the map.
+    pub count: u64,
+    /// The entry of the index if it is present. Otherwise, the entries immediately before
+    /// and after the index, for those that exist.
+    pub entries: Vec<EntryProof>,
+}
+
+impl MapProof {
+    /// Checks the proof against the hash of a [`MerkleMapView`] and returns the serialized
+    /// value of the entry with the serialized index `key`, or `None` if there is no such
+    /// entry.
+    pub fn verify_key(&self, hash: &HasherOutput, key: &[u8]) -> Result<Option<&[u8]>, ViewError> {
+        let mut tree_root = None;
+        for entry in &self.entries {
+            if entry.position >= self.count {
+                return Err(ViewError::InvalidProof(format!(
+                    "entry position {} out of range",
+                    entry.position
+                )));
+            }
+            let leaf = leaf_hash(&entry.key, &entry.value)?;
+            let Some(root) = merkle_root_from_path(leaf, entry.position, self.count, &entry.path)?
+            else {
+                return Err(ViewError::InvalidProof(format!(
+                    "the path of entry {} has the wrong length",
+                    entry.position
+                )));
+            };
+            if *tree_root.get_or_insert(root) != root {
+                return Err(ViewError::InvalidProof(
+                    "the entries are not part of the same tree".to_string(),
+                ));
+            }
+        }
+        if root_hash(self.count, tree_root.as_ref())? != *hash {
+            return Err(ViewError::InvalidProof(
+                "the hash of the map does not match".to_string(),
+            ));
+        }
+        match self.entries.as_slice() {
+            [] if self.count == 0 => Ok(None),
+            [entry] if entry.key == key => Ok(Some(&entry.value)),
+            [entry] if entry.position == 0 && key < entry.key.as_slice() => Ok(None),
+            [entry] if entry.position + 1 == self.count && entry.key.as_slice() < key => Ok(None),
+            [before, after]
+                if before.position + 1 == after.position
+                    && before.key.as_slice() < key
+                    && key < after.key.as_slice() =>
+            {
+                Ok(None)
+            }
+            _ => Err(ViewError::InvalidProof(
+                "the entries do not determine whether the key is present".to_string(),
+            )),
+        }
+    }
+
+    /// Checks the proof against the hash of a [`MerkleMapView`] and returns the value at
+    /// `index`, or `None` if there is no such value.
+    pub fn verify<I, V>(&self, hash: &HasherOutput, index: &I) -> Result<Option<V>, ViewError>
+    where
+        I: Serialize + ?Sized,
+        V: DeserializeOwned,
+    {
+        let key = bcs::to_bytes(index)?;
+        match self.verify_key(hash, &key)? {
+            Some(bytes) => Ok(Some(bcs::from_bytes(bytes)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// A [`MapView`] whose hash is the root of a Merkle tree over its entries, so that the
+/// value at an index, or its absence, can be proven with a [`MapProof`].
+#[derive(Debug)]
+pub struct MerkleMapView<C, I, V> {
+    map: MapView<C, I, V>,
+}
+
+#[async_trait]
+impl<C, I, V> View<C> for MerkleMapView<C, I, V>
+where
+    C: Context + Send + Sync,
+    ViewError: From<C::Error>,
+    I: Send + Sync + Serialize,
+    V: Send + Sync + Serialize,
+{
+    fn context(&self) -> &C {
+        self.map.context()
+    }
+
+    async fn load(context: C) -> Result<Self, ViewError> {
+        let map = MapView::load(context).await?;
+        Ok(MerkleMapView { map })
+    }
+
+    fn rollback(&mut self) {
+        self.map.rollback()
+    }
+
+    fn flush(&mut self, batch: &mut Batch) -> Result<bool, ViewError> {
+        self.map.flush(batch)
+    }
+
+    fn clear(&mut self) {
+        self.map.clear()
+    }
+}
+
+impl<C, I, V> ClonableView<C> for MerkleMapView<C, I, V>
+where
+    C: Context + Send + Sync,
+    ViewError: From<C::Error>,
+    I: Send + Sync + Serialize,
+    V: Clone + Send + Sync + Serialize,
+{
+    fn clone_unchecked(&mut self) -> Result<Self, ViewError> {
+        Ok(MerkleMapView {
+            map: self.map.clone_unchecked()?,
+        })
+    }
+}
+
+impl<C, I, V> Deref for MerkleMapView<C, I, V> {
+    type Target = MapView<C, I, V>;
+
+    fn deref(&self) -> &MapView<C, I, V> {
+        &self.map
+    }
+}
+
+impl<C, I, V> DerefMut for MerkleMapView<C, I, V> {
+    fn deref_mut(&mut self) -> &mut MapView<C, I, V> {
+        &mut self.map
+    }
+}
+
+impl<C, I, V> MerkleMapView<C, I, V>
+where
+    C: Context + Sync,
+    ViewError: From<C::Error>,
+    I: Serialize,
+    V: Sync + Serialize + DeserializeOwned + 'static,
+{
+    /// Returns the serialized indices and values of the entries, in the order of the
+    /// leaves of the tree.
+    async fn entries(&self) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ViewError> {
+        let mut entries = Vec::new();
+        self.map
+            .byte_map()
+            .for_each_key_value(
+                |key, value| {
+                    entries.push((key.to_vec(), value.to_vec()));
+                    Ok(())
+                },
+                Vec::new(),
+            )
+            .await?;
+        Ok(entries)
+    }
+
+    async fn compute_hash(&self) -> Result<HasherOutput, ViewError> {
+        #[cfg(with_metrics)]
+        let _hash_latency = MERKLE_MAP_VIEW_HASH_RUNTIME.measure_latency();
+        let mut leaves = Vec::new();
+        self.map
+            .byte_map()
+            .for_each_key_value(
+                |key, value| {
+                    leaves.push(leaf_hash(key, value)?);
+                    Ok(())
+                },
+                Vec::new(),
+            )
+            .await?;
+        if leaves.is_empty() {
+            return root_hash(0, None);
+        }
+        root_hash(leaves.len() as u64, Some(&merkle_root(&leaves)?))
+    }
+
+    /// Computes a proof of the value at `index`, or of its absence, that can be checked
+    /// against the hash of the view, including the pending changes.
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// # use linera_views::memory::create_memory_context;
+    /// # use linera_views::merkle_map_view::MerkleMapView;
+    /// # use crate::linera_views::views::{HashableView, View};
+    /// # let context = create_memory_context();
+    ///   let mut map : MerkleMapView<_,u32,String> = MerkleMapView::load(context).await.unwrap();
+    ///   map.insert(&(34 as u32), String::from("Hello"));
+    ///   map.insert(&(37 as u32), String::from("Bonjour"));
+    ///   let hash = map.hash().await.unwrap();
+    ///   let proof = map.prove(&(37 as u32)).await.unwrap();
+    ///   let value = proof.verify::<u32, String>(&hash, &37).unwrap();
+    ///   assert_eq!(value, Some(String::from("Bonjour")));
+    ///   let proof = map.prove(&(35 as u32)).await.unwrap();
+    ///   let value = proof.verify::<u32, String>(&hash, &35).unwrap();
+    ///   assert_eq!(value, None);
+    /// # })
+    /// ```
+    pub async fn prove<Q>(&self, index: &Q) -> Result<MapProof, ViewError>
+    where
+        I: Borrow<Q>,
+        Q: Serialize + ?Sized,
+    {
+        let key = C::derive_short_key(index)?;
+        let entries = self.entries().await?;
+        let leaves = entries
+            .iter()
+            .map(|(key, value)| leaf_hash(key, value))
+            .collect::<Result<Vec<_>, _>>()?;
+        let positions = match entries.binary_search_by(|(entry_key, _)| entry_key.cmp(&key)) {
+            Ok(position) => position..position + 1,
+            Err(position) => position.saturating_sub(1)..entries.len().min(position + 1),
+        };
+        let entries = positions
+            .map(|position| {
+                let (key, value) = entries[position].clone();
+                Ok(EntryProof {
+                    position: position as u64,
+                    key,
+                    value,
+                    path: audit_path(&leaves, position)?,
+                })
+            })
+            .collect::<Result<_, ViewError>>()?;
+        Ok(MapProof {
+            count: leaves.len() as u64,
+            entries,
+        })
+    }
+}
+
+#[async_trait]
+impl<C, I, V> HashableView<C> for MerkleMapView<C, I, V>
+where
+    C: Context + Send + Sync,
+    ViewError: From<C::Error>,
+    I: Send + Sync + Serialize,
+    V: Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+{
+    type Hasher = sha3::Sha3_256;
+
+    async fn hash_mut(&mut self) -> Result<<Self::Hasher as Hasher>::Output, ViewError> {
+        self.compute_hash().await
+    }
+
+    async fn hash(&self) -> Result<<Self::Hasher as Hasher>::Output, ViewError> {
+        self.compute_hash().await
+    }
+}
+
+/// Type wrapping `MerkleMapView` while memoizing the hash.
+pub type HashedMerkleMapView<C, I, V> =
+    WrappedHashableContainerView<C, MerkleMapView<C, I, V>, HasherOutput>;
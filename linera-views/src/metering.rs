@@ -10,7 +10,8 @@ use prometheus::HistogramVec;
 
 use crate::{
     batch::Batch,
-    common::{KeyValueStore, ReadableKeyValueStore, WritableKeyValueStore},
+    common::{KeyRange, KeyValueStore, ReadableKeyValueStore, WritableKeyValueStore},
+    journaling::JournalStatus,
 };
 
 #[derive(Clone)]
@@ -21,6 +22,8 @@ pub struct KeyValueStoreMetrics {
     read_multi_values_bytes: HistogramVec,
     find_keys_by_prefix: HistogramVec,
     find_key_values_by_prefix: HistogramVec,
+    find_keys_in_range: HistogramVec,
+    find_key_values_in_range: HistogramVec,
     write_batch: HistogramVec,
     clear_journal: HistogramVec,
 }
@@ -84,6 +87,22 @@ impl KeyValueStoreMetrics {
             register_histogram_vec(&find_key_values1, &find_key_values2, &[], None)
                 .expect("Counter creation should not fail");
 
+        let find_keys_in_range1 = format!("{}_find_keys_in_range", var_name);
+        let find_keys_in_range2 = format!("{} find keys in range", title_name);
+        let find_keys_in_range =
+            register_histogram_vec(&find_keys_in_range1, &find_keys_in_range2, &[], None)
+                .expect("Counter creation should not fail");
+
+        let find_key_values_in_range1 = format!("{}_find_key_values_in_range", var_name);
+        let find_key_values_in_range2 = format!("{} find key values in range", title_name);
+        let find_key_values_in_range = register_histogram_vec(
+            &find_key_values_in_range1,
+            &find_key_values_in_range2,
+            &[],
+            None,
+        )
+        .expect("Counter creation should not fail");
+
         let write_batch1 = format!("{}_write_batch", var_name);
         let write_batch2 = format!("{} write batch", title_name);
         let write_batch = register_histogram_vec(&write_batch1, &write_batch2, &[], None)
@@ -100,6 +119,8 @@ impl KeyValueStoreMetrics {
             read_multi_values_bytes,
             find_keys_by_prefix,
             find_key_values_by_prefix,
+            find_keys_in_range,
+            find_key_values_in_range,
             write_batch,
             clear_journal,
         }
@@ -151,6 +172,24 @@ where
         let _metric = self.counter.find_key_values_by_prefix.measure_latency();
         self.store.find_key_values_by_prefix(key_prefix).await
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, E> {
+        let _metric = self.counter.find_keys_in_range.measure_latency();
+        self.store.find_keys_in_range(key_prefix, range).await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, E> {
+        let _metric = self.counter.find_key_values_in_range.measure_latency();
+        self.store.find_key_values_in_range(key_prefix, range).await
+    }
 }
 
 impl<K, E> WritableKeyValueStore<E> for MeteredStore<K>
@@ -168,6 +207,10 @@ where
         let _metric = self.counter.clear_journal.measure_latency();
         self.store.clear_journal(base_key).await
     }
+
+    async fn inspect_journal(&self, base_key: &[u8]) -> Result<JournalStatus, E> {
+        self.store.inspect_journal(base_key).await
+    }
 }
 
 impl<K> KeyValueStore for MeteredStore<K>
@@ -185,12 +185,18 @@ where
     fn compute_hash(&self) -> Result<<sha3::Sha3_256 as Hasher>::Output, ViewError> {
         #[cfg(with_metrics)]
         let _hash_latency = REGISTER_VIEW_HASH_RUNTIME.measure_latency();
-        let mut hasher = sha3::Sha3_256::default();
-        hasher.update_with_bcs_bytes(self.get())?;
-        Ok(hasher.finalize())
+        value_hash(self.get())
     }
 }
 
+/// Returns the hash of a `RegisterView` holding `value`, e.g. to check a
+/// [`FieldProof`](crate::views::FieldProof) of a register.
+pub fn value_hash<T: Serialize + ?Sized>(value: &T) -> Result<HasherOutput, ViewError> {
+    let mut hasher = sha3::Sha3_256::default();
+    hasher.update_with_bcs_bytes(&value)?;
+    Ok(hasher.finalize())
+}
+
 #[async_trait]
 impl<C, T> HashableView<C> for RegisterView<C, T>
 where
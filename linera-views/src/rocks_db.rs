@@ -3,7 +3,10 @@
 
 use std::{
     ffi::OsString,
-    ops::{Bound, Bound::Excluded},
+    ops::{
+        Bound,
+        Bound::{Excluded, Included, Unbounded},
+    },
     path::PathBuf,
     sync::Arc,
 };
@@ -23,9 +26,11 @@ use crate::metering::{
 use crate::{
     batch::{Batch, WriteOperation},
     common::{
-        get_upper_bound, AdminKeyValueStore, CommonStoreConfig, ContextFromStore, KeyValueStore,
-        ReadableKeyValueStore, WritableKeyValueStore,
+        get_upper_bound, is_above_start, is_below_end, AdminKeyValueStore, CommonStoreConfig,
+        ContextFromStore, Direction, KeyRange, KeyValueStore, ReadableKeyValueStore,
+        WritableKeyValueStore,
     },
+    journaling::JournalStatus,
     lru_caching::LruCachingStore,
     value_splitting::{DatabaseConsistencyError, ValueSplittingStore},
 };
@@ -71,6 +76,76 @@ impl RocksDbStoreInternal {
         }
         Ok(())
     }
+
+    /// Iterates over the keys of the range in its direction, returning the keys without
+    /// the prefix together with their values if `with_values` is set.
+    async fn find_in_range_internal(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+        with_values: bool,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, RocksDbContextError> {
+        ensure!(
+            key_prefix.len() <= MAX_KEY_SIZE,
+            RocksDbContextError::KeyTooLong
+        );
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        let client = self.clone();
+        let len = key_prefix.len();
+        let (start, end) = range.bounds_with_prefix(key_prefix);
+        let direction = range.direction;
+        let limit = range.limit.unwrap_or(usize::MAX);
+        let key_values = tokio::task::spawn_blocking(move || {
+            let mut iter = client.db.raw_iterator();
+            let mut key_values = Vec::new();
+            match direction {
+                Direction::Forward => {
+                    match &start {
+                        Included(start) | Excluded(start) => iter.seek(start),
+                        Unbounded => iter.seek_to_first(),
+                    }
+                    if let (Excluded(start), Some(key)) = (&start, iter.key()) {
+                        if key == start.as_slice() {
+                            iter.next();
+                        }
+                    }
+                }
+                Direction::Reverse => {
+                    match &end {
+                        Included(end) | Excluded(end) => iter.seek_for_prev(end),
+                        Unbounded => iter.seek_to_last(),
+                    }
+                    if let (Excluded(end), Some(key)) = (&end, iter.key()) {
+                        if key == end.as_slice() {
+                            iter.prev();
+                        }
+                    }
+                }
+            }
+            while key_values.len() < limit {
+                let Some(key) = iter.key() else {
+                    break;
+                };
+                if !is_above_start(&start, key) || !is_below_end(&end, key) {
+                    break;
+                }
+                let value = match with_values {
+                    true => iter.value().unwrap_or_default().to_vec(),
+                    false => Vec::new(),
+                };
+                key_values.push((key[len..].to_vec(), value));
+                match direction {
+                    Direction::Forward => iter.next(),
+                    Direction::Reverse => iter.prev(),
+                }
+            }
+            key_values
+        })
+        .await?;
+        Ok(key_values)
+    }
 }
 
 impl ReadableKeyValueStore<RocksDbContextError> for RocksDbStoreInternal {
@@ -176,6 +251,25 @@ impl ReadableKeyValueStore<RocksDbContextError> for RocksDbStoreInternal {
         .await?;
         Ok(key_values)
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, RocksDbContextError> {
+        let key_values = self
+            .find_in_range_internal(key_prefix, range, false)
+            .await?;
+        Ok(key_values.into_iter().map(|(key, _)| key).collect())
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, RocksDbContextError> {
+        self.find_in_range_internal(key_prefix, range, true).await
+    }
 }
 
 impl WritableKeyValueStore<RocksDbContextError> for RocksDbStoreInternal {
@@ -240,6 +334,13 @@ impl WritableKeyValueStore<RocksDbContextError> for RocksDbStoreInternal {
     async fn clear_journal(&self, _base_key: &[u8]) -> Result<(), RocksDbContextError> {
         Ok(())
     }
+
+    async fn inspect_journal(
+        &self,
+        _base_key: &[u8],
+    ) -> Result<JournalStatus, RocksDbContextError> {
+        Ok(JournalStatus::default())
+    }
 }
 
 impl AdminKeyValueStore for RocksDbStoreInternal {
@@ -424,6 +525,22 @@ impl ReadableKeyValueStore<RocksDbContextError> for RocksDbStore {
     ) -> Result<Self::KeyValues, RocksDbContextError> {
         self.store.find_key_values_by_prefix(key_prefix).await
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, RocksDbContextError> {
+        self.store.find_keys_in_range(key_prefix, range).await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, RocksDbContextError> {
+        self.store.find_key_values_in_range(key_prefix, range).await
+    }
 }
 
 impl WritableKeyValueStore<RocksDbContextError> for RocksDbStore {
@@ -436,6 +553,10 @@ impl WritableKeyValueStore<RocksDbContextError> for RocksDbStore {
     async fn clear_journal(&self, base_key: &[u8]) -> Result<(), RocksDbContextError> {
         self.store.clear_journal(base_key).await
     }
+
+    async fn inspect_journal(&self, base_key: &[u8]) -> Result<JournalStatus, RocksDbContextError> {
+        self.store.inspect_journal(base_key).await
+    }
 }
 
 impl AdminKeyValueStore for RocksDbStore {
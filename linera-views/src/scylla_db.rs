@@ -15,7 +15,13 @@
 //! [trait1]: common::KeyValueStore
 //! [trait2]: common::Context
 
-use std::{ops::Deref, sync::Arc};
+use std::{
+    ops::{
+        Bound::{Excluded, Included, Unbounded},
+        Deref,
+    },
+    sync::Arc,
+};
 
 use async_lock::{Semaphore, SemaphoreGuard};
 use async_trait::async_trait;
@@ -34,11 +40,11 @@ use crate::metering::{MeteredStore, LRU_CACHING_METRICS, SCYLLA_DB_METRICS};
 use crate::{
     batch::{Batch, DeletePrefixExpander, UnorderedBatch},
     common::{
-        get_upper_bound_option, AdminKeyValueStore, CommonStoreConfig, ContextFromStore,
-        KeyValueStore, ReadableKeyValueStore, WritableKeyValueStore,
+        get_upper_bound_option, AdminKeyValueStore, CommonStoreConfig, ContextFromStore, Direction,
+        KeyRange, KeyValueStore, ReadableKeyValueStore, WritableKeyValueStore,
     },
     journaling::{
-        DirectKeyValueStore, DirectWritableKeyValueStore, JournalConsistencyError,
+        DirectKeyValueStore, DirectWritableKeyValueStore, JournalConsistencyError, JournalStatus,
         JournalingKeyValueStore,
     },
     lru_caching::LruCachingStore,
@@ -265,6 +271,102 @@ impl ScyllaDbClient {
         }
         Ok(key_values)
     }
+
+    /// Builds the query selecting the `columns` of the entries of a range together with
+    /// the values to bind.
+    fn get_range_query(
+        &self,
+        columns: &str,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> (Query, Vec<Vec<u8>>) {
+        let (start, end) = range.bounds_with_prefix(key_prefix);
+        let mut query = format!(
+            "SELECT {} FROM kv.{} WHERE dummy = 0",
+            columns, self.namespace
+        );
+        let mut values = Vec::new();
+        match start {
+            Included(start) => {
+                query.push_str(" AND k >= ?");
+                values.push(start);
+            }
+            Excluded(start) => {
+                query.push_str(" AND k > ?");
+                values.push(start);
+            }
+            Unbounded => {}
+        }
+        match end {
+            Included(end) => {
+                query.push_str(" AND k <= ?");
+                values.push(end);
+            }
+            Excluded(end) => {
+                query.push_str(" AND k < ?");
+                values.push(end);
+            }
+            Unbounded => {}
+        }
+        if range.direction == Direction::Reverse {
+            query.push_str(" ORDER BY k DESC");
+        }
+        if let Some(limit) = range.limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
+        query.push_str(" ALLOW FILTERING");
+        (Query::new(query), values)
+    }
+
+    async fn find_keys_in_range_internal(
+        &self,
+        key_prefix: Vec<u8>,
+        range: &KeyRange,
+    ) -> Result<Vec<Vec<u8>>, ScyllaDbContextError> {
+        ensure!(
+            key_prefix.len() <= MAX_KEY_SIZE,
+            ScyllaDbContextError::KeyTooLong
+        );
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        let session = &self.session;
+        let len = key_prefix.len();
+        let (query, values) = self.get_range_query("k", &key_prefix, range);
+        let mut rows = session.query_iter(query, values).await?;
+        let mut keys = Vec::new();
+        while let Some(row) = rows.next().await {
+            let key = row?.into_typed::<(Vec<u8>,)>()?;
+            let short_key = key.0[len..].to_vec();
+            keys.push(short_key);
+        }
+        Ok(keys)
+    }
+
+    async fn find_key_values_in_range_internal(
+        &self,
+        key_prefix: Vec<u8>,
+        range: &KeyRange,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, ScyllaDbContextError> {
+        ensure!(
+            key_prefix.len() <= MAX_KEY_SIZE,
+            ScyllaDbContextError::KeyTooLong
+        );
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        let session = &self.session;
+        let len = key_prefix.len();
+        let (query, values) = self.get_range_query("k,v", &key_prefix, range);
+        let mut rows = session.query_iter(query, values).await?;
+        let mut key_values = Vec::new();
+        while let Some(row) = rows.next().await {
+            let key = row?.into_typed::<(Vec<u8>, Vec<u8>)>()?;
+            let short_key = key.0[len..].to_vec();
+            key_values.push((short_key, key.1));
+        }
+        Ok(key_values)
+    }
 }
 
 /// We limit the number of connections that can be done for tests.
@@ -399,6 +501,30 @@ impl ReadableKeyValueStore<ScyllaDbContextError> for ScyllaDbStoreInternal {
             .find_key_values_by_prefix_internal(key_prefix.to_vec())
             .await
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, ScyllaDbContextError> {
+        let store = self.store.deref();
+        let _guard = self.acquire().await;
+        store
+            .find_keys_in_range_internal(key_prefix.to_vec(), range)
+            .await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, ScyllaDbContextError> {
+        let store = self.store.deref();
+        let _guard = self.acquire().await;
+        store
+            .find_key_values_in_range_internal(key_prefix.to_vec(), range)
+            .await
+    }
 }
 
 #[async_trait]
@@ -684,6 +810,22 @@ impl ReadableKeyValueStore<ScyllaDbContextError> for ScyllaDbStore {
     ) -> Result<Self::KeyValues, ScyllaDbContextError> {
         self.store.find_key_values_by_prefix(key_prefix).await
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, ScyllaDbContextError> {
+        self.store.find_keys_in_range(key_prefix, range).await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, ScyllaDbContextError> {
+        self.store.find_key_values_in_range(key_prefix, range).await
+    }
 }
 
 impl WritableKeyValueStore<ScyllaDbContextError> for ScyllaDbStore {
@@ -696,6 +838,13 @@ impl WritableKeyValueStore<ScyllaDbContextError> for ScyllaDbStore {
     async fn clear_journal(&self, base_key: &[u8]) -> Result<(), ScyllaDbContextError> {
         self.store.clear_journal(base_key).await
     }
+
+    async fn inspect_journal(
+        &self,
+        base_key: &[u8],
+    ) -> Result<JournalStatus, ScyllaDbContextError> {
+        self.store.inspect_journal(base_key).await
+    }
 }
 
 impl AdminKeyValueStore for ScyllaDbStore {
@@ -5,6 +5,7 @@ pub mod test_views;
 use std::{
     collections::{BTreeMap, BTreeSet, HashSet},
     fmt::Debug,
+    ops::Bound,
 };
 
 use rand::{Rng, SeedableRng};
@@ -15,7 +16,9 @@ use crate::{
         Batch, WriteOperation,
         WriteOperation::{Delete, Put},
     },
-    common::{KeyIterable, KeyValueIterable, LocalAdminKeyValueStore, LocalKeyValueStore},
+    common::{
+        KeyIterable, KeyRange, KeyValueIterable, LocalAdminKeyValueStore, LocalKeyValueStore,
+    },
 };
 
 // The following seed is chosen to have equal numbers of 1s and 0s, as advised by
@@ -316,6 +319,76 @@ pub async fn run_reads<S: LocalKeyValueStore>(store: S, key_values: Vec<(Vec<u8>
     }
 }
 
+/// Returns a bound that is either unbounded or built from one of the `keys`, possibly
+/// truncated.
+fn get_random_bound<R: Rng>(rng: &mut R, keys: &[Vec<u8>]) -> Bound<Vec<u8>> {
+    let key = &keys[rng.gen_range(0..keys.len())];
+    let key = key[..rng.gen_range(0..=key.len())].to_vec();
+    match rng.gen_range(0..3) {
+        0 => Bound::Included(key),
+        1 => Bound::Excluded(key),
+        _ => Bound::Unbounded,
+    }
+}
+
+/// Checks the range queries of the store against the ones computed from the `key_values`.
+pub async fn run_range_queries<S: LocalKeyValueStore>(
+    store: S,
+    key_values: Vec<(Vec<u8>, Vec<u8>)>,
+) {
+    let mut batch = Batch::new();
+    for (key, value) in &key_values {
+        batch.put_key_value_bytes(key.clone(), value.clone());
+    }
+    store.write_batch(batch, &[]).await.unwrap();
+    let key_prefix = &key_values[0].0[..1];
+    let map = key_values
+        .iter()
+        .filter(|(key, _)| key.starts_with(key_prefix))
+        .map(|(key, value)| (key[1..].to_vec(), value.clone()))
+        .collect::<BTreeMap<_, _>>();
+    let keys = map.keys().cloned().collect::<Vec<_>>();
+    let mut rng = make_deterministic_rng();
+    for _ in 0..20 {
+        let start = get_random_bound(&mut rng, &keys);
+        let end = get_random_bound(&mut rng, &keys);
+        let mut range = KeyRange::new(start, end);
+        if rng.gen() {
+            range = range.reverse();
+        }
+        if rng.gen() {
+            range = range.with_limit(rng.gen_range(0..10));
+        }
+        let expected = range.select(
+            map.iter()
+                .filter(|(key, _)| range.contains(key))
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        let keys = store.find_keys_in_range(key_prefix, &range).await.unwrap();
+        let keys = keys
+            .iterator()
+            .map(|key| key.unwrap().to_vec())
+            .collect::<Vec<_>>();
+        let expected_keys = expected
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, expected_keys);
+        let key_values = store
+            .find_key_values_in_range(key_prefix, &range)
+            .await
+            .unwrap();
+        let key_values = key_values
+            .iterator()
+            .map(|entry| {
+                let (key, value) = entry.unwrap();
+                (key.to_vec(), value.to_vec())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(key_values, expected);
+    }
+}
+
 fn get_random_key_values1(len_value: usize) -> Vec<(Vec<u8>, Vec<u8>)> {
     let key_prefix = vec![0];
     let n = 30;
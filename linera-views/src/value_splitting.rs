@@ -1,7 +1,13 @@
 // Copyright (c) Zefchain Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    ops::{
+        Bound,
+        Bound::{Excluded, Included, Unbounded},
+    },
+};
 
 use futures::FutureExt;
 use linera_base::ensure;
@@ -10,9 +16,10 @@ use thiserror::Error;
 use crate::{
     batch::{Batch, WriteOperation},
     common::{
-        AdminKeyValueStore, CommonStoreConfig, ContextFromStore, KeyIterable, KeyValueIterable,
-        KeyValueStore, ReadableKeyValueStore, WritableKeyValueStore,
+        AdminKeyValueStore, CommonStoreConfig, ContextFromStore, KeyIterable, KeyRange,
+        KeyValueIterable, KeyValueStore, ReadableKeyValueStore, WritableKeyValueStore,
     },
+    journaling::JournalStatus,
     memory::{MemoryContextError, MemoryStore, MemoryStoreConfig, TEST_MEMORY_MAX_STREAM_QUERIES},
 };
 
@@ -192,6 +199,42 @@ where
         }
         Ok(key_values)
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, K::Error> {
+        self.find_first_segment_keys_in_range(key_prefix, range)
+            .await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, K::Error> {
+        // The other segments of a value are not necessarily in the range, so the values
+        // are read separately.
+        let keys = self
+            .find_first_segment_keys_in_range(key_prefix, range)
+            .await?;
+        let full_keys = keys
+            .iter()
+            .map(|key| {
+                let mut full_key = key_prefix.to_vec();
+                full_key.extend_from_slice(key);
+                full_key
+            })
+            .collect();
+        let values = self.read_multi_values_bytes(full_keys).await?;
+        let mut key_values = Vec::new();
+        for (key, value) in keys.into_iter().zip(values) {
+            let value = value.ok_or(DatabaseConsistencyError::MissingSegment)?;
+            key_values.push((key, value));
+        }
+        Ok(key_values)
+    }
 }
 
 impl<K> WritableKeyValueStore<K::Error> for ValueSplittingStore<K>
@@ -237,6 +280,10 @@ where
     async fn clear_journal(&self, base_key: &[u8]) -> Result<(), K::Error> {
         self.store.clear_journal(base_key).await
     }
+
+    async fn inspect_journal(&self, base_key: &[u8]) -> Result<JournalStatus, K::Error> {
+        self.store.inspect_journal(base_key).await
+    }
 }
 
 impl<K> AdminKeyValueStore for ValueSplittingStore<K>
@@ -317,6 +364,66 @@ where
         Ok(bcs::from_bytes::<u32>(&bytes)?)
     }
 
+    /// Converts a bound on the keys into a bound on the keys of their first segments.
+    fn first_segment_bound(bound: &Bound<Vec<u8>>) -> Bound<Vec<u8>> {
+        let first_segment_key = |key: &Vec<u8>| {
+            let mut big_key = key.clone();
+            big_key.extend(&[0, 0, 0, 0]);
+            big_key
+        };
+        match bound {
+            Included(key) => Included(first_segment_key(key)),
+            Excluded(key) => Excluded(first_segment_key(key)),
+            Unbounded => Unbounded,
+        }
+    }
+
+    /// Finds the keys in the range by looking for the keys of their first segments. The
+    /// underlying store also returns the other segments falling in the range, so the
+    /// query is done by pages until enough keys are found.
+    async fn find_first_segment_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Vec<Vec<u8>>, K::Error> {
+        let mut keys = Vec::new();
+        if range.is_empty() {
+            return Ok(keys);
+        }
+        let mut big_range = KeyRange {
+            start: Self::first_segment_bound(&range.start),
+            end: Self::first_segment_bound(&range.end),
+            direction: range.direction,
+            limit: range.limit,
+        };
+        loop {
+            let mut count = 0;
+            let mut last_big_key = None;
+            let big_keys = self
+                .store
+                .find_keys_in_range(key_prefix, &big_range)
+                .await?;
+            for big_key in big_keys.iterator() {
+                let big_key = big_key?;
+                count += 1;
+                if Self::read_index_from_key(big_key)? == 0 {
+                    keys.push(big_key[0..big_key.len() - 4].to_vec());
+                }
+                last_big_key = Some(big_key.to_vec());
+            }
+            let (Some(page_size), Some(limit), Some(last_big_key)) =
+                (big_range.limit, range.limit, last_big_key)
+            else {
+                return Ok(keys);
+            };
+            if count < page_size || keys.len() >= limit {
+                return Ok(keys);
+            }
+            big_range = big_range.after(&last_big_key, 0);
+            big_range.limit = Some(limit - keys.len());
+        }
+    }
+
     fn get_initial_count_first_chunk(count: u32, first_chunk: &[u8]) -> Result<Vec<u8>, K::Error> {
         let mut bytes = bcs::to_bytes(&count)?;
         bytes.reverse();
@@ -376,6 +483,22 @@ impl ReadableKeyValueStore<MemoryContextError> for TestMemoryStoreInternal {
     ) -> Result<Self::KeyValues, MemoryContextError> {
         self.store.find_key_values_by_prefix(key_prefix).await
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, MemoryContextError> {
+        self.store.find_keys_in_range(key_prefix, range).await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, MemoryContextError> {
+        self.store.find_key_values_in_range(key_prefix, range).await
+    }
 }
 
 impl WritableKeyValueStore<MemoryContextError> for TestMemoryStoreInternal {
@@ -394,6 +517,10 @@ impl WritableKeyValueStore<MemoryContextError> for TestMemoryStoreInternal {
     async fn clear_journal(&self, base_key: &[u8]) -> Result<(), MemoryContextError> {
         self.store.clear_journal(base_key).await
     }
+
+    async fn inspect_journal(&self, base_key: &[u8]) -> Result<JournalStatus, MemoryContextError> {
+        self.store.inspect_journal(base_key).await
+    }
 }
 
 impl KeyValueStore for TestMemoryStoreInternal {
@@ -461,6 +588,22 @@ impl ReadableKeyValueStore<MemoryContextError> for TestMemoryStore {
     ) -> Result<Self::KeyValues, MemoryContextError> {
         self.store.find_key_values_by_prefix(key_prefix).await
     }
+
+    async fn find_keys_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::Keys, MemoryContextError> {
+        self.store.find_keys_in_range(key_prefix, range).await
+    }
+
+    async fn find_key_values_in_range(
+        &self,
+        key_prefix: &[u8],
+        range: &KeyRange,
+    ) -> Result<Self::KeyValues, MemoryContextError> {
+        self.store.find_key_values_in_range(key_prefix, range).await
+    }
 }
 
 impl WritableKeyValueStore<MemoryContextError> for TestMemoryStore {
@@ -473,6 +616,10 @@ impl WritableKeyValueStore<MemoryContextError> for TestMemoryStore {
     async fn clear_journal(&self, base_key: &[u8]) -> Result<(), MemoryContextError> {
         self.store.clear_journal(base_key).await
     }
+
+    async fn inspect_journal(&self, base_key: &[u8]) -> Result<JournalStatus, MemoryContextError> {
+        self.store.inspect_journal(base_key).await
+    }
 }
 
 impl KeyValueStore for TestMemoryStore {
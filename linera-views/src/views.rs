@@ -6,9 +6,9 @@ use std::{fmt::Debug, io::Write};
 use async_trait::async_trait;
 use linera_base::{crypto::CryptoHash, data_types::ArithmeticError};
 pub use linera_views_derive::{
-    ClonableView, CryptoHashRootView, CryptoHashView, HashableView, RootView, View,
+    ClonableView, CryptoHashRootView, CryptoHashView, HashableView, ProvableView, RootView, View,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{batch::Batch, common::HasherOutput};
@@ -101,6 +101,10 @@ pub enum ViewError {
     /// The value is too large for the client
     #[error("The value is too large for the client")]
     TooLargeValue,
+
+    /// A proof does not match the hash it is checked against.
+    #[error("Invalid proof: {0}")]
+    InvalidProof(String),
 }
 
 impl ViewError {
@@ -178,6 +182,101 @@ pub trait CryptoHashView<C>: HashableView<C> {
 #[async_trait]
 pub trait CryptoHashRootView<C>: RootView<C> + CryptoHashView<C> {}
 
+/// Returns the bytes of the [`CryptoHash`] computed by [`CryptoHashView::crypto_hash`] for
+/// a view of type `view_name` whose [`HashableView`] hash is `view_hash`.
+pub fn crypto_hash_bytes(view_name: &str, view_hash: &HasherOutput) -> HasherOutput {
+    let mut hasher = sha3::Sha3_256::default();
+    // This matches the `BcsHashable` serialization of the hash type generated by the
+    // derive macros, i.e. `{view_name}Hash::` followed by the bytes of the hash.
+    write!(hasher, "{}Hash::", view_name).expect("Hasher should not fail");
+    hasher.write_all(view_hash).expect("Hasher should not fail");
+    hasher.finalize()
+}
+
+/// A [`HashableView`] whose hash is computed from the hashes of its fields, so that the
+/// hash of each field can be proven to be part of the hash of the view.
+#[async_trait]
+pub trait ProvableView<C>: HashableView<C> {
+    /// The names of the fields, in the order in which their hashes are combined.
+    fn field_names() -> &'static [&'static str];
+
+    /// Computes the hashes of the fields, in the order of [`ProvableView::field_names`].
+    async fn field_hashes(&self) -> Result<Vec<HasherOutput>, ViewError>;
+
+    /// Computes a proof that the field called `name` is part of the hash of the view.
+    async fn prove_field(&self, name: &str) -> Result<FieldProof, ViewError> {
+        let index = Self::field_names()
+            .iter()
+            .position(|field| *field == name)
+            .ok_or_else(|| ViewError::not_found("no field named", name))?;
+        let field_hashes = self.field_hashes().await?;
+        Ok(FieldProof {
+            index,
+            field_hashes,
+        })
+    }
+}
+
+/// A proof that a field of a [`ProvableView`] has a given hash.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldProof {
+    /// The position of the field in the view.
+    pub index: usize,
+    /// The hashes of all the fields of the view.
+    pub field_hashes: Vec<HasherOutput>,
+}
+
+impl FieldProof {
+    /// Returns the hash of the view, as computed by [`HashableView::hash`].
+    pub fn view_hash(&self) -> HasherOutput {
+        let mut hasher = sha3::Sha3_256::default();
+        for field_hash in &self.field_hashes {
+            hasher
+                .write_all(field_hash)
+                .expect("Hasher should not fail");
+        }
+        hasher.finalize()
+    }
+
+    /// Checks that the field has the hash `field_hash` in a view whose hash is `view_hash`.
+    pub fn verify(
+        &self,
+        view_hash: &HasherOutput,
+        field_hash: &HasherOutput,
+    ) -> Result<(), ViewError> {
+        if self.field_hashes.get(self.index) != Some(field_hash) {
+            return Err(ViewError::InvalidProof(format!(
+                "the hash of field {} does not match",
+                self.index
+            )));
+        }
+        if self.view_hash() != *view_hash {
+            return Err(ViewError::InvalidProof(
+                "the hash of the view does not match".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the field has the hash `field_hash` in a view of type `view_name`
+    /// whose [`CryptoHash`] is `hash`, e.g. the state hash of a certified block.
+    pub fn verify_crypto_hash(
+        &self,
+        hash: &CryptoHash,
+        view_name: &str,
+        field_hash: &HasherOutput,
+    ) -> Result<(), ViewError> {
+        let view_hash = self.view_hash();
+        if crypto_hash_bytes(view_name, &view_hash) != *hash.as_bytes() {
+            return Err(ViewError::InvalidProof(format!(
+                "the crypto hash of {} does not match",
+                view_name
+            )));
+        }
+        self.verify(&view_hash, field_hash)
+    }
+}
+
 /// A [`ClonableView`] supports being shared (unsafely) by cloning it.
 ///
 /// Sharing is unsafe because by having two view instances for the same data, they may have invalid
@@ -1,9 +1,13 @@
 // Copyright (c) Zefchain Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Bound,
+};
 
 use linera_views::{
+    common::KeyRange,
     map_view::HashedByteMapView,
     memory::create_memory_context,
     test_utils,
@@ -20,6 +24,16 @@ fn remove_by_prefix<V>(map: &mut BTreeMap<Vec<u8>, V>, key_prefix: Vec<u8>) {
     map.retain(|key, _| !key.starts_with(&key_prefix));
 }
 
+fn random_bound<R: RngCore>(rng: &mut R) -> Bound<Vec<u8>> {
+    let len = rng.gen_range(1..4);
+    let key = (0..len).map(|_| rng.gen_range(0..4)).collect::<Vec<u8>>();
+    match rng.gen_range(0..3) {
+        0 => Bound::Included(key),
+        1 => Bound::Excluded(key),
+        _ => Bound::Unbounded,
+    }
+}
+
 async fn run_map_view_mutability<R: RngCore + Clone>(rng: &mut R) {
     let context = create_memory_context();
     let mut state_map = BTreeMap::new();
@@ -140,6 +154,28 @@ async fn run_map_view_mutability<R: RngCore + Clone>(rng: &mut R) {
                 let test_view = view.map.get(key).await.unwrap().is_some();
                 assert_eq!(test_map, test_view);
             }
+            for _ in 0..4 {
+                let start = random_bound(rng);
+                let end = random_bound(rng);
+                let mut range = KeyRange::new(start, end).with_limit(rng.gen_range(1..5));
+                if rng.gen() {
+                    range = range.reverse();
+                }
+                let part_state_vec = range.select(
+                    new_state_vec
+                        .iter()
+                        .filter(|(key, _)| range.contains(key))
+                        .cloned(),
+                );
+                let part_key_values = view.map.entries_in_range(&range).await.unwrap();
+                assert_eq!(part_state_vec, part_key_values);
+                let part_keys = view.map.keys_in_range(&range).await.unwrap();
+                let part_state_keys = part_state_vec
+                    .into_iter()
+                    .map(|(key, _)| key)
+                    .collect::<Vec<_>>();
+                assert_eq!(part_state_keys, part_keys);
+            }
         }
         if save {
             state_map = new_state_map.clone();
@@ -0,0 +1,117 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use linera_views::{
+    batch::Batch,
+    common::Context,
+    memory::create_memory_context,
+    merkle_map_view::{HashedMerkleMapView, MerkleMapView},
+    register_view::{value_hash, RegisterView},
+    test_utils,
+    views::{CryptoHashView, HashableView, ProvableView, RootView, View, ViewError},
+};
+use linera_views_derive::CryptoHashRootView;
+use rand::Rng;
+
+#[derive(CryptoHashRootView, ProvableView)]
+struct StateView<C> {
+    pub name: RegisterView<C, String>,
+    pub map: HashedMerkleMapView<C, u32, u64>,
+}
+
+#[tokio::test]
+async fn test_merkle_map_view_proofs() {
+    let mut rng = test_utils::make_deterministic_rng();
+    for count in [0, 1, 2, 3, 7, 8, 9, 30] {
+        let context = create_memory_context();
+        let mut map = MerkleMapView::<_, u32, u64>::load(context.clone())
+            .await
+            .unwrap();
+        let mut entries = BTreeMap::<u32, u64>::new();
+        while entries.len() < count {
+            let index = rng.gen_range(0..100u32);
+            let value = rng.gen::<u64>();
+            entries.insert(index, value);
+            map.insert(&index, value).unwrap();
+        }
+        // Check the proofs both before and after the changes are saved.
+        for save in [false, true] {
+            if save {
+                let mut batch = Batch::new();
+                map.flush(&mut batch).unwrap();
+                context.write_batch(batch).await.unwrap();
+                map = MerkleMapView::load(context.clone()).await.unwrap();
+            }
+            let hash = map.hash().await.unwrap();
+            for index in 0..101u32 {
+                let proof = map.prove(&index).await.unwrap();
+                let value = proof.verify::<u32, u64>(&hash, &index).unwrap();
+                assert_eq!(value, entries.get(&index).copied());
+                // The proof never yields a wrong value for another index.
+                if let Ok(other_value) = proof.verify::<u32, u64>(&hash, &(index + 1)) {
+                    assert_eq!(other_value, entries.get(&(index + 1)).copied());
+                }
+                if let Some(value) = value {
+                    let mut forged = proof.clone();
+                    forged.entries[0].value = bcs::to_bytes(&value.wrapping_add(1)).unwrap();
+                    assert!(forged.verify::<u32, u64>(&hash, &index).is_err());
+                }
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_merkle_map_view_exclusion_needs_neighbors() {
+    let context = create_memory_context();
+    let mut map = MerkleMapView::<_, u32, u64>::load(context).await.unwrap();
+    for index in [10u32, 20, 30] {
+        map.insert(&index, index as u64).unwrap();
+    }
+    let hash = map.hash().await.unwrap();
+    let mut proof = map.prove(&25u32).await.unwrap();
+    assert_eq!(proof.verify::<u32, u64>(&hash, &25).unwrap(), None);
+    // Dropping one of the neighbors leaves the absence of the index unproven.
+    proof.entries.remove(0);
+    assert!(matches!(
+        proof.verify::<u32, u64>(&hash, &25),
+        Err(ViewError::InvalidProof(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_provable_view_fields() {
+    let context = create_memory_context();
+    let mut view = StateView::load(context).await.unwrap();
+    view.name.set("state".to_string());
+    view.map.insert(&7u32, 49).unwrap();
+    let view_hash = view.hash().await.unwrap();
+    let crypto_hash = view.crypto_hash().await.unwrap();
+
+    let name_proof = view.prove_field("name").await.unwrap();
+    let name_hash = value_hash("state").unwrap();
+    name_proof.verify(&view_hash, &name_hash).unwrap();
+    name_proof
+        .verify_crypto_hash(&crypto_hash, "StateView", &name_hash)
+        .unwrap();
+    assert!(name_proof
+        .verify_crypto_hash(&crypto_hash, "OtherView", &name_hash)
+        .is_err());
+    assert!(name_proof
+        .verify(&view_hash, &value_hash("other").unwrap())
+        .is_err());
+
+    let map_proof = view.prove_field("map").await.unwrap();
+    let entry_proof = view.map.prove(&7u32).await.unwrap();
+    let map_hash = map_proof.field_hashes[map_proof.index];
+    map_proof
+        .verify_crypto_hash(&crypto_hash, "StateView", &map_hash)
+        .unwrap();
+    let value = entry_proof.verify::<u32, u64>(&map_hash, &7).unwrap();
+    assert_eq!(value, Some(49));
+
+    assert!(view.prove_field("missing").await.is_err());
+    view.save().await.unwrap();
+}
@@ -7,8 +7,8 @@ use linera_views::{
     key_value_store_view::ViewContainer,
     memory::{create_memory_context, create_memory_store},
     test_utils::{
-        self, get_random_test_scenarios, run_big_write_read, run_reads, run_writes_from_blank,
-        run_writes_from_state,
+        self, get_random_test_scenarios, run_big_write_read, run_range_queries, run_reads,
+        run_writes_from_blank, run_writes_from_state,
     },
     value_splitting::create_test_memory_store,
 };
@@ -70,6 +70,67 @@ async fn test_reads_indexed_db() {
     }
 }
 
+#[tokio::test]
+async fn test_range_queries_test_memory() {
+    for scenario in get_random_test_scenarios() {
+        let key_value_store = create_test_memory_store();
+        run_range_queries(key_value_store, scenario).await;
+    }
+}
+
+#[tokio::test]
+async fn test_range_queries_memory() {
+    for scenario in get_random_test_scenarios() {
+        let key_value_store = create_memory_store();
+        run_range_queries(key_value_store, scenario).await;
+    }
+}
+
+#[cfg(with_rocksdb)]
+#[tokio::test]
+async fn test_range_queries_rocks_db() {
+    for scenario in get_random_test_scenarios() {
+        let (key_value_store, _dir) = linera_views::rocks_db::create_rocks_db_test_store().await;
+        run_range_queries(key_value_store, scenario).await;
+    }
+}
+
+#[cfg(with_dynamodb)]
+#[tokio::test]
+async fn test_range_queries_dynamo_db() {
+    for scenario in get_random_test_scenarios() {
+        let key_value_store = linera_views::dynamo_db::create_dynamo_db_test_store().await;
+        run_range_queries(key_value_store, scenario).await;
+    }
+}
+
+#[cfg(with_scylladb)]
+#[tokio::test]
+async fn test_range_queries_scylla_db() {
+    for scenario in get_random_test_scenarios() {
+        let key_value_store = linera_views::scylla_db::create_scylla_db_test_store().await;
+        run_range_queries(key_value_store, scenario).await;
+    }
+}
+
+#[cfg(with_indexeddb)]
+#[wasm_bindgen_test]
+async fn test_range_queries_indexed_db() {
+    for scenario in get_random_test_scenarios() {
+        let key_value_store = linera_views::indexed_db::create_indexed_db_test_store().await;
+        run_range_queries(key_value_store, scenario).await;
+    }
+}
+
+#[tokio::test]
+async fn test_range_queries_key_value_store_view_memory() {
+    for scenario in get_random_test_scenarios() {
+        let context = create_memory_context();
+        let key_value_store = ViewContainer::new(context).await.unwrap();
+        run_range_queries(key_value_store, scenario).await;
+    }
+}
+
 #[tokio::test]
 async fn test_reads_key_value_store_view_memory() {
     for scenario in get_random_test_scenarios() {